@@ -0,0 +1,705 @@
+use core::fmt;
+
+pub use crate::types::SpecConfidence;
+use crate::soc::*;
+use crate::types::{AdrenoSlices, ApiLevels, ApiVersion, DriverSupport, SupportStatus};
+
+/// Adreno GPU architecture
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AdrenoArch {
+    A4xx,
+    A5xx,
+    A6xx,
+    A7xx,
+    A8xx,
+}
+
+impl fmt::Display for AdrenoArch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            AdrenoArch::A4xx => "Adreno 4xx",
+            AdrenoArch::A5xx => "Adreno 5xx",
+            AdrenoArch::A6xx => "Adreno 6xx",
+            AdrenoArch::A7xx => "Adreno 7xx",
+            AdrenoArch::A8xx => "Adreno 8xx",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// LPDDR generation of the SoC's memory
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum MemoryType {
+    Lpddr3,
+    Lpddr4,
+    Lpddr4x,
+    Lpddr5,
+    Lpddr5x,
+}
+
+impl fmt::Display for MemoryType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            MemoryType::Lpddr3 => "LPDDR3",
+            MemoryType::Lpddr4 => "LPDDR4",
+            MemoryType::Lpddr4x => "LPDDR4X",
+            MemoryType::Lpddr5 => "LPDDR5",
+            MemoryType::Lpddr5x => "LPDDR5X",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// Memory the SoCs of a chip typically ship with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AdrenoMemory {
+    pub memory_type: MemoryType,
+    /// Transfer rate in MT/s, twice the memory clock
+    pub transfer_rate_mts: u32,
+}
+
+impl AdrenoMemory {
+    pub const fn new(memory_type: MemoryType, transfer_rate_mts: u32) -> Self {
+        Self { memory_type, transfer_rate_mts }
+    }
+}
+
+/// Adreno GPU specifications based on chip ID
+///
+/// Serialize-only with the `serde` feature: entries borrow from the static
+/// database and cannot be rebuilt from owned input.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct AdrenoSpecs {
+    pub name: &'static str,
+    pub architecture: AdrenoArch,
+    pub shader_cores: u32,
+    /// Slice topology of sliced parts; `shader_cores` is its uSPTP total
+    pub slices: Option<AdrenoSlices>,
+    pub stream_processors: u32,
+    pub gmem_size_kb: u32,
+    pub bus_width_bits: u32,
+    pub max_freq_mhz: u32,
+    pub process_nm: u32,
+    pub year: u32,
+    /// SoCs shipping this GPU, see [`crate::soc`]
+    pub snapdragon_models: &'static [SocRef],
+    pub confidence: SpecConfidence,
+    pub support: DriverSupport,
+    /// Newest APIs of the vendor driver
+    pub apis: ApiLevels,
+    /// Typical memory of the SoCs in `snapdragon_models`
+    pub memory: AdrenoMemory,
+}
+
+/// Vendor KGSL support by series, newest Android common kernel branch known
+/// to ship it. Freedreno covers every series in the database.
+const A4XX_SUPPORT: DriverSupport = DriverSupport::new(SupportStatus::CommunityOnly, "msm-4.9");
+const A5XX_SUPPORT: DriverSupport = DriverSupport::new(SupportStatus::CommunityOnly, "msm-4.19");
+const A6XX_SUPPORT: DriverSupport = DriverSupport::new(SupportStatus::VendorSupported, "android13-5.15");
+const A7XX_SUPPORT: DriverSupport = DriverSupport::new(SupportStatus::VendorSupported, "android14-6.1");
+const A8XX_SUPPORT: DriverSupport = DriverSupport::new(SupportStatus::VendorSupported, "android15-6.6");
+
+/// Newest APIs of the vendor driver by series; the Adreno 4xx never got a
+/// Vulkan driver
+const A4XX_APIS: ApiLevels = ApiLevels::new(None, ApiVersion::new(3, 1));
+const A5XX_APIS: ApiLevels = ApiLevels::new(Some(ApiVersion::new(1, 1)), ApiVersion::new(3, 2));
+const A6XX_APIS: ApiLevels = ApiLevels::new(Some(ApiVersion::new(1, 1)), ApiVersion::new(3, 2));
+const A7XX_APIS: ApiLevels = ApiLevels::new(Some(ApiVersion::new(1, 3)), ApiVersion::new(3, 2));
+const A8XX_APIS: ApiLevels = ApiLevels::new(Some(ApiVersion::new(1, 3)), ApiVersion::new(3, 2));
+
+/// Comprehensive Adreno chip database
+pub const ADRENO_CHIPS: &[(u32, AdrenoSpecs)] = &[
+    // === Adreno 7xx series (2022+) ===
+    (
+        0x07030001,
+        AdrenoSpecs {
+            name: "Adreno 730",
+            architecture: AdrenoArch::A7xx,
+            shader_cores: 4,
+            slices: Some(AdrenoSlices::new(2, 2)),
+            stream_processors: 768,
+            gmem_size_kb: 2048,
+            bus_width_bits: 128,
+            max_freq_mhz: 900,
+            process_nm: 4,
+            year: 2022,
+            snapdragon_models: &[SM8450, SM8475],
+            confidence: SpecConfidence::Measured,
+            support: A7XX_SUPPORT,
+            apis: A7XX_APIS,
+            memory: AdrenoMemory::new(MemoryType::Lpddr5, 6400),
+        },
+    ),
+    (
+        0x07060001,
+        AdrenoSpecs {
+            name: "Adreno 740",
+            architecture: AdrenoArch::A7xx,
+            shader_cores: 6,
+            slices: Some(AdrenoSlices::new(3, 2)),
+            stream_processors: 1024,
+            gmem_size_kb: 3072,
+            bus_width_bits: 256,
+            max_freq_mhz: 680,
+            process_nm: 4,
+            year: 2023,
+            snapdragon_models: &[SM8550],
+            confidence: SpecConfidence::Measured,
+            support: A7XX_SUPPORT,
+            apis: A7XX_APIS,
+            memory: AdrenoMemory::new(MemoryType::Lpddr5x, 8400),
+        },
+    ),
+    (
+        0x07050000,
+        AdrenoSpecs {
+            name: "Adreno 750",
+            architecture: AdrenoArch::A7xx,
+            shader_cores: 6,
+            slices: Some(AdrenoSlices::new(3, 2)),
+            stream_processors: 1536,
+            gmem_size_kb: 4096,
+            bus_width_bits: 256,
+            max_freq_mhz: 1000,
+            process_nm: 4,
+            year: 2023,
+            snapdragon_models: &[SM8650],
+            confidence: SpecConfidence::ReverseEngineered,
+            support: A7XX_SUPPORT,
+            apis: A7XX_APIS,
+            memory: AdrenoMemory::new(MemoryType::Lpddr5x, 9600),
+        },
+    ),
+
+    // === Adreno 6xx series ===
+    (
+        0x06010000,
+        AdrenoSpecs {
+            name: "Adreno 610",
+            architecture: AdrenoArch::A6xx,
+            shader_cores: 2,
+            slices: None,
+            stream_processors: 128,
+            gmem_size_kb: 384,
+            bus_width_bits: 64,
+            max_freq_mhz: 950,
+            process_nm: 11,
+            year: 2019,
+            snapdragon_models: &[SM4250, SM6115, SM6125],
+            confidence: SpecConfidence::Measured,
+            support: A6XX_SUPPORT,
+            apis: A6XX_APIS,
+            memory: AdrenoMemory::new(MemoryType::Lpddr4x, 3733),
+        },
+    ),
+    (
+        0x06010001,
+        AdrenoSpecs {
+            name: "Adreno 618",
+            architecture: AdrenoArch::A6xx,
+            shader_cores: 2,
+            slices: None,
+            stream_processors: 256,
+            gmem_size_kb: 512,
+            bus_width_bits: 64,
+            max_freq_mhz: 825,
+            process_nm: 8,
+            year: 2019,
+            snapdragon_models: &[SM7150_AA, SM7150_AB, SM7150_AC],
+            confidence: SpecConfidence::Measured,
+            support: A6XX_SUPPORT,
+            apis: A6XX_APIS,
+            memory: AdrenoMemory::new(MemoryType::Lpddr4x, 3733),
+        },
+    ),
+    (
+        0x06010500,
+        AdrenoSpecs {
+            name: "Adreno 619",
+            architecture: AdrenoArch::A6xx,
+            shader_cores: 2,
+            slices: None,
+            stream_processors: 256,
+            gmem_size_kb: 512,
+            bus_width_bits: 64,
+            max_freq_mhz: 950,
+            process_nm: 8,
+            year: 2020,
+            snapdragon_models: &[SM7225, SM6350, SM4350],
+            confidence: SpecConfidence::Measured,
+            support: A6XX_SUPPORT,
+            apis: A6XX_APIS,
+            memory: AdrenoMemory::new(MemoryType::Lpddr4x, 4266),
+        },
+    ),
+    (
+        0x06010200,
+        AdrenoSpecs {
+            name: "Adreno 612/615/616",
+            architecture: AdrenoArch::A6xx,
+            shader_cores: 2,
+            slices: None,
+            stream_processors: 256,
+            gmem_size_kb: 768,
+            bus_width_bits: 64,
+            max_freq_mhz: 850,
+            process_nm: 10,
+            year: 2019,
+            snapdragon_models: &[SDM670, SM6150, SDM710, SDM712],
+            confidence: SpecConfidence::Heuristic,
+            support: A6XX_SUPPORT,
+            apis: A6XX_APIS,
+            memory: AdrenoMemory::new(MemoryType::Lpddr4x, 3733),
+        },
+    ),
+    (
+        0x06020000,
+        AdrenoSpecs {
+            name: "Adreno 620",
+            architecture: AdrenoArch::A6xx,
+            shader_cores: 2,
+            slices: None,
+            stream_processors: 256,
+            gmem_size_kb: 768,
+            bus_width_bits: 64,
+            max_freq_mhz: 750,
+            process_nm: 8,
+            year: 2020,
+            snapdragon_models: &[SM7250_AA, SM7250_AB, SM7250_AC],
+            confidence: SpecConfidence::ReverseEngineered,
+            support: A6XX_SUPPORT,
+            apis: A6XX_APIS,
+            memory: AdrenoMemory::new(MemoryType::Lpddr4x, 4266),
+        },
+    ),
+
+    // === Adreno 5xx series ===
+    (
+        0x05000000,
+        AdrenoSpecs {
+            name: "Adreno 504/505",
+            architecture: AdrenoArch::A5xx,
+            shader_cores: 1,
+            slices: None,
+            stream_processors: 96,
+            gmem_size_kb: 256,
+            bus_width_bits: 32,
+            max_freq_mhz: 450,
+            process_nm: 28,
+            year: 2016,
+            snapdragon_models: &[MSM8917, SDM429, MSM8937, MSM8940, SDM439],
+            confidence: SpecConfidence::ReverseEngineered,
+            support: A5XX_SUPPORT,
+            apis: A5XX_APIS,
+            memory: AdrenoMemory::new(MemoryType::Lpddr3, 1866),
+        },
+    ),
+    (
+        0x05060000,
+        AdrenoSpecs {
+            name: "Adreno 506",
+            architecture: AdrenoArch::A5xx,
+            shader_cores: 1,
+            slices: None,
+            stream_processors: 128,
+            gmem_size_kb: 256,
+            bus_width_bits: 32,
+            max_freq_mhz: 650,
+            process_nm: 14,
+            year: 2016,
+            snapdragon_models: &[SDM450, MSM8953, MSM8953_PRO, SDM632],
+            confidence: SpecConfidence::Measured,
+            support: A5XX_SUPPORT,
+            apis: A5XX_APIS,
+            memory: AdrenoMemory::new(MemoryType::Lpddr3, 1866),
+        },
+    ),
+    (
+        0x05080000,
+        AdrenoSpecs {
+            name: "Adreno 508",
+            architecture: AdrenoArch::A5xx,
+            shader_cores: 2,
+            slices: None,
+            stream_processors: 128,
+            gmem_size_kb: 256,
+            bus_width_bits: 64,
+            max_freq_mhz: 650,
+            process_nm: 14,
+            year: 2017,
+            snapdragon_models: &[SDM630, SDM632],
+            confidence: SpecConfidence::ReverseEngineered,
+            support: A5XX_SUPPORT,
+            apis: A5XX_APIS,
+            memory: AdrenoMemory::new(MemoryType::Lpddr4x, 2666),
+        },
+    ),
+    (
+        0x05090000,
+        AdrenoSpecs {
+            name: "Adreno 509",
+            architecture: AdrenoArch::A5xx,
+            shader_cores: 2,
+            slices: None,
+            stream_processors: 128,
+            gmem_size_kb: 384,
+            bus_width_bits: 64,
+            max_freq_mhz: 720,
+            process_nm: 14,
+            year: 2017,
+            snapdragon_models: &[SDM636],
+            confidence: SpecConfidence::ReverseEngineered,
+            support: A5XX_SUPPORT,
+            apis: A5XX_APIS,
+            memory: AdrenoMemory::new(MemoryType::Lpddr4x, 2666),
+        },
+    ),
+    (
+        0x05120000,
+        AdrenoSpecs {
+            name: "Adreno 512",
+            architecture: AdrenoArch::A5xx,
+            shader_cores: 2,
+            slices: None,
+            stream_processors: 256,
+            gmem_size_kb: 512,
+            bus_width_bits: 64,
+            max_freq_mhz: 850,
+            process_nm: 14,
+            year: 2017,
+            snapdragon_models: &[SDM660, SM6115],
+            confidence: SpecConfidence::ReverseEngineered,
+            support: A5XX_SUPPORT,
+            apis: A5XX_APIS,
+            memory: AdrenoMemory::new(MemoryType::Lpddr4x, 3733),
+        },
+    ),
+    (
+        0x05010000,
+        AdrenoSpecs {
+            name: "Adreno 510",
+            architecture: AdrenoArch::A5xx,
+            shader_cores: 2,
+            slices: None,
+            stream_processors: 128,
+            gmem_size_kb: 256,
+            bus_width_bits: 32,
+            max_freq_mhz: 600,
+            process_nm: 14,
+            year: 2016,
+            snapdragon_models: &[MSM8937, MSM8940, MSM8939V2, MSM8952],
+            confidence: SpecConfidence::Measured,
+            support: A5XX_SUPPORT,
+            apis: A5XX_APIS,
+            memory: AdrenoMemory::new(MemoryType::Lpddr3, 1866),
+        },
+    ),
+    (
+        0x04020000,
+        AdrenoSpecs {
+            name: "Adreno 530",
+            architecture: AdrenoArch::A5xx,
+            shader_cores: 3,
+            slices: None,
+            stream_processors: 256,
+            gmem_size_kb: 512,
+            bus_width_bits: 64,
+            max_freq_mhz: 624,
+            process_nm: 14,
+            year: 2016,
+            snapdragon_models: &[MSM8996, MSM8996_PRO],
+            confidence: SpecConfidence::Measured,
+            support: A5XX_SUPPORT,
+            apis: A5XX_APIS,
+            memory: AdrenoMemory::new(MemoryType::Lpddr4, 3733),
+        },
+    ),
+    (
+        0x05020000,
+        AdrenoSpecs {
+            name: "Adreno 540",
+            architecture: AdrenoArch::A5xx,
+            shader_cores: 3,
+            slices: None,
+            stream_processors: 256,
+            gmem_size_kb: 512,
+            bus_width_bits: 64,
+            max_freq_mhz: 710,
+            process_nm: 10,
+            year: 2017,
+            snapdragon_models: &[MSM8998],
+            confidence: SpecConfidence::Measured,
+            support: A5XX_SUPPORT,
+            apis: A5XX_APIS,
+            memory: AdrenoMemory::new(MemoryType::Lpddr4x, 3733),
+        },
+    ),
+
+    // === Adreno 4xx series ===
+    (
+        0x04010000,
+        AdrenoSpecs {
+            name: "Adreno 405",
+            architecture: AdrenoArch::A4xx,
+            shader_cores: 1,
+            slices: None,
+            stream_processors: 48,
+            gmem_size_kb: 128,
+            bus_width_bits: 32,
+            max_freq_mhz: 550,
+            process_nm: 28,
+            year: 2014,
+            snapdragon_models: &[MSM8929, MSM8917, MSM8936],
+            confidence: SpecConfidence::Measured,
+            support: A4XX_SUPPORT,
+            apis: A4XX_APIS,
+            memory: AdrenoMemory::new(MemoryType::Lpddr3, 1600),
+        },
+    ),
+];
+
+/// Generic per-series entries used when no chip matches
+const FALLBACK_A8XX: AdrenoSpecs = AdrenoSpecs {
+    name: "Adreno 8xx (unknown variant)",
+    architecture: AdrenoArch::A8xx,
+    shader_cores: 8,
+    slices: None,
+    stream_processors: 2048,
+    gmem_size_kb: 4096,
+    bus_width_bits: 384,
+    max_freq_mhz: 1100,
+    process_nm: 3,
+    year: 2024,
+    snapdragon_models: &[],
+    confidence: SpecConfidence::Heuristic,
+    support: A8XX_SUPPORT,
+    apis: A8XX_APIS,
+    memory: AdrenoMemory::new(MemoryType::Lpddr5x, 9600),
+};
+
+const FALLBACK_A7XX: AdrenoSpecs = AdrenoSpecs {
+    name: "Adreno 7xx (unknown variant)",
+    architecture: AdrenoArch::A7xx,
+    shader_cores: 5,
+    slices: None,
+    stream_processors: 1024,
+    gmem_size_kb: 3072,
+    bus_width_bits: 192,
+    max_freq_mhz: 900,
+    process_nm: 4,
+    year: 2022,
+    snapdragon_models: &[],
+    confidence: SpecConfidence::Heuristic,
+    support: A7XX_SUPPORT,
+    apis: A7XX_APIS,
+    memory: AdrenoMemory::new(MemoryType::Lpddr5, 6400),
+};
+
+const FALLBACK_A6XX: AdrenoSpecs = AdrenoSpecs {
+    name: "Adreno 6xx (unknown low/mid variant)",
+    architecture: AdrenoArch::A6xx,
+    shader_cores: 2,
+    slices: None,
+    stream_processors: 256,
+    gmem_size_kb: 512,
+    bus_width_bits: 64,
+    max_freq_mhz: 800,
+    process_nm: 8,
+    year: 2019,
+    snapdragon_models: &[],
+    confidence: SpecConfidence::Heuristic,
+    support: A6XX_SUPPORT,
+    apis: A6XX_APIS,
+    memory: AdrenoMemory::new(MemoryType::Lpddr4x, 3733),
+};
+
+const FALLBACK_A5XX: AdrenoSpecs = AdrenoSpecs {
+    name: "Adreno 5xx (low-end variant)",
+    architecture: AdrenoArch::A5xx,
+    shader_cores: 1,
+    slices: None,
+    stream_processors: 96,
+    gmem_size_kb: 256,
+    bus_width_bits: 32,
+    max_freq_mhz: 500,
+    process_nm: 28,
+    year: 2016,
+    snapdragon_models: &[],
+    confidence: SpecConfidence::Heuristic,
+    support: A5XX_SUPPORT,
+    apis: A5XX_APIS,
+    memory: AdrenoMemory::new(MemoryType::Lpddr3, 1866),
+};
+
+const FALLBACK_A4XX: AdrenoSpecs = AdrenoSpecs {
+    name: "Adreno 4xx (unknown variant)",
+    architecture: AdrenoArch::A4xx,
+    shader_cores: 1,
+    slices: None,
+    stream_processors: 48,
+    gmem_size_kb: 128,
+    bus_width_bits: 32,
+    max_freq_mhz: 550,
+    process_nm: 28,
+    year: 2014,
+    snapdragon_models: &[],
+    confidence: SpecConfidence::Heuristic,
+    support: A4XX_SUPPORT,
+    apis: A4XX_APIS,
+    memory: AdrenoMemory::new(MemoryType::Lpddr3, 1600),
+};
+
+/// Driver support assumed for chips of `architecture` built at runtime
+pub const fn default_support(architecture: AdrenoArch) -> DriverSupport {
+    match architecture {
+        AdrenoArch::A4xx => A4XX_SUPPORT,
+        AdrenoArch::A5xx => A5XX_SUPPORT,
+        AdrenoArch::A6xx => A6XX_SUPPORT,
+        AdrenoArch::A7xx => A7XX_SUPPORT,
+        AdrenoArch::A8xx => A8XX_SUPPORT,
+    }
+}
+
+/// API levels assumed for chips of `architecture` built at runtime
+pub const fn default_apis(architecture: AdrenoArch) -> ApiLevels {
+    match architecture {
+        AdrenoArch::A4xx => A4XX_APIS,
+        AdrenoArch::A5xx => A5XX_APIS,
+        AdrenoArch::A6xx => A6XX_APIS,
+        AdrenoArch::A7xx => A7XX_APIS,
+        AdrenoArch::A8xx => A8XX_APIS,
+    }
+}
+
+/// Memory assumed for chips of `architecture` built at runtime that name
+/// none, the series fallback's
+pub const fn default_memory(architecture: AdrenoArch) -> AdrenoMemory {
+    match architecture {
+        AdrenoArch::A4xx => FALLBACK_A4XX.memory,
+        AdrenoArch::A5xx => FALLBACK_A5XX.memory,
+        AdrenoArch::A6xx => FALLBACK_A6XX.memory,
+        AdrenoArch::A7xx => FALLBACK_A7XX.memory,
+        AdrenoArch::A8xx => FALLBACK_A8XX.memory,
+    }
+}
+
+/// Find GPU specifications by chip ID
+///
+/// Falls back to fixed per-series entries for unknown chips; query paths use
+/// [`lookup_adreno_specs`], which extrapolates from the nearest family
+/// member instead. Usable in `const` contexts, so builds targeting known hardware can bake
+/// the specs in:
+///
+/// ```
+/// use armgpuinfo_core::adreno::{find_adreno_specs, AdrenoSpecs};
+///
+/// const A740: &AdrenoSpecs = match find_adreno_specs(0x0706_0001) {
+///     Some(specs) => specs,
+///     None => panic!("unknown chip"),
+/// };
+/// assert_eq!(A740.name, "Adreno 740");
+/// ```
+pub const fn find_adreno_specs(chip_id: u32) -> Option<&'static AdrenoSpecs> {
+    if let Some(specs) = find_known_adreno_specs(chip_id) {
+        return Some(specs);
+    }
+    series_fallback(chip_id)
+}
+
+/// Specs for `chip_id`, extrapolated for chips newer than the database
+///
+/// Known chips return their entry. Unknown chips copy the specs of the
+/// family member (same series) with the nearest model number, under the
+/// generic series name and [`SpecConfidence::Extrapolated`]. Series without
+/// any known member use the fixed heuristic entry.
+///
+/// ```
+/// use armgpuinfo_core::adreno::{lookup_adreno_specs, SpecConfidence};
+///
+/// // Newer than any 7xx chip in the database; nearest is Adreno 740 (0x0706)
+/// let specs = lookup_adreno_specs(0x0709_0000).unwrap();
+/// assert_eq!(specs.confidence, SpecConfidence::Extrapolated);
+/// assert_eq!(specs.shader_cores, 6);
+/// ```
+pub const fn lookup_adreno_specs(chip_id: u32) -> Option<AdrenoSpecs> {
+    if let Some(specs) = find_known_adreno_specs(chip_id) {
+        return Some(*specs);
+    }
+    if let Some(specs) = extrapolate_adreno_specs(chip_id) {
+        return Some(specs);
+    }
+    match series_fallback(chip_id) {
+        Some(specs) => Some(*specs),
+        None => None,
+    }
+}
+
+/// Copy the nearest same-series entry, `None` if the series has no entries
+pub const fn extrapolate_adreno_specs(chip_id: u32) -> Option<AdrenoSpecs> {
+    let series = chip_id >> 24;
+    let model = (chip_id >> 16) & 0xFF;
+    let mut best: Option<&AdrenoSpecs> = None;
+    let mut best_distance = u32::MAX;
+    let mut i = 0;
+    while i < ADRENO_CHIPS.len() {
+        let (id, ref specs) = ADRENO_CHIPS[i];
+        if id >> 24 == series {
+            let distance = ((id >> 16) & 0xFF).abs_diff(model);
+            if distance < best_distance {
+                best = Some(specs);
+                best_distance = distance;
+            }
+        }
+        i += 1;
+    }
+
+    let (Some(nearest), Some(fallback)) = (best, series_fallback(chip_id)) else {
+        return None;
+    };
+    let mut specs = *nearest;
+    specs.name = fallback.name;
+    specs.snapdragon_models = &[];
+    specs.confidence = SpecConfidence::Extrapolated;
+    Some(specs)
+}
+
+/// Exact or base-ID (core/major) database match
+const fn find_known_adreno_specs(chip_id: u32) -> Option<&'static AdrenoSpecs> {
+    // 1. Exact match
+    let mut i = 0;
+    while i < ADRENO_CHIPS.len() {
+        if ADRENO_CHIPS[i].0 == chip_id {
+            return Some(&ADRENO_CHIPS[i].1);
+        }
+        i += 1;
+    }
+
+    // 2. Base ID match (major/minor)
+    let base_id = chip_id & 0xFFFF0000;
+    let mut i = 0;
+    while i < ADRENO_CHIPS.len() {
+        if (ADRENO_CHIPS[i].0 & 0xFFFF0000) == base_id {
+            return Some(&ADRENO_CHIPS[i].1);
+        }
+        i += 1;
+    }
+
+    None
+}
+
+/// Generic per-series entry
+const fn series_fallback(chip_id: u32) -> Option<&'static AdrenoSpecs> {
+    match (chip_id >> 24) & 0xFF {
+        8 => Some(&FALLBACK_A8XX),
+        7 => Some(&FALLBACK_A7XX),
+        6 => Some(&FALLBACK_A6XX),
+        5 => Some(&FALLBACK_A5XX),
+        4 => Some(&FALLBACK_A4XX),
+        _ => None,
+    }
+}
@@ -0,0 +1,13 @@
+//! KGSL device info parser and Adreno chip database
+
+mod database;
+mod parser;
+
+pub use database::{
+    default_apis, default_memory, default_support, extrapolate_adreno_specs, find_adreno_specs,
+    lookup_adreno_specs, AdrenoArch, AdrenoMemory, AdrenoSpecs, MemoryType, SpecConfidence, ADRENO_CHIPS,
+};
+pub use parser::{
+    parse_device_info, parse_device_info_lenient, parse_device_info_strict, KgslPropertyId,
+    KgslPropertyParser, ParsedDeviceInfo, ParserConfig,
+};
@@ -0,0 +1,351 @@
+//! Parser for KGSL property structures
+//! Consistent with Mali parser.rs architecture
+
+use crate::error::ParseError;
+
+/// KGSL Property IDs (`KGSL_PROP_*` from the msm_kgsl.h UAPI)
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KgslPropertyId {
+    DeviceInfo = 0x1,
+    DeviceShadow = 0x2,
+    DevicePower = 0x3,
+    Shmem = 0x4,
+    ShmemApertures = 0x5,
+    MmuEnable = 0x6,
+    InterruptWaits = 0x7,
+    Version = 0x8,
+    GpuResetStat = 0x9,
+    Pwrctrl = 0xE,
+    PwrConstraint = 0x12,
+    UcheGmemVaddr = 0x13,
+    SpGenericMem = 0x14,
+    UcodeVersion = 0x15,
+    GpmuVersion = 0x16,
+    HighestBankBit = 0x17,
+    DeviceBitness = 0x18,
+    DeviceQdssStm = 0x19,
+    MinAccessLength = 0x1A,
+    UbwcMode = 0x1B,
+    DeviceQtimer = 0x20,
+    L3PwrConstraint = 0x22,
+    SecureBufferAlignment = 0x23,
+    SecureCtxtSupport = 0x24,
+    SpeedBin = 0x25,
+    GamingBin = 0x26,
+    QueryCapabilities = 0x27,
+    ContextProperty = 0x28,
+    GpuModel = 0x29,
+    VkDeviceId = 0x2A,
+    IsLpacEnabled = 0x2B,
+    GpuVa64Size = 0x2C,
+    IsRaytracingEnabled = 0x2D,
+    IsFastblendEnabled = 0x2E,
+    UcheTrapBase = 0x2F,
+    IsAqeEnabled = 0x30,
+    GpuSecureVaSize = 0x31,
+    GpuSecureVaInuse = 0x32,
+}
+
+impl TryFrom<u32> for KgslPropertyId {
+    type Error = ();
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0x1 => Ok(KgslPropertyId::DeviceInfo),
+            0x2 => Ok(KgslPropertyId::DeviceShadow),
+            0x3 => Ok(KgslPropertyId::DevicePower),
+            0x4 => Ok(KgslPropertyId::Shmem),
+            0x5 => Ok(KgslPropertyId::ShmemApertures),
+            0x6 => Ok(KgslPropertyId::MmuEnable),
+            0x7 => Ok(KgslPropertyId::InterruptWaits),
+            0x8 => Ok(KgslPropertyId::Version),
+            0x9 => Ok(KgslPropertyId::GpuResetStat),
+            0xE => Ok(KgslPropertyId::Pwrctrl),
+            0x12 => Ok(KgslPropertyId::PwrConstraint),
+            0x13 => Ok(KgslPropertyId::UcheGmemVaddr),
+            0x14 => Ok(KgslPropertyId::SpGenericMem),
+            0x15 => Ok(KgslPropertyId::UcodeVersion),
+            0x16 => Ok(KgslPropertyId::GpmuVersion),
+            0x17 => Ok(KgslPropertyId::HighestBankBit),
+            0x18 => Ok(KgslPropertyId::DeviceBitness),
+            0x19 => Ok(KgslPropertyId::DeviceQdssStm),
+            0x1A => Ok(KgslPropertyId::MinAccessLength),
+            0x1B => Ok(KgslPropertyId::UbwcMode),
+            0x20 => Ok(KgslPropertyId::DeviceQtimer),
+            0x22 => Ok(KgslPropertyId::L3PwrConstraint),
+            0x23 => Ok(KgslPropertyId::SecureBufferAlignment),
+            0x24 => Ok(KgslPropertyId::SecureCtxtSupport),
+            0x25 => Ok(KgslPropertyId::SpeedBin),
+            0x26 => Ok(KgslPropertyId::GamingBin),
+            0x27 => Ok(KgslPropertyId::QueryCapabilities),
+            0x28 => Ok(KgslPropertyId::ContextProperty),
+            0x29 => Ok(KgslPropertyId::GpuModel),
+            0x2A => Ok(KgslPropertyId::VkDeviceId),
+            0x2B => Ok(KgslPropertyId::IsLpacEnabled),
+            0x2C => Ok(KgslPropertyId::GpuVa64Size),
+            0x2D => Ok(KgslPropertyId::IsRaytracingEnabled),
+            0x2E => Ok(KgslPropertyId::IsFastblendEnabled),
+            0x2F => Ok(KgslPropertyId::UcheTrapBase),
+            0x30 => Ok(KgslPropertyId::IsAqeEnabled),
+            0x31 => Ok(KgslPropertyId::GpuSecureVaSize),
+            0x32 => Ok(KgslPropertyId::GpuSecureVaInuse),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Parser configuration for different modes
+#[derive(Debug, Clone, Copy)]
+pub struct ParserConfig {
+    /// Skip unknown properties instead of erroring
+    pub lenient_mode: bool,
+    /// Validate chip ID format and ranges
+    pub validate_chip_id: bool,
+    /// Require all mandatory properties
+    pub require_mandatory: bool,
+    /// Allow zero values for certain properties
+    pub allow_zero_values: bool,
+}
+
+impl ParserConfig {
+    /// Configuration for Parity mode (matches existing behavior)
+    pub const PARITY: Self = Self {
+        lenient_mode: true,
+        validate_chip_id: false,
+        require_mandatory: false,
+        allow_zero_values: true,
+    };
+
+    /// Configuration for Extended mode (strict validation)
+    pub const EXTENDED: Self = Self {
+        lenient_mode: false,
+        validate_chip_id: true,
+        require_mandatory: true,
+        allow_zero_values: false,
+    };
+}
+
+/// KGSL Device Info structure with parsed fields
+#[non_exhaustive]
+#[derive(Debug, Clone, Default)]
+pub struct ParsedDeviceInfo {
+    /// Raw device ID from driver
+    pub device_id: u32,
+    /// Chip ID (includes architecture, generation, revision)
+    pub chip_id: u32,
+    /// MMU enabled flag
+    pub mmu_enabled: bool,
+    /// GPU memory base address
+    pub gmem_baseaddr: u32,
+    /// GPU memory size in bytes
+    pub gmem_sizebytes: u32,
+    /// GPU model code
+    pub gpu_model: u32,
+    
+    // Additional validated fields
+    /// Architecture major version (extracted from chip_id)
+    pub arch_major: u8,
+    /// Architecture minor version
+    pub arch_minor: u8,
+    /// Generation (Adreno 6xx, 7xx, etc.)
+    pub generation: u8,
+    /// Revision
+    pub revision: u8,
+}
+
+impl ParsedDeviceInfo {
+    /// Device info holding only `chip_id`, with the architecture fields
+    /// extracted from it
+    ///
+    /// For sources that report the chip ID alone (msm DRM, sysfs, renderer
+    /// strings); set the other fields on the result.
+    pub fn with_chip_id(chip_id: u32) -> Self {
+        let mut info = Self { chip_id, ..Self::default() };
+        // Only splits the ID, never fails
+        let _ = info.extract_architecture();
+        info
+    }
+
+    /// Extract architecture from chip ID
+    /// Format: 0xAABBCCDD where:
+    ///   AA = architecture major
+    ///   BB = architecture minor  
+    ///   CC = generation
+    ///   DD = revision
+    pub fn extract_architecture(&mut self) -> Result<(), ParseError> {
+        self.arch_major = ((self.chip_id >> 24) & 0xFF) as u8;
+        self.arch_minor = ((self.chip_id >> 16) & 0xFF) as u8;
+        self.generation = ((self.chip_id >> 8) & 0xFF) as u8;
+        self.revision = (self.chip_id & 0xFF) as u8;
+        
+        Ok(())
+    }
+    
+    /// Validate chip ID structure
+    pub fn validate_chip_id(&self) -> Result<(), ParseError> {
+        // Basic validation rules
+        if self.chip_id == 0 {
+            return Err(ParseError::InvalidData("Chip ID is zero"));
+        }
+        
+        // Check reasonable ranges
+        let major = self.arch_major;
+        if !(4..=9).contains(&major) {  // Adreno 4xx-9xx range (database coverage)
+            return Err(ParseError::UnsupportedArchitecture {
+                chip_id: self.chip_id,
+                series: major,
+            });
+        }
+        
+        Ok(())
+    }
+}
+
+/// Unified parser for KGSL properties
+pub struct KgslPropertyParser<'a> {
+    /// Raw property buffer
+    buffer: &'a [u8],
+    /// Current position in buffer
+    pos: usize,
+    /// Parser configuration
+    config: ParserConfig,
+}
+
+impl<'a> KgslPropertyParser<'a> {
+    /// Create a new parser
+    pub fn new(buffer: &'a [u8], config: ParserConfig) -> Self {
+        Self {
+            buffer,
+            pos: 0,
+            config,
+        }
+    }
+    
+    /// Parse device info structure directly (for DEVICE_INFO property)
+    pub fn parse_device_info(mut self) -> Result<ParsedDeviceInfo, ParseError> {
+        // KGSL_DEVICE_INFO is a fixed structure
+        if self.buffer.len() < core::mem::size_of::<RawDeviceInfo>() {
+            if self.config.lenient_mode {
+                return Ok(ParsedDeviceInfo::default());
+            } else {
+                return Err(ParseError::BufferTooSmall {
+                    expected: core::mem::size_of::<RawDeviceInfo>(),
+                    actual: self.buffer.len(),
+                });
+            }
+        }
+        
+        // Parse raw structure (little-endian)
+        let raw = self.parse_raw_device_info()?;
+        
+        // Populate parsed info
+        let mut info = ParsedDeviceInfo {
+            device_id: raw.device_id,
+            chip_id: raw.chip_id,
+            mmu_enabled: raw.mmu_enabled != 0,
+            gmem_baseaddr: raw.gmem_gpubaseaddr,
+            gmem_sizebytes: raw.gmem_sizebytes,
+            gpu_model: raw.gpu_model,
+            ..Default::default()
+        };
+        
+        // Extract architecture
+        info.extract_architecture()?;
+        
+        // Validate if configured
+        if self.config.validate_chip_id {
+            info.validate_chip_id()?;
+        }
+        
+        // Check mandatory fields
+        if self.config.require_mandatory {
+            if info.chip_id == 0 {
+                return Err(ParseError::InvalidData("Missing mandatory chip ID"));
+            }
+            if info.gmem_sizebytes == 0 && !self.config.allow_zero_values {
+                return Err(ParseError::InvalidData("GPU memory size is zero"));
+            }
+        }
+        
+        Ok(info)
+    }
+    
+    /// Parse raw device info structure from buffer
+    fn parse_raw_device_info(&mut self) -> Result<RawDeviceInfo, ParseError> {
+        // Parse each field (little-endian)
+        let mut raw = RawDeviceInfo {
+            device_id: self.read_u32()?,
+            chip_id: self.read_u32()?,
+            mmu_enabled: self.read_u32()?,
+            gmem_gpubaseaddr: self.read_u32()?,
+            gmem_sizebytes: self.read_u32()?,
+            ..Default::default()
+        };
+        
+        // Skip unknown fields if present
+        let remaining = self.buffer.len() - self.pos;
+        if remaining >= 8 {
+            raw.unknown1 = self.read_u32()?;
+            raw.unknown2 = self.read_u32()?;
+        }
+        
+        if remaining >= 12 {
+            raw.gpu_model = self.read_u32()?;
+        }
+        
+        Ok(raw)
+    }
+    
+    /// Read u32 from buffer (little-endian)
+    fn read_u32(&mut self) -> Result<u32, ParseError> {
+        if self.pos + 4 > self.buffer.len() {
+            if self.config.lenient_mode {
+                return Ok(0);
+            } else {
+                return Err(ParseError::BufferTooSmall {
+                    expected: self.pos + 4,
+                    actual: self.buffer.len(),
+                });
+            }
+        }
+        
+        let bytes = &self.buffer[self.pos..self.pos + 4];
+        self.pos += 4;
+        
+        Ok(u32::from_le_bytes(bytes.try_into().map_err(|_| {
+            ParseError::InvalidData("Failed to parse u32 from buffer")
+        })?))
+    }
+}
+
+// Fixed eight-word device info record, the kernel's `kgsl_devinfo`
+// re-encoded with 32-bit words
+#[repr(C)]
+#[derive(Debug, Clone, Default)]
+struct RawDeviceInfo {
+    device_id: u32,
+    chip_id: u32,
+    mmu_enabled: u32,
+    gmem_gpubaseaddr: u32,
+    gmem_sizebytes: u32,
+    unknown1: u32,
+    unknown2: u32,
+    gpu_model: u32,
+}
+
+/// Parse KGSL device info buffer with configuration
+pub fn parse_device_info(buffer: &[u8], config: ParserConfig) -> Result<ParsedDeviceInfo, ParseError> {
+    let parser = KgslPropertyParser::new(buffer, config);
+    parser.parse_device_info()
+}
+
+/// Parse KGSL device info buffer (Extended mode - strict with validation)
+pub fn parse_device_info_strict(buffer: &[u8]) -> Result<ParsedDeviceInfo, ParseError> {
+    parse_device_info(buffer, ParserConfig::EXTENDED)
+}
+
+/// Parse KGSL device info buffer (Parity mode - lenient, matches existing behavior)
+pub fn parse_device_info_lenient(buffer: &[u8]) -> ParsedDeviceInfo {
+    parse_device_info(buffer, ParserConfig::PARITY).unwrap_or_default()
+}
@@ -0,0 +1,49 @@
+use core::fmt;
+
+/// Error decoding a driver buffer
+///
+/// Mirrors the parsing variants of `armgpuinfo::GpuError`, which converts
+/// from it one to one.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// Invalid or malformed data in the buffer
+    InvalidData(&'static str),
+
+    /// Invalid property size encountered
+    InvalidPropertySize(u32),
+
+    /// Buffer too small for data
+    BufferTooSmall {
+        /// Minimum expected buffer size
+        expected: usize,
+        /// Actual buffer size
+        actual: usize,
+    },
+
+    /// Chip ID of an Adreno series outside the database
+    UnsupportedArchitecture {
+        /// Chip ID
+        chip_id: u32,
+        /// Series digit, 3 for the Adreno 3xx
+        series: u8,
+    },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::InvalidData(details) => write!(f, "Invalid data: {details}"),
+            ParseError::InvalidPropertySize(size) => write!(f, "Invalid property size: {size}"),
+            ParseError::BufferTooSmall { expected, actual } => {
+                write!(f, "Buffer too small: expected at least {expected} bytes, got {actual}")
+            }
+            ParseError::UnsupportedArchitecture { chip_id, series } => write!(
+                f,
+                "Unsupported GPU architecture: chip_id=0x{chip_id:08X}, architecture=Adreno {series}xx"
+            ),
+        }
+    }
+}
+
+impl core::error::Error for ParseError {}
@@ -0,0 +1,47 @@
+//! I/O-free core of armgpuinfo
+//!
+//! The kbase property buffer and KGSL device info parsers, the Mali and
+//! Adreno product databases and the SoC table, without the ioctl layer.
+//! `no_std` with `alloc`, for firmware and other environments that get the
+//! buffers some other way. `armgpuinfo` re-exports everything here at its
+//! usual paths and wraps the errors in its `GpuError`.
+//!
+//! ```
+//! # #[cfg(feature = "mali")] {
+//! use armgpuinfo_core::mali::{get_gpu_id, lookup_product, parse_properties_strict};
+//!
+//! // PRODUCT_ID 0xa867 (u32), COHERENCY_NUM_CORE_GROUPS 1 (u8),
+//! // COHERENCY_GROUP_0 with four cores (u64)
+//! let mut buffer = Vec::new();
+//! buffer.extend_from_slice(&((1u32 << 2) | 2).to_le_bytes());
+//! buffer.extend_from_slice(&0xa867u32.to_le_bytes());
+//! buffer.extend_from_slice(&(62u32 << 2).to_le_bytes());
+//! buffer.push(1);
+//! buffer.extend_from_slice(&((64u32 << 2) | 3).to_le_bytes());
+//! buffer.extend_from_slice(&0b1111u64.to_le_bytes());
+//!
+//! let props = parse_properties_strict(&buffer)?;
+//! let product = lookup_product(get_gpu_id(props.gpu_id), props.num_shader_cores).unwrap();
+//! assert_eq!(product.name, "Mali-G610");
+//! # }
+//! # Ok::<(), armgpuinfo_core::ParseError>(())
+//! ```
+
+#![no_std]
+
+extern crate alloc;
+
+pub mod error;
+pub mod soc;
+pub mod types;
+
+#[cfg(feature = "mali")]
+pub mod mali;
+
+#[cfg(feature = "adreno")]
+pub mod adreno;
+
+pub use error::ParseError;
+pub use types::{
+    AdrenoSlices, ApiLevels, ApiVersion, DriverSupport, MaliArch, MaliHwConfig, SpecConfidence, SupportStatus,
+};
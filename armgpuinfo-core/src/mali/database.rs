@@ -0,0 +1,772 @@
+use alloc::borrow::Cow;
+
+use crate::types::{ApiLevels, ApiVersion, DriverSupport, MaliArch, SupportStatus};
+
+/// Mali product database entry
+#[derive(Debug)]
+pub struct ProductEntry {
+    pub id: u32,
+    pub mask: u32,
+    pub min_cores: u32,
+    pub name: &'static str,
+    pub architecture: MaliArch,
+    pub get_num_fp32_fmas_per_engine: fn(u32, u32, u32) -> u32,
+    pub get_num_texels: fn(u32, u32, u32) -> u32,
+    pub get_num_pixels: fn(u32, u32, u32) -> u32,
+    pub get_num_exec_engines: fn(u32, u32, u32) -> u32,
+    pub support: DriverSupport,
+    /// Newest APIs of the last vendor driver release
+    pub apis: ApiLevels,
+}
+
+// Helper für Cow-Konvertierung
+impl ProductEntry {
+    pub fn name_as_cow(&self) -> Cow<'static, str> {
+        Cow::Borrowed(self.name)
+    }
+    
+    pub fn architecture_as_cow(&self) -> Cow<'static, str> {
+        Cow::Borrowed(self.architecture.as_str())
+    }
+}
+
+/// Newest kbase DDK release the support table was checked against
+const KBASE_LATEST: &str = "r54p0";
+
+/// Midgard left the kbase DDK after r28p0; Panfrost covers T720 onwards
+const MIDGARD_EOL: DriverSupport = DriverSupport::new(SupportStatus::EndOfLife, "r28p0");
+const MIDGARD_COMMUNITY: DriverSupport = DriverSupport::new(SupportStatus::CommunityOnly, "r28p0");
+const KBASE_SUPPORTED: DriverSupport = DriverSupport::new(SupportStatus::VendorSupported, KBASE_LATEST);
+
+const GLES_3_0: ApiVersion = ApiVersion::new(3, 0);
+const GLES_3_1: ApiVersion = ApiVersion::new(3, 1);
+const GLES_3_2: ApiVersion = ApiVersion::new(3, 2);
+
+/// The T604 and T620 predate Vulkan; T720 onwards got Vulkan 1.0 drivers
+const T600_APIS: ApiLevels = ApiLevels::new(None, GLES_3_1);
+const T620_APIS: ApiLevels = ApiLevels::new(None, GLES_3_0);
+const T720_APIS: ApiLevels = ApiLevels::new(Some(ApiVersion::new(1, 0)), GLES_3_1);
+const VULKAN_1_0_APIS: ApiLevels = ApiLevels::new(Some(ApiVersion::new(1, 0)), GLES_3_2);
+const VULKAN_1_1_APIS: ApiLevels = ApiLevels::new(Some(ApiVersion::new(1, 1)), GLES_3_2);
+const VULKAN_1_2_APIS: ApiLevels = ApiLevels::new(Some(ApiVersion::new(1, 2)), GLES_3_2);
+const VULKAN_1_3_APIS: ApiLevels = ApiLevels::new(Some(ApiVersion::new(1, 3)), GLES_3_2);
+const VULKAN_1_4_APIS: ApiLevels = ApiLevels::new(Some(ApiVersion::new(1, 4)), GLES_3_2);
+
+const MASK_OLD: u32 = 0xFFFF;
+const MASK_NEW: u32 = 0xF00F;
+
+// Helper functions for product database
+pub fn get_num_1(_: u32, _: u32, _: u32) -> u32 { 1 }
+pub fn get_num_2(_: u32, _: u32, _: u32) -> u32 { 2 }
+pub fn get_num_3(_: u32, _: u32, _: u32) -> u32 { 3 }
+pub fn get_num_4(_: u32, _: u32, _: u32) -> u32 { 4 }
+pub fn get_num_8(_: u32, _: u32, _: u32) -> u32 { 8 }
+pub fn get_num_16(_: u32, _: u32, _: u32) -> u32 { 16 }
+pub fn get_num_32(_: u32, _: u32, _: u32) -> u32 { 32 }
+pub fn get_num_64(_: u32, _: u32, _: u32) -> u32 { 64 }
+
+pub fn get_num_eng_g31(core_count: u32, _: u32, thread_features: u32) -> u32 {
+    if core_count == 1 && (thread_features & 0xFFFF) == 0x2000 { 1 } else { 2 }
+}
+
+pub fn get_num_eng_g51(core_count: u32, _: u32, thread_features: u32) -> u32 {
+    if core_count == 1 && (thread_features & 0xFFFF) == 0x2000 { 1 } else { 3 }
+}
+
+pub fn get_num_eng_g52(_: u32, core_features: u32, _: u32) -> u32 { core_features & 0xF }
+
+pub fn get_num_fma_g510(_: u32, core_features: u32, _: u32) -> u32 {
+    let variant = core_features & 0xF;
+    match variant { 0 => 16, 2 | 3 => 24, _ => 32 }
+}
+
+pub fn get_num_tex_g510(_: u32, core_features: u32, _: u32) -> u32 {
+    let variant = core_features & 0xF;
+    match variant { 0 | 5 => 2, 1 | 2 | 6 => 4, _ => 8 }
+}
+
+pub fn get_num_pix_g510(_: u32, core_features: u32, _: u32) -> u32 {
+    let variant = core_features & 0xF;
+    match variant { 0 | 1 | 5 | 6 => 2, _ => 4 }
+}
+
+pub fn get_num_eng_g510(_: u32, core_features: u32, _: u32) -> u32 {
+    let variant = core_features & 0xF;
+    match variant { 0 | 1 | 5 | 6 => 1, _ => 2 }
+}
+
+static PRODUCT_VERSIONS: [ProductEntry; 38] = [
+    // Mali-T600 series
+    ProductEntry {
+        id: 0x6956,
+        mask: MASK_OLD,
+        min_cores: 1,
+        name: "Mali-T600",
+        architecture: MaliArch::Midgard,
+        get_num_fp32_fmas_per_engine: get_num_4,
+        get_num_texels: get_num_1,
+        get_num_pixels: get_num_1,
+        get_num_exec_engines: get_num_2,
+        support: MIDGARD_EOL,
+        apis: T600_APIS,
+    },
+    ProductEntry {
+        id: 0x0620,
+        mask: MASK_OLD,
+        min_cores: 1,
+        name: "Mali-T620",
+        architecture: MaliArch::Midgard,
+        get_num_fp32_fmas_per_engine: get_num_4,
+        get_num_texels: get_num_1,
+        get_num_pixels: get_num_1,
+        get_num_exec_engines: get_num_2,
+        support: MIDGARD_EOL,
+        apis: T620_APIS,
+    },
+    ProductEntry {
+        id: 0x0720,
+        mask: MASK_OLD,
+        min_cores: 1,
+        name: "Mali-T720",
+        architecture: MaliArch::Midgard,
+        get_num_fp32_fmas_per_engine: get_num_4,
+        get_num_texels: get_num_1,
+        get_num_pixels: get_num_1,
+        get_num_exec_engines: get_num_1,
+        support: MIDGARD_COMMUNITY,
+        apis: T720_APIS,
+    },
+    ProductEntry {
+        id: 0x0750,
+        mask: MASK_OLD,
+        min_cores: 1,
+        name: "Mali-T760",
+        architecture: MaliArch::Midgard,
+        get_num_fp32_fmas_per_engine: get_num_4,
+        get_num_texels: get_num_1,
+        get_num_pixels: get_num_1,
+        get_num_exec_engines: get_num_2,
+        support: MIDGARD_COMMUNITY,
+        apis: VULKAN_1_0_APIS,
+    },
+    ProductEntry {
+        id: 0x0820,
+        mask: MASK_OLD,
+        min_cores: 1,
+        name: "Mali-T820",
+        architecture: MaliArch::Midgard,
+        get_num_fp32_fmas_per_engine: get_num_4,
+        get_num_texels: get_num_1,
+        get_num_pixels: get_num_1,
+        get_num_exec_engines: get_num_1,
+        support: MIDGARD_COMMUNITY,
+        apis: VULKAN_1_0_APIS,
+    },
+    ProductEntry {
+        id: 0x0830,
+        mask: MASK_OLD,
+        min_cores: 1,
+        name: "Mali-T830",
+        architecture: MaliArch::Midgard,
+        get_num_fp32_fmas_per_engine: get_num_4,
+        get_num_texels: get_num_1,
+        get_num_pixels: get_num_1,
+        get_num_exec_engines: get_num_2,
+        support: MIDGARD_COMMUNITY,
+        apis: VULKAN_1_0_APIS,
+    },
+    ProductEntry {
+        id: 0x0860,
+        mask: MASK_OLD,
+        min_cores: 1,
+        name: "Mali-T860",
+        architecture: MaliArch::Midgard,
+        get_num_fp32_fmas_per_engine: get_num_4,
+        get_num_texels: get_num_1,
+        get_num_pixels: get_num_1,
+        get_num_exec_engines: get_num_2,
+        support: MIDGARD_COMMUNITY,
+        apis: VULKAN_1_0_APIS,
+    },
+    ProductEntry {
+        id: 0x0880,
+        mask: MASK_OLD,
+        min_cores: 1,
+        name: "Mali-T880",
+        architecture: MaliArch::Midgard,
+        get_num_fp32_fmas_per_engine: get_num_4,
+        get_num_texels: get_num_1,
+        get_num_pixels: get_num_1,
+        get_num_exec_engines: get_num_3,
+        support: MIDGARD_COMMUNITY,
+        apis: VULKAN_1_0_APIS,
+    },
+
+    // Mali-G71/G72 (Bifrost)
+    ProductEntry {
+        id: 0x6000,
+        mask: MASK_NEW,
+        min_cores: 1,
+        name: "Mali-G71",
+        architecture: MaliArch::Bifrost,
+        get_num_fp32_fmas_per_engine: get_num_4,
+        get_num_texels: get_num_1,
+        get_num_pixels: get_num_1,
+        get_num_exec_engines: get_num_3,
+        support: KBASE_SUPPORTED,
+        apis: VULKAN_1_1_APIS,
+    },
+    ProductEntry {
+        id: 0x6001,
+        mask: MASK_NEW,
+        min_cores: 1,
+        name: "Mali-G72",
+        architecture: MaliArch::Bifrost,
+        get_num_fp32_fmas_per_engine: get_num_4,
+        get_num_texels: get_num_1,
+        get_num_pixels: get_num_1,
+        get_num_exec_engines: get_num_3,
+        support: KBASE_SUPPORTED,
+        apis: VULKAN_1_1_APIS,
+    },
+
+    // Mali-G51/G76/G52/G31 (Bifrost)
+    ProductEntry {
+        id: 0x7000,
+        mask: MASK_NEW,
+        min_cores: 1,
+        name: "Mali-G51",
+        architecture: MaliArch::Bifrost,
+        get_num_fp32_fmas_per_engine: get_num_4,
+        get_num_texels: get_num_2,
+        get_num_pixels: get_num_2,
+        get_num_exec_engines: get_num_eng_g51,
+        support: KBASE_SUPPORTED,
+        apis: VULKAN_1_1_APIS,
+    },
+    ProductEntry {
+        id: 0x7001,
+        mask: MASK_NEW,
+        min_cores: 1,
+        name: "Mali-G76",
+        architecture: MaliArch::Bifrost,
+        get_num_fp32_fmas_per_engine: get_num_8,
+        get_num_texels: get_num_2,
+        get_num_pixels: get_num_2,
+        get_num_exec_engines: get_num_3,
+        support: KBASE_SUPPORTED,
+        apis: VULKAN_1_1_APIS,
+    },
+    ProductEntry {
+        id: 0x7002,
+        mask: MASK_NEW,
+        min_cores: 1,
+        name: "Mali-G52",
+        architecture: MaliArch::Bifrost,
+        get_num_fp32_fmas_per_engine: get_num_8,
+        get_num_texels: get_num_2,
+        get_num_pixels: get_num_2,
+        get_num_exec_engines: get_num_eng_g52,
+        support: KBASE_SUPPORTED,
+        apis: VULKAN_1_1_APIS,
+    },
+    ProductEntry {
+        id: 0x7003,
+        mask: MASK_NEW,
+        min_cores: 1,
+        name: "Mali-G31",
+        architecture: MaliArch::Bifrost,
+        get_num_fp32_fmas_per_engine: get_num_4,
+        get_num_texels: get_num_2,
+        get_num_pixels: get_num_2,
+        get_num_exec_engines: get_num_eng_g31,
+        support: KBASE_SUPPORTED,
+        apis: VULKAN_1_1_APIS,
+    },
+
+    // Mali-G77/G57/G68/G78 (Valhall)
+    ProductEntry {
+        id: 0x9000,
+        mask: MASK_NEW,
+        min_cores: 1,
+        name: "Mali-G77",
+        architecture: MaliArch::Valhall,
+        get_num_fp32_fmas_per_engine: get_num_16,
+        get_num_texels: get_num_4,
+        get_num_pixels: get_num_2,
+        get_num_exec_engines: get_num_2,
+        support: KBASE_SUPPORTED,
+        apis: VULKAN_1_1_APIS,
+    },
+    ProductEntry {
+        id: 0x9001,
+        mask: MASK_NEW,
+        min_cores: 1,
+        name: "Mali-G57",
+        architecture: MaliArch::Valhall,
+        get_num_fp32_fmas_per_engine: get_num_16,
+        get_num_texels: get_num_4,
+        get_num_pixels: get_num_2,
+        get_num_exec_engines: get_num_2,
+        support: KBASE_SUPPORTED,
+        apis: VULKAN_1_1_APIS,
+    },
+    ProductEntry {
+        id: 0x9003,
+        mask: MASK_NEW,
+        min_cores: 1,
+        name: "Mali-G57",
+        architecture: MaliArch::Valhall,
+        get_num_fp32_fmas_per_engine: get_num_16,
+        get_num_texels: get_num_4,
+        get_num_pixels: get_num_2,
+        get_num_exec_engines: get_num_2,
+        support: KBASE_SUPPORTED,
+        apis: VULKAN_1_1_APIS,
+    },
+    ProductEntry {
+        id: 0x9004,
+        mask: MASK_NEW,
+        min_cores: 1,
+        name: "Mali-G68",
+        architecture: MaliArch::Valhall,
+        get_num_fp32_fmas_per_engine: get_num_16,
+        get_num_texels: get_num_4,
+        get_num_pixels: get_num_2,
+        get_num_exec_engines: get_num_2,
+        support: KBASE_SUPPORTED,
+        apis: VULKAN_1_2_APIS,
+    },
+    ProductEntry {
+        id: 0x9002,
+        mask: MASK_NEW,
+        min_cores: 1,
+        name: "Mali-G78",
+        architecture: MaliArch::Valhall,
+        get_num_fp32_fmas_per_engine: get_num_16,
+        get_num_texels: get_num_4,
+        get_num_pixels: get_num_2,
+        get_num_exec_engines: get_num_2,
+        support: KBASE_SUPPORTED,
+        apis: VULKAN_1_2_APIS,
+    },
+    ProductEntry {
+        id: 0x9005,
+        mask: MASK_NEW,
+        min_cores: 1,
+        name: "Mali-G78AE",
+        architecture: MaliArch::Valhall,
+        get_num_fp32_fmas_per_engine: get_num_16,
+        get_num_texels: get_num_4,
+        get_num_pixels: get_num_2,
+        get_num_exec_engines: get_num_2,
+        support: KBASE_SUPPORTED,
+        apis: VULKAN_1_2_APIS,
+    },
+
+    // Mali-G710/G610 (Valhall)
+    ProductEntry {
+        id: 0xa002,
+        mask: MASK_NEW,
+        min_cores: 1,
+        name: "Mali-G710",
+        architecture: MaliArch::Valhall,
+        get_num_fp32_fmas_per_engine: get_num_32,
+        get_num_texels: get_num_8,
+        get_num_pixels: get_num_4,
+        get_num_exec_engines: get_num_2,
+        support: KBASE_SUPPORTED,
+        apis: VULKAN_1_3_APIS,
+    },
+    ProductEntry {
+        id: 0xa007,
+        mask: MASK_NEW,
+        min_cores: 1,
+        name: "Mali-G610",
+        architecture: MaliArch::Valhall,
+        get_num_fp32_fmas_per_engine: get_num_32,
+        get_num_texels: get_num_8,
+        get_num_pixels: get_num_4,
+        get_num_exec_engines: get_num_2,
+        support: KBASE_SUPPORTED,
+        apis: VULKAN_1_3_APIS,
+    },
+
+    // Mali-G510/G310 (Valhall)
+    ProductEntry {
+        id: 0xa003,
+        mask: MASK_NEW,
+        min_cores: 1,
+        name: "Mali-G510",
+        architecture: MaliArch::Valhall,
+        get_num_fp32_fmas_per_engine: get_num_fma_g510,
+        get_num_texels: get_num_tex_g510,
+        get_num_pixels: get_num_pix_g510,
+        get_num_exec_engines: get_num_eng_g510,
+        support: KBASE_SUPPORTED,
+        apis: VULKAN_1_3_APIS,
+    },
+    ProductEntry {
+        id: 0xa004,
+        mask: MASK_NEW,
+        min_cores: 1,
+        name: "Mali-G310",
+        architecture: MaliArch::Valhall,
+        get_num_fp32_fmas_per_engine: get_num_fma_g510,
+        get_num_texels: get_num_tex_g510,
+        get_num_pixels: get_num_pix_g510,
+        get_num_exec_engines: get_num_eng_g510,
+        support: KBASE_SUPPORTED,
+        apis: VULKAN_1_3_APIS,
+    },
+
+    // Immortalis-G715/Mali-G715/G615
+    ProductEntry {
+        id: 0xb002,
+        mask: MASK_NEW,
+        min_cores: 10,
+        name: "Immortalis-G715",
+        architecture: MaliArch::Valhall,
+        get_num_fp32_fmas_per_engine: get_num_64,
+        get_num_texels: get_num_8,
+        get_num_pixels: get_num_4,
+        get_num_exec_engines: get_num_2,
+        support: KBASE_SUPPORTED,
+        apis: VULKAN_1_3_APIS,
+    },
+    ProductEntry {
+        id: 0xb002,
+        mask: MASK_NEW,
+        min_cores: 7,
+        name: "Mali-G715",
+        architecture: MaliArch::Valhall,
+        get_num_fp32_fmas_per_engine: get_num_64,
+        get_num_texels: get_num_8,
+        get_num_pixels: get_num_4,
+        get_num_exec_engines: get_num_2,
+        support: KBASE_SUPPORTED,
+        apis: VULKAN_1_3_APIS,
+    },
+    ProductEntry {
+        id: 0xb002,
+        mask: MASK_NEW,
+        min_cores: 1,
+        name: "Mali-G615",
+        architecture: MaliArch::Valhall,
+        get_num_fp32_fmas_per_engine: get_num_64,
+        get_num_texels: get_num_8,
+        get_num_pixels: get_num_4,
+        get_num_exec_engines: get_num_2,
+        support: KBASE_SUPPORTED,
+        apis: VULKAN_1_3_APIS,
+    },
+    ProductEntry {
+        id: 0xb003,
+        mask: MASK_NEW,
+        min_cores: 1,
+        name: "Mali-G615",
+        architecture: MaliArch::Valhall,
+        get_num_fp32_fmas_per_engine: get_num_64,
+        get_num_texels: get_num_8,
+        get_num_pixels: get_num_4,
+        get_num_exec_engines: get_num_2,
+        support: KBASE_SUPPORTED,
+        apis: VULKAN_1_3_APIS,
+    },
+
+    // Immortalis-G720/Mali-G720/G620
+    ProductEntry {
+        id: 0xc000,
+        mask: MASK_NEW,
+        min_cores: 10,
+        name: "Immortalis-G720",
+        architecture: MaliArch::Gen5,
+        get_num_fp32_fmas_per_engine: get_num_64,
+        get_num_texels: get_num_8,
+        get_num_pixels: get_num_4,
+        get_num_exec_engines: get_num_2,
+        support: KBASE_SUPPORTED,
+        apis: VULKAN_1_3_APIS,
+    },
+    ProductEntry {
+        id: 0xc000,
+        mask: MASK_NEW,
+        min_cores: 6,
+        name: "Mali-G720",
+        architecture: MaliArch::Gen5,
+        get_num_fp32_fmas_per_engine: get_num_64,
+        get_num_texels: get_num_8,
+        get_num_pixels: get_num_4,
+        get_num_exec_engines: get_num_2,
+        support: KBASE_SUPPORTED,
+        apis: VULKAN_1_3_APIS,
+    },
+    ProductEntry {
+        id: 0xc000,
+        mask: MASK_NEW,
+        min_cores: 1,
+        name: "Mali-G620",
+        architecture: MaliArch::Gen5,
+        get_num_fp32_fmas_per_engine: get_num_64,
+        get_num_texels: get_num_8,
+        get_num_pixels: get_num_4,
+        get_num_exec_engines: get_num_2,
+        support: KBASE_SUPPORTED,
+        apis: VULKAN_1_3_APIS,
+    },
+    ProductEntry {
+        id: 0xc001,
+        mask: MASK_NEW,
+        min_cores: 1,
+        name: "Mali-G620",
+        architecture: MaliArch::Gen5,
+        get_num_fp32_fmas_per_engine: get_num_64,
+        get_num_texels: get_num_8,
+        get_num_pixels: get_num_4,
+        get_num_exec_engines: get_num_2,
+        support: KBASE_SUPPORTED,
+        apis: VULKAN_1_3_APIS,
+    },
+
+    // Immortalis-G925/Mali-G725/G625
+    ProductEntry {
+        id: 0xd000,
+        mask: MASK_NEW,
+        min_cores: 10,
+        name: "Immortalis-G925",
+        architecture: MaliArch::Gen5,
+        get_num_fp32_fmas_per_engine: get_num_64,
+        get_num_texels: get_num_8,
+        get_num_pixels: get_num_4,
+        get_num_exec_engines: get_num_2,
+        support: KBASE_SUPPORTED,
+        apis: VULKAN_1_3_APIS,
+    },
+    ProductEntry {
+        id: 0xd000,
+        mask: MASK_NEW,
+        min_cores: 6,
+        name: "Mali-G725",
+        architecture: MaliArch::Gen5,
+        get_num_fp32_fmas_per_engine: get_num_64,
+        get_num_texels: get_num_8,
+        get_num_pixels: get_num_4,
+        get_num_exec_engines: get_num_2,
+        support: KBASE_SUPPORTED,
+        apis: VULKAN_1_3_APIS,
+    },
+    ProductEntry {
+        id: 0xd001,
+        mask: MASK_NEW,
+        min_cores: 1,
+        name: "Mali-G625",
+        architecture: MaliArch::Gen5,
+        get_num_fp32_fmas_per_engine: get_num_64,
+        get_num_texels: get_num_8,
+        get_num_pixels: get_num_4,
+        get_num_exec_engines: get_num_2,
+        support: KBASE_SUPPORTED,
+        apis: VULKAN_1_3_APIS,
+    },
+
+    // Mali G1 series
+    ProductEntry {
+        id: 0xe000,
+        mask: MASK_NEW,
+        min_cores: 10,
+        name: "Mali G1-Ultra",
+        architecture: MaliArch::Gen5,
+        get_num_fp32_fmas_per_engine: get_num_64,
+        get_num_texels: get_num_8,
+        get_num_pixels: get_num_4,
+        get_num_exec_engines: get_num_2,
+        support: KBASE_SUPPORTED,
+        apis: VULKAN_1_4_APIS,
+    },
+    ProductEntry {
+        id: 0xe001,
+        mask: MASK_NEW,
+        min_cores: 6,
+        name: "Mali G1-Premium",
+        architecture: MaliArch::Gen5,
+        get_num_fp32_fmas_per_engine: get_num_64,
+        get_num_texels: get_num_8,
+        get_num_pixels: get_num_4,
+        get_num_exec_engines: get_num_2,
+        support: KBASE_SUPPORTED,
+        apis: VULKAN_1_4_APIS,
+    },
+    ProductEntry {
+        id: 0xe003,
+        mask: MASK_NEW,
+        min_cores: 1,
+        name: "Mali G1-Pro",
+        architecture: MaliArch::Gen5,
+        get_num_fp32_fmas_per_engine: get_num_64,
+        get_num_texels: get_num_8,
+        get_num_pixels: get_num_4,
+        get_num_exec_engines: get_num_2,
+        support: KBASE_SUPPORTED,
+        apis: VULKAN_1_4_APIS,
+    },
+];
+
+/// Driver support assumed for products of `architecture` built at runtime
+pub const fn default_support(architecture: MaliArch) -> DriverSupport {
+    match architecture {
+        MaliArch::Midgard => MIDGARD_EOL,
+        _ => KBASE_SUPPORTED,
+    }
+}
+
+/// API levels assumed for products of `architecture` built at runtime
+pub const fn default_apis(architecture: MaliArch) -> ApiLevels {
+    match architecture {
+        MaliArch::Midgard => VULKAN_1_0_APIS,
+        MaliArch::Bifrost => VULKAN_1_1_APIS,
+        MaliArch::Valhall | MaliArch::Gen5 => VULKAN_1_3_APIS,
+    }
+}
+
+/// Product ID mask assumed for products of `architecture` built at runtime
+pub const fn default_mask(architecture: MaliArch) -> u32 {
+    match architecture {
+        MaliArch::Midgard => MASK_OLD,
+        _ => MASK_NEW,
+    }
+}
+
+/// Per-core rate function returning `value`, `None` for values no
+/// `get_num_*` helper returns
+pub fn constant_rate(value: u32) -> Option<fn(u32, u32, u32) -> u32> {
+    Some(match value {
+        1 => get_num_1,
+        2 => get_num_2,
+        3 => get_num_3,
+        4 => get_num_4,
+        8 => get_num_8,
+        16 => get_num_16,
+        32 => get_num_32,
+        64 => get_num_64,
+        _ => return None,
+    })
+}
+
+/// All known products, in database order
+pub const fn products() -> &'static [ProductEntry] {
+    &PRODUCT_VERSIONS
+}
+
+/// Normalize a driver-reported product ID to the database product ID
+pub const fn get_gpu_id(input_id: u32) -> u32 {
+    let mut i = 0;
+    while i < PRODUCT_VERSIONS.len() {
+        let entry = &PRODUCT_VERSIONS[i];
+        if (input_id & entry.mask) == entry.id {
+            return entry.id;
+        }
+        i += 1;
+    }
+    input_id
+}
+
+/// Look up a product by normalized GPU ID and shader core count
+///
+/// A linear scan over the static table, usable in `const` contexts:
+///
+/// ```
+/// use armgpuinfo_core::mali::{get_gpu_id, lookup_product, ProductEntry};
+///
+/// const G610: &ProductEntry = match lookup_product(get_gpu_id(0xa867), 4) {
+///     Some(entry) => entry,
+///     None => panic!("unknown product"),
+/// };
+/// assert_eq!(G610.name, "Mali-G610");
+/// ```
+pub const fn lookup_product(gpu_id: u32, core_count: u32) -> Option<&'static ProductEntry> {
+    // Several entries share an ID and differ by minimum core count; take the
+    // most specific one the core count qualifies for
+    let mut best: Option<&'static ProductEntry> = None;
+    let mut i = 0;
+    while i < PRODUCT_VERSIONS.len() {
+        let entry = &PRODUCT_VERSIONS[i];
+        if entry.id == gpu_id && core_count >= entry.min_cores {
+            best = match best {
+                Some(current) if current.min_cores > entry.min_cores => Some(current),
+                _ => Some(entry),
+            };
+        }
+        i += 1;
+    }
+    best
+}
+
+/// Nearest known product for an ID newer than the database
+///
+/// Bifrost and later product IDs encode `arch_major:4 arch_minor:4 arch_rev:4
+/// product_major:4`. The candidate must share the architecture major; among
+/// those the closest architecture minor, then product major, wins, and its
+/// per-core figures are used as an estimate. Returns `None` for IDs that
+/// are in the database or have no family member.
+///
+/// ```
+/// use armgpuinfo_core::mali::extrapolate_product;
+///
+/// // A hypothetical 5th-gen part next to Mali G1-Pro (0xe003)
+/// let nearest = extrapolate_product(0xe005, 6).unwrap();
+/// assert_eq!(nearest.name, "Mali G1-Pro");
+/// ```
+pub const fn extrapolate_product(gpu_id: u32, core_count: u32) -> Option<&'static ProductEntry> {
+    if lookup_product(gpu_id, core_count).is_some() {
+        return None;
+    }
+    let arch_major = (gpu_id >> 12) & 0xF;
+    // Midgard uses legacy product IDs without this layout
+    if arch_major < 6 {
+        return None;
+    }
+    let key = (((gpu_id >> 8) & 0xF) << 4) | (gpu_id & 0xF);
+
+    let mut best: Option<u32> = None;
+    let mut best_distance = u32::MAX;
+    let mut i = 0;
+    while i < PRODUCT_VERSIONS.len() {
+        let id = PRODUCT_VERSIONS[i].id;
+        if (id >> 12) & 0xF == arch_major {
+            let distance = ((((id >> 8) & 0xF) << 4) | (id & 0xF)).abs_diff(key);
+            if distance < best_distance {
+                best = Some(id);
+                best_distance = distance;
+            }
+        }
+        i += 1;
+    }
+
+    match best {
+        Some(id) => match lookup_product(id, core_count) {
+            Some(entry) => Some(entry),
+            None => lookup_product(id, 1),
+        },
+        None => None,
+    }
+}
+
+/// Extract (major, minor) architecture version from the raw GPU ID register
+pub const fn extract_architecture(raw_gpu_id: u64) -> (u8, u8) {
+    const COMPAT_SHIFT: u64 = 28;
+    const COMPAT_MASK: u64 = 0xF;
+
+    let is_64bit_id = ((raw_gpu_id >> COMPAT_SHIFT) & COMPAT_MASK) == COMPAT_MASK;
+
+    if !is_64bit_id {
+        (
+            ((raw_gpu_id >> 28) & 0xF) as u8,
+            ((raw_gpu_id >> 24) & 0xF) as u8,
+        )
+    } else {
+        (
+            ((raw_gpu_id >> 56) & 0xFF) as u8,
+            ((raw_gpu_id >> 48) & 0xFF) as u8,
+        )
+    }
+}
@@ -0,0 +1,13 @@
+//! kbase property buffer parser and Mali product database
+
+mod database;
+mod parser;
+
+pub use database::{
+    constant_rate, default_apis, default_mask, default_support, extract_architecture, extrapolate_product,
+    get_gpu_id, lookup_product, products, ProductEntry,
+};
+pub use parser::{
+    parse_properties, parse_properties_lenient, parse_properties_strict, property_name, raw_properties,
+    ParsedProperties, ParserConfig,
+};
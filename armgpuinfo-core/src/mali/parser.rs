@@ -0,0 +1,522 @@
+use alloc::vec::Vec;
+use core::ops::RangeInclusive;
+
+use crate::error::ParseError;
+use crate::types::MaliHwConfig;
+
+/// Property IDs used in Mali property buffer (from kbase_gpuprops.h)
+#[repr(u64)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PropId {
+    ProductId = 1,
+    TextureFeatures0 = 9,
+    TextureFeatures1 = 10,
+    TextureFeatures2 = 11,
+    L2Log2CacheSize = 14,
+    L2NumL2Slices = 15,
+    TilerBinSizeBytes = 16,
+    TilerMaxActiveLevels = 17,
+    MaxThreads = 18,
+    MaxWorkgroupSize = 19,
+    MaxBarrierSize = 20,
+    MaxRegisters = 21,
+    ImplTech = 24,
+    RawShaderPresent = 25,
+    RawTilerPresent = 26,
+    RawL2Present = 27,
+    RawStackPresent = 28,
+    RawL2Features = 29,
+    RawCoreFeatures = 30,
+    RawMemFeatures = 31,
+    RawMmuFeatures = 32,
+    RawAsPresent = 33,
+    RawJsPresent = 34,
+    RawTilerFeatures = 51,
+    RawGpuId = 55,
+    RawThreadMaxThreads = 56,
+    RawThreadMaxWorkgroupSize = 57,
+    RawThreadMaxBarrierSize = 58,
+    RawThreadFeatures = 59,
+    RawCoherencyMode = 60,
+    CoherencyNumGroups = 61,
+    CoherencyNumCoreGroups = 62,
+    CoherencyCoherency = 63,
+    TextureFeatures3 = 80,
+    RawThreadTlsAlloc = 84,
+    RawGpuFeatures = 86,
+}
+
+/// RAW_JS_FEATURES_0..15
+const JS_FEATURES: RangeInclusive<u64> = 35..=50;
+/// RAW_TEXTURE_FEATURES_0..2
+const TEXTURE_FEATURES: RangeInclusive<u64> = 52..=54;
+
+impl TryFrom<u64> for PropId {
+    type Error = ();
+
+    fn try_from(value: u64) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(PropId::ProductId),
+            9 => Ok(PropId::TextureFeatures0),
+            10 => Ok(PropId::TextureFeatures1),
+            11 => Ok(PropId::TextureFeatures2),
+            14 => Ok(PropId::L2Log2CacheSize),
+            15 => Ok(PropId::L2NumL2Slices),
+            16 => Ok(PropId::TilerBinSizeBytes),
+            17 => Ok(PropId::TilerMaxActiveLevels),
+            18 => Ok(PropId::MaxThreads),
+            19 => Ok(PropId::MaxWorkgroupSize),
+            20 => Ok(PropId::MaxBarrierSize),
+            21 => Ok(PropId::MaxRegisters),
+            24 => Ok(PropId::ImplTech),
+            25 => Ok(PropId::RawShaderPresent),
+            26 => Ok(PropId::RawTilerPresent),
+            27 => Ok(PropId::RawL2Present),
+            28 => Ok(PropId::RawStackPresent),
+            29 => Ok(PropId::RawL2Features),
+            30 => Ok(PropId::RawCoreFeatures),
+            31 => Ok(PropId::RawMemFeatures),
+            32 => Ok(PropId::RawMmuFeatures),
+            33 => Ok(PropId::RawAsPresent),
+            34 => Ok(PropId::RawJsPresent),
+            51 => Ok(PropId::RawTilerFeatures),
+            55 => Ok(PropId::RawGpuId),
+            56 => Ok(PropId::RawThreadMaxThreads),
+            57 => Ok(PropId::RawThreadMaxWorkgroupSize),
+            58 => Ok(PropId::RawThreadMaxBarrierSize),
+            59 => Ok(PropId::RawThreadFeatures),
+            60 => Ok(PropId::RawCoherencyMode),
+            61 => Ok(PropId::CoherencyNumGroups),
+            62 => Ok(PropId::CoherencyNumCoreGroups),
+            63 => Ok(PropId::CoherencyCoherency),
+            80 => Ok(PropId::TextureFeatures3),
+            84 => Ok(PropId::RawThreadTlsAlloc),
+            86 => Ok(PropId::RawGpuFeatures),
+            _ => Err(()),
+        }
+    }
+}
+
+/// `KBASE_GPUPROP_*` names by property ID, for decoded dumps
+const PROPERTY_NAMES: &[(u64, &str)] = &[
+    (1, "PRODUCT_ID"),
+    (2, "VERSION_STATUS"),
+    (3, "MINOR_REVISION"),
+    (4, "MAJOR_REVISION"),
+    (6, "GPU_FREQ_KHZ_MAX"),
+    (8, "LOG2_PROGRAM_COUNTER_SIZE"),
+    (9, "TEXTURE_FEATURES_0"),
+    (10, "TEXTURE_FEATURES_1"),
+    (11, "TEXTURE_FEATURES_2"),
+    (12, "GPU_AVAILABLE_MEMORY_SIZE"),
+    (13, "L2_LOG2_LINE_SIZE"),
+    (14, "L2_LOG2_CACHE_SIZE"),
+    (15, "L2_NUM_L2_SLICES"),
+    (16, "TILER_BIN_SIZE_BYTES"),
+    (17, "TILER_MAX_ACTIVE_LEVELS"),
+    (18, "MAX_THREADS"),
+    (19, "MAX_WORKGROUP_SIZE"),
+    (20, "MAX_BARRIER_SIZE"),
+    (21, "MAX_REGISTERS"),
+    (22, "MAX_TASK_QUEUE"),
+    (23, "MAX_THREAD_GROUP_SPLIT"),
+    (24, "IMPL_TECH"),
+    (25, "RAW_SHADER_PRESENT"),
+    (26, "RAW_TILER_PRESENT"),
+    (27, "RAW_L2_PRESENT"),
+    (28, "RAW_STACK_PRESENT"),
+    (29, "RAW_L2_FEATURES"),
+    (30, "RAW_CORE_FEATURES"),
+    (31, "RAW_MEM_FEATURES"),
+    (32, "RAW_MMU_FEATURES"),
+    (33, "RAW_AS_PRESENT"),
+    (34, "RAW_JS_PRESENT"),
+    (35, "RAW_JS_FEATURES_0"),
+    (36, "RAW_JS_FEATURES_1"),
+    (37, "RAW_JS_FEATURES_2"),
+    (38, "RAW_JS_FEATURES_3"),
+    (39, "RAW_JS_FEATURES_4"),
+    (40, "RAW_JS_FEATURES_5"),
+    (41, "RAW_JS_FEATURES_6"),
+    (42, "RAW_JS_FEATURES_7"),
+    (43, "RAW_JS_FEATURES_8"),
+    (44, "RAW_JS_FEATURES_9"),
+    (45, "RAW_JS_FEATURES_10"),
+    (46, "RAW_JS_FEATURES_11"),
+    (47, "RAW_JS_FEATURES_12"),
+    (48, "RAW_JS_FEATURES_13"),
+    (49, "RAW_JS_FEATURES_14"),
+    (50, "RAW_JS_FEATURES_15"),
+    (51, "RAW_TILER_FEATURES"),
+    (52, "RAW_TEXTURE_FEATURES_0"),
+    (53, "RAW_TEXTURE_FEATURES_1"),
+    (54, "RAW_TEXTURE_FEATURES_2"),
+    (55, "RAW_GPU_ID"),
+    (56, "RAW_THREAD_MAX_THREADS"),
+    (57, "RAW_THREAD_MAX_WORKGROUP_SIZE"),
+    (58, "RAW_THREAD_MAX_BARRIER_SIZE"),
+    (59, "RAW_THREAD_FEATURES"),
+    (60, "RAW_COHERENCY_MODE"),
+    (61, "COHERENCY_NUM_GROUPS"),
+    (62, "COHERENCY_NUM_CORE_GROUPS"),
+    (63, "COHERENCY_COHERENCY"),
+    (64, "COHERENCY_GROUP_0"),
+    (65, "COHERENCY_GROUP_1"),
+    (66, "COHERENCY_GROUP_2"),
+    (67, "COHERENCY_GROUP_3"),
+    (68, "COHERENCY_GROUP_4"),
+    (69, "COHERENCY_GROUP_5"),
+    (70, "COHERENCY_GROUP_6"),
+    (71, "COHERENCY_GROUP_7"),
+    (72, "COHERENCY_GROUP_8"),
+    (73, "COHERENCY_GROUP_9"),
+    (74, "COHERENCY_GROUP_10"),
+    (75, "COHERENCY_GROUP_11"),
+    (76, "COHERENCY_GROUP_12"),
+    (77, "COHERENCY_GROUP_13"),
+    (78, "COHERENCY_GROUP_14"),
+    (79, "COHERENCY_GROUP_15"),
+    (80, "TEXTURE_FEATURES_3"),
+    (81, "RAW_TEXTURE_FEATURES_3"),
+    (82, "NUM_EXEC_ENGINES"),
+    (84, "RAW_THREAD_TLS_ALLOC"),
+    (86, "RAW_GPU_FEATURES"),
+];
+
+/// `KBASE_GPUPROP_*` name of a property ID, without the prefix
+pub fn property_name(prop_id: u64) -> Option<&'static str> {
+    PROPERTY_NAMES
+        .iter()
+        .find(|(id, _)| *id == prop_id)
+        .map(|(_, name)| *name)
+}
+
+/// Parser configuration for different modes
+#[derive(Debug, Clone, Copy)]
+pub struct ParserConfig {
+    /// Skip invalid properties instead of erroring
+    pub lenient_mode: bool,
+    /// Validate that core group masks are within bounds
+    pub validate_group_bounds: bool,
+    /// Accept core masks even when num_core_groups is zero
+    pub accept_masks_without_groups: bool,
+    /// Skip out-of-bounds core masks instead of ignoring them
+    pub skip_out_of_bounds_masks: bool,
+}
+
+impl ParserConfig {
+    /// Configuration for Parity mode (matches libgpuinfo exactly)
+    pub const PARITY: Self = Self {
+        lenient_mode: true,
+        validate_group_bounds: false,
+        accept_masks_without_groups: true,
+        skip_out_of_bounds_masks: false,
+    };
+
+    /// Configuration for Extended mode (strict validation)
+    pub const EXTENDED: Self = Self {
+        lenient_mode: false,
+        validate_group_bounds: true,
+        accept_masks_without_groups: false,
+        skip_out_of_bounds_masks: true,
+    };
+}
+
+/// Parsed GPU properties from driver
+#[non_exhaustive]
+#[derive(Debug, Clone, Default)]
+pub struct ParsedProperties {
+    /// GPU product ID from driver
+    pub gpu_id: u32,
+    /// Log2 of L2 cache size per slice
+    pub l2_log2_cache_size: u64,
+    /// Number of L2 cache slices
+    pub num_l2_slices: u64,
+    /// Raw L2 features register value
+    pub raw_l2_features: u64,
+    /// Raw core features register value
+    pub raw_core_features: u32,
+    /// Raw GPU ID register value
+    pub raw_gpu_id: u64,
+    /// Raw thread features register value
+    pub raw_thread_features: u32,
+    /// Number of shader cores (calculated from mask)
+    pub num_shader_cores: u32,
+    /// Bitmask of available shader cores
+    pub shader_core_mask: u64,
+    /// Accepted core mask of each core group, COHERENCY_GROUP_0..15
+    pub core_group_masks: [u64; 16],
+    /// Hardware configuration registers
+    pub hw_config: MaliHwConfig,
+    /// Tiler bin size in bytes, as decoded by the kernel
+    pub tiler_bin_size_bytes: u32,
+    /// Maximum active tiler hierarchy levels, as decoded by the kernel
+    pub tiler_max_active_levels: u32,
+    /// Maximum threads per core, as decoded by the kernel
+    pub max_threads: u32,
+    /// Maximum workgroup size, as decoded by the kernel
+    pub max_workgroup_size: u32,
+    /// Maximum threads synchronized by one barrier, as decoded by the kernel
+    pub max_barrier_size: u32,
+    /// Registers per core available to threads
+    pub max_registers: u32,
+    /// Number of coherency groups
+    pub num_coherency_groups: u32,
+    /// Coherency protocol in use (COHERENCY_COHERENCY)
+    pub coherency: u32,
+    /// TEXTURE_FEATURES_0..3 as decoded by the kernel
+    pub texture_features: [u32; 4],
+}
+
+impl ParsedProperties {
+    /// Create an empty ParsedProperties struct
+    pub fn empty() -> Self {
+        Self::default()
+    }
+}
+
+/// Unified parser for Mali property buffer
+struct UnifiedPropParser<'a> {
+    data: &'a [u8],
+    pos: usize,
+    config: ParserConfig,
+}
+
+impl<'a> UnifiedPropParser<'a> {
+    /// Create a new parser for the given buffer with configuration
+    fn new(data: &'a [u8], config: ParserConfig) -> Self {
+        Self {
+            data,
+            pos: 0,
+            config,
+        }
+    }
+
+    /// Parse the entire buffer into properties
+    fn parse(mut self) -> Result<ParsedProperties, ParseError> {
+        let mut props = ParsedProperties::default();
+        let mut num_core_groups = 0;
+        let mut core_masks_received = 0;
+
+        while let Some((prop_id, value)) = self.next_prop()? {
+            let hw = &mut props.hw_config;
+            match PropId::try_from(prop_id) {
+                Ok(PropId::ProductId) => props.gpu_id = value as u32,
+                Ok(PropId::TextureFeatures0) => props.texture_features[0] = value as u32,
+                Ok(PropId::TextureFeatures1) => props.texture_features[1] = value as u32,
+                Ok(PropId::TextureFeatures2) => props.texture_features[2] = value as u32,
+                Ok(PropId::TextureFeatures3) => props.texture_features[3] = value as u32,
+                Ok(PropId::TilerBinSizeBytes) => props.tiler_bin_size_bytes = value as u32,
+                Ok(PropId::TilerMaxActiveLevels) => props.tiler_max_active_levels = value as u32,
+                Ok(PropId::MaxThreads) => props.max_threads = value as u32,
+                Ok(PropId::MaxWorkgroupSize) => props.max_workgroup_size = value as u32,
+                Ok(PropId::MaxBarrierSize) => props.max_barrier_size = value as u32,
+                Ok(PropId::MaxRegisters) => props.max_registers = value as u32,
+                Ok(PropId::CoherencyNumGroups) => props.num_coherency_groups = value as u32,
+                Ok(PropId::CoherencyCoherency) => props.coherency = value as u32,
+                Ok(PropId::L2Log2CacheSize) => props.l2_log2_cache_size = value,
+                Ok(PropId::L2NumL2Slices) => props.num_l2_slices = value,
+                Ok(PropId::RawL2Features) => props.raw_l2_features = value,
+                Ok(PropId::RawCoreFeatures) => props.raw_core_features = value as u32,
+                Ok(PropId::RawGpuId) => props.raw_gpu_id = value,
+                Ok(PropId::RawThreadFeatures) => props.raw_thread_features = value as u32,
+                Ok(PropId::CoherencyNumCoreGroups) => num_core_groups = value,
+                Ok(PropId::ImplTech) => hw.impl_tech = value as u32,
+                Ok(PropId::RawShaderPresent) => hw.shader_present = value,
+                Ok(PropId::RawTilerPresent) => hw.tiler_present = value,
+                Ok(PropId::RawL2Present) => hw.l2_present = value,
+                Ok(PropId::RawStackPresent) => hw.stack_present = value,
+                Ok(PropId::RawMemFeatures) => hw.mem_features = value as u32,
+                Ok(PropId::RawMmuFeatures) => hw.mmu_features = value as u32,
+                Ok(PropId::RawAsPresent) => hw.as_present = value as u32,
+                Ok(PropId::RawJsPresent) => hw.js_present = value as u32,
+                Ok(PropId::RawTilerFeatures) => hw.tiler_features = value as u32,
+                Ok(PropId::RawThreadMaxThreads) => hw.thread_max_threads = value as u32,
+                Ok(PropId::RawThreadMaxWorkgroupSize) => {
+                    hw.thread_max_workgroup_size = value as u32
+                }
+                Ok(PropId::RawThreadMaxBarrierSize) => hw.thread_max_barrier_size = value as u32,
+                Ok(PropId::RawCoherencyMode) => hw.coherency_mode = value as u32,
+                Ok(PropId::RawThreadTlsAlloc) => hw.thread_tls_alloc = value as u32,
+                Ok(PropId::RawGpuFeatures) => hw.gpu_features = value,
+                Err(_) if JS_FEATURES.contains(&prop_id) => {
+                    hw.js_features[(prop_id - JS_FEATURES.start()) as usize] = value as u32;
+                }
+                Err(_) if TEXTURE_FEATURES.contains(&prop_id) => {
+                    hw.texture_features[(prop_id - TEXTURE_FEATURES.start()) as usize] = value as u32;
+                }
+                Err(_) => {
+                    // Handle core group masks (IDs 64-79) for Midgard/Bifrost
+                    if (64..=79).contains(&prop_id) {
+                        self.handle_core_mask(
+                            prop_id,
+                            value,
+                            num_core_groups,
+                            &mut props,
+                            &mut core_masks_received,
+                        )?;
+                    }
+                }
+            }
+        }
+
+        props.num_shader_cores = props.shader_core_mask.count_ones();
+
+        Ok(props)
+    }
+
+    /// Handle core group mask based on configuration
+    fn handle_core_mask(
+        &self,
+        prop_id: u64,
+        value: u64,
+        num_core_groups: u64,
+        props: &mut ParsedProperties,
+        core_masks_received: &mut u64,
+    ) -> Result<(), ParseError> {
+        let group_idx = prop_id - 64;
+
+        // Check if this mask should be accepted based on configuration
+        let should_accept = if num_core_groups == 0 {
+            // No core groups defined
+            self.config.accept_masks_without_groups
+        } else if group_idx < num_core_groups {
+            // Valid mask within bounds
+            true
+        } else {
+            // Out of bounds mask
+            !self.config.skip_out_of_bounds_masks
+        };
+
+        if should_accept {
+            props.shader_core_mask |= value;
+            props.core_group_masks[group_idx as usize] |= value;
+            if num_core_groups > 0 && group_idx < num_core_groups {
+                *core_masks_received += 1;
+            }
+        } else if self.config.validate_group_bounds {
+            // In Extended mode, we note but don't error on out-of-bounds masks
+        }
+
+        Ok(())
+    }
+
+    /// Get next property from buffer
+    fn next_prop(&mut self) -> Result<Option<(u64, u64)>, ParseError> {
+        if self.pos + 4 > self.data.len() {
+            return Ok(None);
+        }
+
+        // Read key (4 bytes, little-endian)
+        let key_bytes = self.read_bytes(4)?;
+        let key = u32::from_le_bytes(key_bytes.try_into().map_err(|_| {
+            ParseError::InvalidData("Failed to parse property key")
+        })?);
+
+        // Extract property ID and size
+        let prop_id = (key >> 2) as u64;
+        let prop_size = key & 3;
+
+        // Determine value size
+        let value_size = match prop_size {
+            0 => 1,
+            1 => 2,
+            2 => 4,
+            3 => 8,
+            _ => {
+                if self.config.lenient_mode {
+                    return Ok(None); // Skip invalid size in lenient mode
+                } else {
+                    return Err(ParseError::InvalidPropertySize(prop_size));
+                }
+            }
+        };
+
+        // Read value (little-endian)
+        let value = self.read_value(value_size, prop_size)?;
+
+        Ok(Some((prop_id, value)))
+    }
+
+    /// Read bytes from buffer at current position
+    fn read_bytes(&mut self, size: usize) -> Result<&[u8], ParseError> {
+        if self.pos + size > self.data.len() {
+            if self.config.lenient_mode {
+                // Return empty slice in lenient mode to trigger graceful failure
+                self.pos = self.data.len(); // Skip to end
+                return Ok(&[]);
+            } else {
+                return Err(ParseError::BufferTooSmall {
+                    expected: self.pos + size,
+                    actual: self.data.len(),
+                });
+            }
+        }
+
+        let slice = &self.data[self.pos..self.pos + size];
+        self.pos += size;
+        Ok(slice)
+    }
+
+    /// Read a value of the specified size
+    fn read_value(&mut self, size: usize, prop_size: u32) -> Result<u64, ParseError> {
+        let bytes = self.read_bytes(size)?;
+
+        // If bytes is empty (lenient mode hit buffer end), return 0
+        if bytes.is_empty() {
+            return Ok(0);
+        }
+
+        match prop_size {
+            0 => Ok(bytes[0] as u64),
+            1 => Ok(u16::from_le_bytes(bytes.try_into().map_err(|_| {
+                ParseError::InvalidData("Failed to parse u16 property")
+            })?) as u64),
+            2 => Ok(u32::from_le_bytes(bytes.try_into().map_err(|_| {
+                ParseError::InvalidData("Failed to parse u32 property")
+            })?) as u64),
+            3 => Ok(u64::from_le_bytes(bytes.try_into().map_err(|_| {
+                ParseError::InvalidData("Failed to parse u64 property")
+            })?)),
+            _ => {
+                if self.config.lenient_mode {
+                    Ok(0)
+                } else {
+                    Err(ParseError::InvalidPropertySize(prop_size))
+                }
+            }
+        }
+    }
+}
+
+/// Parse properties buffer into structured data with configuration
+pub fn parse_properties(buffer: &[u8], config: ParserConfig) -> Result<ParsedProperties, ParseError> {
+    let parser = UnifiedPropParser::new(buffer, config);
+    parser.parse()
+}
+
+/// Parse properties buffer into structured data (Extended mode - strict with validation)
+pub fn parse_properties_strict(buffer: &[u8]) -> Result<ParsedProperties, ParseError> {
+    parse_properties(buffer, ParserConfig::EXTENDED)
+}
+
+/// Parse properties buffer into structured data (Parity mode - lenient, matches libgpuinfo)
+pub fn parse_properties_lenient(buffer: &[u8]) -> ParsedProperties {
+    match parse_properties(buffer, ParserConfig::PARITY) {
+        Ok(props) => props,
+        Err(_) => ParsedProperties::empty(),
+    }
+}
+
+/// Decode the buffer into `(property ID, value)` pairs in buffer order
+///
+/// Unlike [`parse_properties`] this keeps every property, including the
+/// ones the parser does not interpret.
+pub fn raw_properties(buffer: &[u8]) -> Result<Vec<(u64, u64)>, ParseError> {
+    let mut parser = UnifiedPropParser::new(buffer, ParserConfig::EXTENDED);
+    let mut props = Vec::new();
+    while let Some(prop) = parser.next_prop()? {
+        props.push(prop);
+    }
+    Ok(props)
+}
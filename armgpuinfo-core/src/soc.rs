@@ -0,0 +1,193 @@
+//! SoC references and GPU/SoC linkage
+//!
+//! The Adreno database lists the SoCs each GPU ships in as [`SocRef`]s
+//! defined here, so the relationship can be walked both ways: from a chip
+//! ID to its SoCs ([`socs_using`]) and from a SoC part number or marketing
+//! name back to the GPU ([`gpu_for_soc`]).
+
+use core::fmt;
+
+#[cfg(feature = "serde")]
+use alloc::borrow::Cow;
+
+#[cfg(feature = "adreno")]
+use crate::adreno::{find_adreno_specs, AdrenoSpecs, ADRENO_CHIPS};
+
+/// A system-on-chip a GPU ships in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SocRef {
+    /// SoC vendor (e.g. "Qualcomm")
+    pub vendor: &'static str,
+    /// Product family (e.g. "Snapdragon")
+    pub family: &'static str,
+    /// Part number (e.g. "SM8550")
+    pub model: &'static str,
+    /// Marketing name (e.g. "Snapdragon 8 Gen 2")
+    pub market_name: &'static str,
+}
+
+impl SocRef {
+    /// Whether `name` is this SoC's part number or marketing name
+    ///
+    /// Case-insensitive; the family prefix of the marketing name is
+    /// optional ("8 Gen 2" matches "Snapdragon 8 Gen 2").
+    pub fn matches(&self, name: &str) -> bool {
+        let name = name.trim();
+        if name.eq_ignore_ascii_case(self.model) || name.eq_ignore_ascii_case(self.market_name) {
+            return true;
+        }
+        self.market_name
+            .strip_prefix(self.family)
+            .is_some_and(|short| name.eq_ignore_ascii_case(short.trim_start()))
+    }
+}
+
+impl fmt::Display for SocRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.market_name, self.model)
+    }
+}
+
+const fn snapdragon(model: &'static str, market_name: &'static str) -> SocRef {
+    SocRef { vendor: "Qualcomm", family: "Snapdragon", model, market_name }
+}
+
+pub const MSM8917: SocRef = snapdragon("MSM8917", "Snapdragon 425");
+pub const MSM8929: SocRef = snapdragon("MSM8929", "Snapdragon 415");
+pub const MSM8936: SocRef = snapdragon("MSM8936", "Snapdragon 610");
+pub const MSM8937: SocRef = snapdragon("MSM8937", "Snapdragon 430");
+pub const MSM8939V2: SocRef = snapdragon("MSM8939v2", "Snapdragon 616");
+pub const MSM8940: SocRef = snapdragon("MSM8940", "Snapdragon 435");
+pub const MSM8952: SocRef = snapdragon("MSM8952", "Snapdragon 617");
+pub const MSM8953: SocRef = snapdragon("MSM8953", "Snapdragon 625");
+pub const MSM8953_PRO: SocRef = snapdragon("MSM8953 Pro", "Snapdragon 626");
+pub const MSM8996: SocRef = snapdragon("MSM8996", "Snapdragon 820");
+pub const MSM8996_PRO: SocRef = snapdragon("MSM8996 Pro", "Snapdragon 821");
+pub const MSM8998: SocRef = snapdragon("MSM8998", "Snapdragon 835");
+pub const SDM429: SocRef = snapdragon("SDM429", "Snapdragon 429");
+pub const SDM439: SocRef = snapdragon("SDM439", "Snapdragon 439");
+pub const SDM450: SocRef = snapdragon("SDM450", "Snapdragon 450");
+pub const SDM630: SocRef = snapdragon("SDM630", "Snapdragon 630");
+pub const SDM632: SocRef = snapdragon("SDM632", "Snapdragon 632");
+pub const SDM636: SocRef = snapdragon("SDM636", "Snapdragon 636");
+pub const SDM660: SocRef = snapdragon("SDM660", "Snapdragon 660");
+pub const SDM670: SocRef = snapdragon("SDM670", "Snapdragon 670");
+pub const SDM710: SocRef = snapdragon("SDM710", "Snapdragon 710");
+pub const SDM712: SocRef = snapdragon("SDM712", "Snapdragon 712");
+pub const SM4250: SocRef = snapdragon("SM4250", "Snapdragon 460");
+pub const SM4350: SocRef = snapdragon("SM4350", "Snapdragon 480");
+pub const SM6115: SocRef = snapdragon("SM6115", "Snapdragon 662");
+pub const SM6125: SocRef = snapdragon("SM6125", "Snapdragon 665");
+pub const SM6150: SocRef = snapdragon("SM6150", "Snapdragon 675");
+pub const SM6350: SocRef = snapdragon("SM6350", "Snapdragon 690");
+pub const SM7150_AA: SocRef = snapdragon("SM7150-AA", "Snapdragon 730");
+pub const SM7150_AB: SocRef = snapdragon("SM7150-AB", "Snapdragon 730G");
+pub const SM7150_AC: SocRef = snapdragon("SM7150-AC", "Snapdragon 732G");
+pub const SM7225: SocRef = snapdragon("SM7225", "Snapdragon 750G");
+pub const SM7250_AA: SocRef = snapdragon("SM7250-AA", "Snapdragon 765");
+pub const SM7250_AB: SocRef = snapdragon("SM7250-AB", "Snapdragon 765G");
+pub const SM7250_AC: SocRef = snapdragon("SM7250-AC", "Snapdragon 768G");
+pub const SM8450: SocRef = snapdragon("SM8450", "Snapdragon 8 Gen 1");
+pub const SM8475: SocRef = snapdragon("SM8475", "Snapdragon 8+ Gen 1");
+pub const SM8550: SocRef = snapdragon("SM8550", "Snapdragon 8 Gen 2");
+pub const SM8650: SocRef = snapdragon("SM8650", "Snapdragon 8 Gen 3");
+
+const fn mediatek(family: &'static str, model: &'static str, market_name: &'static str) -> SocRef {
+    SocRef { vendor: "MediaTek", family, model, market_name }
+}
+
+pub const MT6768: SocRef = mediatek("Helio", "MT6768", "Helio G85");
+pub const MT6785: SocRef = mediatek("Helio", "MT6785", "Helio G90T");
+pub const MT6877: SocRef = mediatek("Dimensity", "MT6877", "Dimensity 900");
+pub const MT6886: SocRef = mediatek("Dimensity", "MT6886", "Dimensity 7200");
+pub const MT6893: SocRef = mediatek("Dimensity", "MT6893", "Dimensity 1200");
+pub const MT6897: SocRef = mediatek("Dimensity", "MT6897", "Dimensity 8300");
+pub const MT6983: SocRef = mediatek("Dimensity", "MT6983", "Dimensity 9000");
+pub const MT6985: SocRef = mediatek("Dimensity", "MT6985", "Dimensity 9200");
+
+const fn tensor(model: &'static str, market_name: &'static str) -> SocRef {
+    SocRef { vendor: "Google", family: "Tensor", model, market_name }
+}
+
+pub const GS101: SocRef = tensor("GS101", "Tensor");
+pub const GS201: SocRef = tensor("GS201", "Tensor G2");
+/// Reported by its codename; Google publishes no part number
+pub const ZUMA: SocRef = tensor("Zuma", "Tensor G3");
+
+/// Every SoC defined in this module
+pub static KNOWN_SOCS: &[SocRef] = &[
+    MSM8917, MSM8929, MSM8936, MSM8937, MSM8939V2, MSM8940, MSM8952, MSM8953, MSM8953_PRO,
+    MSM8996, MSM8996_PRO, MSM8998, SDM429, SDM439, SDM450, SDM630, SDM632, SDM636, SDM660,
+    SDM670, SDM710, SDM712, SM4250, SM4350, SM6115, SM6125, SM6150, SM6350, SM7150_AA,
+    SM7150_AB, SM7150_AC, SM7225, SM7250_AA, SM7250_AB, SM7250_AC, SM8450, SM8475, SM8550,
+    SM8650, MT6768, MT6785, MT6877, MT6886, MT6893, MT6897, MT6983, MT6985, GS101, GS201, ZUMA,
+];
+
+/// Mali product each known Mali SoC ships, by database name
+static MALI_SOC_GPUS: &[(SocRef, &str)] = &[
+    (MT6768, "Mali-G52"),
+    (MT6785, "Mali-G76"),
+    (MT6877, "Mali-G68"),
+    (MT6886, "Mali-G610"),
+    (MT6893, "Mali-G77"),
+    (MT6897, "Mali-G615"),
+    (MT6983, "Mali-G710"),
+    (MT6985, "Immortalis-G715"),
+    (GS101, "Mali-G78"),
+    (GS201, "Mali-G710"),
+    (ZUMA, "Mali-G715"),
+];
+
+/// Database name of the Mali GPU `soc` ships, `None` for other SoCs
+pub fn mali_gpu_for_soc(soc: &SocRef) -> Option<&'static str> {
+    MALI_SOC_GPUS.iter().find(|(known, _)| known == soc).map(|&(_, gpu)| gpu)
+}
+
+/// Known SoC by part number or marketing name, see [`SocRef::matches`]
+pub fn find_soc(name: &str) -> Option<&'static SocRef> {
+    KNOWN_SOCS.iter().find(|soc| soc.matches(name))
+}
+
+// Serialized as the part number; deserializing resolves it against
+// KNOWN_SOCS since the fields borrow static strings
+#[cfg(feature = "serde")]
+impl serde::Serialize for SocRef {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.model)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for SocRef {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = <Cow<'de, str>>::deserialize(deserializer)?;
+        find_soc(&name)
+            .copied()
+            .ok_or_else(|| serde::de::Error::custom(format_args!("unknown SoC: {name}")))
+    }
+}
+
+/// SoCs known to ship the Adreno GPU with `chip_id`, in database order
+///
+/// Empty for chips that only resolve to a generic series entry.
+#[cfg(feature = "adreno")]
+pub fn socs_using(chip_id: u32) -> &'static [SocRef] {
+    find_adreno_specs(chip_id).map_or(&[], |specs| specs.snapdragon_models)
+}
+
+/// GPU database entry for a SoC part number or marketing name
+///
+/// ```
+/// use armgpuinfo_core::soc::gpu_for_soc;
+///
+/// let (chip_id, specs) = gpu_for_soc("SM8550").unwrap();
+/// assert_eq!(*chip_id, 0x0706_0001);
+/// assert_eq!(specs.name, "Adreno 740");
+/// assert_eq!(gpu_for_soc("8 Gen 2").unwrap().0, 0x0706_0001);
+/// ```
+#[cfg(feature = "adreno")]
+pub fn gpu_for_soc(name: &str) -> Option<&'static (u32, AdrenoSpecs)> {
+    ADRENO_CHIPS
+        .iter()
+        .find(|(_, specs)| specs.snapdragon_models.iter().any(|soc| soc.matches(name)))
+}
@@ -0,0 +1,309 @@
+//! Types shared by the parsers, the databases and armgpuinfo's `GpuInfo`
+
+use alloc::borrow::Cow;
+use core::fmt;
+
+/// Mali GPU architecture generation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MaliArch {
+    /// Mali-T6xx to T8xx
+    Midgard,
+    /// Mali-G71 to G76, G31/G51/G52
+    Bifrost,
+    /// Mali-G77 onwards up to the G7x5 generation
+    Valhall,
+    /// Arm 5th Gen (Immortalis-G925 and later)
+    Gen5,
+}
+
+impl MaliArch {
+    /// Marketing name of the generation
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            MaliArch::Midgard => "Midgard",
+            MaliArch::Bifrost => "Bifrost",
+            MaliArch::Valhall => "Valhall",
+            MaliArch::Gen5 => "Arm 5th Gen",
+        }
+    }
+}
+
+impl fmt::Display for MaliArch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Confidence level of the specifications
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SpecConfidence {
+    /// Directly measured from known driver-reported chip IDs
+    Measured,
+    /// Confirmed via reverse engineering or reliable community sources
+    ReverseEngineered,
+    /// Estimated/heuristic (common for undisclosed modern specs)
+    Heuristic,
+    /// Copied from the nearest known family member for IDs newer than the
+    /// database
+    Extrapolated,
+}
+impl SpecConfidence {
+    // Methode, die Cow zurückgibt
+    pub fn as_cow(&self) -> Cow<'static, str> {
+        match self {
+            SpecConfidence::Measured => Cow::Borrowed("Measured"),
+            SpecConfidence::ReverseEngineered => Cow::Borrowed("Reverse Engineered"),
+            SpecConfidence::Heuristic => Cow::Borrowed("Heuristic"),
+            SpecConfidence::Extrapolated => Cow::Borrowed("Extrapolated"),
+        }
+    }
+
+    /// Inverse of [`as_cow`](Self::as_cow), for the label stored in
+    /// armgpuinfo's `AdrenoData::spec_confidence`
+    pub fn from_label(label: &str) -> Option<Self> {
+        [
+            SpecConfidence::Measured,
+            SpecConfidence::ReverseEngineered,
+            SpecConfidence::Heuristic,
+            SpecConfidence::Extrapolated,
+        ]
+        .into_iter()
+        .find(|confidence| confidence.as_cow() == label)
+    }
+}
+
+impl fmt::Display for SpecConfidence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_cow())
+    }
+}
+
+/// Driver support status of a database entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum SupportStatus {
+    /// The vendor still ships driver releases covering the GPU
+    VendorSupported,
+    /// Dropped by the vendor, still maintained in Mesa/upstream Linux
+    /// (Panfrost, Freedreno)
+    CommunityOnly,
+    /// No maintained driver
+    EndOfLife,
+}
+
+impl SupportStatus {
+    /// Stable snake_case identifier
+    pub const fn code(self) -> &'static str {
+        match self {
+            SupportStatus::VendorSupported => "vendor_supported",
+            SupportStatus::CommunityOnly => "community_only",
+            SupportStatus::EndOfLife => "eol",
+        }
+    }
+}
+
+impl fmt::Display for SupportStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+/// Driver lifecycle metadata of a database entry
+///
+/// Serialize-only with the `serde` feature, like the database entries that
+/// carry it.
+///
+/// ```
+/// # #[cfg(feature = "mali")] {
+/// use armgpuinfo_core::mali::{get_gpu_id, lookup_product};
+/// use armgpuinfo_core::SupportStatus;
+///
+/// let t760 = lookup_product(get_gpu_id(0x0750), 4).unwrap();
+/// assert_eq!(t760.support.status, SupportStatus::CommunityOnly);
+/// assert_eq!(t760.support.last_driver_branch, "r28p0");
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct DriverSupport {
+    pub status: SupportStatus,
+    /// Last vendor driver branch known to support the GPU: the kbase DDK
+    /// release for Mali, the Android common kernel branch shipping KGSL for
+    /// Adreno
+    pub last_driver_branch: &'static str,
+}
+
+impl DriverSupport {
+    pub const fn new(status: SupportStatus, last_driver_branch: &'static str) -> Self {
+        Self { status, last_driver_branch }
+    }
+
+    /// Whether the vendor still releases drivers for the GPU
+    pub const fn is_vendor_supported(&self) -> bool {
+        matches!(self.status, SupportStatus::VendorSupported)
+    }
+}
+
+/// Graphics API version, e.g. Vulkan 1.3
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ApiVersion {
+    pub major: u8,
+    pub minor: u8,
+}
+
+impl ApiVersion {
+    pub const fn new(major: u8, minor: u8) -> Self {
+        Self { major, minor }
+    }
+}
+
+impl fmt::Display for ApiVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+/// Newest graphics APIs the vendor driver exposes for a database entry
+///
+/// Drivers older than the last release may expose less; Mesa's versions
+/// differ and are not tracked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ApiLevels {
+    /// `None` for GPUs without a Vulkan driver
+    pub vulkan: Option<ApiVersion>,
+    pub gles: ApiVersion,
+}
+
+impl ApiLevels {
+    pub const fn new(vulkan: Option<ApiVersion>, gles: ApiVersion) -> Self {
+        Self { vulkan, gles }
+    }
+}
+
+/// Hardware configuration registers from kbase `gpu_props`
+///
+/// Raw values as the driver reports them, zero where the kernel does not
+/// export the property. The IRQ throttle setting stays inside kbase and is
+/// not part of `gpu_props`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MaliHwConfig {
+    /// SHADER_PRESENT, one bit per shader core
+    pub shader_present: u64,
+    /// TILER_PRESENT
+    pub tiler_present: u64,
+    /// L2_PRESENT, one bit per L2 slice
+    pub l2_present: u64,
+    /// STACK_PRESENT (core stacks, Valhall and newer)
+    pub stack_present: u64,
+    /// AS_PRESENT, one bit per MMU address space
+    pub as_present: u32,
+    /// JS_PRESENT, one bit per job slot (zero on CSF)
+    pub js_present: u32,
+    /// JSn_FEATURES for each job slot
+    pub js_features: [u32; 16],
+    /// MEM_FEATURES
+    pub mem_features: u32,
+    /// MMU_FEATURES
+    pub mmu_features: u32,
+    /// TILER_FEATURES
+    pub tiler_features: u32,
+    /// TEXTURE_FEATURES_0..2
+    pub texture_features: [u32; 3],
+    /// THREAD_MAX_THREADS
+    pub thread_max_threads: u32,
+    /// THREAD_MAX_WORKGROUP_SIZE
+    pub thread_max_workgroup_size: u32,
+    /// THREAD_MAX_BARRIER_SIZE
+    pub thread_max_barrier_size: u32,
+    /// THREAD_TLS_ALLOC
+    pub thread_tls_alloc: u32,
+    /// COHERENCY_FEATURES as selected by the driver
+    pub coherency_mode: u32,
+    /// GPU_FEATURES (Valhall and newer)
+    pub gpu_features: u64,
+    /// Implementation technology (0 = not specified, 1 = silicon, 2 = FPGA, 3 = software model)
+    pub impl_tech: u32,
+}
+
+impl MaliHwConfig {
+    /// Number of tiler units
+    pub fn num_tilers(&self) -> u32 {
+        self.tiler_present.count_ones()
+    }
+
+    /// Number of MMU address spaces
+    pub fn num_address_spaces(&self) -> u32 {
+        self.as_present.count_ones()
+    }
+
+    /// Number of job slots (Job Manager GPUs only)
+    pub fn num_job_slots(&self) -> u32 {
+        self.js_present.count_ones()
+    }
+
+    /// Virtual address bits, MMU_FEATURES[7:0]
+    pub fn va_bits(&self) -> u8 {
+        (self.mmu_features & 0xFF) as u8
+    }
+
+    /// Physical address bits, MMU_FEATURES[15:8]
+    pub fn pa_bits(&self) -> u8 {
+        ((self.mmu_features >> 8) & 0xFF) as u8
+    }
+
+    /// Whether core groups are coherent, MEM_FEATURES[0]
+    pub fn coherent_core_groups(&self) -> bool {
+        self.mem_features & 1 != 0
+    }
+
+    /// Tiler bin size in bytes, 2^TILER_FEATURES[5:0]
+    pub fn tiler_bin_size_bytes(&self) -> u32 {
+        1u32.checked_shl(self.tiler_features & 0x3F).unwrap_or(0)
+    }
+
+    /// Maximum active hierarchy levels, TILER_FEATURES[11:8]
+    pub fn tiler_max_active_levels(&self) -> u32 {
+        (self.tiler_features >> 8) & 0xF
+    }
+
+    /// Ray intersection unit present, GPU_FEATURES[2]
+    pub fn has_ray_intersection(&self) -> bool {
+        self.gpu_features & (1 << 2) != 0
+    }
+}
+
+/// Slice topology of sliced Adreno parts (7xx onwards)
+///
+/// Each slice carries its own uSPTPs (shader processor and texture pipe
+/// pairs) and a share of GMEM, so occupancy and bin partitioning follow the
+/// slice count rather than the total core count.
+///
+/// ```
+/// use armgpuinfo_core::AdrenoSlices;
+///
+/// let a740 = AdrenoSlices::new(3, 2);
+/// assert_eq!(a740.usptps(), 6);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AdrenoSlices {
+    pub slices: u8,
+    pub usptps_per_slice: u8,
+}
+
+impl AdrenoSlices {
+    pub const fn new(slices: u8, usptps_per_slice: u8) -> Self {
+        Self { slices, usptps_per_slice }
+    }
+
+    /// Total uSPTPs, matching `shader_cores` of the database entry
+    pub const fn usptps(&self) -> u32 {
+        self.slices as u32 * self.usptps_per_slice as u32
+    }
+}
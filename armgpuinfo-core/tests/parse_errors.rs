@@ -0,0 +1,35 @@
+//! Errors of the strict parsers
+
+#[cfg(feature = "mali")]
+#[test]
+fn truncated_mali_property() {
+    use armgpuinfo_core::mali::{parse_properties_lenient, parse_properties_strict};
+    use armgpuinfo_core::ParseError;
+
+    // PRODUCT_ID announced as u32, two value bytes present
+    let mut buffer = ((1u32 << 2) | 2).to_le_bytes().to_vec();
+    buffer.extend_from_slice(&[0x67, 0xa8]);
+
+    assert_eq!(
+        parse_properties_strict(&buffer).unwrap_err(),
+        ParseError::BufferTooSmall { expected: 8, actual: 6 }
+    );
+    assert_eq!(parse_properties_lenient(&buffer).gpu_id, 0);
+}
+
+#[cfg(feature = "adreno")]
+#[test]
+fn adreno_chip_outside_database() {
+    use armgpuinfo_core::adreno::{parse_device_info_strict, ParsedDeviceInfo};
+    use armgpuinfo_core::ParseError;
+
+    let fields: [u32; 8] = [0, 0x0302_0000, 1, 0, 256 << 10, 0, 0, 0];
+    let buffer: Vec<u8> = fields.iter().flat_map(|field| field.to_le_bytes()).collect();
+
+    let error = parse_device_info_strict(&buffer).unwrap_err();
+    assert_eq!(error, ParseError::UnsupportedArchitecture { chip_id: 0x0302_0000, series: 3 });
+    assert!(error.to_string().contains("Adreno 3xx"));
+
+    let info = ParsedDeviceInfo::with_chip_id(0x0706_0001);
+    assert_eq!((info.arch_major, info.arch_minor, info.revision), (7, 6, 1));
+}
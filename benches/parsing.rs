@@ -0,0 +1,141 @@
+//! Parser, database and GpuInfo construction benchmarks
+//!
+//! Runs entirely on synthetic/fixture buffers, so no GPU is required:
+//! `cargo bench --bench parsing`
+use std::hint::black_box;
+
+use armgpuinfo::adreno::find_adreno_specs;
+use armgpuinfo::decode::{Decoder, RawRecord};
+use armgpuinfo::mali::{
+    extract_architecture, get_gpu_id, lookup_product, parse_properties, ParserConfig,
+};
+use armgpuinfo::{GpuInfoBuilder, GpuVendor};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+/// Encode (property id, size code, value) triples into a kbase property buffer
+fn encode_props(props: &[(u32, u32, u64)]) -> Vec<u8> {
+    let mut buffer = Vec::new();
+    for &(id, size, value) in props {
+        buffer.extend_from_slice(&((id << 2) | size).to_le_bytes());
+        let bytes = value.to_le_bytes();
+        buffer.extend_from_slice(&bytes[..1 << size]);
+    }
+    buffer
+}
+
+/// Fixture: Mali-G610 MC4 as reported by an RK3588 kbase driver
+fn fixture_g610() -> Vec<u8> {
+    encode_props(&[
+        (1, 2, 0xa867),          // PRODUCT_ID
+        (14, 0, 18),             // L2_LOG2_CACHE_SIZE
+        (15, 0, 1),              // L2_NUM_L2_SLICES
+        (29, 2, 0x0712_0206),    // RAW_L2_FEATURES
+        (30, 2, 0x0000_0002),    // RAW_CORE_FEATURES
+        (55, 3, 0xa867_0000),    // RAW_GPU_ID
+        (59, 2, 0x0400_0400),    // RAW_THREAD_FEATURES
+        (62, 0, 1),              // COHERENCY_NUM_CORE_GROUPS
+        (64, 3, 0x0005_0005),    // COHERENCY_GROUP_0
+    ])
+}
+
+/// Synthetic buffer with `count` unknown properties followed by the fixture
+fn synthetic(count: usize) -> Vec<u8> {
+    let padding: Vec<(u32, u32, u64)> = (0..count)
+        .map(|i| (100 + (i as u32 % 50), (i as u32) % 4, i as u64))
+        .collect();
+    let mut buffer = encode_props(&padding);
+    buffer.extend(fixture_g610());
+    buffer
+}
+
+fn bench_parse(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse_properties");
+    let fixture = fixture_g610();
+
+    for (name, config) in [("parity", ParserConfig::PARITY), ("extended", ParserConfig::EXTENDED)] {
+        group.throughput(Throughput::Bytes(fixture.len() as u64));
+        group.bench_with_input(BenchmarkId::new(name, "fixture_g610"), &fixture, |b, buf| {
+            b.iter(|| parse_properties(black_box(buf), config))
+        });
+
+        for count in [64, 1024] {
+            let buffer = synthetic(count);
+            group.throughput(Throughput::Bytes(buffer.len() as u64));
+            group.bench_with_input(BenchmarkId::new(name, count), &buffer, |b, buf| {
+                b.iter(|| parse_properties(black_box(buf), config))
+            });
+        }
+    }
+    group.finish();
+}
+
+fn bench_database(c: &mut Criterion) {
+    let mut group = c.benchmark_group("database");
+
+    group.bench_function("mali_lookup_product", |b| {
+        b.iter(|| lookup_product(get_gpu_id(black_box(0xa867)), black_box(4)))
+    });
+    group.bench_function("mali_lookup_miss", |b| {
+        b.iter(|| lookup_product(get_gpu_id(black_box(0x1234)), black_box(4)))
+    });
+    group.bench_function("adreno_exact_match", |b| {
+        b.iter(|| find_adreno_specs(black_box(0x0706_0001)))
+    });
+    group.bench_function("adreno_series_fallback", |b| {
+        b.iter(|| find_adreno_specs(black_box(0x08ff_0000)))
+    });
+    group.finish();
+}
+
+fn bench_gpu_info(c: &mut Criterion) {
+    let fixture = fixture_g610();
+
+    c.bench_function("gpu_info_from_fixture", |b| {
+        b.iter(|| {
+            let parsed = parse_properties(black_box(&fixture), ParserConfig::EXTENDED).unwrap();
+            let gpu_id = get_gpu_id(parsed.gpu_id);
+            let product = lookup_product(gpu_id, parsed.num_shader_cores).unwrap();
+            let (major, minor) = extract_architecture(parsed.raw_gpu_id);
+            GpuInfoBuilder::default()
+                .gpu_name(product.name)
+                .architecture(product.architecture.as_str())
+                .mali_arch(product.architecture)
+                .architecture_major(major)
+                .architecture_minor(minor)
+                .gpu_id(gpu_id)
+                .raw_gpu_id(parsed.raw_gpu_id)
+                .num_shader_cores(parsed.num_shader_cores)
+                .shader_core_mask(parsed.shader_core_mask)
+                .num_l2_slices(parsed.num_l2_slices)
+                .num_l2_bytes((1u64 << parsed.l2_log2_cache_size) * parsed.num_l2_slices)
+                .build()
+                .unwrap()
+        })
+    });
+}
+
+fn bench_decode(c: &mut Criterion) {
+    let distinct: [RawRecord; 4] = [
+        (GpuVendor::Mali, 0xa867, 4),
+        (GpuVendor::Mali, 0x9093, 10),
+        (GpuVendor::Adreno, 0x0706_0001, 0),
+        (GpuVendor::Adreno, 0x0605_0000, 0),
+    ];
+    let records: Vec<RawRecord> = distinct.iter().copied().cycle().take(100_000).collect();
+
+    let mut group = c.benchmark_group("decode");
+    group.throughput(Throughput::Elements(records.len() as u64));
+    group.bench_function("batch_100k", |b| {
+        let mut decoder = Decoder::new();
+        let mut out = Vec::with_capacity(records.len());
+        b.iter(|| {
+            out.clear();
+            decoder.decode_into(black_box(records.iter().copied()), &mut out);
+            out.len()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_parse, bench_database, bench_gpu_info, bench_decode);
+criterion_main!(benches);
@@ -24,7 +24,6 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             println!("   Architecture: {}", info.architecture);
             println!("   Arch Version: {}.{}", info.architecture_major, info.architecture_minor);
             println!("   Shader Cores: {}", info.num_shader_cores);
-            println!("   L2 Cache: {} KB", info.num_l2_bytes / 1024);
             println!("   Bus Width: {} bits", info.num_bus_bits);
             
             // WICHTIG: "ref" oder "&" verwenden, um zu borrowen
@@ -33,15 +32,20 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 println!("   Chip ID: 0x{:08X}", adreno.chip_id);
                 println!("   GPU Model Code: 0x{:08X}", adreno.gpu_model_code);
                 println!("   MMU Enabled: {}", adreno.mmu_enabled);
-                println!("   GMEM Size: {} bytes", adreno.gmem_size_bytes);
+                println!("   GMEM Size: {} bytes", adreno.gmem_bytes);
                 println!("   Confidence: {}", adreno.spec_confidence);
                 println!("   Stream Processors: {}", adreno.stream_processors);
+                if let Some(slices) = adreno.slices {
+                    println!("   Slices: {} x {} uSPTP", slices.slices, slices.usptps_per_slice);
+                }
                 println!("   Max Frequency: {} MHz", adreno.max_freq_mhz);
                 println!("   Process: {} nm", adreno.process_nm);
                 println!("   Release Year: {}", adreno.release_year);
                 
                 if !adreno.snapdragon_models.is_empty() {
-                    println!("   Snapdragon Models: {}", adreno.snapdragon_models.join(", "));
+                    let socs: Vec<String> =
+                        adreno.snapdragon_models.iter().map(|soc| soc.to_string()).collect();
+                    println!("   Snapdragon Models: {}", socs.join(", "));
                 }
             }
             
@@ -0,0 +1,11 @@
+//! Same I/O as `agent_probe` without the library, the footprint baseline
+use std::io::Write;
+
+fn main() {
+    let buf = [0u8; 8];
+    let len = std::env::args().count();
+    match std::io::stdout().write_all(&buf[..len.min(buf.len())]) {
+        Ok(()) => {}
+        Err(_) => std::process::exit(1),
+    }
+}
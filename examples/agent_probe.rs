@@ -0,0 +1,19 @@
+//! Minimal health-agent probe: writes the postcard-encoded record to stdout
+//!
+//! Exit code 1 if no GPU could be queried. Built by the `agent_footprint`
+//! test together with `agent_baseline` to measure the library's share of
+//! the binary size.
+use std::io::Write;
+
+use armgpuinfo::agent::{self, MAX_ENCODED_LEN};
+
+fn main() {
+    let mut buf = [0u8; MAX_ENCODED_LEN];
+    let written = agent::query().and_then(|record| record.encode(&mut buf).map(|bytes| bytes.len()));
+    match written {
+        Ok(len) => {
+            let _ = std::io::stdout().write_all(&buf[..len]);
+        }
+        Err(_) => std::process::exit(1),
+    }
+}
@@ -1,5 +1,5 @@
 //! Benchmark comparison: String vs Cow performance
-use armgpuinfo::{GpuInfoBuilder, GpuInfo, GpuVendor};
+use armgpuinfo::GpuInfoBuilder;
 use std::borrow::Cow;
 use std::time::{Instant, Duration};
 
@@ -27,7 +27,7 @@ fn main() {
     // Test 2: With Cow (no allocations for static strings)
     println!("Testing Cow version...");
     let start = Instant::now();
-    for i in 0..ITERATIONS {
+    for _ in 0..ITERATIONS {
         let _ = GpuInfoBuilder::default()
             .gpu_name(Cow::Borrowed("Mali-G710"))
             .architecture(Cow::Borrowed("Valhall"))
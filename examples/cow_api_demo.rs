@@ -1,5 +1,5 @@
 //! Demonstrates the flexible Cow-based API
-use armgpuinfo::{GpuInfo, GpuVendor, GpuInfoBuilder};
+use armgpuinfo::{DataConfidence, GpuCapabilities, GpuInfo, GpuVendor, GpuInfoBuilder};
 use std::borrow::Cow;
 
 fn main() {
@@ -10,17 +10,17 @@ fn main() {
     println!("1. Builder accepts different string types:");
     
     // &'static str
-    let builder1 = GpuInfoBuilder::default()
+    let _builder1 = GpuInfoBuilder::default()
         .gpu_name("Mali-G710")
         .architecture("Valhall");
     
     // String
-    let builder2 = GpuInfoBuilder::default()
+    let _builder2 = GpuInfoBuilder::default()
         .gpu_name(String::from("Adreno 740"))
         .architecture("Adreno 7xx".to_string());
     
     // Cow explicitly
-    let builder3 = GpuInfoBuilder::default()
+    let _builder3 = GpuInfoBuilder::default()
         .gpu_name(Cow::Borrowed("Mali-G57"))
         .architecture(Cow::Owned(String::from("Valhall")));
     
@@ -40,6 +40,11 @@ fn main() {
         num_bus_bits: 64,
         mali_data: None,
         adreno_data: None,
+        vivante_data: None,
+        capabilities: GpuCapabilities::default(),
+        confidence: DataConfidence::Unknown,
+        provenance: Vec::new(),
+        device_path: None,
     };
     
     println!("   Created: {}", gpu1);
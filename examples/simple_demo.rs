@@ -44,6 +44,11 @@ fn print_gpu_info(info: &armgpuinfo::GpuInfo) {
             }
             println!("   💡 Run: cargo run --example adreno_demo");
         }
+        GpuVendor::Vivante => {
+            if let Some(vivante) = &info.vivante_data {
+                println!("   Model: 0x{:04X} rev 0x{:04X}", vivante.model, vivante.revision);
+            }
+        }
         GpuVendor::Unknown => {
             println!("   ℹ️ Unknown GPU vendor");
         }
@@ -62,6 +67,12 @@ fn print_error(error: &GpuError) {
             println!("❌ Permission denied");
             println!("   Try running with sudo or fix permissions");
         }
+        GpuError::DetectionFailed { attempts } => {
+            println!("❌ No GPU device answered");
+            for (_, path, error) in attempts {
+                println!("   {}: {}", path.display(), error);
+            }
+        }
         e => {
             println!("❌ Error: {}", e);
         }
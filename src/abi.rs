@@ -0,0 +1,109 @@
+//! Layout checks of the ioctl structures against the kernel UAPI
+//!
+//! Every `#[repr(C)]` structure handed to a driver is listed next to its
+//! definition with the size and field offsets from the UAPI header. On
+//! arm, aarch64 and x86_64 a mismatch fails the build; on other targets,
+//! where the expected values are unverified, [`self_check`] reports it at
+//! runtime instead of the driver silently reading the wrong bytes.
+//!
+//! ```
+//! armgpuinfo::abi::self_check()?;
+//! assert!(armgpuinfo::abi::checks().iter().all(|check| check.matches()));
+//! # Ok::<(), armgpuinfo::GpuError>(())
+//! ```
+
+use crate::error::{GpuError, GpuResult};
+
+/// One structure size or field offset
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LayoutCheck {
+    /// Kernel struct name, `struct.field` for offsets
+    pub item: &'static str,
+    /// Value from the UAPI header for this target
+    pub expected: usize,
+    /// Value compiled into this build
+    pub actual: usize,
+}
+
+impl LayoutCheck {
+    pub fn matches(&self) -> bool {
+        self.expected == self.actual
+    }
+}
+
+/// Pick the UAPI value for the pointer width of the target
+///
+/// `long`, `size_t` and pointers are the only members whose size differs
+/// between the supported targets.
+pub(crate) const fn by_pointer_width(bits64: usize, bits32: usize) -> usize {
+    if cfg!(target_pointer_width = "64") {
+        bits64
+    } else {
+        bits32
+    }
+}
+
+/// Declare the UAPI layout of ioctl structures
+///
+/// Expands to build-time assertions on the verified targets and to a
+/// function listing the same checks for [`checks`].
+macro_rules! layouts {
+    ($vis:vis fn $fn_name:ident {
+        $($ty:ty as $name:literal: size $size:expr $(, $field:ident @ $offset:expr)*;)*
+    }) => {
+        $(
+            #[cfg(any(target_arch = "arm", target_arch = "aarch64", target_arch = "x86_64"))]
+            const _: () = {
+                assert!(::std::mem::size_of::<$ty>() == $size, concat!("size of ", $name));
+                $(assert!(
+                    ::std::mem::offset_of!($ty, $field) == $offset,
+                    concat!("offset of ", $name, ".", stringify!($field))
+                );)*
+            };
+        )*
+
+        $vis fn $fn_name() -> Vec<$crate::abi::LayoutCheck> {
+            vec![$(
+                $crate::abi::LayoutCheck {
+                    item: $name,
+                    expected: $size,
+                    actual: ::std::mem::size_of::<$ty>(),
+                },
+                $($crate::abi::LayoutCheck {
+                    item: concat!($name, ".", stringify!($field)),
+                    expected: $offset,
+                    actual: ::std::mem::offset_of!($ty, $field),
+                },)*
+            )*]
+        }
+    };
+}
+
+pub(crate) use layouts;
+
+/// Every layout check of the backends compiled into this build
+pub fn checks() -> Vec<LayoutCheck> {
+    let mut checks = Vec::new();
+    #[cfg(feature = "mali-kbase")]
+    checks.extend(crate::mali::kbase_layouts());
+    #[cfg(feature = "adreno-kgsl")]
+    checks.extend(crate::adreno::kgsl_layouts());
+    checks.extend(crate::drm::drm_layouts());
+    #[cfg(feature = "mali-drm")]
+    checks.extend(crate::mali::drm_layouts());
+    #[cfg(feature = "adreno-drm")]
+    checks.extend(crate::adreno::drm_layouts());
+    #[cfg(feature = "vivante")]
+    checks.extend(crate::vivante::drm_layouts());
+    checks
+}
+
+/// Verify every structure layout, returning the first mismatch
+pub fn self_check() -> GpuResult<()> {
+    match checks().into_iter().find(|check| !check.matches()) {
+        Some(LayoutCheck { item, expected, actual }) => {
+            Err(GpuError::AbiMismatch { item, expected, actual })
+        }
+        None => Ok(()),
+    }
+}
@@ -0,0 +1,102 @@
+//! sysinfo/heim-style adapter for general-purpose system monitors
+//!
+//! System monitors model hardware as small components exposing a label, a few
+//! cached readings and a `refresh()` method. [`GpuComponent`] mirrors that
+//! surface so a mobile GPU can be listed next to CPUs and disks without the
+//! monitor knowing anything about kbase or KGSL.
+
+use crate::info::{GpuInfo, GpuVendor};
+use crate::telemetry;
+
+/// Minimal component surface shared with sysinfo/heim-style monitors
+///
+/// All readings are cached and only updated by [`GpuComponent::refresh`].
+pub trait GpuComponent {
+    /// Human-readable GPU name (e.g. "Mali-G710")
+    fn name(&self) -> &str;
+
+    /// Vendor label (e.g. "ARM Mali")
+    fn vendor(&self) -> &str;
+
+    /// Memory usable by the GPU in bytes
+    ///
+    /// Mobile GPUs have no dedicated VRAM and share system RAM, so this
+    /// reports the total system memory.
+    fn total_memory(&self) -> Option<u64>;
+
+    /// GPU temperature in degrees Celsius
+    fn temperature(&self) -> Option<f32>;
+
+    /// GPU busy percentage (0.0 - 100.0)
+    fn usage(&self) -> Option<f32>;
+
+    /// Re-read all dynamic values from sysfs/procfs
+    fn refresh(&mut self);
+}
+
+/// [`GpuComponent`] implementation backed by a queried [`GpuInfo`]
+#[derive(Debug, Clone)]
+pub struct MobileGpu {
+    info: GpuInfo,
+    total_memory: Option<u64>,
+    temperature: Option<f32>,
+    usage: Option<f32>,
+}
+
+impl MobileGpu {
+    /// Wrap an already queried GPU and take an initial reading
+    pub fn new(info: GpuInfo) -> Self {
+        let mut gpu = Self {
+            info,
+            total_memory: None,
+            temperature: None,
+            usage: None,
+        };
+        gpu.refresh();
+        gpu
+    }
+
+    /// Detect the GPU automatically and wrap it
+    #[cfg(all(feature = "auto-detect", any(feature = "mali-kbase", feature = "adreno-kgsl")))]
+    pub fn detect() -> crate::error::GpuResult<Self> {
+        crate::detect::query_gpu_auto(None::<&str>).map(Self::new)
+    }
+
+    /// Static GPU information backing this component
+    pub fn info(&self) -> &GpuInfo {
+        &self.info
+    }
+}
+
+impl GpuComponent for MobileGpu {
+    fn name(&self) -> &str {
+        &self.info.gpu_name
+    }
+
+    fn vendor(&self) -> &str {
+        match self.info.vendor {
+            GpuVendor::Mali => "ARM Mali",
+            GpuVendor::Adreno => "Qualcomm Adreno",
+            GpuVendor::Vivante => "VeriSilicon Vivante",
+            GpuVendor::Unknown => "Unknown",
+        }
+    }
+
+    fn total_memory(&self) -> Option<u64> {
+        self.total_memory
+    }
+
+    fn temperature(&self) -> Option<f32> {
+        self.temperature
+    }
+
+    fn usage(&self) -> Option<f32> {
+        self.usage
+    }
+
+    fn refresh(&mut self) {
+        self.total_memory = telemetry::read_total_memory();
+        self.temperature = telemetry::read_temperature().map(|r| r.value);
+        self.usage = telemetry::read_utilization().map(|r| r.value);
+    }
+}
@@ -0,0 +1,215 @@
+//! GpuInfo assembly shared by the KGSL and DRM backends
+
+use std::borrow::Cow;
+
+use crate::capabilities::GpuCapabilities;
+use crate::diag::diag;
+use crate::error::{GpuError, GpuResult};
+use crate::fields::FieldId;
+use crate::info::{AdrenoData, DataConfidence, GmuInfo, GpuInfo, GpuVendor};
+use crate::instrument::{timed, Phase};
+
+use super::database::{resolve_adreno_specs, AdrenoSpecs, SpecConfidence};
+use super::gmu::{find_gmu_device, read_gmu_firmware_version};
+use super::parser::{parse_device_info, ParsedDeviceInfo, ParserConfig};
+use super::Mode;
+
+/// Common function to create GpuInfo from parsed device info and specs
+///
+/// `gmu` is read from the running system by live queries, see [`gmu_info`];
+/// decoding paths pass `None`.
+pub(crate) fn create_gpu_info_from_specs(
+    device_info: &ParsedDeviceInfo,
+    specs: &AdrenoSpecs,
+    gmu: Option<GmuInfo>,
+) -> GpuInfo {
+    // Prefer the driver-reported GMEM size over the database value
+    let gmem_bytes = match device_info.gmem_sizebytes {
+        0 => specs.gmem_size_kb as u64 * 1024,
+        bytes => bytes as u64,
+    };
+
+    let adreno_data = AdrenoData {
+        chip_id: device_info.chip_id,
+        gpu_model_code: device_info.gpu_model,
+        mmu_enabled: device_info.mmu_enabled,
+        gmem_bytes,
+        driver_model_name: None,
+        driver_version: None,
+        speed_bin: None,
+        ubwc_mode: None,
+        uche_gmem_vaddr: None,
+        highest_bank_bit: None,
+        min_access_length: None,
+        qdss_stm: None,
+        spec_confidence: specs.confidence.as_cow(),
+        stream_processors: specs.stream_processors,
+        slices: specs.slices,
+        max_freq_mhz: specs.max_freq_mhz,
+        measured_max_freq_mhz: None,
+        freq_table: Vec::new(),
+        current_pwrlevel: None,
+        process_nm: specs.process_nm,
+        release_year: specs.year,
+        snapdragon_models: specs.snapdragon_models.to_vec(),
+        gmu,
+        confirmed: if device_info.gmem_sizebytes != 0 {
+            vec![FieldId::GmemBytes]
+        } else {
+            Vec::new()
+        },
+    };
+
+    let mut info = GpuInfo {
+        vendor: GpuVendor::Adreno,
+        gpu_name: Cow::Borrowed(specs.name),
+        architecture: specs.architecture.to_string().into(),
+        architecture_major: device_info.arch_major,
+        architecture_minor: device_info.arch_minor,
+        num_shader_cores: specs.shader_cores,
+        // Compatibility alias, see GpuInfo::num_l2_bytes
+        num_l2_bytes: gmem_bytes,
+        num_bus_bits: specs.bus_width_bits as u64,
+        mali_data: None,
+        adreno_data: Some(adreno_data),
+        vivante_data: None,
+        capabilities: GpuCapabilities::default(),
+        // The core count always comes from the database
+        confidence: specs.confidence.into(),
+        provenance: Vec::new(),
+        device_path: None,
+    };
+    info.capabilities = GpuCapabilities::of(&info);
+    info
+}
+
+/// GMU device and firmware version, `None` on parts without a GMU
+pub(crate) fn gmu_info() -> Option<GmuInfo> {
+    let device = find_gmu_device();
+    let firmware_version = read_gmu_firmware_version(device.as_deref());
+    (device.is_some() || firmware_version.is_some()).then_some(GmuInfo { device, firmware_version })
+}
+
+/// Per-field confidence of a result built by [`create_gpu_info_from_specs`]
+///
+/// The chip ID is measured, GMEM too when the driver reported it; the rest
+/// carries the confidence of the database entry.
+pub(crate) fn provenance(info: &GpuInfo) -> Vec<(FieldId, DataConfidence)> {
+    let Some(adreno) = &info.adreno_data else {
+        return Vec::new();
+    };
+    let measured = DataConfidence::Measured;
+    let database = SpecConfidence::from_label(&adreno.spec_confidence)
+        .map_or(DataConfidence::Unknown, DataConfidence::from);
+    let gmem = if adreno.confirmed.contains(&FieldId::GmemBytes) { measured } else { database };
+
+    vec![
+        (FieldId::ChipId, measured),
+        (FieldId::ArchitectureMajor, measured),
+        (FieldId::ArchitectureMinor, measured),
+        (FieldId::GmemBytes, gmem),
+        (FieldId::NumL2Bytes, gmem),
+        (FieldId::GpuName, database),
+        (FieldId::Architecture, database),
+        (FieldId::NumShaderCores, database),
+        (FieldId::NumBusBits, database),
+        (FieldId::MaxFreqMhz, database),
+    ]
+}
+
+/// Build GpuInfo from a raw `KGSL_PROP_DEVICE_INFO` structure
+///
+/// Covers what both modes read from the structure itself; Extended mode
+/// additionally queries optional properties on the device. `gmu` is passed
+/// through to [`create_gpu_info_from_specs`].
+pub(crate) fn assemble_device_info(
+    raw: &[u8],
+    mode: Mode,
+    config: ParserConfig,
+    validate: bool,
+    gmu: Option<GmuInfo>,
+) -> GpuResult<GpuInfo> {
+    let device_info = timed(Phase::Parse, || parse_device_info(raw, config))?;
+
+    if device_info.chip_id == 0 {
+        return Err(GpuError::InvalidData("Chip ID is zero".into()));
+    }
+
+    if mode == Mode::Extended && device_info.device_id == 0 {
+        diag!(warn, "device ID is zero, driver info might be incomplete");
+    }
+
+    // Look up specs in database
+    let specs = timed(Phase::Lookup, || resolve_adreno_specs(device_info.chip_id))
+        .ok_or(GpuError::UnsupportedGpu {
+            id: device_info.chip_id,
+            cores: 0,
+        })?;
+
+    // Validate confidence level in extended mode
+    if mode == Mode::Extended {
+        match specs.confidence {
+            SpecConfidence::Heuristic => {
+                diag!(warn, "using heuristic specifications for chip ID 0x{:08x}", device_info.chip_id);
+            }
+            SpecConfidence::Extrapolated => {
+                diag!(warn, "using extrapolated specifications for chip ID 0x{:08x}", device_info.chip_id);
+            }
+            _ => {}
+        }
+    }
+
+    let mut info = create_gpu_info_from_specs(&device_info, &specs, gmu);
+    if mode == Mode::Extended {
+        info.provenance = provenance(&info);
+    }
+    if validate {
+        validate_extended_info(&info)?;
+    }
+    Ok(info)
+}
+
+/// Validate GPU info for extended mode
+fn validate_extended_info(info: &GpuInfo) -> GpuResult<()> {
+    if info.num_shader_cores == 0 {
+        return Err(GpuError::InvalidData("Shader core count is zero".into()));
+    }
+    
+    if info.num_l2_bytes == 0 {
+        return Err(GpuError::InvalidData("L2 cache size is zero".into()));
+    }
+    
+    // Validate architecture version makes sense
+    if info.architecture_major < 4 || info.architecture_major > 9 {
+        return Err(GpuError::InvalidData(format!(
+            "Invalid architecture major version: {}",
+            info.architecture_major
+        )));
+    }
+    
+    Ok(())
+}
+
+/// Decode a dumped KGSL device info structure as a query in `mode` would
+///
+/// The optional properties Extended mode reads from the device, and the GMU
+/// found on the running system, are not part of the dump and stay empty.
+///
+/// ```
+/// use armgpuinfo::adreno::{parse_snapshot, Mode};
+///
+/// // device_id, chip_id, mmu_enabled, gmem_gpubaseaddr, gmem_sizebytes, ...
+/// let words: [u32; 8] = [1, 0x0706_0001, 1, 0x0010_0000, 2 << 20, 0, 0, 0];
+/// let raw: Vec<u8> = words.iter().flat_map(|w| w.to_le_bytes()).collect();
+///
+/// let info = parse_snapshot(&raw, Mode::Extended)?;
+/// assert_eq!(info.gpu_name, "Adreno 740");
+/// let adreno = info.adreno_data.unwrap();
+/// assert_eq!(adreno.gmem_bytes, 2 << 20);
+/// // Nothing is read from the machine decoding the dump
+/// assert_eq!(adreno.gmu, None);
+/// # Ok::<(), armgpuinfo::GpuError>(())
+/// ```
+pub fn parse_snapshot(raw: &[u8], mode: Mode) -> GpuResult<GpuInfo> {
+    assemble_device_info(raw, mode, mode.parser_config(), mode == Mode::Extended, None)
+}
@@ -0,0 +1,267 @@
+//! KGSL performance counters
+//!
+//! KGSL multiplexes a fixed number of counter registers per hardware block
+//! ("group"). `IOCTL_KGSL_PERFCOUNTER_GET` reserves a register for a
+//! countable (event selector), `IOCTL_KGSL_PERFCOUNTER_READ` samples the
+//! reserved registers and `IOCTL_KGSL_PERFCOUNTER_PUT` releases them.
+//! [`AdrenoPerfCounterGroup`] wraps the three and releases everything it
+//! reserved on drop.
+//!
+//! Countable numbers follow the `a5xx`/`a6xx` register descriptions from
+//! freedreno; [`PerfCounter`] names the commonly used ones.
+
+use std::fs::File;
+use std::os::unix::io::{AsRawFd, RawFd};
+use std::path::Path;
+
+use crate::error::{GpuError, GpuResult};
+
+use super::database::{resolve_adreno_specs, AdrenoArch};
+use super::ioctl::{
+    KgslPerfcounterGet, KgslPerfcounterPut, KgslPerfcounterRead, KgslPerfcounterReadGroup,
+    IOCTL_KGSL_PERFCOUNTER_GET, IOCTL_KGSL_PERFCOUNTER_PUT, IOCTL_KGSL_PERFCOUNTER_READ,
+};
+use super::ioctl_impl::get_device_info;
+use super::query::open_device;
+
+/// KGSL counter group IDs (`KGSL_PERFCOUNTER_GROUP_*`)
+pub mod group {
+    pub const CP: u32 = 0x00;
+    pub const RBBM: u32 = 0x01;
+    pub const PC: u32 = 0x02;
+    pub const VFD: u32 = 0x03;
+    pub const HLSQ: u32 = 0x04;
+    pub const VPC: u32 = 0x05;
+    pub const TSE: u32 = 0x06;
+    pub const RAS: u32 = 0x07;
+    pub const UCHE: u32 = 0x08;
+    pub const TP: u32 = 0x09;
+    pub const SP: u32 = 0x0A;
+    pub const RB: u32 = 0x0B;
+    pub const PWR: u32 = 0x0C;
+    pub const VBIF: u32 = 0x0D;
+    pub const VBIF_PWR: u32 = 0x0E;
+    pub const VSC: u32 = 0x17;
+    pub const CCU: u32 = 0x18;
+    pub const LRZ: u32 = 0x19;
+    pub const CMP: u32 = 0x1A;
+    pub const ALWAYSON: u32 = 0x1B;
+}
+
+/// Named counters
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PerfCounter {
+    /// Cycles the shader processors were busy
+    SpBusy,
+    /// Cycles the ALUs were working
+    AluWorking,
+    /// Cycles the elementary function units were working
+    EfuWorking,
+    /// Shader cycles stalled on the texture pipe
+    SpStallTp,
+    /// Shader cycles stalled on memory (UCHE)
+    SpStallUche,
+    /// Cycles the texture pipe was busy
+    TpBusy,
+    /// Texture L1 cache line requests (texture fetches)
+    TpL1Requests,
+    /// Texture L1 cache line misses
+    TpL1Misses,
+    /// Texture pipe cycles stalled on memory (UCHE)
+    TpStallUche,
+}
+
+impl PerfCounter {
+    pub const ALL: [PerfCounter; 9] = [
+        PerfCounter::SpBusy,
+        PerfCounter::AluWorking,
+        PerfCounter::EfuWorking,
+        PerfCounter::SpStallTp,
+        PerfCounter::SpStallUche,
+        PerfCounter::TpBusy,
+        PerfCounter::TpL1Requests,
+        PerfCounter::TpL1Misses,
+        PerfCounter::TpStallUche,
+    ];
+
+    /// Countable name without the `PERF_` prefix
+    pub const fn name(self) -> &'static str {
+        match self {
+            PerfCounter::SpBusy => "SP_BUSY_CYCLES",
+            PerfCounter::AluWorking => "SP_ALU_WORKING_CYCLES",
+            PerfCounter::EfuWorking => "SP_EFU_WORKING_CYCLES",
+            PerfCounter::SpStallTp => "SP_STALL_CYCLES_TP",
+            PerfCounter::SpStallUche => "SP_STALL_CYCLES_UCHE",
+            PerfCounter::TpBusy => "TP_BUSY_CYCLES",
+            PerfCounter::TpL1Requests => "TP_L1_CACHELINE_REQUESTS",
+            PerfCounter::TpL1Misses => "TP_L1_CACHELINE_MISSES",
+            PerfCounter::TpStallUche => "TP_STALL_CYCLES_UCHE",
+        }
+    }
+
+    /// KGSL group the counter belongs to
+    pub const fn group(self) -> u32 {
+        match self {
+            PerfCounter::SpBusy
+            | PerfCounter::AluWorking
+            | PerfCounter::EfuWorking
+            | PerfCounter::SpStallTp
+            | PerfCounter::SpStallUche => group::SP,
+            PerfCounter::TpBusy
+            | PerfCounter::TpL1Requests
+            | PerfCounter::TpL1Misses
+            | PerfCounter::TpStallUche => group::TP,
+        }
+    }
+
+    /// Countable selector on `arch`, `None` where the numbering is unknown
+    pub const fn countable(self, arch: AdrenoArch) -> Option<u32> {
+        if matches!(arch, AdrenoArch::A4xx) {
+            return None;
+        }
+        Some(match self {
+            PerfCounter::SpBusy => 0,
+            PerfCounter::AluWorking => 1,
+            PerfCounter::EfuWorking => 2,
+            PerfCounter::SpStallTp => 4,
+            PerfCounter::SpStallUche => 5,
+            PerfCounter::TpBusy => 0,
+            PerfCounter::TpStallUche => 1,
+            PerfCounter::TpL1Requests => 6,
+            PerfCounter::TpL1Misses => 7,
+        })
+    }
+}
+
+/// One sampled counter
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PerfCounterValue {
+    pub group: u32,
+    pub countable: u32,
+    /// The named counter, if the value was reserved through one
+    pub counter: Option<PerfCounter>,
+    /// Raw 64-bit count since the register was reserved
+    pub value: u64,
+}
+
+/// A set of reserved KGSL counters, released on drop
+///
+/// ```no_run
+/// use armgpuinfo::adreno::counters::{AdrenoPerfCounterGroup, PerfCounter};
+///
+/// let mut counters = AdrenoPerfCounterGroup::open("/dev/kgsl-3d0")?;
+/// counters.add(PerfCounter::AluWorking)?;
+/// counters.add(PerfCounter::SpStallUche)?;
+/// for value in counters.read()? {
+///     println!("{:?}: {}", value.counter, value.value);
+/// }
+/// # Ok::<(), armgpuinfo::GpuError>(())
+/// ```
+#[derive(Debug)]
+pub struct AdrenoPerfCounterGroup {
+    file: File,
+    arch: Option<AdrenoArch>,
+    reserved: Vec<(u32, u32, Option<PerfCounter>)>,
+}
+
+impl AdrenoPerfCounterGroup {
+    /// Open a KGSL device node for counter sampling
+    pub fn open<P: AsRef<Path>>(device_path: P) -> GpuResult<Self> {
+        let file = open_device(device_path.as_ref())?;
+        let arch = get_device_info(file.as_raw_fd())
+            .ok()
+            .and_then(|info| resolve_adreno_specs(info.chip_id))
+            .map(|specs| specs.architecture);
+        Ok(Self { file, arch, reserved: Vec::new() })
+    }
+
+    /// Architecture used to resolve named counters
+    pub fn arch(&self) -> Option<AdrenoArch> {
+        self.arch
+    }
+
+    /// Reserve a named counter
+    pub fn add(&mut self, counter: PerfCounter) -> GpuResult<()> {
+        let arch = self.arch.ok_or_else(|| {
+            GpuError::InvalidData("unknown Adreno architecture for named counters".into())
+        })?;
+        let countable = counter.countable(arch).ok_or_else(|| {
+            GpuError::InvalidData(format!("{} not known on {arch}", counter.name()))
+        })?;
+        self.reserve(counter.group(), countable, Some(counter))
+    }
+
+    /// Reserve a raw group/countable pair
+    pub fn add_raw(&mut self, group: u32, countable: u32) -> GpuResult<()> {
+        self.reserve(group, countable, None)
+    }
+
+    fn reserve(&mut self, groupid: u32, countable: u32, counter: Option<PerfCounter>) -> GpuResult<()> {
+        if self.reserved.iter().any(|&(g, c, _)| g == groupid && c == countable) {
+            return Ok(());
+        }
+        let mut get = KgslPerfcounterGet { groupid, countable, ..Default::default() };
+        ioctl(self.file.as_raw_fd(), IOCTL_KGSL_PERFCOUNTER_GET, &mut get)?;
+        self.reserved.push((groupid, countable, counter));
+        Ok(())
+    }
+
+    /// Sample every reserved counter
+    pub fn read(&self) -> GpuResult<Vec<PerfCounterValue>> {
+        let mut reads: Vec<KgslPerfcounterReadGroup> = self
+            .reserved
+            .iter()
+            .map(|&(groupid, countable, _)| KgslPerfcounterReadGroup { groupid, countable, value: 0 })
+            .collect();
+        if reads.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut request = KgslPerfcounterRead {
+            reads: reads.as_mut_ptr(),
+            count: reads.len() as u32,
+            pad: [0; 2],
+        };
+        ioctl(self.file.as_raw_fd(), IOCTL_KGSL_PERFCOUNTER_READ, &mut request)?;
+
+        Ok(reads
+            .iter()
+            .zip(&self.reserved)
+            .map(|(read, &(group, countable, counter))| PerfCounterValue {
+                group,
+                countable,
+                counter,
+                value: read.value,
+            })
+            .collect())
+    }
+
+    /// Release all reserved counters
+    pub fn clear(&mut self) {
+        for (groupid, countable, _) in self.reserved.drain(..) {
+            let mut put = KgslPerfcounterPut { groupid, countable, pad: [0; 2] };
+            // Nothing useful to do if the driver already dropped it
+            let _ = ioctl(self.file.as_raw_fd(), IOCTL_KGSL_PERFCOUNTER_PUT, &mut put);
+        }
+    }
+}
+
+impl Drop for AdrenoPerfCounterGroup {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+fn ioctl<T>(fd: RawFd, request: u64, arg: &mut T) -> GpuResult<()> {
+    // SAFETY: `arg` is the repr(C) structure matching `request`
+    let result = unsafe { libc::ioctl(fd, request as _, arg as *mut T) };
+    if result == 0 {
+        return Ok(());
+    }
+    let err = std::io::Error::last_os_error();
+    Err(match err.raw_os_error() {
+        Some(libc::ENOTTY) => GpuError::DriverNotSupported,
+        Some(libc::EPERM) | Some(libc::EACCES) => GpuError::PermissionDenied,
+        _ => GpuError::IoctlFailed { request, source: err },
+    })
+}
@@ -0,0 +1,99 @@
+//! Upstream msm DRM backend
+//!
+//! Mainline kernels expose Adreno through the msm DRM driver instead of
+//! KGSL. Render nodes (`/dev/dri/renderD*`) need no special permissions,
+//! and `DRM_IOCTL_MSM_GET_PARAM` reports the same chip ID as KGSL, so the
+//! rest of the data comes from the chip database.
+
+use std::os::unix::io::{AsRawFd, RawFd};
+use std::path::Path;
+
+use crate::drm::{driver_name, drm_iowr, ioctl, open_render_node, DRM_COMMAND_BASE};
+use crate::error::{GpuError, GpuResult};
+use crate::fields::FieldId;
+use crate::info::{DataConfidence, GpuInfo};
+
+use super::assemble::{create_gpu_info_from_specs, gmu_info, provenance};
+use super::database::resolve_adreno_specs;
+use super::parser::ParsedDeviceInfo;
+
+/// Driver name reported by `DRM_IOCTL_VERSION`
+const MSM_DRIVER_NAME: &str = "msm";
+
+const MSM_PIPE_3D0: u32 = 0x10;
+const MSM_PARAM_GMEM_SIZE: u32 = 0x02;
+const MSM_PARAM_CHIP_ID: u32 = 0x03;
+const MSM_PARAM_MAX_FREQ: u32 = 0x04;
+
+/// `struct drm_msm_param`
+#[repr(C)]
+#[derive(Default)]
+struct DrmMsmParam {
+    pipe: u32,
+    param: u32,
+    value: u64,
+    len: u32,
+    pad: u32,
+}
+
+crate::abi::layouts! {
+    pub(crate) fn drm_layouts {
+        DrmMsmParam as "drm_msm_param": size 24, value @ 8, len @ 16;
+    }
+}
+
+/// `DRM_COMMAND_BASE + DRM_MSM_GET_PARAM`
+const DRM_IOCTL_MSM_GET_PARAM: u64 = drm_iowr(DRM_COMMAND_BASE, std::mem::size_of::<DrmMsmParam>());
+
+/// Query an msm DRM render node
+///
+/// Returns [`GpuError::DriverNotSupported`] for render nodes of other
+/// drivers.
+pub fn query_adreno_drm<P: AsRef<Path>>(device_path: P) -> GpuResult<GpuInfo> {
+    let device_path = device_path.as_ref();
+    let file = open_render_node(device_path)?;
+    let fd = file.as_raw_fd();
+
+    if driver_name(fd)? != MSM_DRIVER_NAME {
+        return Err(GpuError::DriverNotSupported);
+    }
+
+    let chip_id = get_param(fd, MSM_PARAM_CHIP_ID)? as u32;
+    if chip_id == 0 {
+        return Err(GpuError::InvalidData("Chip ID is zero".into()));
+    }
+    let mut device_info = ParsedDeviceInfo::with_chip_id(chip_id);
+    // GMEM size is optional; the database value is used without it
+    device_info.gmem_sizebytes = get_param(fd, MSM_PARAM_GMEM_SIZE).unwrap_or(0) as u32;
+    // msm always runs the GPU behind its IOMMU
+    device_info.mmu_enabled = true;
+
+    let specs = resolve_adreno_specs(device_info.chip_id).ok_or(GpuError::UnsupportedGpu {
+        id: device_info.chip_id,
+        cores: 0,
+    })?;
+
+    let mut info = create_gpu_info_from_specs(&device_info, &specs, gmu_info());
+    info.provenance = provenance(&info);
+    // The devfreq ceiling of this board, which may differ from the database
+    // boost clock on binned or downclocked parts
+    if let Ok(hz) = get_param(fd, MSM_PARAM_MAX_FREQ) {
+        if let Some(adreno) = info.adreno_data.as_mut().filter(|_| hz > 0) {
+            adreno.max_freq_mhz = (hz / 1_000_000) as u32;
+            info.set_data_confidence(FieldId::MaxFreqMhz, DataConfidence::Measured);
+        }
+    }
+    info.device_path = Some(device_path.to_path_buf());
+    Ok(info)
+}
+
+fn get_param(fd: RawFd, param: u32) -> GpuResult<u64> {
+    let mut req = DrmMsmParam {
+        pipe: MSM_PIPE_3D0,
+        param,
+        ..DrmMsmParam::default()
+    };
+
+    ioctl(fd, DRM_IOCTL_MSM_GET_PARAM, &mut req)?;
+    Ok(req.value)
+}
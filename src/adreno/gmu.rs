@@ -0,0 +1,88 @@
+//! GMU (graphics management unit) discovery
+//!
+//! Adreno 6xx and newer hand power management to a separate GMU core. It is
+//! registered as its own platform device, named `<addr>.qcom,gmu` by the
+//! downstream KGSL driver and `<addr>.gmu` by mainline msm. Its firmware
+//! version is not a KGSL property (`KGSL_PROP_GPMU_VERSION` belongs to the
+//! A5xx GPMU); kernels that export it do so as a text node.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::info::GmuFirmwareVersion;
+
+/// Default platform device directory
+pub const PLATFORM_DEVICES_ROOT: &str = "/sys/bus/platform/devices";
+
+/// KGSL debugfs directory of the first GPU
+pub const KGSL_DEBUGFS_ROOT: &str = "/sys/kernel/debug/kgsl/kgsl-3d0";
+
+/// Find the GMU platform device
+pub fn find_gmu_device() -> Option<PathBuf> {
+    find_gmu_device_in(Path::new(PLATFORM_DEVICES_ROOT))
+}
+
+/// [`find_gmu_device`] under a custom platform device directory
+pub fn find_gmu_device_in(root: &Path) -> Option<PathBuf> {
+    let mut candidates: Vec<PathBuf> = fs::read_dir(root)
+        .ok()?
+        .flatten()
+        .filter(|entry| {
+            let name = entry.file_name().to_string_lossy().to_ascii_lowercase();
+            name.ends_with(".gmu") || name.ends_with(",gmu") || name.contains("kgsl-gmu")
+        })
+        .map(|entry| entry.path())
+        .collect();
+    candidates.sort();
+    candidates.into_iter().next()
+}
+
+/// Firmware version of the GMU at `gmu_device`
+///
+/// Reads `fw_version` of the platform device, then `gmu_fw_version` in the
+/// KGSL debugfs directory (root only).
+pub fn read_gmu_firmware_version(gmu_device: Option<&Path>) -> Option<GmuFirmwareVersion> {
+    read_gmu_firmware_version_in(gmu_device, Path::new(KGSL_DEBUGFS_ROOT))
+}
+
+/// [`read_gmu_firmware_version`] with a custom KGSL debugfs directory
+pub fn read_gmu_firmware_version_in(gmu_device: Option<&Path>, debugfs: &Path) -> Option<GmuFirmwareVersion> {
+    gmu_device
+        .map(|device| device.join("fw_version"))
+        .into_iter()
+        .chain([debugfs.join("gmu_fw_version")])
+        .find_map(|node| parse_gmu_firmware_version(&fs::read_to_string(node).ok()?))
+}
+
+/// Parse a GMU firmware version node
+///
+/// Accepts `major.minor.step` and the packed word KGSL logs, with the
+/// major version in bits 28-31, minor in 16-27 and step in 0-15.
+///
+/// ```
+/// use armgpuinfo::adreno::parse_gmu_firmware_version;
+///
+/// let version = parse_gmu_firmware_version("0x20010004\n").unwrap();
+/// assert_eq!((version.major, version.minor, version.step), (2, 1, 4));
+/// assert_eq!(parse_gmu_firmware_version("2.1.4"), Some(version));
+/// ```
+pub fn parse_gmu_firmware_version(text: &str) -> Option<GmuFirmwareVersion> {
+    let text = text.trim();
+    let version = if let Some(hex) = text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+        let packed = u32::from_str_radix(hex, 16).ok()?;
+        GmuFirmwareVersion { major: packed >> 28, minor: (packed >> 16) & 0xFFF, step: packed & 0xFFFF }
+    } else {
+        let mut parts = text.split('.').map(|part| part.parse::<u32>().ok());
+        let version = GmuFirmwareVersion {
+            major: parts.next()??,
+            minor: parts.next()??,
+            step: parts.next().unwrap_or(Some(0))?,
+        };
+        if parts.next().is_some() {
+            return None;
+        }
+        version
+    };
+    // Unloaded firmware reads as zero
+    (version != GmuFirmwareVersion { major: 0, minor: 0, step: 0 }).then_some(version)
+}
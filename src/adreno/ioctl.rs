@@ -1,49 +1,310 @@
 //! Typisierte KGSL ioctl-Strukturen und Funktionen
 //! 
-use std::os::unix::io::RawFd;
+use crate::abi::by_pointer_width;
 
-use crate::error::{GpuError, GpuResult};
+use super::parser::KgslPropertyId;
 
-/// KGSL Property Types
-#[repr(u32)]
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum KgslPropertyType {
-    DeviceInfo = 0x1,
-}
+/// KGSL Property Types (alias kept for existing callers)
+pub type KgslPropertyType = KgslPropertyId;
 
-/// KGSL Device Get Property ioctl structure
+/// KGSL Device Get Property ioctl structure (`struct kgsl_device_getproperty`)
 #[repr(C)]
 pub struct KgslDeviceGetProperty {
     pub type_: u32,
     pub value: *mut std::ffi::c_void,
-    pub sizebytes: u32,
+    pub sizebytes: usize,
+}
+
+/// Typed KGSL property payload
+///
+/// # Safety
+///
+/// Implementors must be plain-old-data with exactly the layout of the kernel
+/// payload for [`KgslProperty::ID`], valid for any bit pattern the driver writes.
+pub unsafe trait KgslProperty: Copy + Default {
+    /// Property queried via IOCTL_KGSL_DEVICE_GETPROPERTY
+    const ID: KgslPropertyId;
 }
 
-/// KGSL Device Info structure
+/// Declare `#[repr(transparent)]` newtypes for scalar property payloads
+macro_rules! scalar_properties {
+    ($($(#[$doc:meta])* $name:ident($ty:ty) = $id:ident;)*) => {
+        $(
+            $(#[$doc])*
+            #[repr(transparent)]
+            #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+            pub struct $name(pub $ty);
+
+            unsafe impl KgslProperty for $name {
+                const ID: KgslPropertyId = KgslPropertyId::$id;
+            }
+        )*
+    };
+}
+
+scalar_properties! {
+    /// KGSL_PROP_MMU_ENABLE
+    MmuEnable(u32) = MmuEnable;
+    /// KGSL_PROP_UCHE_GMEM_VADDR: GPU address of GMEM as seen by UCHE
+    UcheGmemVaddr(u64) = UcheGmemVaddr;
+    /// KGSL_PROP_HIGHEST_BANK_BIT: highest DDR bank bit used for tiling
+    HighestBankBit(u32) = HighestBankBit;
+    /// KGSL_PROP_DEVICE_BITNESS: GPU virtual address width
+    DeviceBitness(u32) = DeviceBitness;
+    /// KGSL_PROP_MIN_ACCESS_LENGTH: minimum DDR access length in bytes
+    MinAccessLength(u32) = MinAccessLength;
+    /// KGSL_PROP_UBWC_MODE: UBWC compression version
+    UbwcMode(u32) = UbwcMode;
+    /// KGSL_PROP_SECURE_BUFFER_ALIGNMENT
+    SecureBufferAlignment(u32) = SecureBufferAlignment;
+    /// KGSL_PROP_SECURE_CTXT_SUPPORT
+    SecureCtxtSupport(u32) = SecureCtxtSupport;
+    /// KGSL_PROP_SPEED_BIN: fused speed bin
+    SpeedBin(u32) = SpeedBin;
+    /// KGSL_PROP_GAMING_BIN: fused gaming bin
+    GamingBin(u32) = GamingBin;
+    /// KGSL_PROP_VK_DEVICE_ID: Vulkan deviceID reported by the driver
+    VkDeviceId(u32) = VkDeviceId;
+    /// KGSL_PROP_IS_LPAC_ENABLED
+    IsLpacEnabled(u32) = IsLpacEnabled;
+    /// KGSL_PROP_GPU_VA64_SIZE: size of the 64-bit GPU VA range
+    GpuVa64Size(u64) = GpuVa64Size;
+    /// KGSL_PROP_IS_RAYTRACING_ENABLED
+    IsRaytracingEnabled(u32) = IsRaytracingEnabled;
+    /// KGSL_PROP_IS_FASTBLEND_ENABLED
+    IsFastblendEnabled(u32) = IsFastblendEnabled;
+    /// KGSL_PROP_UCHE_TRAP_BASE
+    UcheTrapBase(u64) = UcheTrapBase;
+    /// KGSL_PROP_IS_AQE_ENABLED
+    IsAqeEnabled(u32) = IsAqeEnabled;
+}
+
+/// KGSL_PROP_DEVICE_INFO payload (`struct kgsl_devinfo`)
+///
+/// `gmem_gpubaseaddr` (`unsigned long`) and `gmem_sizebytes` (`size_t`)
+/// follow the pointer width: the structure is 40 bytes on 64-bit and 24
+/// bytes on 32-bit targets.
 #[repr(C)]
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Default)]
 pub struct KgslDeviceInfo {
     pub device_id: u32,
+    /// `coreid:8 majorrev:8 minorrev:8 patch:8`
     pub chip_id: u32,
     pub mmu_enabled: u32,
-    pub gmem_gpubaseaddr: u32,
-    pub gmem_sizebytes: u32,
-    pub unknown1: u32,
-    pub unknown2: u32,
-    pub gpu_model: u32,
-}
-
-impl Default for KgslDeviceInfo {
-    fn default() -> Self {
-        Self {
-            device_id: 0,
-            chip_id: 0,
-            mmu_enabled: 0,
-            gmem_gpubaseaddr: 0,
-            gmem_sizebytes: 0,
-            unknown1: 0,
-            unknown2: 0,
-            gpu_model: 0,
+    pub gmem_gpubaseaddr: usize,
+    /// Adreno revision number, e.g. 650
+    pub gpu_id: u32,
+    pub gmem_sizebytes: usize,
+}
+
+impl KgslDeviceInfo {
+    /// Fixed eight-word little-endian record decoded by
+    /// [`parse_device_info`](super::parse_device_info) and stored in snapshots
+    ///
+    /// Words: `device_id`, `chip_id`, `mmu_enabled`, `gmem_gpubaseaddr`,
+    /// `gmem_sizebytes`, two reserved words and `gpu_id`. The GMEM address
+    /// and size are truncated to 32 bits.
+    pub fn to_bytes(&self) -> [u8; 32] {
+        let words = [
+            self.device_id,
+            self.chip_id,
+            self.mmu_enabled,
+            self.gmem_gpubaseaddr as u32,
+            self.gmem_sizebytes as u32,
+            0,
+            0,
+            self.gpu_id,
+        ];
+        let mut bytes = [0u8; 32];
+        for (chunk, word) in bytes.chunks_exact_mut(4).zip(words) {
+            chunk.copy_from_slice(&word.to_le_bytes());
         }
+        bytes
+    }
+}
+
+/// KGSL_PROP_VERSION payload (`struct kgsl_version`)
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct KgslVersion {
+    pub drv_major: u32,
+    pub drv_minor: u32,
+    pub dev_major: u32,
+    pub dev_minor: u32,
+}
+
+/// KGSL_PROP_GPU_MODEL payload (`struct kgsl_gpu_model`)
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct KgslGpuModel {
+    pub gpu_model: [u8; 32],
+}
+
+impl KgslGpuModel {
+    /// Model string up to the first NUL byte
+    pub fn as_str(&self) -> Option<&str> {
+        let len = self.gpu_model.iter().position(|&b| b == 0).unwrap_or(self.gpu_model.len());
+        std::str::from_utf8(&self.gpu_model[..len]).ok().filter(|s| !s.is_empty())
     }
-}
\ No newline at end of file
+}
+
+/// KGSL_PROP_DEVICE_SHADOW payload (`struct kgsl_shadowprop`)
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct KgslShadowProp {
+    pub gpuaddr: usize,
+    pub size: usize,
+    pub flags: u32,
+}
+
+/// KGSL_PROP_DEVICE_QDSS_STM payload (`struct kgsl_qdss_stm_prop`)
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct KgslQdssStmProp {
+    pub gpuaddr: u64,
+    pub size: u64,
+}
+
+/// KGSL_PROP_UCODE_VERSION payload (`struct kgsl_ucode_version`)
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct KgslUcodeVersion {
+    pub pfp: u32,
+    pub pm4: u32,
+}
+
+/// KGSL_PROP_GPMU_VERSION payload (`struct kgsl_gpmu_version`)
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct KgslGpmuVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub features: u32,
+}
+
+unsafe impl KgslProperty for KgslDeviceInfo {
+    const ID: KgslPropertyId = KgslPropertyId::DeviceInfo;
+}
+
+unsafe impl KgslProperty for KgslShadowProp {
+    const ID: KgslPropertyId = KgslPropertyId::DeviceShadow;
+}
+
+unsafe impl KgslProperty for KgslVersion {
+    const ID: KgslPropertyId = KgslPropertyId::Version;
+}
+
+unsafe impl KgslProperty for KgslUcodeVersion {
+    const ID: KgslPropertyId = KgslPropertyId::UcodeVersion;
+}
+
+unsafe impl KgslProperty for KgslGpmuVersion {
+    const ID: KgslPropertyId = KgslPropertyId::GpmuVersion;
+}
+
+unsafe impl KgslProperty for KgslQdssStmProp {
+    const ID: KgslPropertyId = KgslPropertyId::DeviceQdssStm;
+}
+
+unsafe impl KgslProperty for KgslGpuModel {
+    const ID: KgslPropertyId = KgslPropertyId::GpuModel;
+}
+
+/// `KGSL_IOC_TYPE`
+pub const KGSL_IOC_TYPE: u32 = 0x09;
+
+/// Encode a read/write (`_IOWR`) KGSL request number
+pub const fn kgsl_iowr(nr: u32, size: usize) -> u64 {
+    (3u64 << 30) | ((size as u64) << 16) | ((KGSL_IOC_TYPE as u64) << 8) | nr as u64
+}
+
+/// Encode a write (`_IOW`) KGSL request number
+pub const fn kgsl_iow(nr: u32, size: usize) -> u64 {
+    (1u64 << 30) | ((size as u64) << 16) | ((KGSL_IOC_TYPE as u64) << 8) | nr as u64
+}
+
+/// `struct kgsl_perfcounter_get`
+///
+/// The driver fills `offset`/`offset_hi` with the register the countable
+/// was assigned to.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct KgslPerfcounterGet {
+    pub groupid: u32,
+    pub countable: u32,
+    pub offset: u32,
+    pub offset_hi: u32,
+    pub pad: u32,
+}
+
+/// `struct kgsl_perfcounter_put`
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct KgslPerfcounterPut {
+    pub groupid: u32,
+    pub countable: u32,
+    pub pad: [u32; 2],
+}
+
+/// `struct kgsl_perfcounter_read_group`
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct KgslPerfcounterReadGroup {
+    pub groupid: u32,
+    pub countable: u32,
+    pub value: u64,
+}
+
+/// `struct kgsl_perfcounter_read`
+#[repr(C)]
+#[derive(Debug)]
+pub struct KgslPerfcounterRead {
+    pub reads: *mut KgslPerfcounterReadGroup,
+    pub count: u32,
+    pub pad: [u32; 2],
+}
+
+/// `IOCTL_KGSL_DEVICE_GETPROPERTY`
+pub const IOCTL_KGSL_DEVICE_GETPROPERTY: u64 =
+    kgsl_iowr(0x02, std::mem::size_of::<KgslDeviceGetProperty>());
+const _: () = assert!(
+    IOCTL_KGSL_DEVICE_GETPROPERTY == by_pointer_width(0xc018_0902, 0xc00c_0902) as u64
+);
+/// `IOCTL_KGSL_PERFCOUNTER_GET`
+pub const IOCTL_KGSL_PERFCOUNTER_GET: u64 =
+    kgsl_iowr(0x38, std::mem::size_of::<KgslPerfcounterGet>());
+/// `IOCTL_KGSL_PERFCOUNTER_PUT`
+pub const IOCTL_KGSL_PERFCOUNTER_PUT: u64 =
+    kgsl_iow(0x39, std::mem::size_of::<KgslPerfcounterPut>());
+/// `IOCTL_KGSL_PERFCOUNTER_READ`
+pub const IOCTL_KGSL_PERFCOUNTER_READ: u64 =
+    kgsl_iowr(0x3B, std::mem::size_of::<KgslPerfcounterRead>());
+
+crate::abi::layouts! {
+    pub(crate) fn kgsl_layouts {
+        KgslDeviceGetProperty as "kgsl_device_getproperty":
+            size by_pointer_width(24, 12),
+            type_ @ 0,
+            value @ by_pointer_width(8, 4),
+            sizebytes @ by_pointer_width(16, 8);
+        KgslDeviceInfo as "kgsl_devinfo":
+            size by_pointer_width(40, 24),
+            chip_id @ 4,
+            mmu_enabled @ 8,
+            gmem_gpubaseaddr @ by_pointer_width(16, 12),
+            gpu_id @ by_pointer_width(24, 16),
+            gmem_sizebytes @ by_pointer_width(32, 20);
+        KgslVersion as "kgsl_version": size 16, drv_major @ 0, dev_minor @ 12;
+        KgslGpuModel as "kgsl_gpu_model": size 32;
+        KgslShadowProp as "kgsl_shadowprop":
+            size by_pointer_width(24, 12), size @ by_pointer_width(8, 4), flags @ by_pointer_width(16, 8);
+        KgslQdssStmProp as "kgsl_qdss_stm_prop": size 16, size @ 8;
+        KgslUcodeVersion as "kgsl_ucode_version": size 8, pm4 @ 4;
+        KgslGpmuVersion as "kgsl_gpmu_version": size 12, features @ 8;
+        KgslPerfcounterGet as "kgsl_perfcounter_get": size 20, offset @ 8, pad @ 16;
+        KgslPerfcounterPut as "kgsl_perfcounter_put": size 16, pad @ 8;
+        KgslPerfcounterReadGroup as "kgsl_perfcounter_read_group": size 16, value @ 8;
+        KgslPerfcounterRead as "kgsl_perfcounter_read":
+            size by_pointer_width(24, 16), count @ by_pointer_width(8, 4), pad @ by_pointer_width(12, 8);
+    }
+}
@@ -1,5 +1,6 @@
 //! Autodetection of KGSL ioctl numbers
 use std::os::unix::io::RawFd;
+use crate::diag::diag;
 use crate::error::{GpuError, GpuResult};
 use nix::libc;
 
@@ -30,7 +31,7 @@ impl KgslIoctls {
         for (base, name) in candidates {
             // Teste READ (0x80000000) und WRITE (0xC0000000) Varianten
             if let Ok(()) = test_ioctl(fd, 0x80000000 | (base << 2)) {
-                println!("✅ Found GETPROPERTY at 0x{:08X} ({})", 0x80000000 | (base << 2), name);
+                diag!(debug, "found GETPROPERTY at 0x{:08X} ({})", 0x80000000 | (base << 2), name);
                 detected.get_property = 0x80000000 | (base << 2);
                 break;
             }
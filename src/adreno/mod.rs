@@ -2,43 +2,139 @@
 //! 
 //! This module provides functionality to query Qualcomm Adreno GPU information
 //! via KGSL kernel driver ioctls on Linux/Android systems.
+//!
+//! The chip database is always available with `adreno-core`; the KGSL ioctl
+//! backend additionally requires the `adreno-kgsl` feature, the upstream msm
+//! DRM backend the `adreno-drm` feature.
 
 // Re-export public API
-pub use query::{query_adreno, query_adreno_with_mode, query_adreno_robust};
+#[cfg(feature = "adreno-kgsl")]
+pub use query::{
+    dump_raw_properties, query_adreno, query_adreno_robust, query_adreno_with_mode,
+    query_adreno_with_opts,
+};
+#[cfg(feature = "adreno-kgsl")]
+pub(crate) use query::{open_device, query_fd};
+#[cfg(feature = "adreno-drm")]
+pub use drm::query_adreno_drm;
+#[cfg(feature = "adreno-drm")]
+pub(crate) use drm::drm_layouts;
+#[cfg(feature = "adreno-kgsl")]
+pub(crate) use ioctl::kgsl_layouts;
+
+#[cfg(feature = "adreno-kgsl")]
+pub mod counters;
 
 // Internal modules
+#[cfg(feature = "adreno-kgsl")]
 mod ioctl;
+#[cfg(feature = "adreno-kgsl")]
 mod ioctl_impl;
+mod assemble;
 mod database;
+#[cfg(feature = "adreno-drm")]
+mod drm;
+mod gmu;
+mod parser;
+#[cfg(feature = "adreno-kgsl")]
 mod query;
+pub mod sysfs;
 
 /// Operation mode for Adreno GPUs
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum Mode {
     /// Parity mode - matches existing libgpuinfo behavior (lenient)
+    #[default]
     Parity,
     /// Extended mode - full validation and additional features
     Extended,
 }
 
-impl Default for Mode {
-    fn default() -> Self {
-        Mode::Parity
+impl Mode {
+    /// Which `GpuInfo` fields this mode populates, see
+    /// [`crate::Mode::capabilities`]
+    pub fn capabilities(self) -> Vec<crate::fields::FieldCapability> {
+        crate::fields::capabilities(self.into(), crate::GpuVendor::Adreno)
+    }
+
+    /// Default parser configuration for this mode
+    pub fn parser_config(self) -> ParserConfig {
+        match self {
+            Mode::Parity => ParserConfig::PARITY,
+            Mode::Extended => ParserConfig::EXTENDED,
+        }
+    }
+}
+
+/// Options for Adreno queries, shared with the Mali backend
+pub use crate::options::QueryOptions;
+
+impl From<crate::Mode> for Mode {
+    fn from(mode: crate::Mode) -> Self {
+        match mode {
+            crate::Mode::Parity => Mode::Parity,
+            crate::Mode::Extended => Mode::Extended,
+        }
+    }
+}
+
+impl From<Mode> for crate::Mode {
+    fn from(mode: Mode) -> Self {
+        match mode {
+            Mode::Parity => crate::Mode::Parity,
+            Mode::Extended => crate::Mode::Extended,
+        }
+    }
+}
+
+impl From<Mode> for QueryOptions {
+    fn from(mode: Mode) -> Self {
+        Self::new(mode)
     }
 }
 
 /// Database access functions
-pub use database::{find_adreno_specs, AdrenoSpecs, SpecConfidence, AdrenoArch};
+pub use gmu::{
+    find_gmu_device, find_gmu_device_in, parse_gmu_firmware_version, read_gmu_firmware_version,
+    read_gmu_firmware_version_in, KGSL_DEBUGFS_ROOT, PLATFORM_DEVICES_ROOT,
+};
+pub use database::{
+    extrapolate_adreno_specs, find_adreno_specs, lookup_adreno_specs, AdrenoArch, AdrenoMemory, AdrenoSpecs,
+    MemoryType, SpecConfidence, ADRENO_CHIPS,
+};
+pub(crate) use database::resolve_adreno_specs;
+#[cfg(feature = "database-file")]
+pub(crate) use database::{default_apis, default_memory, default_support, register_chips};
+
+/// Offline decoding of dumped device info
+pub use assemble::parse_snapshot;
+#[cfg(all(feature = "mock", feature = "adreno-kgsl"))]
+pub(crate) use assemble::assemble_device_info;
+#[cfg(any(feature = "egl", feature = "android-props"))]
+pub(crate) use assemble::create_gpu_info_from_specs;
+
+/// sysfs fallback
+pub use sysfs::{query_adreno_sysfs, query_adreno_sysfs_in, KgslSysfs};
+
+/// Device info parser
+pub use parser::{
+    parse_device_info, parse_device_info_lenient, parse_device_info_strict, KgslPropertyId,
+    ParsedDeviceInfo, ParserConfig,
+};
 
 /// Ioctl structures
+#[cfg(feature = "adreno-kgsl")]
 pub use ioctl::{
-    KgslDeviceGetProperty, KgslDeviceInfo, KgslPropertyType,
+    DeviceBitness, GamingBin, GpuVa64Size, HighestBankBit, IsAqeEnabled, IsFastblendEnabled,
+    IsLpacEnabled, IsRaytracingEnabled, KgslDeviceGetProperty, KgslDeviceInfo, KgslGpmuVersion,
+    KgslGpuModel, KgslProperty, KgslPropertyType, KgslQdssStmProp, KgslShadowProp,
+    KgslUcodeVersion, KgslVersion, MinAccessLength, MmuEnable, SecureBufferAlignment,
+    SecureCtxtSupport, SpeedBin, UbwcMode, UcheGmemVaddr, UcheTrapBase, VkDeviceId,
 };
 
 /// Ioctl implementation functions
-pub use ioctl_impl::{
-    get_device_info, get_property, detect_working_ioctl,
-};
+#[cfg(feature = "adreno-kgsl")]
+pub use ioctl_impl::{get_device_info, get_kgsl_property, get_property};
 
 #[cfg(feature = "debug")]
 pub use query::debug_device_info;
\ No newline at end of file
@@ -1,25 +1,96 @@
 use std::borrow::Cow;
 use std::fs::File;
-use std::os::unix::io::AsRawFd;
+use std::os::unix::io::{AsRawFd, RawFd};
 use std::path::Path;
 
+use crate::diag::diag;
 use crate::error::{GpuError, GpuResult};
-use crate::info::{GpuInfo, GpuVendor, AdrenoData};
+use crate::info::{GpuInfo, AdrenoData};
+use crate::instrument::{record, timed, traced, Phase};
+use crate::options::{with_timeout, Fallback};
 
-use super::database::{find_adreno_specs, SpecConfidence};
-use super::ioctl_impl::{get_device_info, detect_working_ioctl};
-use super::ioctl::KgslDeviceInfo;  // Typ aus ioctl.rs
-use super::Mode;
+use super::assemble::{assemble_device_info, gmu_info};
+#[cfg(feature = "debug")]
+use super::database::resolve_adreno_specs;
+use super::ioctl_impl::{get_device_info, get_kgsl_property};
+use super::ioctl::{
+    HighestBankBit, KgslDeviceInfo, KgslGpuModel, KgslQdssStmProp, KgslVersion,
+    MinAccessLength, SpeedBin, UbwcMode, UcheGmemVaddr,
+};  // Typen aus ioctl.rs
+use super::parser::ParserConfig;
+use super::sysfs::{query_adreno_sysfs, KgslSysfs};
+use super::{Mode, QueryOptions};
 
 /// Query Adreno GPU information with mode selection
 pub fn query_adreno_with_mode<P: AsRef<Path>>(
     device_path: P,
     mode: Mode,
 ) -> GpuResult<GpuInfo> {
-    match mode {
-        Mode::Parity => query_adreno_parity(device_path),
-        Mode::Extended => query_adreno_extended(device_path),
+    query_adreno_with_opts(device_path, QueryOptions::new(mode))
+}
+
+/// Query Adreno GPU information with explicit options
+///
+/// Modes are tried on one open node as [`QueryOptions::modes`] lists them;
+/// with [`Fallback::ParityThenSysfs`] the KGSL sysfs nodes are read when
+/// all of them fail, and the ioctl error is returned if that fails too.
+pub fn query_adreno_with_opts<P: AsRef<Path>>(
+    device_path: P,
+    options: impl Into<QueryOptions>,
+) -> GpuResult<GpuInfo> {
+    let options = options.into();
+    #[cfg(feature = "mock")]
+    if let Some(result) = crate::mock::query_adreno(device_path.as_ref(), &options) {
+        return result;
     }
+    let device_path = device_path.as_ref().to_path_buf();
+    with_timeout(options.timeout, move || {
+        traced(options.instrumentation.as_ref(), &device_path, || {
+            let result =
+                open_device(&device_path).and_then(|file| query_fd(file.as_raw_fd(), &options));
+            match result {
+                Ok(mut info) => {
+                    info.device_path = Some(device_path.clone());
+                    Ok(info)
+                }
+                Err(e) if options.uses_sysfs() => {
+                    record(|trace| trace.sysfs_fallback = true);
+                    match query_adreno_sysfs() {
+                        Ok(info) => {
+                            diag!(info, "{} not usable, using sysfs fallback", device_path.display());
+                            Ok(info)
+                        }
+                        Err(sysfs_error) => {
+                            diag!(debug, "sysfs fallback failed: {}", sysfs_error);
+                            Err(e)
+                        }
+                    }
+                }
+                Err(e) => Err(e),
+            }
+        })
+    })
+}
+
+/// Open a KGSL device node, mapping the common failures
+pub(crate) fn open_device(device_path: &Path) -> GpuResult<File> {
+    timed(Phase::Open, || File::open(device_path)).map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => GpuError::DeviceNotFound,
+        std::io::ErrorKind::PermissionDenied => GpuError::PermissionDenied,
+        _ => GpuError::Io(e),
+    })
+}
+
+/// Query an already open KGSL device
+pub(crate) fn query_fd(fd: RawFd, options: &QueryOptions) -> GpuResult<GpuInfo> {
+    options.try_modes(|mode| {
+        let config = options.adreno_config(mode);
+        let validate = options.validates(mode);
+        match mode {
+            crate::Mode::Parity => query_adreno_parity(fd, config, validate),
+            crate::Mode::Extended => query_adreno_extended(fd, config, validate),
+        }
+    })
 }
 
 /// Query Adreno GPU information (defaults to Parity mode)
@@ -27,213 +98,89 @@ pub fn query_adreno<P: AsRef<Path>>(device_path: P) -> GpuResult<GpuInfo> {
     query_adreno_with_mode(device_path, Mode::Parity)
 }
 
-/// Common function to create GpuInfo from device info and specs
-fn create_gpu_info_from_specs(
-    device_info: &KgslDeviceInfo,
-    specs: &super::database::AdrenoSpecs,
-) -> GpuInfo {
-    // Extract architecture from chip ID
-    let major = ((device_info.chip_id >> 24) & 0xFF) as u8;
-    let minor = ((device_info.chip_id >> 16) & 0xFF) as u8;
-
-    let adreno_data = AdrenoData {
-        chip_id: device_info.chip_id,
-        gpu_model_code: device_info.gpu_model,
-        mmu_enabled: device_info.mmu_enabled != 0,
-        gmem_size_bytes: device_info.gmem_sizebytes,
-        spec_confidence: specs.confidence.as_cow(),
-        stream_processors: specs.stream_processors,
-        max_freq_mhz: specs.max_freq_mhz,
-        process_nm: specs.process_nm,
-        release_year: specs.year,
-        snapdragon_models: specs.snapdragon_models
-            .iter()
-            .map(|&s| Cow::Borrowed(s))
-            .collect(),
-    };
-
-    GpuInfo {
-        vendor: GpuVendor::Adreno,
-        gpu_name: Cow::Borrowed(specs.name),
-        architecture: specs.architecture.to_string().into(),
-        architecture_major: major,
-        architecture_minor: minor,
-        num_shader_cores: specs.shader_cores,
-        num_l2_bytes: specs.gmem_size_kb as u64 * 1024,
-        num_bus_bits: specs.bus_width_bits as u64,
-        mali_data: None,
-        adreno_data: Some(adreno_data),
-    }
+/// Parity mode query - matches existing behavior
+fn query_adreno_parity(fd: RawFd, config: ParserConfig, validate: bool) -> GpuResult<GpuInfo> {
+    let raw_info = read_device_info(fd)?;
+    assemble_device_info(&raw_info.to_bytes(), Mode::Parity, config, validate, gmu_info())
 }
 
-/// Parity mode query - matches existing behavior
-fn query_adreno_parity<P: AsRef<Path>>(device_path: P) -> GpuResult<GpuInfo> {
-    let file = match File::open(&device_path) {
-        Ok(file) => file,
-        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
-            return Err(GpuError::DeviceNotFound);
-        }
-        Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => {
-            return Err(GpuError::PermissionDenied);
-        }
-        Err(e) => return Err(GpuError::Io(e)),
-    };
-    
-    let fd = file.as_raw_fd();
-    
-    // Debug: Try to detect which ioctl works
-    #[cfg(debug_assertions)]
-    match detect_working_ioctl(fd) {
-        Ok(ioctl_num) => eprintln!("🔍 Detected working ioctl: 0x{:08x}", ioctl_num),
-        Err(e) => eprintln!("⚠️ Could not detect ioctl: {}", e),
-    }
-    
-    let device_info = get_device_info(fd)?;
-    
-    // Validate basic device info
-    if device_info.chip_id == 0 {
-        return Err(GpuError::InvalidData("Chip ID is zero".into()));
+/// Extended mode query - with additional validation
+fn query_adreno_extended(fd: RawFd, config: ParserConfig, validate: bool) -> GpuResult<GpuInfo> {
+    // Chip ID and GMEM checks are driven by the parser config
+    let raw_info = read_device_info(fd)?;
+    let mut info =
+        assemble_device_info(&raw_info.to_bytes(), Mode::Extended, config, validate, gmu_info())?;
+
+    // Walk the optional properties; older kernels reject some of them
+    if let Some(adreno) = info.adreno_data.as_mut() {
+        read_extended_properties(fd, adreno);
     }
     
-    // Look up specs in database
-    let specs = find_adreno_specs(device_info.chip_id)
-        .ok_or_else(|| GpuError::UnsupportedGpu {
-            id: device_info.chip_id,
-            cores: 0,
-        })?;
+    Ok(info)
+}
 
-    Ok(create_gpu_info_from_specs(&device_info, specs))
+/// Timed `KGSL_PROP_DEVICE_INFO` query
+fn read_device_info(fd: RawFd) -> GpuResult<KgslDeviceInfo> {
+    let raw_info = timed(Phase::Ioctl, || get_device_info(fd))?;
+    record(|trace| trace.bytes += std::mem::size_of::<KgslDeviceInfo>());
+    Ok(raw_info)
 }
 
-/// Extended mode query - with additional validation
-fn query_adreno_extended<P: AsRef<Path>>(device_path: P) -> GpuResult<GpuInfo> {
-    let file = match File::open(&device_path) {
-        Ok(file) => file,
-        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
-            return Err(GpuError::DeviceNotFound);
-        }
-        Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => {
-            return Err(GpuError::PermissionDenied);
-        }
-        Err(e) => return Err(GpuError::Io(e)),
-    };
-    
-    let fd = file.as_raw_fd();
-    
-    let device_info = get_device_info(fd)?;
-    
-    // Extended validation
-    if device_info.chip_id == 0 {
-        return Err(GpuError::InvalidData("Chip ID is zero".into()));
-    }
-    
-    if device_info.gmem_sizebytes == 0 {
-        return Err(GpuError::InvalidData("GPU memory size is zero".into()));
-    }
-    
-    if device_info.device_id == 0 {
-        eprintln!("⚠️ Device ID is zero, might be incomplete driver info");
+/// Read optional KGSL properties into `adreno`, skipping any the driver rejects
+fn read_extended_properties(fd: RawFd, adreno: &mut AdrenoData) {
+    if let Ok(model) = get_kgsl_property::<KgslGpuModel>(fd) {
+        adreno.driver_model_name = model.as_str().map(|s| Cow::Owned(s.to_string()));
     }
 
-    // Look up specs in database
-    let specs = find_adreno_specs(device_info.chip_id)
-        .ok_or_else(|| GpuError::UnsupportedGpu {
-            id: device_info.chip_id,
-            cores: 0,
-        })?;
-
-    // Validate confidence level in extended mode
-    if specs.confidence == SpecConfidence::Heuristic {
-        eprintln!("⚠️ Using heuristic specifications for chip ID: 0x{:08x}", device_info.chip_id);
+    if let Ok(version) = get_kgsl_property::<KgslVersion>(fd) {
+        adreno.driver_version = Some((version.drv_major, version.drv_minor));
     }
 
-    let info = create_gpu_info_from_specs(&device_info, specs);
-    
-    // Additional validation for extended mode
-    validate_extended_info(&info)?;
-    
-    Ok(info)
+    adreno.speed_bin = get_kgsl_property::<SpeedBin>(fd).ok().map(|v| v.0);
+    adreno.ubwc_mode = get_kgsl_property::<UbwcMode>(fd).ok().map(|v| v.0);
+    adreno.uche_gmem_vaddr = get_kgsl_property::<UcheGmemVaddr>(fd).ok().map(|v| v.0);
+    adreno.highest_bank_bit = get_kgsl_property::<HighestBankBit>(fd).ok().map(|v| v.0);
+    adreno.min_access_length = get_kgsl_property::<MinAccessLength>(fd).ok().map(|v| v.0);
+    // Kernels without a trace region answer with an empty one
+    adreno.qdss_stm = get_kgsl_property::<KgslQdssStmProp>(fd)
+        .ok()
+        .filter(|stm| stm.size != 0)
+        .map(|stm| (stm.gpuaddr, stm.size));
+
+    // No KGSL property carries the power level table
+    KgslSysfs::read().apply_power_levels(adreno);
 }
 
-/// Validate GPU info for extended mode
-fn validate_extended_info(info: &GpuInfo) -> GpuResult<()> {
-    if info.num_shader_cores == 0 {
-        return Err(GpuError::InvalidData("Shader core count is zero".into()));
-    }
-    
-    if info.num_l2_bytes == 0 {
-        return Err(GpuError::InvalidData("L2 cache size is zero".into()));
-    }
-    
-    // Validate architecture version makes sense
-    if info.architecture_major < 4 || info.architecture_major > 9 {
-        return Err(GpuError::InvalidData(format!(
-            "Invalid architecture major version: {}",
-            info.architecture_major
-        )));
-    }
-    
-    Ok(())
+/// Dump the raw `KGSL_PROP_DEVICE_INFO` structure of `device_path`
+///
+/// The bytes can be attached to a bug report and decoded later with
+/// [`parse_snapshot`](super::parse_snapshot).
+pub fn dump_raw_properties<P: AsRef<Path>>(device_path: P) -> GpuResult<Vec<u8>> {
+    let file = open_device(device_path.as_ref())?;
+    Ok(get_device_info(file.as_raw_fd())?.to_bytes().to_vec())
 }
 
-/// Type alias for query functions
-type QueryFn<P> = fn(P) -> GpuResult<GpuInfo>;
-
 /// Try multiple methods to query Adreno GPU
+///
+/// Extended and Parity mode are tried on `device_path`, then the KGSL sysfs
+/// nodes for callers denied the ioctls; the ioctl error is returned if the
+/// sysfs fallback fails too.
 pub fn query_adreno_robust<P: AsRef<Path>>(device_path: P) -> GpuResult<GpuInfo> {
-    // Explizite Funktionszeiger-Typen
-    let methods: &[(&str, fn(&Path) -> GpuResult<GpuInfo>)] = &[
-        ("Extended mode", query_adreno_extended_ref as fn(&Path) -> GpuResult<GpuInfo>),
-        ("Parity mode", query_adreno_parity_ref as fn(&Path) -> GpuResult<GpuInfo>),
-    ];
-    
-    let mut last_error = None;
-    let mut tried_methods = Vec::new();
-    
-    for (name, method) in methods {
-        tried_methods.push(*name);
-        match method(device_path.as_ref()) {
-            Ok(info) => {
-                if tried_methods.len() > 1 {
-                    eprintln!("✅ Success with {} after trying: {}", name, tried_methods.join(" → "));
-                }
-                return Ok(info);
-            }
-            Err(e) => {
-                eprintln!("❌ {} failed: {}", name, e);
-                last_error = Some(e);
-            }
-        }
-    }
-    
-    Err(last_error.unwrap_or(GpuError::DeviceNotFound))
-}
-
-// Hilfsfunktionen mit &Path statt generischem P
-fn query_adreno_extended_ref(device_path: &Path) -> GpuResult<GpuInfo> {
-    query_adreno_extended(device_path)
+    query_adreno_with_opts(
+        device_path,
+        QueryOptions::new(Mode::Extended).fallback(Fallback::ParityThenSysfs),
+    )
 }
 
-fn query_adreno_parity_ref(device_path: &Path) -> GpuResult<GpuInfo> {
-    query_adreno_parity(device_path)
-}
 /// Debug function to print detailed device info
 #[cfg(feature = "debug")]
 pub fn debug_device_info<P: AsRef<Path>>(device_path: P) -> GpuResult<()> {
-    use super::ioctl::KgslDeviceInfo;
-    
     let file = File::open(device_path).map_err(GpuError::Io)?;
     let fd = file.as_raw_fd();
     
     println!("🔍 Debug KGSL Device Info");
     println!("=========================");
     
-    // Try to detect working ioctl
-    match detect_working_ioctl(fd) {
-        Ok(ioctl_num) => println!("Working ioctl: 0x{:08x}", ioctl_num),
-        Err(e) => println!("Could not detect ioctl: {}", e),
-    }
-    
     // Try to get device info
     match get_device_info(fd) {
         Ok(info) => {
@@ -244,12 +191,10 @@ pub fn debug_device_info<P: AsRef<Path>>(device_path: P) -> GpuResult<()> {
             println!("  GMEM Base: 0x{:08x}", info.gmem_gpubaseaddr);
             println!("  GMEM Size: {} bytes ({} KB)", 
                 info.gmem_sizebytes, info.gmem_sizebytes / 1024);
-            println!("  GPU Model: 0x{:08x}", info.gpu_model);
-            println!("  Unknown1:  0x{:08x}", info.unknown1);
-            println!("  Unknown2:  0x{:08x}", info.unknown2);
+            println!("  GPU ID:    {}", info.gpu_id);
             
             // Try to find in database
-            if let Some(specs) = find_adreno_specs(info.chip_id) {
+            if let Some(specs) = resolve_adreno_specs(info.chip_id) {
                 println!("\nDatabase Match:");
                 println!("  Name:      {}", specs.name);
                 println!("  Arch:      {}", specs.architecture);
@@ -70,6 +70,7 @@ impl AdrenoQueryStrategy for ParityStrategy {
             num_bus_bits: specs.bus_width_bits as u64,
             mali_data: None,
             adreno_data: Some(adreno_data),
+            vivante_data: None,
         })
     }
 
@@ -166,6 +167,7 @@ impl AdrenoQueryStrategy for ExtendedStrategy {
             num_bus_bits: specs.bus_width_bits as u64,
             mali_data: None,
             adreno_data: Some(adreno_data),
+            vivante_data: None,
         };
 
         // Additional validation if configured
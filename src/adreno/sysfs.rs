@@ -0,0 +1,160 @@
+//! sysfs fallback for processes that cannot open `/dev/kgsl-3d0`
+//!
+//! SELinux policies on production devices often deny the KGSL ioctls to
+//! untrusted apps (EPERM), while the KGSL class device still exposes the
+//! chip ID and model string. Everything beyond those comes from the chip
+//! database; Extended-only driver properties stay empty.
+
+use std::path::{Path, PathBuf};
+
+use crate::error::{GpuError, GpuResult};
+use crate::info::{AdrenoData, GpuInfo};
+use crate::telemetry::busy::{BusyFormat, BusyTime};
+use crate::telemetry::read_node;
+
+use super::assemble::{create_gpu_info_from_specs, gmu_info};
+use super::database::resolve_adreno_specs;
+use super::parser::ParsedDeviceInfo;
+
+/// KGSL class device of the 3D core
+pub const KGSL_SYSFS_DEVICE_DIR: &str = "/sys/class/kgsl/kgsl-3d0";
+
+/// Raw values of the KGSL device nodes, `None` for unreadable ones
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct KgslSysfs {
+    /// `gpu_model`, e.g. "Adreno740v2"
+    pub gpu_model: Option<String>,
+    /// `chip_id`
+    pub chip_id: Option<u32>,
+    /// `gpu_busy_percentage`
+    pub busy_percent: Option<f32>,
+    /// `max_gpuclk`
+    pub max_gpuclk_hz: Option<u64>,
+    /// `gpubusy` over the driver's last window
+    pub gpubusy: Option<BusyTime>,
+    /// `gpu_available_frequencies`, or the devfreq OPP table, fastest first
+    pub available_frequencies_hz: Vec<u64>,
+    /// `gpuclk`, or the devfreq current clock
+    pub gpuclk_hz: Option<u64>,
+}
+
+impl KgslSysfs {
+    /// Read the nodes under [`KGSL_SYSFS_DEVICE_DIR`]
+    pub fn read() -> Self {
+        Self::read_from(Path::new(KGSL_SYSFS_DEVICE_DIR))
+    }
+
+    /// Read the nodes of a KGSL device directory
+    ///
+    /// ```
+    /// use armgpuinfo::adreno::KgslSysfs;
+    ///
+    /// let dir = tempfile::tempdir()?;
+    /// std::fs::write(dir.path().join("gpu_available_frequencies"), "315000000 680000000 492000000\n")?;
+    /// std::fs::write(dir.path().join("gpuclk"), "492000000\n")?;
+    ///
+    /// let nodes = KgslSysfs::read_from(dir.path());
+    /// assert_eq!(nodes.freq_table_mhz(), [680, 492, 315]);
+    /// assert_eq!(nodes.current_pwrlevel(), Some(1));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn read_from(device_dir: &Path) -> Self {
+        let node = |name: &str| read_node(&device_dir.join(name));
+        Self {
+            gpu_model: node("gpu_model"),
+            chip_id: node("chip_id").and_then(|text| parse_chip_id(&text)),
+            busy_percent: node("gpu_busy_percentage")
+                .and_then(|text| text.trim_end_matches('%').trim().parse().ok()),
+            max_gpuclk_hz: node("max_gpuclk").and_then(|text| text.parse().ok()),
+            gpubusy: node("gpubusy").and_then(|text| BusyFormat::KgslGpubusy.parse(&text)),
+            available_frequencies_hz: node("gpu_available_frequencies")
+                .or_else(|| node("devfreq/available_frequencies"))
+                .map(|text| parse_frequencies(&text))
+                .unwrap_or_default(),
+            gpuclk_hz: node("gpuclk")
+                .or_else(|| node("devfreq/cur_freq"))
+                .and_then(|text| text.parse().ok()),
+        }
+    }
+
+    /// Clock of each power level in MHz, level 0 (fastest) first
+    pub fn freq_table_mhz(&self) -> Vec<u32> {
+        self.available_frequencies_hz.iter().map(|&hz| (hz / 1_000_000) as u32).collect()
+    }
+
+    /// Power level whose clock matches `gpuclk`
+    pub fn current_pwrlevel(&self) -> Option<u32> {
+        let clock = self.gpuclk_hz?;
+        let level = self.available_frequencies_hz.iter().position(|&hz| hz == clock)?;
+        u32::try_from(level).ok()
+    }
+
+    /// Fill the power level fields of `adreno`
+    pub(crate) fn apply_power_levels(&self, adreno: &mut AdrenoData) {
+        adreno.freq_table = self.freq_table_mhz();
+        adreno.current_pwrlevel = self.current_pwrlevel();
+    }
+}
+
+/// Query the default KGSL device through sysfs
+pub fn query_adreno_sysfs() -> GpuResult<GpuInfo> {
+    query_adreno_sysfs_in(KGSL_SYSFS_DEVICE_DIR)
+}
+
+/// Query a KGSL sysfs device directory
+///
+/// ```
+/// use armgpuinfo::adreno::query_adreno_sysfs_in;
+///
+/// let dir = tempfile::tempdir()?;
+/// std::fs::write(dir.path().join("chip_id"), "0x07060001\n")?;
+/// std::fs::write(dir.path().join("gpu_model"), "Adreno740v2\n")?;
+///
+/// let info = query_adreno_sysfs_in(dir.path())?;
+/// assert_eq!(info.gpu_name, "Adreno 740");
+/// assert_eq!(info.adreno_data.unwrap().driver_model_name.as_deref(), Some("Adreno740v2"));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn query_adreno_sysfs_in<P: AsRef<Path>>(device_dir: P) -> GpuResult<GpuInfo> {
+    let device_dir = device_dir.as_ref();
+    if !device_dir.is_dir() {
+        return Err(GpuError::DeviceNotFound);
+    }
+    let nodes = KgslSysfs::read_from(device_dir);
+
+    let chip_id = nodes.chip_id.filter(|&id| id != 0).ok_or_else(|| {
+        GpuError::InvalidData(format!("no chip_id under {}", device_dir.display()))
+    })?;
+    let device_info = ParsedDeviceInfo::with_chip_id(chip_id);
+
+    let specs = resolve_adreno_specs(device_info.chip_id).ok_or(GpuError::UnsupportedGpu {
+        id: device_info.chip_id,
+        cores: 0,
+    })?;
+
+    let mut info = create_gpu_info_from_specs(&device_info, &specs, gmu_info());
+    if let Some(adreno) = info.adreno_data.as_mut() {
+        nodes.apply_power_levels(adreno);
+        adreno.driver_model_name = nodes.gpu_model.map(Into::into);
+    }
+    info.device_path = Some(PathBuf::from(device_dir));
+    Ok(info)
+}
+
+/// Whitespace-separated clocks in Hz, sorted fastest first like KGSL power
+/// levels
+fn parse_frequencies(text: &str) -> Vec<u64> {
+    let mut freqs: Vec<u64> = text.split_whitespace().filter_map(|f| f.parse().ok()).collect();
+    freqs.sort_unstable_by(|a, b| b.cmp(a));
+    freqs.dedup();
+    freqs
+}
+
+/// Parse `chip_id`, written in hex by KGSL and in decimal by some vendor
+/// kernels
+fn parse_chip_id(text: &str) -> Option<u32> {
+    match text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+        Some(hex) => u32::from_str_radix(hex, 16).ok(),
+        None => text.parse().ok(),
+    }
+}
@@ -0,0 +1,169 @@
+//! Lightweight profile for embedded health agents
+//!
+//! Watchdog and health agents on constrained system partitions only need
+//! to report *which* GPU is present; names and specs can be resolved
+//! server-side with [`decode::batch`](crate::decode::batch). This module
+//! reads raw IDs straight from the kernel into an [`AgentRecord`] without
+//! touching the product databases or allocating on the success path, and
+//! encodes it with postcard into a caller-provided buffer.
+//!
+//! The intended feature combination is
+//!
+//! ```text
+//! armgpuinfo = { default-features = false, features = ["agent", "mali-kbase", "adreno-kgsl"] }
+//! ```
+//!
+//! built with the `agent` profile from this crate's manifest. The
+//! `agent_footprint` integration test keeps the size added to a binary
+//! below [`FOOTPRINT_BUDGET_BYTES`].
+
+use crate::error::{GpuError, GpuResult};
+use crate::info::GpuVendor;
+
+#[cfg(any(feature = "mali-kbase", feature = "adreno-kgsl"))]
+use std::path::Path;
+
+/// Size budget for the code this profile adds to an agent binary
+pub const FOOTPRINT_BUDGET_BYTES: u64 = 100 * 1024;
+
+/// Upper bound of an encoded [`AgentRecord`]
+///
+/// postcard writes the vendor as a one-byte variant index and integers as
+/// varints (at most 10 bytes for `u64`, 5 for `u32`).
+pub const MAX_ENCODED_LEN: usize = 1 + 10 + 5 + 10 + 10 + 10;
+
+/// kbase node probed by [`query`]
+pub const MALI_DEVICE_PATH: &str = "/dev/mali0";
+/// KGSL node probed by [`query`]
+pub const KGSL_DEVICE_PATH: &str = "/dev/kgsl-3d0";
+
+/// Stack buffer for the kbase property buffer; drivers report well under
+/// 1 KiB
+#[cfg(feature = "mali-kbase")]
+const PROPS_BUFFER_LEN: usize = 4096;
+
+/// IDs and sizes of the GPU, without strings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct AgentRecord {
+    pub vendor: GpuVendor,
+    /// Mali product ID as reported by the driver or Adreno chip ID
+    pub raw_id: u64,
+    /// Shader cores from the core mask, 0 for Adreno (KGSL does not report it)
+    pub core_count: u32,
+    pub shader_core_mask: u64,
+    /// Mali L2 size or Adreno GMEM size in bytes
+    pub cache_bytes: u64,
+    /// Bus width, 0 if not reported
+    pub num_bus_bits: u64,
+}
+
+impl AgentRecord {
+    /// Encode into `buf`, returning the written prefix
+    ///
+    /// ```
+    /// use armgpuinfo::agent::{AgentRecord, MAX_ENCODED_LEN};
+    /// use armgpuinfo::GpuVendor;
+    ///
+    /// let record = AgentRecord {
+    ///     vendor: GpuVendor::Mali,
+    ///     raw_id: 0xa867,
+    ///     core_count: 4,
+    ///     shader_core_mask: 0b1111,
+    ///     cache_bytes: 512 * 1024,
+    ///     num_bus_bits: 128,
+    /// };
+    /// let mut buf = [0u8; MAX_ENCODED_LEN];
+    /// let bytes = record.encode(&mut buf)?;
+    /// assert_eq!(AgentRecord::decode(bytes)?, record);
+    /// # Ok::<(), armgpuinfo::GpuError>(())
+    /// ```
+    pub fn encode<'a>(&self, buf: &'a mut [u8]) -> GpuResult<&'a mut [u8]> {
+        postcard::to_slice(self, buf).map_err(|e| GpuError::InvalidData(e.to_string()))
+    }
+
+    /// Decode a record produced by [`encode`](Self::encode)
+    pub fn decode(bytes: &[u8]) -> GpuResult<Self> {
+        postcard::from_bytes(bytes).map_err(|e| GpuError::InvalidData(e.to_string()))
+    }
+
+    /// Input for [`decode::batch`](crate::decode::batch) on the collecting side
+    #[cfg(any(feature = "mali-core", feature = "adreno-core"))]
+    pub fn raw_record(&self) -> crate::decode::RawRecord {
+        (self.vendor, self.raw_id, self.core_count)
+    }
+}
+
+/// Read the raw IDs of a kbase device
+#[cfg(feature = "mali-kbase")]
+pub fn query_mali_record<P: AsRef<Path>>(device_path: P) -> GpuResult<AgentRecord> {
+    use crate::mali::{get_properties_into, open_session, parse_properties_lenient};
+    use std::os::unix::io::AsRawFd;
+
+    let (file, _) = open_session(device_path.as_ref())?;
+    let mut buffer = [0u8; PROPS_BUFFER_LEN];
+    let len = get_properties_into(file.as_raw_fd(), &mut buffer)?;
+    let parsed = parse_properties_lenient(&buffer[..len]);
+    if parsed.gpu_id == 0 {
+        return Err(GpuError::InvalidGpuProperties(
+            "GET_GPUPROPS returned no product id".into(),
+        ));
+    }
+
+    let cache_bytes = if parsed.l2_log2_cache_size > 0 {
+        (1u64 << parsed.l2_log2_cache_size) * parsed.num_l2_slices
+    } else {
+        0
+    };
+    let num_bus_bits = if parsed.raw_l2_features != 0 {
+        1u64.checked_shl(((parsed.raw_l2_features >> 24) & 0xFF) as u32).unwrap_or(0)
+    } else {
+        0
+    };
+
+    Ok(AgentRecord {
+        vendor: GpuVendor::Mali,
+        raw_id: u64::from(parsed.gpu_id),
+        core_count: parsed.num_shader_cores,
+        shader_core_mask: parsed.shader_core_mask,
+        cache_bytes,
+        num_bus_bits,
+    })
+}
+
+/// Read the raw IDs of a KGSL device
+#[cfg(feature = "adreno-kgsl")]
+pub fn query_adreno_record<P: AsRef<Path>>(device_path: P) -> GpuResult<AgentRecord> {
+    use crate::adreno::{get_device_info, open_device};
+    use std::os::unix::io::AsRawFd;
+
+    let file = open_device(device_path.as_ref())?;
+    let info = get_device_info(file.as_raw_fd())?;
+    Ok(AgentRecord {
+        vendor: GpuVendor::Adreno,
+        raw_id: u64::from(info.chip_id),
+        core_count: 0,
+        shader_core_mask: 0,
+        cache_bytes: info.gmem_sizebytes as u64,
+        num_bus_bits: 0,
+    })
+}
+
+/// Probe the default Mali, then Adreno node and return the first GPU found
+#[cfg(any(feature = "mali-kbase", feature = "adreno-kgsl"))]
+pub fn query() -> GpuResult<AgentRecord> {
+    let probes: &[fn() -> GpuResult<AgentRecord>] = &[
+        #[cfg(feature = "mali-kbase")]
+        || query_mali_record(MALI_DEVICE_PATH),
+        #[cfg(feature = "adreno-kgsl")]
+        || query_adreno_record(KGSL_DEVICE_PATH),
+    ];
+
+    let mut last_error = GpuError::DeviceNotFound;
+    for probe in probes {
+        match probe() {
+            Ok(record) => return Ok(record),
+            Err(e) => last_error = e,
+        }
+    }
+    Err(last_error)
+}
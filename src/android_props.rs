@@ -0,0 +1,186 @@
+//! GPU identification from Android system properties
+//!
+//! When neither the device nodes nor the GPU sysfs files are accessible,
+//! `ro.board.platform` still names the SoC (e.g. "kona" for the Snapdragon
+//! 865) and `ro.hardware.egl` the GL driver ("adreno", "mali").
+//! [`query_android_props`] maps known platform names to the GPU they ship
+//! with. Nothing about the GPU is queried: the core count is the SoC's
+//! stock configuration and the result has [`DataConfidence::Heuristic`].
+//!
+//! ```
+//! use armgpuinfo::android_props::gpu_info_for_platform;
+//! use armgpuinfo::DataConfidence;
+//!
+//! # #[cfg(feature = "adreno-core")]
+//! # {
+//! let info = gpu_info_for_platform("kona", Some("adreno"))?;
+//! assert_eq!(info.gpu_name, "Adreno 650");
+//! assert_eq!(info.confidence, DataConfidence::Heuristic);
+//! # }
+//! # Ok::<(), armgpuinfo::GpuError>(())
+//! ```
+
+use std::borrow::Cow;
+
+use crate::capabilities::GpuCapabilities;
+use crate::error::{GpuError, GpuResult};
+use crate::info::{DataConfidence, GpuInfo, GpuVendor};
+
+/// SoC platform name, e.g. "kona", "gs101", "mt6893"
+pub const BOARD_PLATFORM_PROPERTY: &str = "ro.board.platform";
+/// GL driver name, e.g. "adreno" or "mali"
+pub const HARDWARE_EGL_PROPERTY: &str = "ro.hardware.egl";
+
+/// GPU a platform ships with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlatformGpu {
+    /// `ro.board.platform` value
+    pub platform: &'static str,
+    /// GPU name as the vendor markets it
+    pub gpu_name: &'static str,
+    pub gpu: PlatformGpuId,
+}
+
+/// Database key of a [`PlatformGpu`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlatformGpuId {
+    /// Mali product ID and the SoC's shader core count
+    Mali { gpu_id: u32, cores: u32 },
+    /// Adreno chip ID
+    Adreno { chip_id: u32 },
+}
+
+impl PlatformGpu {
+    pub fn vendor(&self) -> GpuVendor {
+        match self.gpu {
+            PlatformGpuId::Mali { .. } => GpuVendor::Mali,
+            PlatformGpuId::Adreno { .. } => GpuVendor::Adreno,
+        }
+    }
+}
+
+const fn mali(platform: &'static str, gpu_name: &'static str, gpu_id: u32, cores: u32) -> PlatformGpu {
+    PlatformGpu { platform, gpu_name, gpu: PlatformGpuId::Mali { gpu_id, cores } }
+}
+
+const fn adreno(platform: &'static str, gpu_name: &'static str, chip_id: u32) -> PlatformGpu {
+    PlatformGpu { platform, gpu_name, gpu: PlatformGpuId::Adreno { chip_id } }
+}
+
+/// Known platforms; Adreno chip IDs match the database keys where the chip
+/// has an entry
+pub static KNOWN_PLATFORMS: &[PlatformGpu] = &[
+    // Qualcomm
+    adreno("msm8953", "Adreno 506", 0x0506_0000),
+    adreno("msm8996", "Adreno 530", 0x0402_0000),
+    adreno("msm8998", "Adreno 540", 0x0502_0000),
+    adreno("sdm660", "Adreno 512", 0x0512_0000),
+    adreno("sdm845", "Adreno 630", 0x0603_0000),
+    adreno("msmnile", "Adreno 640", 0x0604_0000),
+    adreno("kona", "Adreno 650", 0x0605_0000),
+    adreno("lahaina", "Adreno 660", 0x0606_0000),
+    adreno("taro", "Adreno 730", 0x0703_0001),
+    adreno("kalama", "Adreno 740", 0x0706_0001),
+    adreno("pineapple", "Adreno 750", 0x0705_0000),
+    adreno("trinket", "Adreno 610", 0x0601_0000),
+    adreno("bengal", "Adreno 610", 0x0601_0000),
+    adreno("atoll", "Adreno 618", 0x0601_0001),
+    adreno("holi", "Adreno 619", 0x0601_0500),
+    adreno("lito", "Adreno 620", 0x0602_0000),
+    // Google Tensor
+    mali("gs101", "Mali-G78", 0x9002, 20),
+    mali("gs201", "Mali-G710", 0xa002, 7),
+    mali("zuma", "Mali-G715", 0xb002, 7),
+    // MediaTek
+    mali("mt6768", "Mali-G52", 0x7002, 2),
+    mali("mt6785", "Mali-G76", 0x7001, 4),
+    mali("mt6877", "Mali-G68", 0x9004, 4),
+    mali("mt6893", "Mali-G77", 0x9000, 9),
+    mali("mt6983", "Mali-G710", 0xa002, 10),
+    // Samsung
+    mali("exynos990", "Mali-G77", 0x9000, 11),
+    mali("exynos2100", "Mali-G78", 0x9002, 14),
+];
+
+/// Known platform by `ro.board.platform` value, case-insensitive
+pub fn find_platform(platform: &str) -> Option<&'static PlatformGpu> {
+    let platform = platform.trim();
+    KNOWN_PLATFORMS.iter().find(|known| known.platform.eq_ignore_ascii_case(platform))
+}
+
+/// Identify the GPU from `ro.board.platform` and `ro.hardware.egl`
+///
+/// Fails with [`GpuError::UnsupportedPlatform`] off Android and
+/// [`GpuError::InvalidData`] if the platform is unknown.
+pub fn query_android_props() -> GpuResult<GpuInfo> {
+    let platform = system_property(BOARD_PLATFORM_PROPERTY).ok_or(GpuError::UnsupportedPlatform)?;
+    gpu_info_for_platform(&platform, system_property(HARDWARE_EGL_PROPERTY).as_deref())
+}
+
+/// [`GpuInfo`] of the GPU `platform` ships with
+///
+/// `egl_hardware` is the `ro.hardware.egl` value; a driver of the other
+/// vendor means the platform name is not the stock SoC's, and the lookup
+/// fails with [`GpuError::InvalidData`].
+pub fn gpu_info_for_platform(platform: &str, egl_hardware: Option<&str>) -> GpuResult<GpuInfo> {
+    let known = find_platform(platform)
+        .ok_or_else(|| GpuError::InvalidData(format!("platform {platform:?} is not known")))?;
+    if let Some(driver) = egl_hardware.map(|driver| driver.to_ascii_lowercase()) {
+        let conflicting = match known.vendor() {
+            GpuVendor::Mali => driver.contains("adreno"),
+            _ => driver.contains("mali"),
+        };
+        if conflicting {
+            return Err(GpuError::InvalidData(format!(
+                "platform {platform:?} ships {}, but the GL driver is {driver:?}",
+                known.gpu_name
+            )));
+        }
+    }
+
+    let mut info = match known.gpu {
+        #[cfg(feature = "mali-core")]
+        PlatformGpuId::Mali { gpu_id, cores } => {
+            let entry = crate::mali::lookup_product(gpu_id, cores)
+                .ok_or(GpuError::UnsupportedGpu { id: gpu_id, cores })?;
+            crate::mali::product_gpu_info(entry, cores)
+        }
+        #[cfg(feature = "adreno-core")]
+        PlatformGpuId::Adreno { chip_id } => {
+            // Chips without an entry take the specs of their nearest sibling
+            let specs = crate::adreno::lookup_adreno_specs(chip_id)
+                .ok_or_else(|| GpuError::InvalidData(format!("chip 0x{chip_id:08X} is not in the database")))?;
+            let device_info = crate::adreno::ParsedDeviceInfo::with_chip_id(chip_id);
+            crate::adreno::create_gpu_info_from_specs(&device_info, &specs, None)
+        }
+        #[allow(unreachable_patterns)]
+        _ => return Err(GpuError::DriverNotSupported),
+    };
+    info.gpu_name = Cow::Borrowed(known.gpu_name);
+    info.confidence = DataConfidence::Heuristic;
+    info.provenance = Vec::new();
+    info.capabilities = GpuCapabilities::of(&info);
+    Ok(info)
+}
+
+/// Value of the system property `name`, `None` if unset or off Android
+pub fn system_property(name: &str) -> Option<String> {
+    #[cfg(target_os = "android")]
+    {
+        let name = std::ffi::CString::new(name).ok()?;
+        let mut value = [0 as std::os::raw::c_char; libc::PROP_VALUE_MAX as usize];
+        // SAFETY: `value` has the PROP_VALUE_MAX bytes the call may write
+        let len = unsafe { libc::__system_property_get(name.as_ptr(), value.as_mut_ptr()) };
+        if len <= 0 {
+            return None;
+        }
+        // SAFETY: the value is NUL-terminated within the buffer
+        let value = unsafe { std::ffi::CStr::from_ptr(value.as_ptr()) };
+        Some(value.to_string_lossy().into_owned())
+    }
+    #[cfg(not(target_os = "android"))]
+    {
+        let _ = name;
+        None
+    }
+}
@@ -0,0 +1,241 @@
+//! `gpuinfo` - print GPU information as text or JSON
+//!
+//! ```text
+//! gpuinfo [--device PATH] [--mode parity|extended] [--all] [--json]
+//! ```
+
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use armgpuinfo::soc::{identify_soc, SocInfo};
+use armgpuinfo::{adreno, enumerate_gpus, GpuError, GpuInfo, GpuResult, Mode};
+
+const USAGE: &str = "\
+Usage: gpuinfo [OPTIONS]
+
+Options:
+  -d, --device PATH    Query this device node instead of auto-detecting
+  -m, --mode MODE      Query mode: parity (default) or extended
+  -a, --all            Report every GPU found, not just the first
+  -j, --json           Print JSON instead of text
+  -h, --help           Print this help
+  -V, --version        Print the version
+
+Exit status:
+  0                    Success
+  1                    Output could not be written
+  2                    Invalid arguments
+  10-31                Query failed, see GpuErrorKind for the codes";
+
+struct Args {
+    device: Option<PathBuf>,
+    mode: Mode,
+    all: bool,
+    json: bool,
+}
+
+enum Command {
+    Run(Args),
+    Help,
+    Version,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let mut parsed = Args { device: None, mode: Mode::Parity, all: false, json: false };
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-d" | "--device" => {
+                let path = args.next().ok_or("--device needs a path")?;
+                parsed.device = Some(PathBuf::from(path));
+            }
+            "-m" | "--mode" => {
+                parsed.mode = match args.next().as_deref() {
+                    Some("parity") => Mode::Parity,
+                    Some("extended") => Mode::Extended,
+                    Some(other) => return Err(format!("unknown mode '{other}'")),
+                    None => return Err("--mode needs a value".into()),
+                };
+            }
+            "-a" | "--all" => parsed.all = true,
+            "-j" | "--json" => parsed.json = true,
+            "-h" | "--help" => return Ok(Command::Help),
+            "-V" | "--version" => return Ok(Command::Version),
+            other => return Err(format!("unexpected argument '{other}'")),
+        }
+    }
+
+    if parsed.all && parsed.device.is_some() {
+        return Err("--all and --device are mutually exclusive".into());
+    }
+    Ok(Command::Run(parsed))
+}
+
+/// Name the drivers bound to the render nodes, which is all that is known
+/// about a GPU no backend could query
+fn print_render_nodes() {
+    for node in armgpuinfo::drm::enumerate_render_nodes() {
+        let bus = node.bus_id.map(|bus| format!(" at {bus}")).unwrap_or_default();
+        eprintln!("gpuinfo: {} is driven by {}{bus}", node.path.display(), node.driver);
+    }
+}
+
+/// Query one node with the backend its name belongs to
+fn query_path(path: &Path, mode: Mode) -> GpuResult<GpuInfo> {
+    let name = path.file_name().map(|n| n.to_string_lossy()).unwrap_or_default();
+    let adreno_mode = adreno::Mode::from(mode);
+
+    if name.starts_with("mali") {
+        return armgpuinfo::query_mali_with_mode(path, mode);
+    }
+    if name.starts_with("kgsl") {
+        return adreno::query_adreno_with_mode(path, adreno_mode);
+    }
+    #[cfg(feature = "adreno-drm")]
+    if name.starts_with("renderD") {
+        return armgpuinfo::query_adreno_drm(path);
+    }
+    armgpuinfo::query_mali_with_mode(path, mode)
+        .or_else(|_| adreno::query_adreno_with_mode(path, adreno_mode))
+}
+
+fn collect(args: &Args) -> GpuResult<Vec<GpuInfo>> {
+    if let Some(device) = &args.device {
+        return query_path(device, args.mode).map(|info| vec![info]);
+    }
+
+    let mut gpus = enumerate_gpus()?;
+    // enumerate_gpus queries in Parity mode; redo with the requested mode
+    if args.mode == Mode::Extended {
+        gpus = gpus
+            .iter()
+            .filter_map(|info| info.device_path.as_deref())
+            .map(|path| query_path(path, args.mode))
+            .collect::<GpuResult<_>>()?;
+    }
+    if !args.all {
+        gpus.truncate(1);
+    }
+    Ok(gpus)
+}
+
+fn print_text(info: &GpuInfo, soc: &SocInfo) {
+    if let Some(path) = &info.device_path {
+        println!("Device:        {}", path.display());
+    }
+    println!("GPU:           {}", info.gpu_name);
+    println!("Vendor:        {}", info.vendor);
+    match (&soc.soc, soc.name()) {
+        (Some(known), _) => println!("SoC:           {known}"),
+        (None, Some(machine)) => println!("SoC:           {machine}"),
+        _ => {}
+    }
+    println!(
+        "Architecture:  {} ({}.{})",
+        info.architecture, info.architecture_major, info.architecture_minor
+    );
+    println!("Shader cores:  {}", info.num_shader_cores);
+    println!("L2 cache:      {} KB", info.num_l2_bytes / 1024);
+    if info.num_bus_bits != 0 {
+        println!("Bus width:     {} bits", info.num_bus_bits);
+    }
+    if let Some(revision) = info.revision() {
+        println!("Revision:      {revision}");
+    }
+
+    if let Some(mali) = &info.mali_data {
+        println!("GPU ID:        0x{:04x} (raw 0x{:08x})", mali.gpu_id, mali.raw_gpu_id);
+        println!("Core mask:     0x{:x}", mali.shader_core_mask);
+        if let Some(instance) = mali.instance {
+            println!("Instance:      {instance}");
+        }
+        match (mali.flavor, mali.driver_version) {
+            (Some(flavor), Some((major, minor))) => println!("Driver:        kbase {flavor}, UK {major}.{minor}"),
+            (Some(flavor), None) => println!("Driver:        kbase {flavor}"),
+            _ => {}
+        }
+        if mali.num_fp32_fmas_per_core != 0 {
+            println!("FP32 FMA/core: {}", mali.num_fp32_fmas_per_core);
+            println!("Texels/core:   {}", mali.num_texels_per_core);
+            println!("Pixels/core:   {}", mali.num_pixels_per_core);
+        }
+    }
+
+    if let Some(adreno) = &info.adreno_data {
+        println!("Chip ID:       0x{:08x}", adreno.chip_id);
+        println!("GMEM:          {} KB", adreno.gmem_bytes / 1024);
+        println!("Max clock:     {} MHz", adreno.max_freq_mhz);
+        println!("Specs:         {}", adreno.spec_confidence);
+        if !adreno.snapdragon_models.is_empty() {
+            let socs: Vec<String> = adreno.snapdragon_models.iter().map(|s| s.to_string()).collect();
+            println!("SoCs:          {}", socs.join(", "));
+        }
+    }
+
+    for quirk in info.known_quirks() {
+        println!("Quirk:         {}", quirk.description);
+    }
+}
+
+/// Stable [`GpuError::code`], all of which fit the 8-bit exit status
+fn exit_code(error: &GpuError) -> ExitCode {
+    ExitCode::from(error.code() as u8)
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(Command::Run(args)) => args,
+        Ok(Command::Help) => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Ok(Command::Version) => {
+            println!("gpuinfo {}", env!("CARGO_PKG_VERSION"));
+            return ExitCode::SUCCESS;
+        }
+        Err(message) => {
+            eprintln!("gpuinfo: {message}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let gpus = match collect(&args) {
+        Ok(gpus) => gpus,
+        Err(GpuError::DeviceNotFound) => {
+            eprintln!("gpuinfo: no GPU device found");
+            print_render_nodes();
+            return exit_code(&GpuError::DeviceNotFound);
+        }
+        Err(e) => {
+            eprintln!("gpuinfo: {e}");
+            print_render_nodes();
+            return exit_code(&e);
+        }
+    };
+
+    if args.json {
+        // A single object unless --all was asked for
+        let json = if args.all {
+            serde_json::to_string_pretty(&gpus)
+        } else {
+            serde_json::to_string_pretty(&gpus[0])
+        };
+        match json {
+            Ok(json) => println!("{json}"),
+            Err(e) => {
+                eprintln!("gpuinfo: {e}");
+                return ExitCode::FAILURE;
+            }
+        }
+    } else {
+        let soc = identify_soc();
+        for (i, info) in gpus.iter().enumerate() {
+            if i > 0 {
+                println!();
+            }
+            print_text(info, &soc);
+        }
+    }
+
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,97 @@
+//! Memory budget suggestions
+//!
+//! Mobile GPUs have no VRAM of their own, so the useful question for an
+//! engine is not "how much memory does the GPU have" but "how much of the
+//! shared RAM can I take before the OS starts killing me". The numbers here
+//! are heuristics derived from system RAM, on-chip memory and a rough bus
+//! bandwidth estimate; treat them as starting points, not hard limits.
+
+use crate::info::{GpuInfo, GpuVendor};
+
+/// RAM assumed when /proc/meminfo is unreadable
+pub const DEFAULT_SYSTEM_RAM: u64 = 2 << 30;
+
+/// RAM kept back for the OS and other apps before any GPU share is taken
+const OS_RESERVE: u64 = 1 << 30;
+
+/// Estimated bandwidth below which render targets get a smaller share
+const LOW_BANDWIDTH: u64 = 20_000_000_000;
+
+/// How much of the available memory to claim
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BudgetProfile {
+    /// Background or multi-window apps that must survive low-memory kills
+    Conservative,
+    /// Typical foreground game or 3D app
+    #[default]
+    Balanced,
+    /// Fullscreen apps that own the device (benchmarks, kiosks)
+    Aggressive,
+}
+
+impl BudgetProfile {
+    /// Share of available RAM used for GPU resources
+    pub fn ram_fraction(self) -> f64 {
+        match self {
+            BudgetProfile::Conservative => 0.125,
+            BudgetProfile::Balanced => 0.2,
+            BudgetProfile::Aggressive => 0.33,
+        }
+    }
+}
+
+/// Suggested budgets in bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryBudget {
+    pub profile: BudgetProfile,
+    /// System RAM the budget was derived from, `None` if
+    /// [`DEFAULT_SYSTEM_RAM`] was assumed
+    pub system_ram_bytes: Option<u64>,
+    /// Peak memory bandwidth (bus width x memory transfer rate), if known
+    pub est_bandwidth_bytes_per_sec: Option<u64>,
+    /// On-chip tile memory (Adreno GMEM, Mali L2)
+    pub tile_memory_bytes: u64,
+    /// Total GPU share of system RAM
+    pub total_bytes: u64,
+    /// Texture and buffer pool
+    pub texture_pool_bytes: u64,
+    /// Render targets, depth buffers and other attachments
+    pub render_target_bytes: u64,
+}
+
+/// Compute a budget for `info`, `system_ram` in bytes
+pub fn suggest(info: &GpuInfo, profile: BudgetProfile, system_ram: Option<u64>) -> MemoryBudget {
+    let ram = system_ram.unwrap_or(DEFAULT_SYSTEM_RAM);
+    let available = ram.saturating_sub(OS_RESERVE).max(ram / 4);
+    let total = (available as f64 * profile.ram_fraction()) as u64;
+
+    let bandwidth = estimate_bandwidth(info);
+    // Every render target pass costs bandwidth for loads/stores; slow buses
+    // are better off spending memory on textures instead
+    let rt_fraction = match bandwidth {
+        Some(bw) if bw < LOW_BANDWIDTH => 0.15,
+        _ => 0.25,
+    };
+    let render_target = (total as f64 * rt_fraction) as u64;
+
+    let tile_memory = match (&info.vendor, &info.adreno_data) {
+        (GpuVendor::Adreno, Some(adreno)) => adreno.gmem_bytes,
+        _ => info.num_l2_bytes,
+    };
+
+    MemoryBudget {
+        profile,
+        system_ram_bytes: system_ram,
+        est_bandwidth_bytes_per_sec: bandwidth,
+        tile_memory_bytes: tile_memory,
+        total_bytes: total,
+        texture_pool_bytes: total - render_target,
+        render_target_bytes: render_target,
+    }
+}
+
+/// Bus width times the database's memory transfer rate, `None` without
+/// one; the GPU clock says nothing about DRAM bandwidth
+fn estimate_bandwidth(info: &GpuInfo) -> Option<u64> {
+    info.estimated_bandwidth_gbps().map(|gbps| (gbps * 1e9) as u64)
+}
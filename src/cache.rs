@@ -0,0 +1,104 @@
+//! Process-wide cache of query results
+//!
+//! The GPU a node reports does not change while the system runs, but every
+//! query re-opens the node and re-issues its ioctls. [`query_gpu_cached`]
+//! keeps the first successful result per device path and hands out copies
+//! until it is older than the [TTL](set_ttl) or [`invalidate`] drops it.
+//! Errors are not cached, so a GPU that is not ready yet is queried again
+//! on the next call.
+//!
+//! Clocks and other telemetry in a cached result are those of the first
+//! query; read them through [`telemetry`](crate::telemetry) instead.
+
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::detect::query_gpu_auto;
+use crate::error::GpuResult;
+use crate::info::GpuInfo;
+
+/// How long a result stays cached unless [`set_ttl`] changes it
+pub const DEFAULT_TTL: Duration = Duration::from_secs(300);
+
+struct Cache {
+    ttl: Option<Duration>,
+    /// Bumped by every invalidation, so a query that was running meanwhile
+    /// does not store its result
+    generation: u64,
+    /// Requested device path (`None` for auto-detection), time of the
+    /// query and its result
+    entries: Vec<(Option<PathBuf>, Instant, GpuInfo)>,
+}
+
+impl Cache {
+    /// Result for `key` that has not expired yet
+    fn fresh(&self, key: &Option<PathBuf>) -> Option<&GpuInfo> {
+        self.entries
+            .iter()
+            .find(|(k, queried, _)| k == key && self.ttl.is_none_or(|ttl| queried.elapsed() < ttl))
+            .map(|(_, _, info)| info)
+    }
+}
+
+static CACHE: Mutex<Cache> = Mutex::new(Cache {
+    ttl: Some(DEFAULT_TTL),
+    generation: 0,
+    entries: Vec::new(),
+});
+
+fn cache() -> std::sync::MutexGuard<'static, Cache> {
+    CACHE.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// [`query_gpu_auto`], answered from the cache while a result for
+/// `device_path` is fresh
+///
+/// The lock is not held during the query: threads missing the cache at the
+/// same time each query the device, and the first result stored is kept
+/// and returned to all of them. A result whose query overlapped an
+/// [`invalidate`] is returned but not stored.
+pub fn query_gpu_cached<P: AsRef<Path>>(device_path: Option<P>) -> GpuResult<GpuInfo> {
+    let key = device_path.as_ref().map(|path| path.as_ref().to_path_buf());
+    let generation = {
+        let cache = cache();
+        if let Some(info) = cache.fresh(&key) {
+            return Ok(info.clone());
+        }
+        cache.generation
+    };
+
+    let info = query_gpu_auto(key.as_deref())?;
+    let mut cache = cache();
+    if cache.generation != generation {
+        return Ok(info);
+    }
+    if let Some(first) = cache.fresh(&key) {
+        return Ok(first.clone());
+    }
+    cache.entries.retain(|(k, _, _)| *k != key);
+    cache.entries.push((key, Instant::now(), info.clone()));
+    Ok(info)
+}
+
+/// Drop every cached result
+pub fn invalidate() {
+    let mut cache = cache();
+    cache.generation += 1;
+    cache.entries.clear();
+}
+
+/// Drop the cached result for `device_path`, `None` for auto-detection
+pub fn invalidate_device<P: AsRef<Path>>(device_path: Option<P>) {
+    let key = device_path.as_ref().map(|path| path.as_ref());
+    let mut cache = cache();
+    cache.generation += 1;
+    cache.entries.retain(|(k, _, _)| k.as_deref() != key);
+}
+
+/// Set how long results stay cached, `None` to keep them until invalidated
+///
+/// Applies to results already cached too.
+pub fn set_ttl(ttl: Option<Duration>) {
+    cache().ttl = ttl;
+}
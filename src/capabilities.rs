@@ -0,0 +1,133 @@
+//! Feature support of the GPU
+//!
+//! Neither kbase nor KGSL reports which features the GPU implements; they
+//! follow from the product (the databases) and its architecture
+//! generation. [`GpuCapabilities::of`] derives them once per query and
+//! stores them in [`GpuInfo::capabilities`]. API-level support still
+//! depends on the driver: a GPU with ray tracing hardware only exposes it
+//! through drivers that implement `VK_KHR_ray_query`.
+//!
+//! ```
+//! use armgpuinfo::GpuInfo;
+//!
+//! let info = GpuInfo::adreno_builder()
+//!     .chip_id(0x0706_0001)
+//!     .gpu_name("Adreno 740")
+//!     .architecture("Adreno 7xx")
+//!     .num_shader_cores(6)
+//!     .gmem_bytes(3 << 20)
+//!     .build()?;
+//! assert!(info.capabilities.ray_tracing);
+//! assert!(info.capabilities.ubwc);
+//! assert!(!info.capabilities.afbc);
+//! # Ok::<(), armgpuinfo::BuilderError>(())
+//! ```
+
+use crate::display::{hints, ScanoutCompression};
+use crate::info::{GpuInfo, GpuVendor, MaliArch};
+
+/// Bifrost products with the int8 dot product instructions (G76, G52)
+const MALI_INT8_DOT_BIFROST: [u32; 2] = [0x7001, 0x7002];
+
+/// First Mali product ID with variable rate shading (G715)
+const MALI_VRS_FIRST: u32 = 0xb000;
+
+/// Hardware features of a GPU
+///
+/// All `false` for unknown GPUs and results deserialized from before the
+/// field existed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GpuCapabilities {
+    /// Native half-precision arithmetic
+    pub fp16: bool,
+    /// 8-bit integer dot product instructions
+    pub int8_dot_product: bool,
+    /// Arm Frame Buffer Compression
+    pub afbc: bool,
+    /// Hardware ray tracing
+    pub ray_tracing: bool,
+    /// Variable rate shading
+    pub variable_rate_shading: bool,
+    /// Tile-based rendering into on-chip memory
+    pub tile_based_rendering: bool,
+    /// Qualcomm Universal Bandwidth Compression
+    pub ubwc: bool,
+}
+
+impl GpuCapabilities {
+    /// Derive the capabilities of `info` from its product and architecture
+    pub fn of(info: &GpuInfo) -> Self {
+        let compression = hints(info).compression;
+        let afbc = compression == Some(ScanoutCompression::Afbc);
+        let ubwc = matches!(compression, Some(ScanoutCompression::Ubwc(_)));
+        match info.vendor {
+            GpuVendor::Mali => {
+                let Some(mali) = &info.mali_data else {
+                    return Self { tile_based_rendering: true, ..Self::default() };
+                };
+                let bifrost_or_later = matches!(mali.arch, Some(arch) if arch != MaliArch::Midgard);
+                Self {
+                    // Every generation from Midgard on has FP16 ALUs
+                    fp16: mali.arch.is_some() || mali.num_fp16_fmas_per_core > 0,
+                    int8_dot_product: matches!(mali.arch, Some(MaliArch::Valhall | MaliArch::Gen5))
+                        || MALI_INT8_DOT_BIFROST.contains(&mali.gpu_id),
+                    afbc,
+                    // Immortalis is the ray tracing configuration of its product
+                    ray_tracing: info.gpu_name.starts_with("Immortalis"),
+                    variable_rate_shading: bifrost_or_later && mali.gpu_id >= MALI_VRS_FIRST,
+                    tile_based_rendering: true,
+                    ubwc: false,
+                }
+            }
+            GpuVendor::Adreno => {
+                let series = info.architecture_major;
+                let model = adreno_model(&info.gpu_name);
+                Self {
+                    fp16: series >= 5,
+                    int8_dot_product: series >= 7,
+                    afbc: false,
+                    ray_tracing: model.map_or(series >= 8, |model| model >= 740),
+                    // Introduced with the Adreno 660
+                    variable_rate_shading: model.map_or(series >= 7, |model| model >= 660),
+                    // GMEM binning
+                    tile_based_rendering: true,
+                    ubwc,
+                }
+            }
+            // Vivante GC cores are immediate-mode renderers; FP16 support is
+            // not decoded from the feature words
+            GpuVendor::Vivante | GpuVendor::Unknown => Self { afbc, ubwc, ..Self::default() },
+        }
+    }
+
+    /// Names of the supported features, in field order
+    ///
+    /// ```
+    /// use armgpuinfo::GpuCapabilities;
+    ///
+    /// let caps = GpuCapabilities { fp16: true, ubwc: true, ..Default::default() };
+    /// assert_eq!(caps.names(), ["fp16", "ubwc"]);
+    /// ```
+    pub fn names(&self) -> Vec<&'static str> {
+        [
+            ("fp16", self.fp16),
+            ("int8_dot_product", self.int8_dot_product),
+            ("afbc", self.afbc),
+            ("ray_tracing", self.ray_tracing),
+            ("variable_rate_shading", self.variable_rate_shading),
+            ("tile_based_rendering", self.tile_based_rendering),
+            ("ubwc", self.ubwc),
+        ]
+        .into_iter()
+        .filter_map(|(name, supported)| supported.then_some(name))
+        .collect()
+    }
+}
+
+/// Model number of an "Adreno 740" style name, `None` for the "Adreno 7xx
+/// (unknown variant)" entries
+fn adreno_model(name: &str) -> Option<u32> {
+    let digits = name.strip_prefix("Adreno ")?.get(..3)?;
+    digits.parse().ok()
+}
@@ -0,0 +1,111 @@
+//! String format and ordering guarantees
+//!
+//! Downstream telemetry pipelines store our strings as keys and group by
+//! them, so the following are part of the public API and only change in a
+//! major release:
+//!
+//! | API | Format |
+//! | --- | --- |
+//! | [`GpuInfo::stable_id`](crate::GpuInfo::stable_id) | `mali:0x<4 hex>:mc<cores>`, `adreno:0x<8 hex>`, `vivante:0x<model>:0x<revision>` (4 hex each), lowercase |
+//! | [`GpuVendor::code`](crate::GpuVendor::code) / [`GpuInfo::vendor_code`](crate::GpuInfo::vendor_code) | `mali`, `adreno`, `vivante`, `unknown` |
+//! | [`ArchitectureCode`](crate::ArchitectureCode) `code`, `Display` and serde | `mali_midgard` ... `mali_gen5`, `adreno_4xx` ... `adreno_8xx` |
+//! | [`GpuErrorKind`](crate::GpuErrorKind) `name`, `Display` and serde | `io`, `device_not_found`, `permission_denied`, ... |
+//! | [`GpuErrorKind::code`](crate::GpuErrorKind::code) / [`GpuError::code`](crate::GpuError::code) | `10` ... `31`, never reused; 0 means success in the C interface |
+//! | [`GpuVendor`](crate::GpuVendor) `Display` | `ARM Mali`, `Qualcomm Adreno`, `VeriSilicon Vivante`, `Unknown` |
+//! | [`MaliFlavor`](crate::MaliFlavor) `Display` | `JM`, `CSF` |
+//! | [`MaliArch::as_str`](crate::MaliArch::as_str) / `Display` | `Midgard`, `Bifrost`, `Valhall`, `Arm 5th Gen` |
+//! | `AdrenoArch` `Display` | `Adreno 4xx` ... `Adreno 8xx` |
+//! | [`SpecConfidence`](crate::SpecConfidence) `Display` | `Measured`, `Reverse Engineered`, `Heuristic`, `Extrapolated` |
+//! | [`Provenance`](crate::telemetry::Provenance) `Display` | lowercase source, e.g. `sysfs` |
+//! | [`Revision`](crate::quirks::Revision) `Display` | `r<major>p<minor>` |
+//! | [`SocRef`](crate::soc::SocRef) `Display` | `<market name> (<part number>)`, e.g. `Snapdragon 8 Gen 2 (SM8550)` |
+//! | Product names (`GpuInfo::gpu_name` from the databases) | `Mali-G710`, `Immortalis-G720`, `Adreno 740` |
+//!
+//! Lists are returned in database order, never hash order:
+//! `AdrenoData::snapdragon_models`, `mali::products()`, `adreno::ADRENO_CHIPS`
+//! and `GpuInfo::known_quirks()` are stable between calls and releases
+//! (new entries may be appended or inserted).
+//!
+//! With the `serde` feature, fields and enum variants serialize under their
+//! Rust names (`MaliFlavor::Csf` as `"Csf"`, not its `Display` form), except
+//! that an `ArchitectureCode` serializes as its code, a `GpuErrorKind` as
+//! its snake_case name and a `SocRef` as its part number. Prefer the codes over `Display` strings as database keys;
+//! `Display` wording may be improved within the guarantees above.
+//!
+//! [`GpuInfo::to_string`](crate::GpuInfo::to_string) and `Debug` output are
+//! for humans and carry no guarantee.
+//!
+//! The formats are locked down by the example below:
+//!
+//! ```
+//! use armgpuinfo::quirks::Revision;
+//! use armgpuinfo::telemetry::Provenance;
+//! use armgpuinfo::{ArchitectureCode, GpuInfo, GpuVendor, MaliArch, MaliFlavor};
+//!
+//! let mali = GpuInfo::builder()
+//!     .gpu_id(0x9002)
+//!     .raw_gpu_id(0x9002_0010)
+//!     .gpu_name("Mali-G78")
+//!     .architecture("Valhall")
+//!     .architecture_major(9)
+//!     .architecture_minor(0)
+//!     .num_shader_cores(24)
+//!     .num_l2_bytes(2 << 20)
+//!     .build()
+//!     .unwrap();
+//! assert_eq!(mali.stable_id().as_deref(), Some("mali:0x9002:mc24"));
+//!
+//! assert_eq!(mali.vendor_code(), "mali");
+//! assert_eq!(mali.architecture_code().map(ArchitectureCode::code), Some("mali_valhall"));
+//! assert_eq!(GpuVendor::Adreno.code(), "adreno");
+//! assert_eq!(GpuVendor::Vivante.code(), "vivante");
+//! assert_eq!(GpuVendor::Unknown.code(), "unknown");
+//! assert_eq!(ArchitectureCode::MaliGen5.to_string(), "mali_gen5");
+//! assert_eq!(ArchitectureCode::from_code("adreno_7xx"), Some(ArchitectureCode::Adreno7xx));
+//!
+//! assert_eq!(GpuVendor::Mali.to_string(), "ARM Mali");
+//! assert_eq!(GpuVendor::Adreno.to_string(), "Qualcomm Adreno");
+//! assert_eq!(GpuVendor::Vivante.to_string(), "VeriSilicon Vivante");
+//! assert_eq!(GpuVendor::Unknown.to_string(), "Unknown");
+//! assert_eq!(MaliFlavor::JobManager.to_string(), "JM");
+//! assert_eq!(MaliFlavor::Csf.to_string(), "CSF");
+//! assert_eq!(MaliArch::Midgard.as_str(), "Midgard");
+//! assert_eq!(MaliArch::Bifrost.as_str(), "Bifrost");
+//! assert_eq!(MaliArch::Valhall.as_str(), "Valhall");
+//! assert_eq!(MaliArch::Gen5.to_string(), "Arm 5th Gen");
+//! assert_eq!(Provenance::Sysfs.to_string(), "sysfs");
+//! assert_eq!(Provenance::Ioctl.to_string(), "ioctl");
+//! assert_eq!(Revision::new(1, 2).to_string(), "r1p2");
+//!
+//! #[cfg(feature = "mali-core")]
+//! {
+//!     use armgpuinfo::mali::lookup_product;
+//!     assert_eq!(lookup_product(0xa002, 16).unwrap().name, "Mali-G710");
+//!     assert_eq!(lookup_product(0xc000, 12).unwrap().name, "Immortalis-G720");
+//! }
+//!
+//! #[cfg(feature = "adreno-core")]
+//! {
+//!     use armgpuinfo::adreno::{find_adreno_specs, AdrenoArch, SpecConfidence};
+//!     assert_eq!(AdrenoArch::A4xx.to_string(), "Adreno 4xx");
+//!     assert_eq!(AdrenoArch::A8xx.to_string(), "Adreno 8xx");
+//!     assert_eq!(SpecConfidence::ReverseEngineered.to_string(), "Reverse Engineered");
+//!
+//!     let a730 = find_adreno_specs(0x0703_0001).unwrap();
+//!     assert_eq!(a730.name, "Adreno 730");
+//!     let socs: Vec<_> = a730.snapdragon_models.iter().map(|s| s.model).collect();
+//!     assert_eq!(socs, ["SM8450", "SM8475"]);
+//! }
+//!
+//! #[cfg(feature = "serde")]
+//! {
+//!     use armgpuinfo::soc::{SocRef, SM8550};
+//!     assert_eq!(serde_json::to_string(&MaliFlavor::Csf).unwrap(), r#""Csf""#);
+//!     assert_eq!(serde_json::to_string(&SM8550).unwrap(), r#""SM8550""#);
+//!     assert_eq!(
+//!         serde_json::to_string(&ArchitectureCode::Adreno8xx).unwrap(),
+//!         r#""adreno_8xx""#
+//!     );
+//!     assert_eq!(serde_json::from_str::<SocRef>(r#""SM8550""#).unwrap(), SM8550);
+//! }
+//! ```
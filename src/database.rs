@@ -0,0 +1,219 @@
+//! Product entries loaded at runtime
+//!
+//! [`load_extra_entries`] adds Mali products and Adreno chips from a JSON
+//! file, so GPUs newer than this release get a proper name and per-core
+//! figures instead of [`UnsupportedGpu`](crate::GpuError::UnsupportedGpu).
+//! Loaded entries take precedence over the built-in tables in every query
+//! path and in [`decode`](crate::decode); the `const` lookups such as
+//! `mali::lookup_product` only see the built-in tables.
+//!
+//! IDs are JSON numbers or `"0x"`-prefixed hex strings. Per-core Mali rates
+//! must be 1, 2, 3, 4, 8, 16, 32 or 64. Entries of a vendor whose backend is
+//! not compiled in are ignored.
+//!
+//! ```json
+//! {
+//!   "mali": [{
+//!     "id": "0xe005", "name": "Mali-G2-Pro", "architecture": "Gen5",
+//!     "fp32_fmas_per_engine": 64, "texels_per_core": 8,
+//!     "pixels_per_core": 4, "exec_engines": 1
+//!   }],
+//!   "adreno": [{
+//!     "chip_id": "0x07090000", "name": "Adreno 790", "architecture": "A7xx",
+//!     "shader_cores": 6, "stream_processors": 1536, "gmem_size_kb": 3072,
+//!     "bus_width_bits": 128, "max_freq_mhz": 1000, "process_nm": 3,
+//!     "year": 2026,
+//!     "memory": { "memory_type": "lpddr5x", "transfer_rate_mts": 10667 }
+//!   }]
+//! }
+//! ```
+//!
+//! Loaded entries live until the process exits; load each file once, at
+//! startup.
+
+use std::fs;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::error::{GpuError, GpuResult};
+
+#[cfg(feature = "adreno-core")]
+use crate::adreno::{AdrenoArch, AdrenoMemory};
+#[cfg(feature = "adreno-core")]
+use crate::info::{AdrenoSlices, SpecConfidence};
+#[cfg(feature = "mali-core")]
+use crate::info::MaliArch;
+
+/// Number of entries [`load_extra_entries`] added, per vendor
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LoadedEntries {
+    pub mali: usize,
+    pub adreno: usize,
+}
+
+/// Add the entries of the JSON file at `path` to the product databases
+///
+/// The file is validated as a whole: on error nothing is added. Fails with
+/// [`GpuError::Io`] if the file cannot be read and
+/// [`GpuError::InvalidData`] if it is malformed.
+pub fn load_extra_entries(path: impl AsRef<Path>) -> GpuResult<LoadedEntries> {
+    let path = path.as_ref();
+    let invalid = |message: String| GpuError::InvalidData(format!("{}: {message}", path.display()));
+    let file: ExtraFile = serde_json::from_str(&fs::read_to_string(path)?)
+        .map_err(|e| invalid(e.to_string()))?;
+    let mut loaded = LoadedEntries::default();
+
+    #[cfg(feature = "mali-core")]
+    {
+        let products = file
+            .mali
+            .into_iter()
+            .map(MaliEntry::into_product)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(invalid)?;
+        loaded.mali = products.len();
+        crate::mali::register_products(products);
+    }
+
+    #[cfg(feature = "adreno-core")]
+    {
+        let chips: Vec<_> = file.adreno.into_iter().map(AdrenoEntry::into_chip).collect();
+        loaded.adreno = chips.len();
+        crate::adreno::register_chips(chips);
+    }
+
+    Ok(loaded)
+}
+
+/// Top level of an entries file
+#[derive(Deserialize)]
+struct ExtraFile {
+    #[cfg(feature = "mali-core")]
+    #[serde(default)]
+    mali: Vec<MaliEntry>,
+    #[cfg(feature = "adreno-core")]
+    #[serde(default)]
+    adreno: Vec<AdrenoEntry>,
+}
+
+/// A Mali product, see [`ProductEntry`](crate::mali::ProductEntry)
+#[cfg(feature = "mali-core")]
+#[derive(Deserialize)]
+struct MaliEntry {
+    #[serde(deserialize_with = "id")]
+    id: u32,
+    /// Defaults to the mask of the architecture's product ID layout
+    #[serde(default, deserialize_with = "optional_id")]
+    mask: Option<u32>,
+    #[serde(default)]
+    min_cores: u32,
+    name: String,
+    architecture: MaliArch,
+    fp32_fmas_per_engine: u32,
+    texels_per_core: u32,
+    pixels_per_core: u32,
+    exec_engines: u32,
+}
+
+#[cfg(feature = "mali-core")]
+impl MaliEntry {
+    fn into_product(self) -> Result<crate::mali::ProductEntry, String> {
+        let rate = |field: &str, value: u32| {
+            crate::mali::constant_rate(value)
+                .ok_or_else(|| format!("{}: unsupported {field} {value}", self.name))
+        };
+        Ok(crate::mali::ProductEntry {
+            id: self.id,
+            mask: self.mask.unwrap_or(crate::mali::default_mask(self.architecture)),
+            min_cores: self.min_cores,
+            get_num_fp32_fmas_per_engine: rate("fp32_fmas_per_engine", self.fp32_fmas_per_engine)?,
+            get_num_texels: rate("texels_per_core", self.texels_per_core)?,
+            get_num_pixels: rate("pixels_per_core", self.pixels_per_core)?,
+            get_num_exec_engines: rate("exec_engines", self.exec_engines)?,
+            support: crate::mali::default_support(self.architecture),
+            apis: crate::mali::default_apis(self.architecture),
+            architecture: self.architecture,
+            name: Box::leak(self.name.into_boxed_str()),
+        })
+    }
+}
+
+/// An Adreno chip, see [`AdrenoSpecs`](crate::adreno::AdrenoSpecs)
+#[cfg(feature = "adreno-core")]
+#[derive(Deserialize)]
+struct AdrenoEntry {
+    #[serde(deserialize_with = "id")]
+    chip_id: u32,
+    name: String,
+    architecture: AdrenoArch,
+    shader_cores: u32,
+    #[serde(default)]
+    slices: Option<AdrenoSlices>,
+    stream_processors: u32,
+    gmem_size_kb: u32,
+    bus_width_bits: u32,
+    max_freq_mhz: u32,
+    process_nm: u32,
+    year: u32,
+    /// Defaults to [`SpecConfidence::Heuristic`]
+    #[serde(default)]
+    confidence: Option<SpecConfidence>,
+    /// Defaults to the series' typical memory
+    #[serde(default)]
+    memory: Option<AdrenoMemory>,
+}
+
+#[cfg(feature = "adreno-core")]
+impl AdrenoEntry {
+    fn into_chip(self) -> (u32, crate::adreno::AdrenoSpecs) {
+        let specs = crate::adreno::AdrenoSpecs {
+            name: Box::leak(self.name.into_boxed_str()),
+            architecture: self.architecture,
+            shader_cores: self.shader_cores,
+            slices: self.slices,
+            stream_processors: self.stream_processors,
+            gmem_size_kb: self.gmem_size_kb,
+            bus_width_bits: self.bus_width_bits,
+            max_freq_mhz: self.max_freq_mhz,
+            process_nm: self.process_nm,
+            year: self.year,
+            snapdragon_models: &[],
+            confidence: self.confidence.unwrap_or(SpecConfidence::Heuristic),
+            support: crate::adreno::default_support(self.architecture),
+            apis: crate::adreno::default_apis(self.architecture),
+            memory: self.memory.unwrap_or(crate::adreno::default_memory(self.architecture)),
+        };
+        (self.chip_id, specs)
+    }
+}
+
+/// A JSON number or `"0x"`-prefixed hex string
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawId {
+    Number(u32),
+    Text(String),
+}
+
+impl RawId {
+    fn value<E: serde::de::Error>(self) -> Result<u32, E> {
+        match self {
+            RawId::Number(value) => Ok(value),
+            RawId::Text(text) => text
+                .strip_prefix("0x")
+                .or_else(|| text.strip_prefix("0X"))
+                .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                .ok_or_else(|| E::custom(format!("invalid ID {text:?}, expected a number or 0x-prefixed hex"))),
+        }
+    }
+}
+
+fn id<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<u32, D::Error> {
+    RawId::deserialize(deserializer)?.value()
+}
+
+#[cfg(feature = "mali-core")]
+fn optional_id<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Option<u32>, D::Error> {
+    Option::<RawId>::deserialize(deserializer)?.map(RawId::value).transpose()
+}
@@ -0,0 +1,174 @@
+//! Batch offline decoding of raw IDs
+//!
+//! Lightweight agents often only ship the Mali product ID or Adreno chip ID
+//! plus a core count. [`batch`] turns such records back into product names
+//! and architecture codes. Decoded values borrow from the static databases,
+//! and a [`Decoder`] memoizes every distinct record, so fleets with millions
+//! of rows but a few hundred distinct GPUs cost one database scan per GPU.
+
+use std::collections::HashMap;
+
+use crate::info::{ArchitectureCode, DriverSupport, GpuVendor, SpecConfidence};
+
+#[cfg(feature = "adreno-core")]
+use crate::adreno::resolve_adreno_specs;
+#[cfg(feature = "mali-core")]
+use crate::mali::{extrapolate_product, resolve_gpu_id, resolve_product};
+
+/// One input record: vendor, raw ID and shader core count
+///
+/// The raw ID is the Mali product ID (`MaliData::gpu_id` or the
+/// driver-reported value before normalization) or the Adreno chip ID.
+/// Pass a core count of 0 if the agent did not record one.
+pub type RawRecord = (GpuVendor, u64, u32);
+
+/// Result of decoding one [`RawRecord`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecodedGpu {
+    pub vendor: GpuVendor,
+    pub raw_id: u64,
+    /// Core count from the record; for Adreno the database value if the
+    /// record had 0
+    pub core_count: u32,
+    /// Database product name, `None` if the ID is unknown or only a family
+    /// member was found
+    pub name: Option<&'static str>,
+    pub architecture: Option<ArchitectureCode>,
+    /// How the entry was matched, `None` if nothing matched
+    pub confidence: Option<SpecConfidence>,
+    /// Driver lifecycle of the matched entry
+    pub support: Option<DriverSupport>,
+}
+
+impl DecodedGpu {
+    fn unknown((vendor, raw_id, core_count): RawRecord) -> Self {
+        Self {
+            vendor,
+            raw_id,
+            core_count,
+            name: None,
+            architecture: None,
+            confidence: None,
+            support: None,
+        }
+    }
+
+    /// Whether any database entry matched
+    pub fn is_known(&self) -> bool {
+        self.confidence.is_some()
+    }
+}
+
+/// Memoizing decoder, reusable across batches
+#[derive(Debug, Default)]
+pub struct Decoder {
+    cache: HashMap<RawRecord, DecodedGpu>,
+}
+
+impl Decoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Decode a single record
+    pub fn decode(&mut self, record: RawRecord) -> DecodedGpu {
+        *self.cache.entry(record).or_insert_with(|| decode_uncached(record))
+    }
+
+    /// Decode `records` and append the results to `out`
+    ///
+    /// Reusing `out` across calls avoids reallocating the result buffer.
+    pub fn decode_into<I>(&mut self, records: I, out: &mut Vec<DecodedGpu>)
+    where
+        I: IntoIterator<Item = RawRecord>,
+    {
+        let records = records.into_iter();
+        out.reserve(records.size_hint().0);
+        out.extend(records.map(|record| self.decode(record)));
+    }
+
+    /// Number of distinct records seen so far
+    pub fn distinct(&self) -> usize {
+        self.cache.len()
+    }
+}
+
+/// Decode a batch of records in input order
+///
+/// ```
+/// use armgpuinfo::decode::batch;
+/// use armgpuinfo::GpuVendor;
+///
+/// let decoded = batch([
+///     (GpuVendor::Mali, 0xa867, 4),
+///     (GpuVendor::Adreno, 0x0703_0001, 0),
+///     (GpuVendor::Mali, 0xa867, 4),
+/// ]);
+/// assert_eq!(decoded.len(), 3);
+/// # #[cfg(feature = "mali-core")]
+/// assert_eq!(decoded[0].name, Some("Mali-G610"));
+/// # #[cfg(feature = "adreno-core")]
+/// assert_eq!(decoded[1].name, Some("Adreno 730"));
+/// ```
+pub fn batch<I>(records: I) -> Vec<DecodedGpu>
+where
+    I: IntoIterator<Item = RawRecord>,
+{
+    let mut out = Vec::new();
+    Decoder::new().decode_into(records, &mut out);
+    out
+}
+
+fn decode_uncached(record: RawRecord) -> DecodedGpu {
+    match record.0 {
+        #[cfg(feature = "mali-core")]
+        GpuVendor::Mali => decode_mali(record),
+        #[cfg(feature = "adreno-core")]
+        GpuVendor::Adreno => decode_adreno(record),
+        _ => DecodedGpu::unknown(record),
+    }
+}
+
+#[cfg(feature = "mali-core")]
+fn decode_mali(record: RawRecord) -> DecodedGpu {
+    let (_, raw_id, core_count) = record;
+    let Ok(product_id) = u32::try_from(raw_id) else {
+        return DecodedGpu::unknown(record);
+    };
+    let gpu_id = resolve_gpu_id(product_id);
+    // Without a core count, the entry with the lowest threshold applies
+    let cores = core_count.max(1);
+
+    let (entry, confidence) = match resolve_product(gpu_id, cores) {
+        Some(entry) => (entry, SpecConfidence::Measured),
+        None => match extrapolate_product(gpu_id, cores) {
+            Some(entry) => (entry, SpecConfidence::Extrapolated),
+            None => return DecodedGpu::unknown(record),
+        },
+    };
+
+    DecodedGpu {
+        name: (confidence == SpecConfidence::Measured).then_some(entry.name),
+        architecture: Some(entry.architecture.into()),
+        confidence: Some(confidence),
+        support: Some(entry.support),
+        ..DecodedGpu::unknown(record)
+    }
+}
+
+#[cfg(feature = "adreno-core")]
+fn decode_adreno(record: RawRecord) -> DecodedGpu {
+    let (_, raw_id, core_count) = record;
+    let Some(specs) = u32::try_from(raw_id).ok().and_then(resolve_adreno_specs) else {
+        return DecodedGpu::unknown(record);
+    };
+
+    DecodedGpu {
+        core_count: if core_count == 0 { specs.shader_cores } else { core_count },
+        name: Some(specs.name),
+        architecture: ArchitectureCode::from_adreno_major((raw_id >> 24) as u8),
+        confidence: Some(specs.confidence),
+        support: Some(specs.support),
+        ..DecodedGpu::unknown(record)
+    }
+}
@@ -1,39 +1,740 @@
-use std::path::Path;
+use std::ffi::CString;
+use std::fs;
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+use std::os::unix::ffi::OsStrExt;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
 
+#[cfg(any(feature = "mali-drm", feature = "adreno-drm", feature = "vivante"))]
+use crate::drm::DRI_ROOT;
 use crate::error::{GpuError, GpuResult};
-use crate::info::GpuInfo;
+use crate::info::{GpuInfo, GpuVendor};
+
+/// How often [`wait_for_gpu`] re-checks the device nodes
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+#[cfg(feature = "mali-kbase")]
+const MALI_NODE: &str = "/dev/mali0";
+#[cfg(feature = "adreno-kgsl")]
+const KGSL_NODE: &str = "/dev/kgsl-3d0";
+
+const DEV_ROOT: &str = "/dev";
 
 /// Automatically detect and query GPU
-#[cfg(all(feature = "auto-detect", any(feature = "mali", feature = "adreno")))]
+///
+/// Probes the nodes of [`DetectConfig::from_env`]; an explicit
+/// `device_path` is tried as a Mali node before them. Fails with
+/// [`GpuError::DeviceNotFound`] if no node exists and
+/// [`GpuError::DetectionFailed`] with every node's error otherwise.
 pub fn query_gpu_auto<P: AsRef<std::path::Path>>(device_path: Option<P>) -> GpuResult<GpuInfo> {
-    // Try Mali first if device path is provided or default exists
-    #[cfg(feature = "mali")]
-    {
-        // REMOVED: use crate::Mode; // Not needed here
+    let mut config = DetectConfig::from_env();
+    if let Some(path) = device_path {
+        config.mali_paths.insert(0, path.as_ref().to_path_buf());
+    }
+    query_gpu_with_config(&config)
+}
+
+/// Environment variable listing the device nodes to query, separated by
+/// `:`, e.g. `GPUINFO_DEVICE=/dev/mali1` or `/dev/kgsl/kgsl-3d0`
+pub const DEVICE_ENV: &str = "GPUINFO_DEVICE";
+
+/// Device nodes [`query_gpu_with_config`] probes and their order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DetectConfig {
+    /// kbase nodes, tried in order
+    pub mali_paths: Vec<PathBuf>,
+    /// KGSL nodes, tried in order
+    pub adreno_paths: Vec<PathBuf>,
+    /// Vendors to try first, in order; the others follow
+    pub prefer: Vec<GpuVendor>,
+    /// Also scan `/dev` for `mali<N>` and `kgsl-3d0`, and `/dev/dri` for
+    /// render nodes with the DRM backends
+    pub scan_dev: bool,
+}
+
+impl DetectConfig {
+    /// `/dev/mali0`, `/dev/kgsl-3d0` and `/dev/kgsl/kgsl-3d0`, Mali first,
+    /// then whatever else `/dev` holds
+    pub fn new() -> Self {
+        Self {
+            mali_paths: vec![PathBuf::from("/dev/mali0")],
+            adreno_paths: vec![PathBuf::from("/dev/kgsl-3d0"), PathBuf::from("/dev/kgsl/kgsl-3d0")],
+            prefer: vec![GpuVendor::Mali, GpuVendor::Adreno],
+            scan_dev: true,
+        }
+    }
+
+    /// [`new`](Self::new), overridden by [`DEVICE_ENV`] if it is set
+    pub fn from_env() -> Self {
+        match std::env::var(DEVICE_ENV) {
+            Ok(devices) if !devices.trim().is_empty() => Self::new().devices(&devices),
+            _ => Self::new(),
+        }
+    }
+
+    /// Query only the `:`-separated nodes in `devices`
+    ///
+    /// Nodes whose name contains "kgsl" are KGSL nodes, all others kbase
+    /// nodes. Scanning is turned off.
+    ///
+    /// ```
+    /// use std::path::PathBuf;
+    /// use armgpuinfo::detect::DetectConfig;
+    ///
+    /// let config = DetectConfig::new().devices("/dev/mali1:/dev/kgsl/kgsl-3d0");
+    /// assert_eq!(config.mali_paths, [PathBuf::from("/dev/mali1")]);
+    /// assert_eq!(config.adreno_paths, [PathBuf::from("/dev/kgsl/kgsl-3d0")]);
+    /// assert!(!config.scan_dev);
+    /// ```
+    pub fn devices(mut self, devices: &str) -> Self {
+        let (adreno, mali): (Vec<PathBuf>, Vec<PathBuf>) = devices
+            .split(':')
+            .map(str::trim)
+            .filter(|device| !device.is_empty())
+            .map(PathBuf::from)
+            .partition(|path| path.file_name().is_some_and(|name| name.to_string_lossy().contains("kgsl")));
+        self.mali_paths = mali;
+        self.adreno_paths = adreno;
+        self.scan_dev = false;
+        self
+    }
+
+    /// Try `vendors` first, in this order
+    ///
+    /// Only Mali and Adreno have configurable node lists; other vendors are
+    /// ignored here.
+    pub fn prefer(mut self, vendors: impl Into<Vec<GpuVendor>>) -> Self {
+        self.prefer = vendors.into();
+        self
+    }
 
-        if let Some(path) = &device_path {
-            // FIXED: Use query_mali_with_mode with explicit Mode::Parity
-            if let Ok(info) = crate::mali::query_mali_with_mode(path, crate::Mode::Parity) {
-                return Ok(info);
+    /// Enable or disable scanning `/dev`
+    pub fn scan_dev(mut self, enabled: bool) -> Self {
+        self.scan_dev = enabled;
+        self
+    }
+
+    /// Nodes to probe with their backend's vendor, in probe order and
+    /// without duplicates
+    fn candidates(&self) -> Vec<(GpuVendor, PathBuf)> {
+        let mut order: Vec<GpuVendor> = Vec::new();
+        for vendor in self.prefer.iter().chain(&[GpuVendor::Mali, GpuVendor::Adreno]) {
+            if matches!(vendor, GpuVendor::Mali | GpuVendor::Adreno) && !order.contains(vendor) {
+                order.push(*vendor);
+            }
+        }
+
+        let mut candidates: Vec<(GpuVendor, PathBuf)> = Vec::new();
+        for vendor in order {
+            let mut paths = match vendor {
+                GpuVendor::Mali => self.mali_paths.clone(),
+                GpuVendor::Adreno => self.adreno_paths.clone(),
+                _ => continue,
+            };
+            if self.scan_dev {
+                match vendor {
+                    #[cfg(feature = "mali-kbase")]
+                    GpuVendor::Mali => paths.extend(mali_nodes()),
+                    #[cfg(feature = "adreno-kgsl")]
+                    GpuVendor::Adreno => paths.extend(nodes_with_prefix(Path::new(DEV_ROOT), "kgsl-3d0")),
+                    _ => {}
+                }
+            }
+            for path in paths {
+                if !candidates.iter().any(|(_, known)| *known == path) {
+                    candidates.push((vendor, path));
+                }
             }
-        } else if Path::new("/dev/mali0").exists() {
-            // FIXED: Use query_mali_with_mode with explicit Mode::Parity
-            if let Ok(info) = crate::mali::query_mali_with_mode("/dev/mali0", crate::Mode::Parity) {
-                return Ok(info);
+        }
+        candidates
+    }
+}
+
+impl Default for DetectConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Query the nodes of `config` until one answers
+///
+/// The nodes of each vendor are tried in [`prefer`](DetectConfig::prefer)
+/// order; with [`scan_dev`](DetectConfig::scan_dev) the DRM render nodes
+/// come last. Nodes of a vendor whose backend is not compiled in are
+/// skipped. Errors are reported as in [`query_gpu_auto`].
+pub fn query_gpu_with_config(config: &DetectConfig) -> GpuResult<GpuInfo> {
+    let mut attempts = Vec::new();
+    let mut record = |vendor, path: &Path, error: GpuError| {
+        if !error.is_not_found_error() {
+            attempts.push((vendor, path.to_path_buf(), error));
+        }
+    };
+    for (vendor, path) in config.candidates() {
+        let result = match vendor {
+            #[cfg(feature = "mali-kbase")]
+            GpuVendor::Mali => crate::mali::query_mali_with_mode(&path, crate::Mode::Parity),
+            #[cfg(feature = "adreno-kgsl")]
+            GpuVendor::Adreno if node_exists(&path) => crate::adreno::query_adreno(&path),
+            _ => continue,
+        };
+        match result {
+            Ok(info) => return Ok(info),
+            Err(error) => record(vendor, &path, error),
+        }
+    }
+
+    // Mainline kernels expose the GPU through render nodes instead
+    #[cfg(any(feature = "mali-drm", feature = "adreno-drm", feature = "vivante"))]
+    if config.scan_dev {
+        for node in nodes_with_prefix(Path::new(DRI_ROOT), "renderD") {
+            match query_render_node(&node) {
+                Ok(info) => return Ok(info),
+                Err(error) => record(GpuVendor::Unknown, &node, error),
             }
         }
     }
 
-    // Try Adreno if KGSL device exists
-    #[cfg(feature = "adreno")]
+    if attempts.is_empty() {
+        return Err(GpuError::DeviceNotFound);
+    }
+    Err(GpuError::DetectionFailed { attempts })
+}
+
+/// Options for [`query_gpu_with_options`]
+#[derive(Debug, Clone, Copy)]
+pub struct DetectOptions {
+    /// Only query `/dev/mali<N>`; `None` probes every instance in order
+    pub mali_instance: Option<u32>,
+    /// Query mode for every backend
+    pub mode: crate::Mode,
+    /// Identify the GPU through EGL if no device node answers, see
+    /// [`egl`](crate::egl)
+    #[cfg(feature = "egl")]
+    pub egl_fallback: bool,
+    /// Identify the GPU from the SoC platform property if nothing else
+    /// answers, see [`android_props`](crate::android_props)
+    #[cfg(feature = "android-props")]
+    pub android_props_fallback: bool,
+}
+
+impl DetectOptions {
+    /// Probe every instance in Parity mode
+    pub fn new() -> Self {
+        Self {
+            mali_instance: None,
+            mode: crate::Mode::Parity,
+            #[cfg(feature = "egl")]
+            egl_fallback: false,
+            #[cfg(feature = "android-props")]
+            android_props_fallback: false,
+        }
+    }
+
+    /// Select `/dev/mali<N>` explicitly
+    pub fn mali_instance(mut self, instance: u32) -> Self {
+        self.mali_instance = Some(instance);
+        self
+    }
+
+    /// Override the query mode
+    pub fn mode(mut self, mode: crate::Mode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Fall back to [`egl::query_egl`](crate::egl::query_egl) if no device
+    /// node answers
+    #[cfg(feature = "egl")]
+    pub fn egl_fallback(mut self, enabled: bool) -> Self {
+        self.egl_fallback = enabled;
+        self
+    }
+
+    /// Fall back to
+    /// [`android_props::query_android_props`](crate::android_props::query_android_props)
+    /// if neither a device node nor EGL answers
+    #[cfg(feature = "android-props")]
+    pub fn android_props_fallback(mut self, enabled: bool) -> Self {
+        self.android_props_fallback = enabled;
+        self
+    }
+}
+
+impl Default for DetectOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Detect and query a GPU as selected by `options`
+///
+/// An explicit Mali instance is queried on its own: its error is returned
+/// rather than falling back to other GPUs. Without the `mali-kbase` feature
+/// the instance is ignored. Otherwise errors are reported as in
+/// [`query_gpu_auto`], unless a fallback is enabled: then the fallback's
+/// error is returned.
+pub fn query_gpu_with_options(options: &DetectOptions) -> GpuResult<GpuInfo> {
+    let mut attempts = Vec::new();
+    let mut record = |vendor, path: &Path, error: GpuError| {
+        if !error.is_not_found_error() {
+            attempts.push((vendor, path.to_path_buf(), error));
+        }
+    };
+
+    #[cfg(feature = "mali-kbase")]
     {
-        if Path::new("/dev/kgsl-3d0").exists() {
-            if let Ok(info) = crate::adreno::query_adreno("/dev/kgsl-3d0") {
-                return Ok(info);
+        if let Some(instance) = options.mali_instance {
+            let node = Path::new(DEV_ROOT).join(format!("mali{instance}"));
+            return crate::mali::query_mali_with_mode(node, options.mode);
+        }
+        for node in mali_nodes() {
+            match crate::mali::query_mali_with_mode(&node, options.mode) {
+                Ok(info) => return Ok(info),
+                Err(error) => record(GpuVendor::Mali, &node, error),
+            }
+        }
+    }
+
+    #[cfg(feature = "adreno-kgsl")]
+    if node_exists(Path::new(KGSL_NODE)) {
+        match crate::adreno::query_adreno_with_mode(KGSL_NODE, options.mode.into()) {
+            Ok(info) => return Ok(info),
+            Err(error) => record(GpuVendor::Adreno, Path::new(KGSL_NODE), error),
+        }
+    }
+
+    // The DRM backends have a single mode
+    #[cfg(any(feature = "mali-drm", feature = "adreno-drm", feature = "vivante"))]
+    for node in nodes_with_prefix(Path::new(DRI_ROOT), "renderD") {
+        match query_render_node(&node) {
+            Ok(info) => return Ok(info),
+            Err(error) => record(GpuVendor::Unknown, &node, error),
+        }
+    }
+
+    #[cfg(feature = "egl")]
+    if options.egl_fallback {
+        match crate::egl::query_egl() {
+            Ok(info) => return Ok(info),
+            #[cfg(feature = "android-props")]
+            Err(_) if options.android_props_fallback => {}
+            Err(error) => return Err(error),
+        }
+    }
+
+    #[cfg(feature = "android-props")]
+    if options.android_props_fallback {
+        return crate::android_props::query_android_props();
+    }
+
+    if attempts.is_empty() {
+        return Err(GpuError::DeviceNotFound);
+    }
+    Err(GpuError::DetectionFailed { attempts })
+}
+
+/// Query every GPU device node on the system
+///
+/// Scans `/dev/mali*` and `/dev/kgsl-3d0` for the enabled backends, plus
+/// render nodes (`/dev/dri/renderD*`) with the `mali-drm` (panfrost,
+/// panthor), `adreno-drm` (msm) or `vivante` (etnaviv) feature. Each result
+/// records its [`device_path`](GpuInfo::device_path). Nodes that fail to
+/// answer are skipped; if none answers, errors are reported as in
+/// [`query_gpu_auto`].
+pub fn enumerate_gpus() -> GpuResult<Vec<GpuInfo>> {
+    let mut gpus = Vec::new();
+    let mut attempts = Vec::new();
+
+    for (vendor, path) in candidate_nodes() {
+        match query_node(&path) {
+            Ok(info) => gpus.push(info),
+            Err(error) if error.is_not_found_error() => {}
+            Err(error) => attempts.push((vendor, path, error)),
+        }
+    }
+
+    if !gpus.is_empty() {
+        return Ok(gpus);
+    }
+    if attempts.is_empty() {
+        return Err(GpuError::DeviceNotFound);
+    }
+    Err(GpuError::DetectionFailed { attempts })
+}
+
+/// Device nodes [`enumerate_gpus`] probes, in probe order, with the vendor
+/// of their backend; render nodes are [`GpuVendor::Unknown`] until queried
+fn candidate_nodes() -> Vec<(GpuVendor, PathBuf)> {
+    let mut nodes = Vec::new();
+    #[cfg(feature = "mali-kbase")]
+    nodes.extend(mali_nodes().into_iter().map(|node| (GpuVendor::Mali, node)));
+    #[cfg(feature = "adreno-kgsl")]
+    nodes.extend(
+        nodes_with_prefix(Path::new(DEV_ROOT), "kgsl-3d0")
+            .into_iter()
+            .map(|node| (GpuVendor::Adreno, node)),
+    );
+    #[cfg(any(feature = "mali-drm", feature = "adreno-drm", feature = "vivante"))]
+    nodes.extend(
+        nodes_with_prefix(Path::new(DRI_ROOT), "renderD")
+            .into_iter()
+            .map(|node| (GpuVendor::Unknown, node)),
+    );
+    nodes
+}
+
+/// kbase nodes by instance index, `/dev/mali0` first
+#[cfg(feature = "mali-kbase")]
+fn mali_nodes() -> Vec<PathBuf> {
+    let mut nodes = nodes_with_prefix(Path::new(DEV_ROOT), "mali");
+    // Numeric order, so mali10 sorts after mali2
+    nodes.sort_by_key(|node| crate::mali::instance_index(node));
+    nodes
+}
+
+/// Entries of `dir` whose name starts with `prefix`, sorted
+fn nodes_with_prefix(dir: &Path, prefix: &str) -> Vec<PathBuf> {
+    #[cfg(feature = "mock")]
+    if let Some(nodes) = crate::mock::nodes_with_prefix(dir, prefix) {
+        return nodes;
+    }
+    let mut nodes: Vec<PathBuf> = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|entry| entry.file_name().to_string_lossy().starts_with(prefix))
+        .map(|entry| entry.path())
+        .collect();
+    nodes.sort();
+    nodes
+}
+
+/// Whether a device node exists at `path`
+fn node_exists(path: &Path) -> bool {
+    #[cfg(feature = "mock")]
+    if let Some(exists) = crate::mock::node_exists(path) {
+        return exists;
+    }
+    path.exists()
+}
+
+/// Outcome of [`wait_for_gpu`]
+#[derive(Debug)]
+pub enum GpuReadiness {
+    /// A device node exists and the driver answered a query
+    Ready {
+        info: Box<GpuInfo>,
+        /// Time until the driver first answered
+        elapsed: Duration,
+    },
+    /// A device node appeared but the driver never answered
+    NodePresent {
+        path: PathBuf,
+        /// Error of the last query attempt
+        error: GpuError,
+    },
+    /// No GPU device node appeared
+    NotFound,
+}
+
+impl GpuReadiness {
+    /// Whether the GPU is usable
+    pub fn is_ready(&self) -> bool {
+        matches!(self, GpuReadiness::Ready { .. })
+    }
+
+    /// Convert into the queried info, or the error that kept it from being ready
+    pub fn into_result(self) -> GpuResult<GpuInfo> {
+        match self {
+            GpuReadiness::Ready { info, .. } => Ok(*info),
+            GpuReadiness::NodePresent { error, .. } => Err(error),
+            GpuReadiness::NotFound => Err(GpuError::DeviceNotFound),
+        }
+    }
+}
+
+/// Wait up to `timeout` for the GPU to become usable
+///
+/// Polls the default device nodes until one exists and its driver answers a
+/// property query. Early-boot services can use this instead of sleeping a
+/// fixed duration; node permissions set late by ueventd are retried too.
+/// Returns as soon as the GPU is ready.
+pub fn wait_for_gpu(timeout: Duration) -> GpuReadiness {
+    let start = Instant::now();
+    let mut last_seen: Option<(PathBuf, GpuError)> = None;
+
+    loop {
+        for path in DEFAULT_NODES.iter().map(Path::new) {
+            if !node_exists(path) {
+                continue;
+            }
+            match query_node(path) {
+                Ok(info) => {
+                    return GpuReadiness::Ready {
+                        info: Box::new(info),
+                        elapsed: start.elapsed(),
+                    };
+                }
+                Err(error) => last_seen = Some((path.to_path_buf(), error)),
             }
         }
+
+        let elapsed = start.elapsed();
+        if elapsed >= timeout {
+            break;
+        }
+        thread::sleep(POLL_INTERVAL.min(timeout - elapsed));
     }
 
-    // No GPU found
+    match last_seen {
+        Some((path, error)) => GpuReadiness::NodePresent { path, error },
+        None => GpuReadiness::NotFound,
+    }
+}
+
+/// Device nodes of the enabled backends, in probe order
+const DEFAULT_NODES: &[&str] = &[
+    #[cfg(feature = "mali-kbase")]
+    MALI_NODE,
+    #[cfg(feature = "adreno-kgsl")]
+    KGSL_NODE,
+];
+
+/// Query a node with the backend its name belongs to
+fn query_node(path: &Path) -> GpuResult<GpuInfo> {
+    let name = path.file_name().map(|n| n.to_string_lossy()).unwrap_or_default();
+    #[cfg(feature = "mali-kbase")]
+    if name.starts_with("mali") {
+        return crate::mali::query_mali_with_mode(path, crate::Mode::Parity);
+    }
+    #[cfg(feature = "adreno-kgsl")]
+    if name.starts_with("kgsl") {
+        return crate::adreno::query_adreno(path);
+    }
+    #[cfg(any(feature = "mali-drm", feature = "adreno-drm", feature = "vivante"))]
+    if name.starts_with("renderD") {
+        return query_render_node(path);
+    }
     Err(GpuError::DeviceNotFound)
-}
\ No newline at end of file
+}
+
+/// Query a render node with the DRM backend of its driver
+///
+/// [`GpuError::DriverNotSupported`] for drivers without a compiled-in
+/// backend.
+#[cfg(any(feature = "mali-drm", feature = "adreno-drm", feature = "vivante"))]
+fn query_render_node(path: &Path) -> GpuResult<GpuInfo> {
+    let node = crate::drm::read_render_node(path)?;
+    match node.vendor() {
+        #[cfg(feature = "mali-drm")]
+        GpuVendor::Mali => crate::mali::query_mali_drm(path),
+        #[cfg(feature = "adreno-drm")]
+        GpuVendor::Adreno => crate::adreno::query_adreno_drm(path),
+        #[cfg(feature = "vivante")]
+        GpuVendor::Vivante => crate::vivante::query_vivante_drm(path),
+        _ => Err(GpuError::DriverNotSupported),
+    }
+}
+
+/// Change to a GPU device node reported by [`watch`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeviceEvent {
+    /// The node was created, e.g. when the driver module loaded
+    Added(PathBuf),
+    /// The node was removed, e.g. when the driver module was unloaded
+    Removed(PathBuf),
+    /// Owner or mode changed; ueventd sets them after creating the node, so
+    /// a query may only succeed from this event on
+    PermissionsChanged(PathBuf),
+}
+
+impl DeviceEvent {
+    /// Node the event is about
+    pub fn path(&self) -> &Path {
+        match self {
+            DeviceEvent::Added(path) | DeviceEvent::Removed(path) | DeviceEvent::PermissionsChanged(path) => path,
+        }
+    }
+}
+
+/// How long the watch thread blocks before checking for a stop request
+const WATCH_STOP_LATENCY_MS: libc::c_int = 100;
+
+const WATCH_MASK: u32 =
+    libc::IN_CREATE | libc::IN_DELETE | libc::IN_ATTRIB | libc::IN_MOVED_TO | libc::IN_MOVED_FROM;
+
+/// A running [`watch`]; watching stops when it is dropped
+#[derive(Debug)]
+pub struct DeviceWatch {
+    stop: Option<mpsc::Sender<()>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl DeviceWatch {
+    /// Stop watching and wait for the thread to exit
+    pub fn stop(mut self) {
+        self.join();
+    }
+
+    fn join(&mut self) {
+        drop(self.stop.take());
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Drop for DeviceWatch {
+    fn drop(&mut self) {
+        self.join();
+    }
+}
+
+/// Call `callback` whenever a GPU device node of the enabled backends
+/// appears, disappears or changes permissions in `/dev`
+///
+/// Uses inotify on `/dev`, and on `/dev/dri` with the DRM backends, so
+/// daemons started before the driver learn when it becomes available
+/// instead of polling. Nodes present when the watch starts are not
+/// reported; check them with [`enumerate_gpus`] after starting the watch.
+/// The callback runs on a background thread.
+pub fn watch<F>(callback: F) -> GpuResult<DeviceWatch>
+where
+    F: FnMut(DeviceEvent) + Send + 'static,
+{
+    watch_in(DEV_ROOT, callback)
+}
+
+/// [`watch`] with `dev_root` in place of `/dev`
+///
+/// Fails if inotify is unavailable or `dev_root` cannot be watched.
+pub fn watch_in<P, F>(dev_root: P, mut callback: F) -> GpuResult<DeviceWatch>
+where
+    P: AsRef<Path>,
+    F: FnMut(DeviceEvent) + Send + 'static,
+{
+    let dev_root = dev_root.as_ref().to_path_buf();
+    // SAFETY: inotify_init1 takes no pointers; the fd is owned from here on
+    let fd = unsafe { libc::inotify_init1(libc::IN_CLOEXEC | libc::IN_NONBLOCK) };
+    if fd < 0 {
+        return Err(std::io::Error::last_os_error().into());
+    }
+    // SAFETY: `fd` is a freshly created descriptor nothing else owns
+    let fd = unsafe { OwnedFd::from_raw_fd(fd) };
+
+    let mut watches = vec![(add_watch(&fd, &dev_root)?, dev_root.clone())];
+    // /dev/dri only exists once a DRM driver has loaded
+    let dri = dev_root.join("dri");
+    if let Ok(wd) = add_watch(&fd, &dri) {
+        watches.push((wd, dri));
+    }
+
+    let (stop, stopped) = mpsc::channel::<()>();
+    let thread = thread::spawn(move || {
+        let mut buffer = [0u8; 4096];
+        loop {
+            let mut pollfd = libc::pollfd { fd: fd.as_raw_fd(), events: libc::POLLIN, revents: 0 };
+            // SAFETY: one valid pollfd
+            let ready = unsafe { libc::poll(&mut pollfd, 1, WATCH_STOP_LATENCY_MS) };
+            // A message or a dropped sender both end watching
+            if !matches!(stopped.try_recv(), Err(mpsc::TryRecvError::Empty)) {
+                break;
+            }
+            if ready <= 0 {
+                continue;
+            }
+            // SAFETY: reads at most `buffer.len()` bytes into `buffer`
+            let len = unsafe { libc::read(fd.as_raw_fd(), buffer.as_mut_ptr().cast(), buffer.len()) };
+            if len <= 0 {
+                continue;
+            }
+            for (wd, mask, name) in inotify_events(&buffer[..len as usize]) {
+                // The kernel dropped the watch, e.g. after /dev/dri was removed
+                if mask & libc::IN_IGNORED != 0 {
+                    watches.retain(|(watched, _)| *watched != wd);
+                    continue;
+                }
+                let Some(dir) = watches.iter().find(|(watched, _)| *watched == wd).map(|(_, dir)| dir.clone()) else {
+                    continue;
+                };
+                let path = dir.join(&name);
+                if dir == dev_root && name == "dri" && mask & libc::IN_ISDIR != 0 {
+                    if mask & (libc::IN_CREATE | libc::IN_MOVED_TO) != 0 {
+                        if let Ok(wd) = add_watch(&fd, &path) {
+                            // Render nodes created before the watch was added
+                            for node in nodes_with_prefix(&path, "renderD") {
+                                if node.file_name().is_some_and(|n| is_gpu_node(false, &n.to_string_lossy())) {
+                                    callback(DeviceEvent::Added(node));
+                                }
+                            }
+                            watches.push((wd, path));
+                        }
+                    }
+                    continue;
+                }
+                if !is_gpu_node(dir == dev_root, &name) {
+                    continue;
+                }
+                let event = if mask & (libc::IN_CREATE | libc::IN_MOVED_TO) != 0 {
+                    DeviceEvent::Added(path)
+                } else if mask & (libc::IN_DELETE | libc::IN_MOVED_FROM) != 0 {
+                    DeviceEvent::Removed(path)
+                } else {
+                    DeviceEvent::PermissionsChanged(path)
+                };
+                callback(event);
+            }
+        }
+    });
+    Ok(DeviceWatch { stop: Some(stop), thread: Some(thread) })
+}
+
+fn add_watch(fd: &OwnedFd, dir: &Path) -> GpuResult<libc::c_int> {
+    let path = CString::new(dir.as_os_str().as_bytes())
+        .map_err(|_| GpuError::InvalidData(format!("{} contains a NUL byte", dir.display())))?;
+    // SAFETY: `path` is NUL-terminated and outlives the call
+    let wd = unsafe { libc::inotify_add_watch(fd.as_raw_fd(), path.as_ptr(), WATCH_MASK) };
+    if wd < 0 {
+        return Err(std::io::Error::last_os_error().into());
+    }
+    Ok(wd)
+}
+
+/// Whether `name` is a node of an enabled backend; render nodes live in
+/// `/dev/dri`, the others directly in `/dev`
+fn is_gpu_node(in_dev_root: bool, name: &str) -> bool {
+    if in_dev_root {
+        #[cfg(feature = "mali-kbase")]
+        if name.strip_prefix("mali").is_some_and(|n| n.parse::<u32>().is_ok()) {
+            return true;
+        }
+        #[cfg(feature = "adreno-kgsl")]
+        if name == "kgsl-3d0" {
+            return true;
+        }
+        false
+    } else {
+        cfg!(any(feature = "mali-drm", feature = "adreno-drm", feature = "vivante")) && name.starts_with("renderD")
+    }
+}
+
+/// `(wd, mask, name)` of each `inotify_event` in `buffer`
+fn inotify_events(buffer: &[u8]) -> Vec<(libc::c_int, u32, String)> {
+    const HEADER: usize = std::mem::size_of::<libc::inotify_event>();
+    let mut events = Vec::new();
+    let mut offset = 0;
+    while offset + HEADER <= buffer.len() {
+        // SAFETY: HEADER bytes are in bounds; read_unaligned needs no alignment
+        let event: libc::inotify_event = unsafe { std::ptr::read_unaligned(buffer[offset..].as_ptr().cast()) };
+        let name_start = offset + HEADER;
+        let name_end = (name_start + event.len as usize).min(buffer.len());
+        // The name is NUL-padded
+        let name = &buffer[name_start..name_end];
+        let name = name.split(|&b| b == 0).next().unwrap_or_default();
+        events.push((event.wd, event.mask, String::from_utf8_lossy(name).into_owned()));
+        offset = name_end;
+    }
+    events
+}
@@ -0,0 +1,158 @@
+//! Persistent device handle
+//!
+//! The `query_*` functions open and close the device node on every call.
+//! [`GpuDevice`] keeps it open instead, so monitoring loops only pay for
+//! the property ioctls on each [`refresh`](GpuDevice::refresh).
+//!
+//! ```no_run
+//! use armgpuinfo::GpuDevice;
+//!
+//! let mut device = GpuDevice::open("/dev/mali0")?;
+//! println!("{}", device.info().gpu_name);
+//! for _ in 0..10 {
+//!     let info = device.refresh()?;
+//!     println!("{} cores", info.num_shader_cores);
+//! }
+//! # Ok::<(), armgpuinfo::GpuError>(())
+//! ```
+
+use std::fs::File;
+use std::os::unix::io::{AsRawFd, RawFd};
+use std::path::{Path, PathBuf};
+
+use crate::error::{GpuError, GpuResult};
+use crate::info::GpuInfo;
+use crate::instrument::traced;
+
+#[cfg(feature = "adreno-kgsl")]
+use crate::adreno;
+#[cfg(feature = "mali-kbase")]
+use crate::mali::{self, Handshake};
+#[cfg(any(feature = "mali-kbase", feature = "adreno-kgsl"))]
+use crate::options::QueryOptions;
+#[cfg(feature = "mali-kbase")]
+use crate::Mode;
+
+/// Driver interface behind an open device
+#[derive(Debug, Clone)]
+enum Backend {
+    #[cfg(feature = "mali-kbase")]
+    Mali { options: QueryOptions, handshake: Handshake },
+    #[cfg(feature = "adreno-kgsl")]
+    Adreno(QueryOptions),
+}
+
+type OpenFn = fn(&Path) -> GpuResult<GpuDevice>;
+
+/// An open GPU device node that can be queried repeatedly
+#[derive(Debug)]
+pub struct GpuDevice {
+    file: File,
+    path: PathBuf,
+    backend: Backend,
+    info: GpuInfo,
+}
+
+impl GpuDevice {
+    /// Open `path` and query it once
+    ///
+    /// The backend is picked from the node name (`mali*` or `kgsl*`); other
+    /// names try each enabled backend in turn. Queries use Parity mode,
+    /// like `query_gpu_auto`.
+    pub fn open<P: AsRef<Path>>(path: P) -> GpuResult<Self> {
+        let path = path.as_ref();
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_ascii_lowercase())
+            .unwrap_or_default();
+
+        #[cfg(feature = "mali-kbase")]
+        if name.starts_with("mali") {
+            return Self::open_mali(path, Mode::Parity);
+        }
+        #[cfg(feature = "adreno-kgsl")]
+        if name.starts_with("kgsl") {
+            return Self::open_adreno(path, adreno::Mode::Parity);
+        }
+
+        let backends: &[OpenFn] = &[
+            #[cfg(feature = "mali-kbase")]
+            |path| Self::open_mali(path, Mode::Parity),
+            #[cfg(feature = "adreno-kgsl")]
+            |path| Self::open_adreno(path, adreno::Mode::Parity),
+        ];
+        let mut last_error = None;
+        for open in backends {
+            match open(path) {
+                Ok(device) => return Ok(device),
+                Err(e) => last_error = Some(e),
+            }
+        }
+        Err(last_error.unwrap_or(GpuError::DeviceNotFound))
+    }
+
+    /// Open a kbase device node
+    #[cfg(feature = "mali-kbase")]
+    pub fn open_mali<P: AsRef<Path>>(path: P, options: impl Into<QueryOptions>) -> GpuResult<Self> {
+        let path = path.as_ref();
+        let (file, handshake) = mali::open_session(path)?;
+        Self::new(file, path, Backend::Mali { options: options.into(), handshake })
+    }
+
+    /// Open a KGSL device node
+    #[cfg(feature = "adreno-kgsl")]
+    pub fn open_adreno<P: AsRef<Path>>(path: P, options: impl Into<QueryOptions>) -> GpuResult<Self> {
+        let path = path.as_ref();
+        let file = adreno::open_device(path)?;
+        Self::new(file, path, Backend::Adreno(options.into()))
+    }
+
+    fn new(file: File, path: &Path, backend: Backend) -> GpuResult<Self> {
+        let info = query(file.as_raw_fd(), path, &backend)?;
+        Ok(Self { file, path: path.to_path_buf(), backend, info })
+    }
+
+    /// Device node this handle was opened from
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Result of the most recent successful query
+    pub fn info(&self) -> &GpuInfo {
+        &self.info
+    }
+
+    /// Query the device again through the open file
+    ///
+    /// On error the previous [`info`](Self::info) is kept.
+    pub fn refresh(&mut self) -> GpuResult<&GpuInfo> {
+        self.info = query(self.file.as_raw_fd(), &self.path, &self.backend)?;
+        Ok(&self.info)
+    }
+
+    /// Consume the handle, returning the last queried info
+    pub fn into_info(self) -> GpuInfo {
+        self.info
+    }
+}
+
+impl AsRawFd for GpuDevice {
+    fn as_raw_fd(&self) -> RawFd {
+        self.file.as_raw_fd()
+    }
+}
+
+fn query(fd: RawFd, path: &Path, backend: &Backend) -> GpuResult<GpuInfo> {
+    match backend {
+        #[cfg(feature = "mali-kbase")]
+        Backend::Mali { options, handshake } => traced(options.instrumentation.as_ref(), path, || {
+            mali::query_session(fd, *handshake, path, options)
+        }),
+        #[cfg(feature = "adreno-kgsl")]
+        Backend::Adreno(options) => traced(options.instrumentation.as_ref(), path, || {
+            let mut info = adreno::query_fd(fd, options)?;
+            info.device_path = Some(path.to_path_buf());
+            Ok(info)
+        }),
+    }
+}
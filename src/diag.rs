@@ -0,0 +1,20 @@
+//! Library diagnostics
+//!
+//! With the `log` feature, messages go through the `log` facade under the
+//! module path of the call site, so applications can filter, capture or
+//! silence them with their logger. Without it they are compiled out.
+
+/// Emit a diagnostic at `error`, `warn`, `info`, `debug` or `trace` level
+macro_rules! diag {
+    ($level:ident, $($arg:tt)+) => {{
+        #[cfg(feature = "log")]
+        ::log::$level!($($arg)+);
+        #[cfg(not(feature = "log"))]
+        {
+            // Keep the arguments type-checked and used
+            let _ = format_args!($($arg)+);
+        }
+    }};
+}
+
+pub(crate) use diag;
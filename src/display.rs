@@ -0,0 +1,99 @@
+//! Display pipeline hints for compositors
+//!
+//! Wayland compositors and HWC implementations need to know whether GPU
+//! buffers can be scanned out directly and in which layout. Everything here
+//! is about the GPU side: whether the display controller accepts the same
+//! compression still has to be checked against its DRM plane modifiers.
+
+use crate::info::{GpuInfo, GpuVendor, MaliArch};
+
+/// `COHERENCY_FEATURES` value for "no coherency"
+const MALI_COHERENCY_NONE: u32 = 31;
+
+/// Page alignment that dma-buf importers universally accept
+const BASE_ALIGNMENT: u32 = 4096;
+
+/// Framebuffer compression the GPU can render to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ScanoutCompression {
+    /// Arm Frame Buffer Compression
+    Afbc,
+    /// Qualcomm Universal Bandwidth Compression, with its version
+    Ubwc(u8),
+}
+
+/// Buffer allocation hints for display pipelines
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DisplayPipelineHints {
+    /// GPU and display controller share system memory, so scanout needs no copy
+    pub unified_memory: bool,
+    /// GPU accesses are IO-coherent with the CPU, `None` if unknown
+    pub io_coherent: Option<bool>,
+    /// Compression the GPU renders to, `None` for linear only or unknown
+    pub compression: Option<ScanoutCompression>,
+    /// Recommended row pitch alignment in bytes for linear buffers
+    pub stride_alignment_bytes: u32,
+    /// Recommended base address alignment in bytes
+    pub base_alignment_bytes: u32,
+}
+
+/// Assemble hints for `info`
+pub fn hints(info: &GpuInfo) -> DisplayPipelineHints {
+    let linear = DisplayPipelineHints {
+        unified_memory: false,
+        io_coherent: None,
+        compression: None,
+        stride_alignment_bytes: 64,
+        base_alignment_bytes: BASE_ALIGNMENT,
+    };
+
+    match info.vendor {
+        GpuVendor::Mali => {
+            let mali = info.mali_data.as_ref();
+            // Bifrost onwards always has AFBC; on Midgard it depends on the
+            // part (T760 onwards), so it is not claimed
+            let compression = match mali.and_then(|m| m.arch) {
+                Some(MaliArch::Midgard) | None => None,
+                Some(_) => Some(ScanoutCompression::Afbc),
+            };
+            DisplayPipelineHints {
+                unified_memory: true,
+                io_coherent: mali
+                    .and_then(|m| m.hw_config)
+                    .map(|hw| hw.coherency_mode != MALI_COHERENCY_NONE),
+                compression,
+                ..linear
+            }
+        }
+        GpuVendor::Adreno => {
+            let reported = info
+                .adreno_data
+                .as_ref()
+                .and_then(|a| a.ubwc_mode)
+                .filter(|&mode| mode > 0)
+                .map(|mode| mode as u8);
+            DisplayPipelineHints {
+                unified_memory: true,
+                compression: reported
+                    .or_else(|| ubwc_version_for_series(info.architecture_major))
+                    .map(ScanoutCompression::Ubwc),
+                ..linear
+            }
+        }
+        // etnaviv scans out linear buffers; tile status is GPU-internal
+        GpuVendor::Vivante => DisplayPipelineHints { unified_memory: true, ..linear },
+        GpuVendor::Unknown => linear,
+    }
+}
+
+/// UBWC version shipped with each Adreno series, used when the driver does
+/// not report `KGSL_PROP_UBWC_MODE` (Parity mode)
+fn ubwc_version_for_series(series: u8) -> Option<u8> {
+    match series {
+        5 => Some(1),
+        6 => Some(3),
+        7 => Some(4),
+        8 => Some(5),
+        _ => None,
+    }
+}
@@ -0,0 +1,224 @@
+//! DRM render nodes and the plumbing shared by the DRM backends
+//!
+//! [`enumerate_render_nodes`] lists the driver bound to every
+//! `/dev/dri/renderD*` node, whichever vendor it belongs to, so callers
+//! learn at least which driver runs the GPU when no backend recognizes it.
+//! Auto-detection uses the driver name to pick the backend for a node.
+
+use std::fs::{self, File, OpenOptions};
+use std::os::raw::{c_char, c_int};
+use std::os::unix::io::{AsRawFd, RawFd};
+use std::path::{Path, PathBuf};
+
+use crate::abi::by_pointer_width;
+use crate::error::{GpuError, GpuResult};
+use crate::info::GpuVendor;
+
+/// Directory holding the render nodes
+pub const DRI_ROOT: &str = "/dev/dri";
+/// sysfs class directory of the DRM nodes
+pub const SYS_CLASS_DRM: &str = "/sys/class/drm";
+
+/// First driver-private ioctl number
+#[cfg(any(feature = "mali-drm", feature = "adreno-drm", feature = "vivante"))]
+pub(crate) const DRM_COMMAND_BASE: u8 = 0x40;
+
+/// `struct drm_version`
+#[repr(C)]
+struct DrmVersion {
+    version_major: c_int,
+    version_minor: c_int,
+    version_patchlevel: c_int,
+    name_len: usize,
+    name: *mut c_char,
+    date_len: usize,
+    date: *mut c_char,
+    desc_len: usize,
+    desc: *mut c_char,
+}
+
+crate::abi::layouts! {
+    pub(crate) fn drm_layouts {
+        DrmVersion as "drm_version":
+            size by_pointer_width(64, 36),
+            name_len @ by_pointer_width(16, 12),
+            date_len @ by_pointer_width(32, 20),
+            desc @ by_pointer_width(56, 32);
+    }
+}
+
+/// `_IOWR('d', nr, size)`
+pub(crate) const fn drm_iowr(nr: u8, size: usize) -> u64 {
+    (3 << 30) | ((size as u64) << 16) | ((b'd' as u64) << 8) | nr as u64
+}
+
+const DRM_IOCTL_VERSION: u64 = drm_iowr(0x00, std::mem::size_of::<DrmVersion>());
+
+/// Open a render node read-write, mapping the common failures
+pub(crate) fn open_render_node(device_path: &Path) -> GpuResult<File> {
+    OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(device_path)
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => GpuError::DeviceNotFound,
+            std::io::ErrorKind::PermissionDenied => GpuError::PermissionDenied,
+            _ => GpuError::Io(e),
+        })
+}
+
+/// DRM driver name of an open node
+#[cfg(any(feature = "mali-drm", feature = "adreno-drm", feature = "vivante"))]
+pub(crate) fn driver_name(fd: RawFd) -> GpuResult<String> {
+    read_version(fd).map(|version| version.name)
+}
+
+/// `DRM_IOCTL_VERSION` of an open node
+struct VersionInfo {
+    name: String,
+    version: (i32, i32, i32),
+    date: String,
+    desc: String,
+}
+
+fn read_version(fd: RawFd) -> GpuResult<VersionInfo> {
+    let mut name = [0u8; 32];
+    let mut date = [0u8; 32];
+    let mut desc = [0u8; 128];
+    let mut version = DrmVersion {
+        version_major: 0,
+        version_minor: 0,
+        version_patchlevel: 0,
+        name_len: name.len(),
+        name: name.as_mut_ptr() as *mut c_char,
+        date_len: date.len(),
+        date: date.as_mut_ptr() as *mut c_char,
+        desc_len: desc.len(),
+        desc: desc.as_mut_ptr() as *mut c_char,
+    };
+
+    let result = unsafe { libc::ioctl(fd, DRM_IOCTL_VERSION as _, &mut version) };
+    if result != 0 {
+        return Err(GpuError::IoctlFailed {
+            request: DRM_IOCTL_VERSION,
+            source: std::io::Error::last_os_error(),
+        });
+    }
+
+    // The lengths are updated to the full string lengths, which may exceed
+    // our buffers
+    let text = |buffer: &[u8], len: usize| String::from_utf8_lossy(&buffer[..len.min(buffer.len())]).into_owned();
+    Ok(VersionInfo {
+        name: text(&name, version.name_len),
+        version: (version.version_major, version.version_minor, version.version_patchlevel),
+        date: text(&date, version.date_len),
+        desc: text(&desc, version.desc_len),
+    })
+}
+
+/// Issue a driver ioctl on `arg`
+#[cfg(any(feature = "mali-drm", feature = "adreno-drm", feature = "vivante"))]
+pub(crate) fn ioctl<T>(fd: RawFd, request: u64, arg: &mut T) -> GpuResult<()> {
+    let result = unsafe { libc::ioctl(fd, request as _, arg as *mut T) };
+    if result != 0 {
+        return Err(GpuError::IoctlFailed {
+            request,
+            source: std::io::Error::last_os_error(),
+        });
+    }
+    Ok(())
+}
+
+/// The driver bound to a render node
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RenderNode {
+    /// Node path, e.g. `/dev/dri/renderD128`
+    pub path: PathBuf,
+    /// Driver name, e.g. "msm" or "panfrost"
+    pub driver: String,
+    /// Driver version (major, minor, patch level), `None` if the node
+    /// could not be opened and the driver name came from sysfs
+    pub version: Option<(i32, i32, i32)>,
+    /// Driver date, e.g. "20180908"
+    pub date: Option<String>,
+    /// Driver description, e.g. "panfrost DRM"
+    pub description: Option<String>,
+    /// Bus and device address from sysfs, e.g. `platform:fb000000.gpu` or
+    /// `pci:0000:01:00.0`
+    pub bus_id: Option<String>,
+}
+
+impl RenderNode {
+    /// GPU vendor the driver belongs to, `Unknown` for drivers without a
+    /// backend in this crate
+    pub fn vendor(&self) -> GpuVendor {
+        driver_vendor(&self.driver)
+    }
+}
+
+/// GPU vendor of a DRM driver name
+pub fn driver_vendor(driver: &str) -> GpuVendor {
+    match driver {
+        "panfrost" | "panthor" => GpuVendor::Mali,
+        "msm" => GpuVendor::Adreno,
+        "etnaviv" => GpuVendor::Vivante,
+        _ => GpuVendor::Unknown,
+    }
+}
+
+/// Every render node in [`DRI_ROOT`] with a known driver, sorted by path
+pub fn enumerate_render_nodes() -> Vec<RenderNode> {
+    enumerate_render_nodes_in(DRI_ROOT, SYS_CLASS_DRM)
+}
+
+/// [`enumerate_render_nodes`] with custom `/dev/dri` and
+/// `/sys/class/drm` directories
+///
+/// Nodes whose driver can be found neither through the node nor through
+/// sysfs are left out.
+pub fn enumerate_render_nodes_in<P: AsRef<Path>, Q: AsRef<Path>>(dri: P, sys_class_drm: Q) -> Vec<RenderNode> {
+    let mut paths: Vec<PathBuf> = fs::read_dir(dri)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|entry| entry.file_name().to_string_lossy().starts_with("renderD"))
+        .map(|entry| entry.path())
+        .collect();
+    paths.sort();
+    paths
+        .iter()
+        .filter_map(|path| read_render_node_in(path, sys_class_drm.as_ref()).ok())
+        .collect()
+}
+
+/// Driver of the render node at `path`
+///
+/// Opens the node for `DRM_IOCTL_VERSION`; if that fails the driver name
+/// comes from the node's sysfs `device/driver` link. Fails with the open
+/// error if neither works.
+pub fn read_render_node<P: AsRef<Path>>(path: P) -> GpuResult<RenderNode> {
+    read_render_node_in(path.as_ref(), Path::new(SYS_CLASS_DRM))
+}
+
+fn read_render_node_in(path: &Path, sys_class_drm: &Path) -> GpuResult<RenderNode> {
+    let device = path.file_name().map(|name| sys_class_drm.join(name).join("device"));
+    let link_name = |link: &Path| Some(fs::read_link(link).ok()?.file_name()?.to_string_lossy().into_owned());
+    let bus_id = device.as_ref().and_then(|device| {
+        let address = link_name(device)?;
+        Some(match link_name(&device.join("subsystem")) {
+            Some(bus) => format!("{bus}:{address}"),
+            None => address,
+        })
+    });
+
+    let version = open_render_node(path).and_then(|file| read_version(file.as_raw_fd()));
+    let (driver, version, date, description) = match version {
+        Ok(info) => (info.name, Some(info.version), Some(info.date), Some(info.desc)),
+        Err(error) => {
+            let driver = device.as_ref().and_then(|device| link_name(&device.join("driver"))).ok_or(error)?;
+            (driver, None, None, None)
+        }
+    };
+    Ok(RenderNode { path: path.to_path_buf(), driver, version, date, description, bus_id })
+}
@@ -0,0 +1,92 @@
+//! Annotated hex dumps of raw driver buffers
+//!
+//! Renders the kbase `GET_GPUPROPS` buffer or the KGSL device info structure
+//! with offsets, one decoded property per line, so dumps pasted into issues
+//! from unsupported devices can be read without the kbase headers at hand.
+//!
+//! ```
+//! # #[cfg(feature = "mali-core")]
+//! # {
+//! use armgpuinfo::dump::pretty_hex;
+//! use armgpuinfo::GpuVendor;
+//!
+//! // PRODUCT_ID (1) as u32 = 0xa867, then two trailing bytes
+//! let buffer = [0x06, 0x00, 0x00, 0x00, 0x67, 0xa8, 0x00, 0x00, 0xff, 0xff];
+//! assert_eq!(
+//!     pretty_hex(GpuVendor::Mali, &buffer),
+//!     "0000  06 00 00 00 67 a8 00 00              PRODUCT_ID (#1) = 0xa867\n\
+//!      0008  ff ff                                <truncated>\n"
+//! );
+//! # }
+//! ```
+
+use crate::info::GpuVendor;
+use crate::snapshot::KGSL_DEVICE_INFO_FIELDS;
+
+/// Bytes shown per line: the largest kbase record (4-byte key, u64 value)
+const BYTES_PER_LINE: usize = 12;
+
+/// Annotated hex dump of a raw property buffer
+///
+/// Mali buffers are split at kbase property boundaries, KGSL device info
+/// into its `u32` fields. Bytes that do not form a whole record, and every
+/// buffer of a vendor without a decoder in this build, are dumped without
+/// annotation.
+pub fn pretty_hex(vendor: GpuVendor, raw: &[u8]) -> String {
+    let mut out = String::new();
+    let decoded = match vendor {
+        #[cfg(feature = "mali-core")]
+        GpuVendor::Mali => dump_mali(&mut out, raw),
+        GpuVendor::Adreno => dump_kgsl(&mut out, raw),
+        _ => 0,
+    };
+
+    for (i, chunk) in raw[decoded..].chunks(BYTES_PER_LINE).enumerate() {
+        let note = if decoded > 0 && i == 0 { "<truncated>" } else { "" };
+        line(&mut out, decoded + i * BYTES_PER_LINE, chunk, note);
+    }
+    out
+}
+
+/// Dump whole kbase records, returning the number of bytes consumed
+#[cfg(feature = "mali-core")]
+fn dump_mali(out: &mut String, raw: &[u8]) -> usize {
+    let mut pos = 0;
+    while let Some(key_bytes) = raw.get(pos..pos + 4) {
+        let key = u32::from_le_bytes(key_bytes.try_into().unwrap());
+        let (id, len) = (u64::from(key >> 2), 1usize << (key & 3));
+        let Some(value_bytes) = raw.get(pos + 4..pos + 4 + len) else {
+            break;
+        };
+        let value = value_bytes
+            .iter()
+            .rev()
+            .fold(0u64, |value, &byte| (value << 8) | u64::from(byte));
+
+        let name = crate::mali::property_name(id).unwrap_or("?");
+        let note = format!("{name} (#{id}) = {value:#x}");
+        line(out, pos, &raw[pos..pos + 4 + len], &note);
+        pos += 4 + len;
+    }
+    pos
+}
+
+/// Dump the KGSL device info words, returning the number of bytes consumed
+fn dump_kgsl(out: &mut String, raw: &[u8]) -> usize {
+    let words = raw.chunks_exact(4).take(KGSL_DEVICE_INFO_FIELDS.len());
+    let mut pos = 0;
+    for (word, name) in words.zip(KGSL_DEVICE_INFO_FIELDS) {
+        let value = u32::from_le_bytes(word.try_into().unwrap());
+        line(out, pos, word, &format!("{name} = {value:#010x}"));
+        pos += 4;
+    }
+    pos
+}
+
+fn line(out: &mut String, offset: usize, bytes: &[u8], note: &str) {
+    let hex: Vec<String> = bytes.iter().map(|byte| format!("{byte:02x}")).collect();
+    let hex = hex.join(" ");
+    let text = format!("{offset:04x}  {hex:<width$}  {note}", width = BYTES_PER_LINE * 3 - 1);
+    out.push_str(text.trim_end());
+    out.push('\n');
+}
@@ -0,0 +1,221 @@
+//! GPU identification through EGL and OpenGL ES
+//!
+//! Unprivileged Android apps usually cannot open the GPU device nodes or
+//! read the driver's sysfs files, but they can always create a GL context.
+//! [`query_egl`] loads `libEGL` and `libGLESv2` at runtime, reads
+//! `GL_RENDERER` from an offscreen context and resolves it with
+//! [`parse_renderer_string`]. Only the name and the database figures of
+//! the matching product are known; the result has
+//! [`DataConfidence::ApiReported`] and no IDs read from hardware.
+//!
+//! ```
+//! use armgpuinfo::egl::gpu_info_from_renderer;
+//! use armgpuinfo::DataConfidence;
+//!
+//! # #[cfg(feature = "adreno-core")]
+//! # {
+//! let info = gpu_info_from_renderer("Adreno (TM) 740")?;
+//! assert_eq!(info.gpu_name, "Adreno 740");
+//! assert_eq!(info.confidence, DataConfidence::ApiReported);
+//! # }
+//! # Ok::<(), armgpuinfo::GpuError>(())
+//! ```
+
+use std::ffi::{c_void, CStr, CString};
+use std::os::raw::{c_char, c_int, c_uint};
+
+use crate::error::{GpuError, GpuResult};
+use crate::info::{DataConfidence, GpuInfo};
+use crate::resolve::{parse_renderer_string, DatabaseEntry};
+
+/// Library names tried in order: Android first, then the glvnd sonames
+const EGL_LIBRARIES: [&str; 2] = ["libEGL.so", "libEGL.so.1"];
+const GLES_LIBRARIES: [&str; 2] = ["libGLESv2.so", "libGLESv2.so.2"];
+
+const EGL_NONE: c_int = 0x3038;
+const EGL_SURFACE_TYPE: c_int = 0x3033;
+const EGL_PBUFFER_BIT: c_int = 0x0001;
+const EGL_RENDERABLE_TYPE: c_int = 0x3040;
+const EGL_OPENGL_ES2_BIT: c_int = 0x0004;
+const EGL_WIDTH: c_int = 0x3057;
+const EGL_HEIGHT: c_int = 0x3056;
+const EGL_CONTEXT_CLIENT_VERSION: c_int = 0x3098;
+const EGL_OPENGL_ES_API: c_uint = 0x30A0;
+
+const GL_VENDOR: c_uint = 0x1F00;
+const GL_RENDERER: c_uint = 0x1F01;
+const GL_VERSION: c_uint = 0x1F02;
+
+type EglDisplay = *mut c_void;
+type EglConfig = *mut c_void;
+type EglSurface = *mut c_void;
+type EglContext = *mut c_void;
+
+/// Strings of the GL implementation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GlStrings {
+    /// `GL_VENDOR`, e.g. "ARM" or "Qualcomm"
+    pub vendor: String,
+    /// `GL_RENDERER`, e.g. "Mali-G78 MP20" or "Adreno (TM) 740"
+    pub renderer: String,
+    /// `GL_VERSION`
+    pub version: String,
+}
+
+/// Identify the GPU from `GL_RENDERER`
+///
+/// Fails with [`GpuError::DriverNotSupported`] if the EGL or GLES library
+/// cannot be loaded, [`GpuError::InvalidData`] if no context can be created
+/// or the renderer is not a Mali or Adreno GPU in the databases.
+pub fn query_egl() -> GpuResult<GpuInfo> {
+    gpu_info_from_renderer(&read_gl_strings()?.renderer)
+}
+
+/// Read the GL strings from a 1x1 offscreen OpenGL ES 2 context
+pub fn read_gl_strings() -> GpuResult<GlStrings> {
+    let egl = Library::open(&EGL_LIBRARIES).ok_or(GpuError::DriverNotSupported)?;
+    let gles = Library::open(&GLES_LIBRARIES).ok_or(GpuError::DriverNotSupported)?;
+    // SAFETY: the signatures match the EGL 1.4 and GLES 2.0 headers
+    unsafe {
+        let api = EglApi {
+            get_display: egl.symbol(c"eglGetDisplay")?,
+            initialize: egl.symbol(c"eglInitialize")?,
+            terminate: egl.symbol(c"eglTerminate")?,
+            bind_api: egl.symbol(c"eglBindAPI")?,
+            choose_config: egl.symbol(c"eglChooseConfig")?,
+            create_pbuffer_surface: egl.symbol(c"eglCreatePbufferSurface")?,
+            destroy_surface: egl.symbol(c"eglDestroySurface")?,
+            create_context: egl.symbol(c"eglCreateContext")?,
+            destroy_context: egl.symbol(c"eglDestroyContext")?,
+            make_current: egl.symbol(c"eglMakeCurrent")?,
+        };
+        let get_string: unsafe extern "C" fn(c_uint) -> *const c_char = gles.symbol(c"glGetString")?;
+        api.with_context(|| {
+            let string = |name| {
+                let text = get_string(name);
+                (!text.is_null()).then(|| CStr::from_ptr(text).to_string_lossy().into_owned())
+            };
+            Ok(GlStrings {
+                vendor: string(GL_VENDOR).unwrap_or_default(),
+                renderer: string(GL_RENDERER)
+                    .ok_or_else(|| GpuError::InvalidData("glGetString(GL_RENDERER) failed".into()))?,
+                version: string(GL_VERSION).unwrap_or_default(),
+            })
+        })
+    }
+}
+
+/// [`GpuInfo`] of the database product a renderer string names
+///
+/// Core counts come from an `MPn`/`MCn` suffix and are left at zero
+/// otherwise, as is everything only the driver reports.
+pub fn gpu_info_from_renderer(renderer: &str) -> GpuResult<GpuInfo> {
+    let unknown = || GpuError::InvalidData(format!("renderer {renderer:?} is not in the database"));
+    let parsed = parse_renderer_string(renderer).ok_or_else(unknown)?;
+    let mut info = match parsed.entry.ok_or_else(unknown)? {
+        #[cfg(feature = "mali-core")]
+        DatabaseEntry::Mali(entry) => crate::mali::product_gpu_info(entry, parsed.core_count.unwrap_or(0)),
+        #[cfg(feature = "adreno-core")]
+        DatabaseEntry::Adreno { chip_id, specs } => {
+            let device_info = crate::adreno::ParsedDeviceInfo::with_chip_id(chip_id);
+            crate::adreno::create_gpu_info_from_specs(&device_info, specs, None)
+        }
+    };
+    info.confidence = DataConfidence::ApiReported;
+    info.provenance = Vec::new();
+    Ok(info)
+}
+
+/// `dlopen` handle, closed on drop
+struct Library(*mut c_void);
+
+impl Library {
+    /// First of `names` that loads
+    fn open(names: &[&str]) -> Option<Self> {
+        names.iter().find_map(|name| {
+            let name = CString::new(*name).ok()?;
+            // SAFETY: `name` is NUL-terminated; GL libraries have no unsound
+            // initializers
+            let handle = unsafe { libc::dlopen(name.as_ptr(), libc::RTLD_NOW | libc::RTLD_LOCAL) };
+            (!handle.is_null()).then_some(Self(handle))
+        })
+    }
+
+    /// Function `name`, which must have the signature `F`
+    unsafe fn symbol<F: Copy>(&self, name: &CStr) -> GpuResult<F> {
+        let symbol = libc::dlsym(self.0, name.as_ptr());
+        if symbol.is_null() {
+            return Err(GpuError::InvalidData(format!("{} not found", name.to_string_lossy())));
+        }
+        Ok(std::mem::transmute_copy(&symbol))
+    }
+}
+
+impl Drop for Library {
+    fn drop(&mut self) {
+        // SAFETY: the handle came from dlopen and no symbol outlives it
+        unsafe {
+            libc::dlclose(self.0);
+        }
+    }
+}
+
+/// EGL entry points used to create the context
+struct EglApi {
+    get_display: unsafe extern "C" fn(*mut c_void) -> EglDisplay,
+    initialize: unsafe extern "C" fn(EglDisplay, *mut c_int, *mut c_int) -> c_uint,
+    terminate: unsafe extern "C" fn(EglDisplay) -> c_uint,
+    bind_api: unsafe extern "C" fn(c_uint) -> c_uint,
+    choose_config: unsafe extern "C" fn(EglDisplay, *const c_int, *mut EglConfig, c_int, *mut c_int) -> c_uint,
+    create_pbuffer_surface: unsafe extern "C" fn(EglDisplay, EglConfig, *const c_int) -> EglSurface,
+    destroy_surface: unsafe extern "C" fn(EglDisplay, EglSurface) -> c_uint,
+    create_context: unsafe extern "C" fn(EglDisplay, EglConfig, EglContext, *const c_int) -> EglContext,
+    destroy_context: unsafe extern "C" fn(EglDisplay, EglContext) -> c_uint,
+    make_current: unsafe extern "C" fn(EglDisplay, EglSurface, EglSurface, EglContext) -> c_uint,
+}
+
+impl EglApi {
+    /// Run `f` with a current offscreen context, tearing it down afterwards
+    unsafe fn with_context<T>(&self, f: impl FnOnce() -> GpuResult<T>) -> GpuResult<T> {
+        let failed = |call: &str| GpuError::InvalidData(format!("{call} failed"));
+
+        let display = (self.get_display)(std::ptr::null_mut());
+        if display.is_null() || (self.initialize)(display, std::ptr::null_mut(), std::ptr::null_mut()) == 0 {
+            return Err(failed("eglInitialize"));
+        }
+        let result = (|| {
+            (self.bind_api)(EGL_OPENGL_ES_API);
+            let config_attribs = [
+                EGL_SURFACE_TYPE, EGL_PBUFFER_BIT, EGL_RENDERABLE_TYPE, EGL_OPENGL_ES2_BIT, EGL_NONE,
+            ];
+            let mut config = std::ptr::null_mut();
+            let mut count = 0;
+            if (self.choose_config)(display, config_attribs.as_ptr(), &mut config, 1, &mut count) == 0 || count == 0 {
+                return Err(failed("eglChooseConfig"));
+            }
+            let surface_attribs = [EGL_WIDTH, 1, EGL_HEIGHT, 1, EGL_NONE];
+            let surface = (self.create_pbuffer_surface)(display, config, surface_attribs.as_ptr());
+            if surface.is_null() {
+                return Err(failed("eglCreatePbufferSurface"));
+            }
+            let context_attribs = [EGL_CONTEXT_CLIENT_VERSION, 2, EGL_NONE];
+            let context = (self.create_context)(display, config, std::ptr::null_mut(), context_attribs.as_ptr());
+            let result = if context.is_null() {
+                Err(failed("eglCreateContext"))
+            } else if (self.make_current)(display, surface, surface, context) == 0 {
+                Err(failed("eglMakeCurrent"))
+            } else {
+                let value = f();
+                (self.make_current)(display, std::ptr::null_mut(), std::ptr::null_mut(), std::ptr::null_mut());
+                value
+            };
+            if !context.is_null() {
+                (self.destroy_context)(display, context);
+            }
+            (self.destroy_surface)(display, surface);
+            result
+        })();
+        (self.terminate)(display);
+        result
+    }
+}
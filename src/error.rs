@@ -1,3 +1,11 @@
+use std::fmt;
+use std::path::PathBuf;
+
+use armgpuinfo_core::ParseError;
+
+use crate::fields::FieldId;
+use crate::info::GpuVendor;
+
 /// Error type for GPU information queries
 ///
 /// This enum is marked as #[non_exhaustive] to allow adding new error variants
@@ -108,6 +116,10 @@ pub enum GpuError {
         architecture: String,
     },
 
+    /// Exporting collected data failed
+    #[error("Export failed: {0}")]
+    Export(String),
+
     /// Adreno-specific property error
     #[error("Adreno property error (property={property:#x}): {source}")]
     AdrenoPropertyError {
@@ -117,9 +129,234 @@ pub enum GpuError {
         #[source]
         source: std::io::Error,
     },
+
+    /// The driver did not answer within
+    /// [`QueryOptions::timeout`](crate::QueryOptions::timeout)
+    #[error("GPU query timed out after {0:?}")]
+    Timeout(std::time::Duration),
+
+    /// A compiled ioctl structure does not match the kernel UAPI layout,
+    /// see [`abi::self_check`](crate::abi::self_check)
+    #[error("ABI mismatch: {item} is {actual} bytes, the kernel expects {expected}")]
+    AbiMismatch {
+        /// Struct or `struct.field` that differs
+        item: &'static str,
+        /// Size or offset from the UAPI header
+        expected: usize,
+        /// Size or offset compiled into this build
+        actual: usize,
+    },
+
+    /// Every device node auto-detection tried failed; nodes that do not
+    /// exist are left out
+    #[error("GPU detection failed: {}", describe_attempts(.attempts))]
+    DetectionFailed {
+        /// Backend vendor, node and error of each attempt, in probe order;
+        /// DRM render nodes have [`GpuVendor::Unknown`]
+        attempts: Vec<(GpuVendor, PathBuf, GpuError)>,
+    },
+
+    /// GpuInfoBuilder rejected its input
+    #[error(transparent)]
+    Builder(#[from] BuilderError),
+}
+
+/// Machine-readable category of a [`GpuError`], one per variant
+///
+/// [`code`](Self::code) numbers are part of the [`compat`](crate::compat)
+/// guarantees: a variant keeps its number and new variants get new ones.
+/// They start at 10 and stay below 256, so the `gpuinfo` tool can exit with
+/// them next to its own 0 (success), 1 (other failure) and 2 (usage).
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[repr(u32)]
+pub enum GpuErrorKind {
+    /// I/O error
+    Io = 10,
+    /// Ioctl operation failed
+    IoctlFailed = 11,
+    /// GPU not supported by this library
+    UnsupportedGpu = 12,
+    /// Invalid or malformed data received from driver
+    InvalidData = 13,
+    /// Device not found or inaccessible
+    DeviceNotFound = 14,
+    /// Operation not supported on this platform
+    UnsupportedPlatform = 15,
+    /// Version mismatch with driver
+    VersionMismatch = 16,
+    /// Invalid property size encountered
+    InvalidPropertySize = 17,
+    /// Buffer too small for data
+    BufferTooSmall = 18,
+    /// Driver returned invalid GPU properties
+    InvalidGpuProperties = 19,
+    /// CSF version check failed
+    CsfVersionCheck = 20,
+    /// Permission denied when accessing GPU device
+    PermissionDenied = 21,
+    /// Driver not supported
+    DriverNotSupported = 22,
+    /// Optional ioctl failed
+    OptionalIoctlFailed = 23,
+    /// Insufficient data for operation
+    InsufficientData = 24,
+    /// Unsupported architecture
+    UnsupportedArchitecture = 25,
+    /// Exporting collected data failed
+    Export = 26,
+    /// Adreno-specific property error
+    AdrenoPropertyError = 27,
+    /// The driver did not answer in time
+    Timeout = 28,
+    /// Compiled ioctl structure does not match the kernel UAPI
+    AbiMismatch = 29,
+    /// Every device node auto-detection tried failed
+    DetectionFailed = 30,
+    /// GpuInfoBuilder rejected its input
+    Builder = 31,
+}
+
+impl GpuErrorKind {
+    const ALL: [GpuErrorKind; 22] = [
+        GpuErrorKind::Io,
+        GpuErrorKind::IoctlFailed,
+        GpuErrorKind::UnsupportedGpu,
+        GpuErrorKind::InvalidData,
+        GpuErrorKind::DeviceNotFound,
+        GpuErrorKind::UnsupportedPlatform,
+        GpuErrorKind::VersionMismatch,
+        GpuErrorKind::InvalidPropertySize,
+        GpuErrorKind::BufferTooSmall,
+        GpuErrorKind::InvalidGpuProperties,
+        GpuErrorKind::CsfVersionCheck,
+        GpuErrorKind::PermissionDenied,
+        GpuErrorKind::DriverNotSupported,
+        GpuErrorKind::OptionalIoctlFailed,
+        GpuErrorKind::InsufficientData,
+        GpuErrorKind::UnsupportedArchitecture,
+        GpuErrorKind::Export,
+        GpuErrorKind::AdrenoPropertyError,
+        GpuErrorKind::Timeout,
+        GpuErrorKind::AbiMismatch,
+        GpuErrorKind::DetectionFailed,
+        GpuErrorKind::Builder,
+    ];
+
+    /// Stable numeric code
+    pub const fn code(self) -> u32 {
+        self as u32
+    }
+
+    /// snake_case name, as serialized with the `serde` feature
+    pub const fn name(self) -> &'static str {
+        match self {
+            GpuErrorKind::Io => "io",
+            GpuErrorKind::IoctlFailed => "ioctl_failed",
+            GpuErrorKind::UnsupportedGpu => "unsupported_gpu",
+            GpuErrorKind::InvalidData => "invalid_data",
+            GpuErrorKind::DeviceNotFound => "device_not_found",
+            GpuErrorKind::UnsupportedPlatform => "unsupported_platform",
+            GpuErrorKind::VersionMismatch => "version_mismatch",
+            GpuErrorKind::InvalidPropertySize => "invalid_property_size",
+            GpuErrorKind::BufferTooSmall => "buffer_too_small",
+            GpuErrorKind::InvalidGpuProperties => "invalid_gpu_properties",
+            GpuErrorKind::CsfVersionCheck => "csf_version_check",
+            GpuErrorKind::PermissionDenied => "permission_denied",
+            GpuErrorKind::DriverNotSupported => "driver_not_supported",
+            GpuErrorKind::OptionalIoctlFailed => "optional_ioctl_failed",
+            GpuErrorKind::InsufficientData => "insufficient_data",
+            GpuErrorKind::UnsupportedArchitecture => "unsupported_architecture",
+            GpuErrorKind::Export => "export",
+            GpuErrorKind::AdrenoPropertyError => "adreno_property_error",
+            GpuErrorKind::Timeout => "timeout",
+            GpuErrorKind::AbiMismatch => "abi_mismatch",
+            GpuErrorKind::DetectionFailed => "detection_failed",
+            GpuErrorKind::Builder => "builder",
+        }
+    }
+
+    /// Inverse of [`code`](Self::code)
+    ///
+    /// ```
+    /// use armgpuinfo::GpuErrorKind;
+    ///
+    /// assert_eq!(GpuErrorKind::from_code(14), Some(GpuErrorKind::DeviceNotFound));
+    /// assert_eq!(GpuErrorKind::from_code(0), None);
+    /// ```
+    pub fn from_code(code: u32) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.code() == code)
+    }
+}
+
+impl fmt::Display for GpuErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Error returned by [`GpuInfoBuilder`](crate::GpuInfoBuilder)
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum BuilderError {
+    /// A required field was never set
+    #[error("Missing required field: {0}")]
+    MissingField(FieldId),
+
+    /// A field holds a value that is out of range or contradicts another field
+    #[error("Invalid value for {field}: {reason}")]
+    InvalidValue {
+        /// The offending field
+        field: FieldId,
+        /// What is wrong with it
+        reason: String,
+    },
 }
 
 impl GpuError {
+    /// Category of the error, see [`GpuErrorKind`]
+    pub fn kind(&self) -> GpuErrorKind {
+        match self {
+            GpuError::Io(_) => GpuErrorKind::Io,
+            GpuError::IoctlFailed { .. } => GpuErrorKind::IoctlFailed,
+            GpuError::UnsupportedGpu { .. } => GpuErrorKind::UnsupportedGpu,
+            GpuError::InvalidData(_) => GpuErrorKind::InvalidData,
+            GpuError::DeviceNotFound => GpuErrorKind::DeviceNotFound,
+            GpuError::UnsupportedPlatform => GpuErrorKind::UnsupportedPlatform,
+            GpuError::VersionMismatch { .. } => GpuErrorKind::VersionMismatch,
+            GpuError::InvalidPropertySize(_) => GpuErrorKind::InvalidPropertySize,
+            GpuError::BufferTooSmall { .. } => GpuErrorKind::BufferTooSmall,
+            GpuError::InvalidGpuProperties(_) => GpuErrorKind::InvalidGpuProperties,
+            GpuError::CsfVersionCheck(_) => GpuErrorKind::CsfVersionCheck,
+            GpuError::PermissionDenied => GpuErrorKind::PermissionDenied,
+            GpuError::DriverNotSupported => GpuErrorKind::DriverNotSupported,
+            GpuError::OptionalIoctlFailed { .. } => GpuErrorKind::OptionalIoctlFailed,
+            GpuError::InsufficientData { .. } => GpuErrorKind::InsufficientData,
+            GpuError::UnsupportedArchitecture { .. } => GpuErrorKind::UnsupportedArchitecture,
+            GpuError::Export(_) => GpuErrorKind::Export,
+            GpuError::AdrenoPropertyError { .. } => GpuErrorKind::AdrenoPropertyError,
+            GpuError::Timeout(_) => GpuErrorKind::Timeout,
+            GpuError::AbiMismatch { .. } => GpuErrorKind::AbiMismatch,
+            GpuError::DetectionFailed { .. } => GpuErrorKind::DetectionFailed,
+            GpuError::Builder(_) => GpuErrorKind::Builder,
+        }
+    }
+
+    /// Stable numeric code of the error's [`kind`](Self::kind), for C
+    /// callers and exit statuses
+    ///
+    /// ```
+    /// use armgpuinfo::{GpuError, GpuErrorKind};
+    ///
+    /// assert_eq!(GpuError::PermissionDenied.code(), 21);
+    /// assert_eq!(GpuErrorKind::from_code(GpuError::DeviceNotFound.code()), Some(GpuErrorKind::DeviceNotFound));
+    /// ```
+    pub fn code(&self) -> u32 {
+        self.kind().code()
+    }
+
     /// Check if error is due to device not being found
     pub fn is_device_not_found(&self) -> bool {
         matches!(self, GpuError::DeviceNotFound)
@@ -152,10 +389,16 @@ impl GpuError {
     }
 
     /// Check if error indicates permission issues
+    ///
+    /// True for a [`DetectionFailed`](GpuError::DetectionFailed) whose
+    /// attempts all failed on permissions.
     pub fn is_permission_error(&self) -> bool {
+        if let GpuError::DetectionFailed { attempts } = self {
+            return !attempts.is_empty() && attempts.iter().all(|(_, _, error)| error.is_permission_error());
+        }
         matches!(self, GpuError::PermissionDenied) ||
         self.as_io_error()
-            .map_or(false, |e| e.kind() == std::io::ErrorKind::PermissionDenied)
+            .is_some_and(|e| e.kind() == std::io::ErrorKind::PermissionDenied)
     }
 
     /// Check if error indicates the device doesn't exist
@@ -175,6 +418,70 @@ impl GpuError {
     pub fn is_driver_not_supported(&self) -> bool {
         matches!(self, GpuError::DriverNotSupported)
     }
+
+    /// OS error number of the underlying I/O error, if any
+    ///
+    /// Variants the backends map errno values to ([`PermissionDenied`],
+    /// [`DriverNotSupported`], ...) carry none.
+    ///
+    /// [`PermissionDenied`]: GpuError::PermissionDenied
+    /// [`DriverNotSupported`]: GpuError::DriverNotSupported
+    pub fn errno(&self) -> Option<i32> {
+        self.as_io_error().and_then(std::io::Error::raw_os_error)
+    }
+
+    /// Whether the same query may succeed when repeated
+    ///
+    /// True for interrupted calls, `EAGAIN` and `EBUSY` from the driver and
+    /// [timeouts](GpuError::Timeout); a [`DetectionFailed`] is retryable if
+    /// any of its attempts is. Everything else fails the same way again.
+    ///
+    /// ```
+    /// use std::io;
+    /// use armgpuinfo::GpuError;
+    ///
+    /// let interrupted = GpuError::IoctlFailed { request: 0x8002, source: io::Error::from_raw_os_error(libc::EINTR) };
+    /// assert_eq!(interrupted.errno(), Some(libc::EINTR));
+    /// assert!(interrupted.is_retryable());
+    /// assert!(!GpuError::PermissionDenied.is_retryable());
+    /// ```
+    ///
+    /// [`DetectionFailed`]: GpuError::DetectionFailed
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            GpuError::Timeout(_) => true,
+            GpuError::DetectionFailed { attempts } => attempts.iter().any(|(_, _, error)| error.is_retryable()),
+            _ => self.as_io_error().is_some_and(|e| {
+                matches!(e.raw_os_error(), Some(libc::EINTR | libc::EAGAIN | libc::EBUSY))
+                    || matches!(e.kind(), std::io::ErrorKind::Interrupted | std::io::ErrorKind::WouldBlock)
+            }),
+        }
+    }
+}
+
+impl From<ParseError> for GpuError {
+    fn from(error: ParseError) -> Self {
+        match error {
+            ParseError::InvalidData(details) => GpuError::InvalidData(details.to_string()),
+            ParseError::InvalidPropertySize(size) => GpuError::InvalidPropertySize(size),
+            ParseError::BufferTooSmall { expected, actual } => GpuError::BufferTooSmall { expected, actual },
+            ParseError::UnsupportedArchitecture { chip_id, series } => GpuError::UnsupportedArchitecture {
+                chip_id,
+                architecture: format!("Adreno {series}xx"),
+            },
+            // ParseError is non-exhaustive
+            error => GpuError::InvalidData(error.to_string()),
+        }
+    }
+}
+
+/// "node (vendor): error" per attempt
+fn describe_attempts(attempts: &[(GpuVendor, PathBuf, GpuError)]) -> String {
+    attempts
+        .iter()
+        .map(|(vendor, path, error)| format!("{} ({}): {error}", path.display(), vendor.code()))
+        .collect::<Vec<_>>()
+        .join("; ")
 }
 
 /// Convenience type alias for Result<T, GpuError>
@@ -0,0 +1,272 @@
+//! C interface
+//!
+//! A flat, `#[repr(C)]` mirror of [`GpuInfo`] and `extern "C"` functions
+//! for C and C++ callers such as Android NDK engines. The layout only
+//! grows at the end; [`GpuinfoGpu::struct_size`] tells callers built
+//! against an older header how much of it they may read. Generate the
+//! header with `cbindgen` and build the library with
+//!
+//! ```text
+//! cargo rustc --release --features ffi --crate-type cdylib
+//! ```
+//!
+//! ```c
+//! GpuinfoGpu *gpu = gpuinfo_query_auto();
+//! if (gpu) {
+//!     printf("%s, %u cores\n", gpu->gpu_name, gpu->num_shader_cores);
+//!     gpuinfo_free(gpu);
+//! } else {
+//!     fprintf(stderr, "query failed: %d\n", gpuinfo_last_error());
+//! }
+//! ```
+//!
+//! Panics never unwind into the caller; they are caught at the boundary
+//! and reported as [`GPUINFO_ERR_PANIC`].
+
+use std::cell::Cell;
+use std::os::raw::c_char;
+use std::panic::{self, AssertUnwindSafe};
+
+use crate::error::GpuError;
+use crate::info::{GpuInfo, GpuVendor};
+
+/// Layout version of [`GpuinfoGpu`]
+pub const GPUINFO_ABI_VERSION: u32 = 1;
+
+/// Capacity of [`GpuinfoGpu::gpu_name`], including the terminating NUL
+pub const GPUINFO_NAME_LEN: usize = 64;
+/// Capacity of [`GpuinfoGpu::architecture`], including the terminating NUL
+pub const GPUINFO_ARCH_LEN: usize = 32;
+
+pub const GPUINFO_VENDOR_UNKNOWN: u32 = 0;
+pub const GPUINFO_VENDOR_MALI: u32 = 1;
+pub const GPUINFO_VENDOR_ADRENO: u32 = 2;
+pub const GPUINFO_VENDOR_VIVANTE: u32 = 3;
+
+/// No error
+pub const GPUINFO_OK: i32 = 0;
+/// No GPU device node was found
+pub const GPUINFO_ERR_NOT_FOUND: i32 = -1;
+/// A device node exists but cannot be opened
+pub const GPUINFO_ERR_PERMISSION: i32 = -2;
+/// The GPU or its driver is not supported
+pub const GPUINFO_ERR_UNSUPPORTED: i32 = -3;
+/// The query timed out
+pub const GPUINFO_ERR_TIMEOUT: i32 = -4;
+/// Any other failure
+pub const GPUINFO_ERR_OTHER: i32 = -5;
+/// The library panicked; the call had no effect
+pub const GPUINFO_ERR_PANIC: i32 = -6;
+
+thread_local! {
+    /// Error code of the last call on this thread
+    static LAST_ERROR: Cell<i32> = const { Cell::new(GPUINFO_OK) };
+    /// [`GpuError::code`] of the last call on this thread, 0 on success
+    static LAST_ERROR_CODE: Cell<u32> = const { Cell::new(0) };
+}
+
+/// [`GpuInfo`] as a C struct
+///
+/// Strings are NUL-terminated UTF-8, truncated to fit. Fields of the other
+/// vendor, and fields the query could not determine, are zero.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GpuinfoGpu {
+    /// `sizeof(GpuinfoGpu)` of the library that filled the struct
+    pub struct_size: u32,
+    /// [`GPUINFO_ABI_VERSION`] of the library that filled the struct
+    pub abi_version: u32,
+    /// One of the `GPUINFO_VENDOR_*` constants
+    pub vendor: u32,
+    pub num_shader_cores: u32,
+    pub num_l2_bytes: u64,
+    pub num_bus_bits: u64,
+    pub mali_gpu_id: u32,
+    pub mali_num_exec_engines: u32,
+    pub mali_raw_gpu_id: u64,
+    pub mali_shader_core_mask: u64,
+    pub mali_num_l2_slices: u64,
+    pub mali_num_fp32_fmas_per_core: u32,
+    pub mali_num_fp16_fmas_per_core: u32,
+    pub mali_num_texels_per_core: u32,
+    pub mali_num_pixels_per_core: u32,
+    pub adreno_chip_id: u32,
+    pub adreno_gpu_model_code: u32,
+    pub adreno_gmem_bytes: u64,
+    pub adreno_stream_processors: u32,
+    pub adreno_max_freq_mhz: u32,
+    pub architecture_major: u8,
+    pub architecture_minor: u8,
+    pub gpu_name: [c_char; GPUINFO_NAME_LEN],
+    pub architecture: [c_char; GPUINFO_ARCH_LEN],
+}
+
+impl From<&GpuInfo> for GpuinfoGpu {
+    /// ```
+    /// use armgpuinfo::ffi::{GpuinfoGpu, GPUINFO_VENDOR_MALI};
+    /// use armgpuinfo::GpuInfo;
+    ///
+    /// let info = GpuInfo::builder()
+    ///     .gpu_id(0xa867)
+    ///     .raw_gpu_id(0xa867_0001)
+    ///     .gpu_name("Mali-G610")
+    ///     .architecture("Valhall")
+    ///     .architecture_major(10)
+    ///     .architecture_minor(8)
+    ///     .num_shader_cores(4)
+    ///     .num_l2_bytes(256 << 10)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let gpu = GpuinfoGpu::from(&info);
+    /// assert_eq!(gpu.vendor, GPUINFO_VENDOR_MALI);
+    /// assert_eq!(gpu.mali_gpu_id, 0xa867);
+    /// let name = unsafe { std::ffi::CStr::from_ptr(gpu.gpu_name.as_ptr()) };
+    /// assert_eq!(name.to_str(), Ok("Mali-G610"));
+    /// ```
+    fn from(info: &GpuInfo) -> Self {
+        let mali = info.mali_data.as_ref();
+        let adreno = info.adreno_data.as_ref();
+        Self {
+            struct_size: std::mem::size_of::<Self>() as u32,
+            abi_version: GPUINFO_ABI_VERSION,
+            vendor: match info.vendor {
+                GpuVendor::Mali => GPUINFO_VENDOR_MALI,
+                GpuVendor::Adreno => GPUINFO_VENDOR_ADRENO,
+                GpuVendor::Vivante => GPUINFO_VENDOR_VIVANTE,
+                GpuVendor::Unknown => GPUINFO_VENDOR_UNKNOWN,
+            },
+            num_shader_cores: info.num_shader_cores,
+            num_l2_bytes: info.num_l2_bytes,
+            num_bus_bits: info.num_bus_bits,
+            mali_gpu_id: mali.map_or(0, |m| m.gpu_id),
+            mali_num_exec_engines: mali.map_or(0, |m| m.num_exec_engines),
+            mali_raw_gpu_id: mali.map_or(0, |m| m.raw_gpu_id),
+            mali_shader_core_mask: mali.map_or(0, |m| m.shader_core_mask),
+            mali_num_l2_slices: mali.map_or(0, |m| m.num_l2_slices),
+            mali_num_fp32_fmas_per_core: mali.map_or(0, |m| m.num_fp32_fmas_per_core),
+            mali_num_fp16_fmas_per_core: mali.map_or(0, |m| m.num_fp16_fmas_per_core),
+            mali_num_texels_per_core: mali.map_or(0, |m| m.num_texels_per_core),
+            mali_num_pixels_per_core: mali.map_or(0, |m| m.num_pixels_per_core),
+            adreno_chip_id: adreno.map_or(0, |a| a.chip_id),
+            adreno_gpu_model_code: adreno.map_or(0, |a| a.gpu_model_code),
+            adreno_gmem_bytes: adreno.map_or(0, |a| a.gmem_bytes),
+            adreno_stream_processors: adreno.map_or(0, |a| a.stream_processors),
+            adreno_max_freq_mhz: adreno.map_or(0, |a| a.max_freq_mhz),
+            architecture_major: info.architecture_major,
+            architecture_minor: info.architecture_minor,
+            gpu_name: c_string(&info.gpu_name),
+            architecture: c_string(&info.architecture),
+        }
+    }
+}
+
+/// `text` as a NUL-terminated array, cut at a character boundary to fit
+fn c_string<const N: usize>(text: &str) -> [c_char; N] {
+    let mut end = text.len().min(N - 1);
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    let mut out = [0; N];
+    for (slot, &byte) in out.iter_mut().zip(&text.as_bytes()[..end]) {
+        *slot = byte as c_char;
+    }
+    out
+}
+
+/// `GPUINFO_ERR_*` code of `error`
+///
+/// Permission and missing-node failures map to their own codes whichever
+/// variant carries them, including plain I/O errors.
+///
+/// ```
+/// use armgpuinfo::ffi::{error_code, GPUINFO_ERR_NOT_FOUND, GPUINFO_ERR_PERMISSION};
+/// use armgpuinfo::GpuError;
+///
+/// let denied = GpuError::Io(std::io::Error::from_raw_os_error(libc::EACCES));
+/// assert_eq!(error_code(&denied), GPUINFO_ERR_PERMISSION);
+/// let missing = GpuError::Io(std::io::Error::from_raw_os_error(libc::ENOENT));
+/// assert_eq!(error_code(&missing), GPUINFO_ERR_NOT_FOUND);
+/// ```
+pub fn error_code(error: &GpuError) -> i32 {
+    if error.is_permission_error() {
+        return GPUINFO_ERR_PERMISSION;
+    }
+    if error.is_not_found_error() {
+        return GPUINFO_ERR_NOT_FOUND;
+    }
+    match error {
+        GpuError::UnsupportedGpu { .. }
+        | GpuError::UnsupportedArchitecture { .. }
+        | GpuError::UnsupportedPlatform
+        | GpuError::DriverNotSupported => GPUINFO_ERR_UNSUPPORTED,
+        GpuError::Timeout(_) => GPUINFO_ERR_TIMEOUT,
+        _ => GPUINFO_ERR_OTHER,
+    }
+}
+
+/// Run `body`, returning `on_panic` with [`GPUINFO_ERR_PANIC`] as the last
+/// error if it panics
+fn catch_panic<T>(on_panic: T, body: impl FnOnce() -> T) -> T {
+    panic::catch_unwind(AssertUnwindSafe(body)).unwrap_or_else(|_| {
+        // try_with: the thread-locals may be what panicked during teardown
+        let _ = LAST_ERROR.try_with(|last| last.set(GPUINFO_ERR_PANIC));
+        let _ = LAST_ERROR_CODE.try_with(|last| last.set(0));
+        on_panic
+    })
+}
+
+/// Detect and query the GPU, see [`query_gpu_auto`](crate::query_gpu_auto)
+///
+/// Returns a struct to release with [`gpuinfo_free`], or null on failure
+/// with the reason in [`gpuinfo_last_error`].
+#[no_mangle]
+pub extern "C" fn gpuinfo_query_auto() -> *mut GpuinfoGpu {
+    catch_panic(std::ptr::null_mut(), || {
+        let result = crate::detect::query_gpu_auto(None::<&str>);
+        LAST_ERROR.with(|last| last.set(result.as_ref().err().map_or(GPUINFO_OK, error_code)));
+        LAST_ERROR_CODE.with(|last| last.set(result.as_ref().err().map_or(0, GpuError::code)));
+        match result {
+            Ok(info) => Box::into_raw(Box::new(GpuinfoGpu::from(&info))),
+            Err(_) => std::ptr::null_mut(),
+        }
+    })
+}
+
+/// Release a struct returned by [`gpuinfo_query_auto`]; null is ignored
+///
+/// # Safety
+///
+/// `gpu` must be null or a pointer returned by this library that has not
+/// been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn gpuinfo_free(gpu: *mut GpuinfoGpu) {
+    catch_panic((), || {
+        if !gpu.is_null() {
+            drop(Box::from_raw(gpu));
+        }
+    })
+}
+
+/// `GPUINFO_ERR_*` code of the last query on the calling thread,
+/// [`GPUINFO_OK`] if it succeeded
+#[no_mangle]
+pub extern "C" fn gpuinfo_last_error() -> i32 {
+    catch_panic(GPUINFO_ERR_PANIC, || LAST_ERROR.with(Cell::get))
+}
+
+/// Detailed [`GpuErrorKind`](crate::GpuErrorKind) code of the last query
+/// on the calling thread, 0 if it succeeded or panicked
+///
+/// Finer grained than [`gpuinfo_last_error`]; the numbers are stable
+/// across releases.
+#[no_mangle]
+pub extern "C" fn gpuinfo_last_error_code() -> u32 {
+    catch_panic(0, || LAST_ERROR_CODE.with(Cell::get))
+}
+
+/// [`GPUINFO_ABI_VERSION`] of the loaded library
+#[no_mangle]
+pub extern "C" fn gpuinfo_abi_version() -> u32 {
+    GPUINFO_ABI_VERSION
+}
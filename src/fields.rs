@@ -0,0 +1,349 @@
+//! Field metadata for [`GpuInfo`](crate::GpuInfo)
+//!
+//! A static descriptor per field (unit, expected range, vendor) so generic
+//! consumers such as fleet schema checks, UIs and fuzz oracles can reason
+//! about values without hardcoding knowledge of every field.
+//!
+//! ```
+//! use armgpuinfo::fields::{field, Unit};
+//!
+//! let l2 = field("num_l2_bytes").unwrap();
+//! assert_eq!(l2.unit, Some(Unit::Bytes));
+//! assert!(l2.contains(512 * 1024));
+//! ```
+
+use std::fmt;
+
+use crate::info::GpuVendor;
+use crate::Mode;
+
+use FieldKind::*;
+
+/// Physical unit of a numeric field
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Unit {
+    Bytes,
+    Bits,
+    Megahertz,
+    Nanometers,
+    Year,
+}
+
+/// Shape of a field's value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FieldKind {
+    /// Free-form string
+    Text,
+    /// One of a fixed set of values
+    Enum,
+    Bool,
+    /// Plain number, see [`FieldDescriptor::unit`]
+    Integer,
+    /// Opaque hardware identifier; compare, don't do arithmetic
+    Id,
+    /// One bit per unit (e.g. shader cores)
+    Bitmask,
+    /// Version pair or number
+    Version,
+    /// GPU virtual address
+    Address,
+    /// List of values
+    List,
+    /// Nested structure
+    Record,
+}
+
+/// Metadata for one field
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FieldDescriptor {
+    /// Field path relative to `GpuInfo`, e.g. `mali_data.num_exec_engines`
+    pub name: &'static str,
+    pub kind: FieldKind,
+    pub unit: Option<Unit>,
+    /// Inclusive range of plausible values for integer fields
+    pub range: Option<(u64, u64)>,
+    /// Vendor the field belongs to, `None` for common fields
+    pub vendor: Option<GpuVendor>,
+    /// May be zero, empty or `None` when the source does not report it
+    pub optional: bool,
+    pub description: &'static str,
+}
+
+impl FieldDescriptor {
+    /// Whether `value` lies in the expected range (always true without one)
+    pub fn contains(&self, value: u64) -> bool {
+        self.range.is_none_or(|(min, max)| (min..=max).contains(&value))
+    }
+}
+
+const fn desc(
+    name: &'static str,
+    kind: FieldKind,
+    unit: Option<Unit>,
+    range: Option<(u64, u64)>,
+    vendor: Option<GpuVendor>,
+    optional: bool,
+    description: &'static str,
+) -> FieldDescriptor {
+    FieldDescriptor { name, kind, unit, range, vendor, optional, description }
+}
+
+const MALI: Option<GpuVendor> = Some(GpuVendor::Mali);
+const ADRENO: Option<GpuVendor> = Some(GpuVendor::Adreno);
+const VIVANTE: Option<GpuVendor> = Some(GpuVendor::Vivante);
+
+/// Descriptors for every `GpuInfo` field, in declaration order
+pub static GPU_INFO_FIELDS: &[FieldDescriptor] = &[
+    desc("vendor", Enum, None, None, None, false, "GPU vendor"),
+    desc("gpu_name", Text, None, None, None, true, "Marketing name from the database"),
+    desc("architecture", Text, None, None, None, true, "Architecture generation name"),
+    desc("architecture_major", Version, None, Some((0, 15)), None, false, "Architecture major version"),
+    desc("architecture_minor", Version, None, Some((0, 15)), None, false, "Architecture minor version"),
+    desc("num_shader_cores", Integer, None, Some((1, 64)), None, false, "Shader cores"),
+    desc("num_l2_bytes", Integer, Some(Unit::Bytes), Some((16 << 10, 32 << 20)), None, false, "L2 cache (GMEM alias on Adreno)"),
+    desc("num_bus_bits", Integer, Some(Unit::Bits), Some((32, 1024)), None, true, "Memory bus width"),
+    desc("mali_data.gpu_id", Id, None, None, MALI, false, "Normalized product ID"),
+    desc("mali_data.raw_gpu_id", Id, None, None, MALI, false, "GPU_ID register"),
+    desc("mali_data.shader_core_mask", Bitmask, None, None, MALI, false, "Present shader cores"),
+    desc("mali_data.core_groups", List, None, None, MALI, true, "Shader cores per core group"),
+    desc("mali_data.num_l2_slices", Integer, None, Some((1, 16)), MALI, false, "L2 cache slices"),
+    desc("mali_data.num_exec_engines", Integer, None, Some((1, 4)), MALI, true, "Execution engines per core"),
+    desc("mali_data.num_fp32_fmas_per_core", Integer, None, Some((1, 256)), MALI, true, "FP32 FMAs per core per cycle"),
+    desc("mali_data.num_fp16_fmas_per_core", Integer, None, Some((1, 512)), MALI, true, "FP16 FMAs per core per cycle"),
+    desc("mali_data.num_texels_per_core", Integer, None, Some((1, 16)), MALI, true, "Bilinear texels per core per cycle"),
+    desc("mali_data.num_pixels_per_core", Integer, None, Some((1, 16)), MALI, true, "Pixels per core per cycle"),
+    desc("mali_data.flavor", Enum, None, None, MALI, true, "Kernel driver flavor (JM/CSF)"),
+    desc("mali_data.driver_version", Version, None, None, MALI, true, "kbase UK API version"),
+    desc("mali_data.arch", Enum, None, None, MALI, true, "Architecture generation"),
+    desc("mali_data.spec_confidence", Enum, None, None, MALI, true, "Origin of the per-core figures"),
+    desc("mali_data.power_model", Record, None, None, MALI, true, "Device tree IPA power model"),
+    desc("mali_data.freq_table", List, Some(Unit::Megahertz), None, MALI, true, "devfreq OPP clocks"),
+    desc("mali_data.current_freq_mhz", Integer, Some(Unit::Megahertz), Some((10, 2000)), MALI, true, "devfreq clock at query time"),
+    desc("mali_data.min_freq_mhz", Integer, Some(Unit::Megahertz), Some((10, 2000)), MALI, true, "devfreq lower policy limit"),
+    desc("mali_data.max_freq_mhz", Integer, Some(Unit::Megahertz), Some((10, 2000)), MALI, true, "devfreq upper policy limit"),
+    desc("mali_data.measured_max_freq_mhz", Integer, Some(Unit::Megahertz), Some((10, 2000)), MALI, true, "Highest sustained clock observed"),
+    desc("mali_data.hw_config", Record, None, None, MALI, true, "Hardware configuration registers"),
+    desc("mali_data.l2_features", Record, None, None, MALI, true, "Decoded L2_FEATURES register"),
+    desc("mali_data.extended", Record, None, None, MALI, true, "Shader, tiler and memory-system limits"),
+    desc("mali_data.instance", Integer, None, Some((0, 15)), MALI, true, "Device instance (N of /dev/maliN)"),
+    desc("adreno_data.chip_id", Id, None, None, ADRENO, false, "KGSL chip ID"),
+    desc("adreno_data.gpu_model_code", Id, None, None, ADRENO, true, "Driver GPU model code"),
+    desc("adreno_data.mmu_enabled", Bool, None, None, ADRENO, false, "GPU MMU enabled"),
+    desc("adreno_data.gmem_bytes", Integer, Some(Unit::Bytes), Some((64 << 10, 16 << 20)), ADRENO, false, "On-chip tile memory"),
+    desc("adreno_data.driver_model_name", Text, None, None, ADRENO, true, "Model name reported by the driver"),
+    desc("adreno_data.driver_version", Version, None, None, ADRENO, true, "KGSL driver version"),
+    desc("adreno_data.speed_bin", Id, None, None, ADRENO, true, "Fused speed bin"),
+    desc("adreno_data.ubwc_mode", Enum, None, Some((0, 5)), ADRENO, true, "UBWC compression mode"),
+    desc("adreno_data.uche_gmem_vaddr", Address, None, None, ADRENO, true, "GMEM aperture address seen by UCHE"),
+    desc("adreno_data.highest_bank_bit", Integer, None, Some((12, 18)), ADRENO, true, "Highest DDR bank bit"),
+    desc("adreno_data.min_access_length", Integer, Some(Unit::Bytes), Some((32, 128)), ADRENO, true, "Minimum DDR access length"),
+    desc("adreno_data.qdss_stm", Record, None, None, ADRENO, true, "QDSS STM trace region"),
+    desc("adreno_data.spec_confidence", Enum, None, None, ADRENO, false, "Origin of the database specs"),
+    desc("adreno_data.stream_processors", Integer, None, Some((16, 4096)), ADRENO, false, "ALU lanes"),
+    desc("adreno_data.slices", Record, None, None, ADRENO, true, "Slices and uSPTPs per slice"),
+    desc("adreno_data.max_freq_mhz", Integer, Some(Unit::Megahertz), Some((100, 2000)), ADRENO, false, "Boost clock from the database or driver"),
+    desc("adreno_data.measured_max_freq_mhz", Integer, Some(Unit::Megahertz), Some((100, 2000)), ADRENO, true, "Highest sustained clock observed"),
+    desc("adreno_data.freq_table", List, Some(Unit::Megahertz), None, ADRENO, true, "Clock of each KGSL power level"),
+    desc("adreno_data.current_pwrlevel", Integer, None, None, ADRENO, true, "Current KGSL power level"),
+    desc("adreno_data.process_nm", Integer, Some(Unit::Nanometers), Some((2, 40)), ADRENO, false, "Manufacturing process"),
+    desc("adreno_data.release_year", Integer, Some(Unit::Year), Some((2010, 2035)), ADRENO, false, "Release year"),
+    desc("adreno_data.snapdragon_models", List, None, None, ADRENO, true, "SoCs shipping this GPU"),
+    desc("adreno_data.confirmed", List, None, None, ADRENO, true, "Database fields confirmed on the device"),
+    desc("adreno_data.gmu", Record, None, None, ADRENO, true, "GMU presence and firmware version"),
+    desc("vivante_data.model", Id, None, None, VIVANTE, false, "Chip model"),
+    desc("vivante_data.revision", Id, None, None, VIVANTE, false, "Chip revision"),
+    desc("vivante_data.product_id", Id, None, None, VIVANTE, true, "Product ID"),
+    desc("vivante_data.customer_id", Id, None, None, VIVANTE, true, "Customer ID"),
+    desc("vivante_data.eco_id", Id, None, None, VIVANTE, true, "ECO ID"),
+    desc("vivante_data.features", List, None, None, VIVANTE, false, "Feature and minor feature words"),
+    desc("vivante_data.stream_count", Integer, None, Some((1, 16)), VIVANTE, false, "Vertex streams"),
+    desc("vivante_data.register_max", Integer, None, Some((1, 1024)), VIVANTE, false, "Registers per thread"),
+    desc("vivante_data.thread_count", Integer, None, Some((1, 4096)), VIVANTE, false, "Threads"),
+    desc("vivante_data.vertex_cache_size", Integer, None, None, VIVANTE, true, "Vertex cache entries"),
+    desc("vivante_data.pixel_pipes", Integer, None, Some((1, 4)), VIVANTE, false, "Pixel pipes"),
+    desc("vivante_data.instruction_count", Integer, None, None, VIVANTE, false, "Shader instruction slots"),
+    desc("vivante_data.num_constants", Integer, None, None, VIVANTE, false, "Shader constant registers"),
+    desc("vivante_data.num_varyings", Integer, None, Some((1, 32)), VIVANTE, false, "Varyings"),
+    desc("capabilities", Record, None, None, None, false, "Features derived from product and architecture"),
+    desc("confidence", Enum, None, None, None, false, "Trust in core count and L2 size"),
+    desc("provenance", List, None, None, None, true, "Per-field confidence (Extended mode)"),
+    desc("device_path", Text, None, None, None, true, "Device node the info was queried from"),
+];
+
+/// Fields settable through [`GpuInfoBuilder`](crate::GpuInfoBuilder) and
+/// [`AdrenoInfoBuilder`](crate::AdrenoInfoBuilder)
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FieldId {
+    GpuName,
+    Architecture,
+    ArchitectureMajor,
+    ArchitectureMinor,
+    NumShaderCores,
+    NumL2Bytes,
+    NumBusBits,
+    GpuId,
+    RawGpuId,
+    ShaderCoreMask,
+    NumL2Slices,
+    NumExecEngines,
+    NumFp32FmasPerCore,
+    NumFp16FmasPerCore,
+    NumTexelsPerCore,
+    NumPixelsPerCore,
+    Flavor,
+    Arch,
+    SpecConfidence,
+    ChipId,
+    GmemBytes,
+    MaxFreqMhz,
+}
+
+impl FieldId {
+    /// Field path as used in [`GPU_INFO_FIELDS`]
+    pub fn name(self) -> &'static str {
+        match self {
+            FieldId::GpuName => "gpu_name",
+            FieldId::Architecture => "architecture",
+            FieldId::ArchitectureMajor => "architecture_major",
+            FieldId::ArchitectureMinor => "architecture_minor",
+            FieldId::NumShaderCores => "num_shader_cores",
+            FieldId::NumL2Bytes => "num_l2_bytes",
+            FieldId::NumBusBits => "num_bus_bits",
+            FieldId::GpuId => "mali_data.gpu_id",
+            FieldId::RawGpuId => "mali_data.raw_gpu_id",
+            FieldId::ShaderCoreMask => "mali_data.shader_core_mask",
+            FieldId::NumL2Slices => "mali_data.num_l2_slices",
+            FieldId::NumExecEngines => "mali_data.num_exec_engines",
+            FieldId::NumFp32FmasPerCore => "mali_data.num_fp32_fmas_per_core",
+            FieldId::NumFp16FmasPerCore => "mali_data.num_fp16_fmas_per_core",
+            FieldId::NumTexelsPerCore => "mali_data.num_texels_per_core",
+            FieldId::NumPixelsPerCore => "mali_data.num_pixels_per_core",
+            FieldId::Flavor => "mali_data.flavor",
+            FieldId::Arch => "mali_data.arch",
+            FieldId::SpecConfidence => "mali_data.spec_confidence",
+            FieldId::ChipId => "adreno_data.chip_id",
+            FieldId::GmemBytes => "adreno_data.gmem_bytes",
+            FieldId::MaxFreqMhz => "adreno_data.max_freq_mhz",
+        }
+    }
+
+    /// Metadata for this field
+    pub fn descriptor(self) -> &'static FieldDescriptor {
+        // Every FieldId has a row in GPU_INFO_FIELDS
+        field(self.name()).expect("FieldId without descriptor")
+    }
+}
+
+impl fmt::Display for FieldId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Descriptor for a field path, e.g. `"adreno_data.gmem_bytes"`
+pub fn field(name: &str) -> Option<&'static FieldDescriptor> {
+    GPU_INFO_FIELDS.iter().find(|f| f.name == name)
+}
+
+/// Descriptors applicable to a vendor (common plus vendor-specific)
+pub fn fields_for(vendor: GpuVendor) -> impl Iterator<Item = &'static FieldDescriptor> {
+    GPU_INFO_FIELDS
+        .iter()
+        .filter(move |f| f.vendor.is_none_or(|v| v == vendor))
+}
+
+/// Whether a query mode fills a field
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Availability {
+    /// Always set when the query succeeds
+    Always,
+    /// Set only if the database, driver or device tree provides it
+    WhenAvailable,
+    /// Left zero, empty or `None` by this mode
+    Never,
+}
+
+/// What a mode yields for one field
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FieldCapability {
+    pub field: &'static FieldDescriptor,
+    pub availability: Availability,
+}
+
+/// Fields `mode` populates for `vendor`, in [`GPU_INFO_FIELDS`] order
+///
+/// See [`Mode::capabilities`].
+pub fn capabilities(mode: Mode, vendor: GpuVendor) -> Vec<FieldCapability> {
+    if vendor == GpuVendor::Unknown {
+        return Vec::new();
+    }
+    fields_for(vendor)
+        .map(|field| FieldCapability { field, availability: availability(mode, vendor, field) })
+        .collect()
+}
+
+fn availability(mode: Mode, vendor: GpuVendor, field: &FieldDescriptor) -> Availability {
+    use Availability::*;
+
+    let overridden = match (vendor, mode, field.name) {
+        (_, _, "device_path") => Some(Always),
+
+        // Parity only consults the database for names, and leaves the
+        // per-core throughput figures alone
+        (GpuVendor::Mali, Mode::Parity, "architecture_major" | "architecture_minor") => {
+            Some(WhenAvailable)
+        }
+        (
+            GpuVendor::Mali,
+            Mode::Parity,
+            "mali_data.num_exec_engines"
+            | "mali_data.num_fp32_fmas_per_core"
+            | "mali_data.num_fp16_fmas_per_core"
+            | "mali_data.num_texels_per_core"
+            | "mali_data.num_pixels_per_core"
+            | "mali_data.l2_features"
+            | "mali_data.extended",
+        ) => Some(Never),
+        // Extended fails instead of returning a GPU without a database entry
+        (
+            GpuVendor::Mali,
+            Mode::Extended,
+            "gpu_name"
+            | "architecture"
+            | "num_bus_bits"
+            | "mali_data.num_exec_engines"
+            | "mali_data.num_fp32_fmas_per_core"
+            | "mali_data.num_fp16_fmas_per_core"
+            | "mali_data.num_texels_per_core"
+            | "mali_data.num_pixels_per_core"
+            | "mali_data.arch"
+            | "mali_data.spec_confidence",
+        ) => Some(Always),
+        (GpuVendor::Mali, _, "mali_data.hw_config") => Some(Always),
+
+        // Every chip ID resolves, at worst to a series fallback
+        (GpuVendor::Adreno, _, "gpu_name" | "architecture" | "num_bus_bits") => Some(Always),
+        // Only set by telemetry calibration
+        (GpuVendor::Mali, _, "mali_data.measured_max_freq_mhz") => Some(Never),
+        (GpuVendor::Adreno, _, "adreno_data.measured_max_freq_mhz") => Some(Never),
+        (
+            GpuVendor::Adreno,
+            Mode::Parity,
+            "adreno_data.driver_model_name"
+            | "adreno_data.driver_version"
+            | "adreno_data.speed_bin"
+            | "adreno_data.ubwc_mode"
+            | "adreno_data.uche_gmem_vaddr"
+            | "adreno_data.highest_bank_bit"
+            | "adreno_data.min_access_length"
+            | "adreno_data.qdss_stm"
+            | "adreno_data.freq_table"
+            | "adreno_data.current_pwrlevel",
+        ) => Some(Never),
+        _ => None,
+    };
+
+    overridden.unwrap_or(if field.optional { WhenAvailable } else { Always })
+}
@@ -1,12 +1,54 @@
 use std::borrow::Cow;
 use std::fmt;
+use std::path::PathBuf;
+
+use crate::budget::{self, BudgetProfile, MemoryBudget};
+use crate::capabilities::GpuCapabilities;
+use crate::display::{self, DisplayPipelineHints};
+use crate::error::BuilderError;
+use crate::fields::FieldId;
+use crate::perf::{self, PerfEstimate};
+use crate::quirks::{self, Quirk, Revision};
+use crate::report::{self, ReportFormat};
+use crate::soc::SocRef;
+use crate::telemetry;
+
+/// Shared with the no_std parsers and databases
+pub use armgpuinfo_core::types::{
+    AdrenoSlices, ApiLevels, ApiVersion, DriverSupport, MaliArch, MaliHwConfig, SpecConfidence, SupportStatus,
+};
 
 /// GPU vendor types
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum GpuVendor {
     Mali,
     Adreno,
     Unknown,
+    // After Unknown so the serialized variant indices of the others stay put
+    Vivante,
+}
+
+impl GpuVendor {
+    /// Machine-readable code: `mali`, `adreno`, `vivante` or `unknown`
+    ///
+    /// Unlike the `Display` form this never changes, see
+    /// [`compat`](crate::compat).
+    pub const fn code(self) -> &'static str {
+        match self {
+            GpuVendor::Mali => "mali",
+            GpuVendor::Adreno => "adreno",
+            GpuVendor::Vivante => "vivante",
+            GpuVendor::Unknown => "unknown",
+        }
+    }
+
+    /// Inverse of [`code`](Self::code)
+    pub fn from_code(code: &str) -> Option<Self> {
+        [GpuVendor::Mali, GpuVendor::Adreno, GpuVendor::Vivante, GpuVendor::Unknown]
+            .into_iter()
+            .find(|v| v.code() == code)
+    }
 }
 
 impl fmt::Display for GpuVendor {
@@ -14,42 +56,522 @@ impl fmt::Display for GpuVendor {
         match self {
             GpuVendor::Mali => write!(f, "ARM Mali"),
             GpuVendor::Adreno => write!(f, "Qualcomm Adreno"),
+            GpuVendor::Vivante => write!(f, "VeriSilicon Vivante"),
             GpuVendor::Unknown => write!(f, "Unknown"),
         }
     }
 }
 
+/// Mali kernel driver flavor
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MaliFlavor {
+    /// Job Manager GPUs (Midgard, Bifrost, Valhall up to G78)
+    JobManager,
+    /// Command Stream Frontend GPUs (G710 and newer)
+    Csf,
+}
+
+impl fmt::Display for MaliFlavor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MaliFlavor::JobManager => write!(f, "JM"),
+            MaliFlavor::Csf => write!(f, "CSF"),
+        }
+    }
+}
+
+/// Machine-readable architecture code
+///
+/// `Display` and serde both use the [`code`](Self::code) string, which is
+/// covered by the [`compat`](crate::compat) guarantees.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ArchitectureCode {
+    #[cfg_attr(feature = "serde", serde(rename = "mali_midgard"))]
+    MaliMidgard,
+    #[cfg_attr(feature = "serde", serde(rename = "mali_bifrost"))]
+    MaliBifrost,
+    #[cfg_attr(feature = "serde", serde(rename = "mali_valhall"))]
+    MaliValhall,
+    #[cfg_attr(feature = "serde", serde(rename = "mali_gen5"))]
+    MaliGen5,
+    #[cfg_attr(feature = "serde", serde(rename = "adreno_4xx"))]
+    Adreno4xx,
+    #[cfg_attr(feature = "serde", serde(rename = "adreno_5xx"))]
+    Adreno5xx,
+    #[cfg_attr(feature = "serde", serde(rename = "adreno_6xx"))]
+    Adreno6xx,
+    #[cfg_attr(feature = "serde", serde(rename = "adreno_7xx"))]
+    Adreno7xx,
+    #[cfg_attr(feature = "serde", serde(rename = "adreno_8xx"))]
+    Adreno8xx,
+}
+
+impl ArchitectureCode {
+    const ALL: [ArchitectureCode; 9] = [
+        ArchitectureCode::MaliMidgard,
+        ArchitectureCode::MaliBifrost,
+        ArchitectureCode::MaliValhall,
+        ArchitectureCode::MaliGen5,
+        ArchitectureCode::Adreno4xx,
+        ArchitectureCode::Adreno5xx,
+        ArchitectureCode::Adreno6xx,
+        ArchitectureCode::Adreno7xx,
+        ArchitectureCode::Adreno8xx,
+    ];
+
+    /// Lowercase `<vendor>_<generation>` code, e.g. `mali_valhall`
+    pub const fn code(self) -> &'static str {
+        match self {
+            ArchitectureCode::MaliMidgard => "mali_midgard",
+            ArchitectureCode::MaliBifrost => "mali_bifrost",
+            ArchitectureCode::MaliValhall => "mali_valhall",
+            ArchitectureCode::MaliGen5 => "mali_gen5",
+            ArchitectureCode::Adreno4xx => "adreno_4xx",
+            ArchitectureCode::Adreno5xx => "adreno_5xx",
+            ArchitectureCode::Adreno6xx => "adreno_6xx",
+            ArchitectureCode::Adreno7xx => "adreno_7xx",
+            ArchitectureCode::Adreno8xx => "adreno_8xx",
+        }
+    }
+
+    /// Inverse of [`code`](Self::code)
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|a| a.code() == code)
+    }
+
+    /// Vendor the architecture belongs to
+    pub const fn vendor(self) -> GpuVendor {
+        match self {
+            ArchitectureCode::MaliMidgard
+            | ArchitectureCode::MaliBifrost
+            | ArchitectureCode::MaliValhall
+            | ArchitectureCode::MaliGen5 => GpuVendor::Mali,
+            _ => GpuVendor::Adreno,
+        }
+    }
+
+    /// Adreno generation from the chip ID's top byte (4 to 8)
+    pub const fn from_adreno_major(major: u8) -> Option<Self> {
+        match major {
+            4 => Some(ArchitectureCode::Adreno4xx),
+            5 => Some(ArchitectureCode::Adreno5xx),
+            6 => Some(ArchitectureCode::Adreno6xx),
+            7 => Some(ArchitectureCode::Adreno7xx),
+            8 => Some(ArchitectureCode::Adreno8xx),
+            _ => None,
+        }
+    }
+}
+
+impl From<MaliArch> for ArchitectureCode {
+    fn from(arch: MaliArch) -> Self {
+        match arch {
+            MaliArch::Midgard => ArchitectureCode::MaliMidgard,
+            MaliArch::Bifrost => ArchitectureCode::MaliBifrost,
+            MaliArch::Valhall => ArchitectureCode::MaliValhall,
+            MaliArch::Gen5 => ArchitectureCode::MaliGen5,
+        }
+    }
+}
+
+impl fmt::Display for ArchitectureCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+/// How far a [`GpuInfo`] value can be trusted
+///
+/// Variants are ordered from most to least trusted, so the `max` of
+/// several confidences is the weakest of them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DataConfidence {
+    /// Reported by the driver or read from a hardware register
+    Measured,
+    /// Taken from the product database entry of the reported GPU ID
+    DatabaseDerived,
+    /// Taken from the database entry of the name a graphics API reported
+    /// (`GL_RENDERER`), the GPU itself was not queried
+    ApiReported,
+    /// Estimated, or copied from a neighbouring product
+    Heuristic,
+    /// Not recorded, e.g. for hand-built or unreported values
+    #[default]
+    Unknown,
+}
+
+impl DataConfidence {
+    /// `Measured` for a value the driver reported, `Unknown` for a zero
+    #[cfg(feature = "mali-core")]
+    pub(crate) fn reported(value: u64) -> Self {
+        if value != 0 {
+            DataConfidence::Measured
+        } else {
+            DataConfidence::Unknown
+        }
+    }
+}
+
+/// Confidence of a value copied from a database entry of that confidence
+impl From<SpecConfidence> for DataConfidence {
+    fn from(spec: SpecConfidence) -> Self {
+        match spec {
+            SpecConfidence::Measured | SpecConfidence::ReverseEngineered => {
+                DataConfidence::DatabaseDerived
+            }
+            SpecConfidence::Heuristic | SpecConfidence::Extrapolated => DataConfidence::Heuristic,
+        }
+    }
+}
+
+/// IPA power-model coefficients from the device tree `power_model` node
+///
+/// Follows kbase's simple power model: static power scales with V³ and a
+/// temperature polynomial, dynamic power with V²·f. Feed the result of
+/// [`estimate_watts`](Self::estimate_watts) into
+/// [`Telemetry::with_power_estimate`](crate::telemetry::Telemetry::with_power_estimate).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MaliPowerModel {
+    /// Model compatible string (e.g. "arm,mali-simple-power-model")
+    pub compatible: String,
+    /// Static coefficient in µW/V³
+    pub static_coefficient: u32,
+    /// Dynamic coefficient in µW/(MHz·V²)
+    pub dynamic_coefficient: u32,
+    /// Temperature scaling polynomial `ts[0] + ts[1]·T + ts[2]·T² + ts[3]·T³`
+    /// (T in °C, result in millionths)
+    pub temperature_scaling: [i32; 4],
+    /// Thermal zone the model reads its temperature from
+    pub thermal_zone: Option<String>,
+}
+
+impl MaliPowerModel {
+    /// Leakage power in mW at `voltage_mv` and `temp_c`
+    pub fn static_power_mw(&self, voltage_mv: u32, temp_c: f32) -> f32 {
+        let volts = voltage_mv as f64 / 1000.0;
+        let t = temp_c as f64;
+        let [ts0, ts1, ts2, ts3] = self.temperature_scaling.map(f64::from);
+        // kbase clamps the scaling factor to [0, 10.0]
+        let scale = (ts3 * t * t * t + ts2 * t * t + ts1 * t + ts0).clamp(0.0, 10_000_000.0);
+        (self.static_coefficient as f64 * volts.powi(3) / 1000.0 * scale / 1_000_000.0) as f32
+    }
+
+    /// Switching power in mW at full utilization
+    pub fn dynamic_power_mw(&self, freq_hz: u64, voltage_mv: u32) -> f32 {
+        let volts = voltage_mv as f64 / 1000.0;
+        let mhz = freq_hz as f64 / 1_000_000.0;
+        (self.dynamic_coefficient as f64 * volts * volts * mhz / 1000.0) as f32
+    }
+
+    /// Total power in watts; `utilization` (0.0 - 100.0) scales the dynamic part
+    pub fn estimate_watts(&self, freq_hz: u64, voltage_mv: u32, temp_c: f32, utilization: f32) -> f32 {
+        let dynamic = self.dynamic_power_mw(freq_hz, voltage_mv) * utilization.clamp(0.0, 100.0) / 100.0;
+        (self.static_power_mw(voltage_mv, temp_c) + dynamic) / 1000.0
+    }
+}
+
+/// Shader, tiler and memory-system limits, Extended mode only
+///
+/// Taken from the properties kbase decodes itself; where a kernel does not
+/// export one, it is derived from the raw register in [`MaliHwConfig`] if
+/// possible and zero otherwise.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExtendedMaliData {
+    pub tiler_bin_size_bytes: u32,
+    pub tiler_max_active_levels: u32,
+    /// Maximum threads per core
+    pub max_threads: u32,
+    pub max_workgroup_size: u32,
+    /// Maximum threads synchronized by one barrier
+    pub max_barrier_size: u32,
+    /// Registers per core available to threads
+    pub max_registers: u32,
+    /// MMU address spaces
+    pub num_address_spaces: u32,
+    /// Number of coherency groups
+    pub num_coherency_groups: u32,
+    /// Coherency protocol in use: 0 = ACE-Lite, 1 = ACE, 31 = none
+    pub coherency_mode: u32,
+    /// TEXTURE_FEATURES_0..3, one bit per supported texture format
+    pub texture_features: [u32; 4],
+}
+
+/// Decoded L2_FEATURES register
+///
+/// Every field is log2-encoded in the register. The internal bus between
+/// shader cores and L2 is not described by any register, so only the
+/// external (memory-side) width is available.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MaliL2Features {
+    /// Cache line size in bytes, 2^L2_FEATURES[7:0]
+    pub line_size_bytes: u32,
+    /// Ways per set, 2^L2_FEATURES[15:8]
+    pub associativity: u32,
+    /// Size of one slice in bytes, 2^L2_FEATURES[23:16]
+    pub slice_size_bytes: u64,
+    /// External bus width in bits, 2^L2_FEATURES[31:24]
+    pub external_bus_bits: u32,
+    /// Register value as reported by the driver
+    pub raw: u64,
+}
+
+impl MaliL2Features {
+    /// Decode a raw L2_FEATURES value, `None` if the driver reported zero
+    ///
+    /// ```
+    /// use armgpuinfo::MaliL2Features;
+    ///
+    /// let l2 = MaliL2Features::decode(0x0712_0206).unwrap();
+    /// assert_eq!(l2.line_size_bytes, 64);
+    /// assert_eq!(l2.associativity, 4);
+    /// assert_eq!(l2.slice_size_bytes, 256 * 1024);
+    /// assert_eq!(l2.external_bus_bits, 128);
+    /// ```
+    pub fn decode(raw: u64) -> Option<Self> {
+        if raw == 0 {
+            return None;
+        }
+        let field = |shift: u32| ((raw >> shift) & 0xFF) as u32;
+        Some(Self {
+            line_size_bytes: 1u32.checked_shl(field(0)).unwrap_or(0),
+            associativity: 1u32.checked_shl(field(8)).unwrap_or(0),
+            slice_size_bytes: 1u64.checked_shl(field(16)).unwrap_or(0),
+            external_bus_bits: 1u32.checked_shl(field(24)).unwrap_or(0),
+            raw,
+        })
+    }
+}
+
+/// Shader cores of one core group
+///
+/// Midgard GPUs can have several core groups with their own L2; Bifrost and
+/// newer report a single group with every core.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CoreGroup {
+    pub mask: u64,
+    pub core_count: u32,
+}
+
+impl CoreGroup {
+    pub const fn new(mask: u64) -> Self {
+        Self { mask, core_count: mask.count_ones() }
+    }
+}
+
 /// Mali-specific GPU data
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MaliData {
     pub gpu_id: u32,
     pub raw_gpu_id: u64,
     pub shader_core_mask: u64,
+    /// Shader cores per core group, empty if the driver did not report them
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub core_groups: Vec<CoreGroup>,
     pub num_l2_slices: u64,
     pub num_exec_engines: u32,
     pub num_fp32_fmas_per_core: u32,
     pub num_fp16_fmas_per_core: u32,
     pub num_texels_per_core: u32,
     pub num_pixels_per_core: u32,
+    /// Driver flavor, from whichever version check the kernel accepted
+    pub flavor: Option<MaliFlavor>,
+    /// kbase UK API version (major, minor) the kernel answered the version
+    /// check with
+    pub driver_version: Option<(u16, u16)>,
+    /// Architecture generation, if the product is in the database
+    pub arch: Option<MaliArch>,
+    /// How the per-core figures were obtained; `None` if no database entry
+    /// was used
+    pub spec_confidence: Option<SpecConfidence>,
+    /// Device tree IPA power model, if the board defines one
+    pub power_model: Option<MaliPowerModel>,
+    /// devfreq OPP clocks in MHz, ascending; empty without a devfreq device
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub freq_table: Vec<u32>,
+    /// devfreq clock in MHz at query time
+    pub current_freq_mhz: Option<u32>,
+    /// devfreq lower policy limit in MHz at query time
+    pub min_freq_mhz: Option<u32>,
+    /// devfreq upper policy limit in MHz at query time, below the top OPP
+    /// while thermally capped
+    pub max_freq_mhz: Option<u32>,
+    /// Highest sustained clock observed by
+    /// [`calibrate_max_freq`](crate::telemetry::calibration::calibrate_max_freq)
+    pub measured_max_freq_mhz: Option<u32>,
+    /// Decoded hardware configuration registers, if queried from the driver
+    pub hw_config: Option<MaliHwConfig>,
+    /// Decoded L2_FEATURES, Extended mode only
+    pub l2_features: Option<MaliL2Features>,
+    /// Shader, tiler and memory-system limits, Extended mode only
+    pub extended: Option<ExtendedMaliData>,
+    /// Device instance, `N` of `/dev/maliN`
+    pub instance: Option<u32>,
+}
+
+impl MaliData {
+    /// Whether cores are unevenly distributed, typically because some are
+    /// fused off
+    ///
+    /// True if the core groups differ in size or the cores do not split
+    /// evenly across the L2 slices.
+    ///
+    /// ```
+    /// use armgpuinfo::{CoreGroup, GpuInfo};
+    ///
+    /// let info = GpuInfo::builder()
+    ///     .gpu_id(0x0860)
+    ///     .raw_gpu_id(0x0860_0002)
+    ///     .gpu_name("Mali-T860")
+    ///     .architecture("Midgard")
+    ///     .architecture_major(0)
+    ///     .architecture_minor(8)
+    ///     .num_shader_cores(7)
+    ///     .num_l2_bytes(256 << 10)
+    ///     .shader_core_mask(0x7f)
+    ///     .build()
+    ///     .unwrap();
+    /// let mut mali = info.mali_data.unwrap();
+    /// mali.core_groups = vec![CoreGroup::new(0x0f), CoreGroup::new(0x70)];
+    /// assert!(mali.is_asymmetric());
+    /// ```
+    pub fn is_asymmetric(&self) -> bool {
+        let counts = || self.core_groups.iter().map(|group| group.core_count);
+        let uneven_groups = counts().min() != counts().max();
+        let cores = u64::from(self.shader_core_mask.count_ones());
+        let uneven_slices = self.num_l2_slices > 1 && cores % self.num_l2_slices != 0;
+        uneven_groups || uneven_slices
+    }
+
+    /// Highest OPP clock in MHz, or the devfreq upper limit without an OPP
+    /// table
+    pub fn peak_freq_mhz(&self) -> Option<u32> {
+        self.freq_table.last().copied().or(self.max_freq_mhz)
+    }
+}
+
+/// Adreno GMU (graphics management unit) firmware version
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GmuFirmwareVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub step: u32,
+}
+
+impl fmt::Display for GmuFirmwareVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.step)
+    }
+}
+
+/// GMU presence and firmware, found on Adreno 6xx and newer
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GmuInfo {
+    /// GMU platform device directory in sysfs, if one is registered
+    pub device: Option<PathBuf>,
+    /// Firmware version, if the kernel exports it
+    pub firmware_version: Option<GmuFirmwareVersion>,
 }
 
 /// Adreno-specific GPU data
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AdrenoData {
     pub chip_id: u32,
     pub gpu_model_code: u32,
     pub mmu_enabled: bool,
-    pub gmem_size_bytes: u32,
+    /// GMEM (tile memory) size in bytes as reported by the driver,
+    /// falling back to the database value when the driver reports zero
+    pub gmem_bytes: u64,
+    /// Driver-reported model string (e.g. "Adreno740v2"), Extended mode only
+    pub driver_model_name: Option<Cow<'static, str>>,
+    /// KGSL driver version (major, minor), Extended mode only
+    pub driver_version: Option<(u32, u32)>,
+    /// Speed bin fused into the chip, Extended mode only
+    pub speed_bin: Option<u32>,
+    /// UBWC (bandwidth compression) mode, Extended mode only
+    pub ubwc_mode: Option<u32>,
+    /// GPU virtual address of the GMEM aperture seen by UCHE, Extended mode only
+    pub uche_gmem_vaddr: Option<u64>,
+    /// Highest DDR bank bit used for UBWC/tiling layouts, Extended mode only
+    pub highest_bank_bit: Option<u32>,
+    /// Minimum DDR access length in bytes, Extended mode only
+    pub min_access_length: Option<u32>,
+    /// QDSS STM trace region (GPU address, size), Extended mode only
+    pub qdss_stm: Option<(u64, u64)>,
     pub spec_confidence: Cow<'static, str>,  // Geändert von String zu Cow
     pub stream_processors: u32,
+    /// Slice topology from the database, `None` for unsliced parts
+    pub slices: Option<AdrenoSlices>,
+    /// Boost clock from the database, or the driver's maximum on the msm
+    /// DRM backend
     pub max_freq_mhz: u32,
+    /// Highest sustained clock observed by
+    /// [`calibrate_max_freq`](crate::telemetry::calibration::calibrate_max_freq)
+    pub measured_max_freq_mhz: Option<u32>,
+    /// Clock of each KGSL power level in MHz, level 0 (fastest) first;
+    /// Extended mode and the sysfs fallback only
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub freq_table: Vec<u32>,
+    /// Power level the GPU is clocked at, an index into `freq_table`
+    pub current_pwrlevel: Option<u32>,
     pub process_nm: u32,
     pub release_year: u32,
-    pub snapdragon_models: Vec<Cow<'static, str>>,  // Geändert von Vec<String> zu Vec<Cow>
+    /// SoCs shipping this GPU
+    pub snapdragon_models: Vec<SocRef>,
+    /// GMU presence and firmware, `None` if no GMU was found
+    pub gmu: Option<GmuInfo>,
+    /// Database fields whose value was confirmed on the device, see
+    /// [`GpuInfo::field_confidence`]
+    pub confirmed: Vec<FieldId>,
+}
+
+/// Vivante-specific GPU data, from the etnaviv `GET_PARAM` ioctls
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VivanteData {
+    /// Chip model, e.g. 0x7000 for the GC7000
+    pub model: u32,
+    /// Chip revision, e.g. 0x6214
+    pub revision: u32,
+    /// Product ID, zero on cores that predate the register
+    pub product_id: u32,
+    pub customer_id: u32,
+    pub eco_id: u32,
+    /// `chipFeatures` followed by `chipMinorFeatures0` to `11`, 13 words
+    /// on current kernels
+    pub features: Vec<u32>,
+    pub stream_count: u32,
+    pub register_max: u32,
+    pub thread_count: u32,
+    pub vertex_cache_size: u32,
+    pub pixel_pipes: u32,
+    pub instruction_count: u32,
+    pub num_constants: u32,
+    pub num_varyings: u32,
+}
+
+impl VivanteData {
+    /// Whether bit `bit` of feature word `word` is set; word 0 is
+    /// `chipFeatures`, word `n` is `chipMinorFeatures<n - 1>`
+    pub fn has_feature(&self, word: usize, bit: u32) -> bool {
+        self.features.get(word).is_some_and(|value| value & (1 << bit) != 0)
+    }
 }
 
 /// Unified GPU information structure
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GpuInfo {
     // Common fields for all GPUs
     pub vendor: GpuVendor,
@@ -58,12 +580,35 @@ pub struct GpuInfo {
     pub architecture_major: u8,
     pub architecture_minor: u8,
     pub num_shader_cores: u32,
+    /// L2 cache size in bytes.
+    ///
+    /// For Adreno this is a compatibility alias carrying the GMEM size; use
+    /// [`AdrenoData::gmem_bytes`] instead. The UCHE (L2) size is not
+    /// reported by the driver nor disclosed per model.
     pub num_l2_bytes: u64,
     pub num_bus_bits: u64,
 
     // Vendor-specific data (optional)
     pub mali_data: Option<MaliData>,
     pub adreno_data: Option<AdrenoData>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub vivante_data: Option<VivanteData>,
+
+    /// Hardware features, see [`capabilities`](crate::capabilities)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub capabilities: GpuCapabilities,
+
+    /// Confidence of `num_shader_cores` and `num_l2_bytes`, the weaker of
+    /// the two
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub confidence: DataConfidence,
+    /// Per-field confidence, listed in Extended mode and by the DRM
+    /// backends; see [`data_confidence`](Self::data_confidence)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub provenance: Vec<(FieldId, DataConfidence)>,
+
+    /// Device node the info was queried from, `None` if built by hand
+    pub device_path: Option<PathBuf>,
 }
 
 impl GpuInfo {
@@ -72,24 +617,286 @@ impl GpuInfo {
         GpuInfoBuilder::default()
     }
 
-    /// Check if GPU supports FP16 operations
-    pub fn supports_fp16(&self) -> bool {
+    /// Create a new builder for Adreno GpuInfo
+    pub fn adreno_builder() -> AdrenoInfoBuilder {
+        AdrenoInfoBuilder::default()
+    }
+
+    /// Hardware revision decoded from the GPU/chip ID
+    ///
+    /// Mali: `rXpY` from the 32-bit GPU_ID layout (`None` for the 64-bit
+    /// layout). Adreno: minor and patch bytes of the chip ID.
+    pub fn revision(&self) -> Option<Revision> {
         match self.vendor {
             GpuVendor::Mali => {
-                if let Some(mali) = &self.mali_data {
-                    mali.num_fp16_fmas_per_core > 0
-                } else {
-                    false
+                let raw = self.mali_data.as_ref()?.raw_gpu_id;
+                if raw > u32::MAX as u64 {
+                    return None;
                 }
+                Some(Revision::new(((raw >> 12) & 0xF) as u8, ((raw >> 4) & 0xFF) as u8))
+            }
+            GpuVendor::Adreno => {
+                let chip_id = self.adreno_data.as_ref()?.chip_id;
+                Some(Revision::new(((chip_id >> 8) & 0xFF) as u8, (chip_id & 0xFF) as u8))
+            }
+            // Vivante revisions are plain numbers such as 0x6214, not rXpY
+            GpuVendor::Vivante | GpuVendor::Unknown => None,
+        }
+    }
+
+    /// Known errata and missing capabilities for this product and revision
+    pub fn known_quirks(&self) -> Vec<&'static Quirk> {
+        let product = match self.vendor {
+            GpuVendor::Mali => self.mali_data.as_ref().map(|m| m.gpu_id),
+            GpuVendor::Adreno => self.adreno_data.as_ref().map(|a| a.chip_id & 0xFFFF0000),
+            GpuVendor::Vivante | GpuVendor::Unknown => None,
+        };
+        match product {
+            Some(product) => quirks::known_quirks(self.vendor, product, self.revision()),
+            None => Vec::new(),
+        }
+    }
+
+    /// Suggest texture-pool and render-target budgets
+    ///
+    /// Reads total system RAM from /proc/meminfo; see
+    /// [`suggest_memory_budget_with_ram`](Self::suggest_memory_budget_with_ram)
+    /// to supply it yourself.
+    pub fn suggest_memory_budget(&self, profile: BudgetProfile) -> MemoryBudget {
+        budget::suggest(self, profile, telemetry::read_total_memory())
+    }
+
+    /// Suggest budgets for a known amount of system RAM in bytes
+    pub fn suggest_memory_budget_with_ram(&self, profile: BudgetProfile, system_ram: u64) -> MemoryBudget {
+        budget::suggest(self, profile, Some(system_ram))
+    }
+
+    /// Scanout compression, coherency and alignment hints for compositors
+    ///
+    /// See [`display`](crate::display) for what these do and do not cover.
+    pub fn display_pipeline_hints(&self) -> DisplayPipelineHints {
+        display::hints(self)
+    }
+
+    /// Newest Vulkan version the vendor driver exposes for this GPU
+    ///
+    /// From the database entry of the product, so no graphics context is
+    /// needed. `None` if the GPU has no Vulkan driver or no entry, or the
+    /// vendor's backend is not compiled in.
+    ///
+    /// ```
+    /// use armgpuinfo::{ApiVersion, GpuInfo};
+    ///
+    /// let info = GpuInfo::adreno_builder()
+    ///     .chip_id(0x0706_0001)
+    ///     .gpu_name("Adreno 740")
+    ///     .architecture("Adreno 7xx")
+    ///     .num_shader_cores(6)
+    ///     .gmem_bytes(3 << 20)
+    ///     .build()?;
+    /// # #[cfg(feature = "adreno-core")]
+    /// assert_eq!(info.max_vulkan_version(), Some(ApiVersion::new(1, 3)));
+    /// # Ok::<(), armgpuinfo::BuilderError>(())
+    /// ```
+    pub fn max_vulkan_version(&self) -> Option<ApiVersion> {
+        self.api_levels()?.vulkan
+    }
+
+    /// Newest OpenGL ES version the vendor driver exposes for this GPU
+    ///
+    /// See [`max_vulkan_version`](Self::max_vulkan_version).
+    pub fn max_gles_version(&self) -> Option<ApiVersion> {
+        Some(self.api_levels()?.gles)
+    }
+
+    /// API levels of the database entry, extrapolated for Mali products
+    /// newer than the database like the query paths do
+    fn api_levels(&self) -> Option<ApiLevels> {
+        match self.vendor {
+            #[cfg(feature = "mali-core")]
+            GpuVendor::Mali => {
+                let gpu_id = self.mali_data.as_ref()?.gpu_id;
+                let cores = self.num_shader_cores.max(1);
+                crate::mali::resolve_product(gpu_id, cores)
+                    .or_else(|| crate::mali::extrapolate_product(gpu_id, cores))
+                    .map(|entry| entry.apis)
             }
+            #[cfg(feature = "adreno-core")]
             GpuVendor::Adreno => {
-                // Adreno 6xx and newer typically support FP16
-                self.architecture_major >= 6
+                crate::adreno::resolve_adreno_specs(self.adreno_data.as_ref()?.chip_id).map(|specs| specs.apis)
             }
-            _ => false,
+            _ => None,
+        }
+    }
+
+    /// Peak memory bandwidth in GB/s, from the bus width and the memory
+    /// the database pairs the GPU with
+    ///
+    /// Adreno only: the Mali database has no memory information, as the SoC
+    /// vendor picks it. Use
+    /// [`estimated_bandwidth_gbps_with_clock`](Self::estimated_bandwidth_gbps_with_clock)
+    /// with the memory's transfer rate instead.
+    pub fn estimated_bandwidth_gbps(&self) -> Option<f64> {
+        let rate_mts = self.memory_transfer_rate_mts()?;
+        self.estimated_bandwidth_gbps_with_clock(u64::from(rate_mts) * 1_000_000)
+    }
+
+    /// Peak memory bandwidth in GB/s from the bus width and the memory's
+    /// effective transfer rate, e.g. 6_400_000_000 for LPDDR5-6400
+    ///
+    /// `None` if the bus width is unknown.
+    ///
+    /// ```
+    /// use armgpuinfo::GpuInfo;
+    ///
+    /// let info = GpuInfo::builder()
+    ///     .gpu_id(0xa867)
+    ///     .raw_gpu_id(0xa867_0001)
+    ///     .gpu_name("Mali-G610")
+    ///     .architecture("Valhall")
+    ///     .architecture_major(10)
+    ///     .architecture_minor(8)
+    ///     .num_shader_cores(4)
+    ///     .num_l2_bytes(256 << 10)
+    ///     .num_bus_bits(128)
+    ///     .build()?;
+    /// assert_eq!(info.estimated_bandwidth_gbps_with_clock(4_266_000_000), Some(68.256));
+    /// # Ok::<(), armgpuinfo::BuilderError>(())
+    /// ```
+    pub fn estimated_bandwidth_gbps_with_clock(&self, transfers_per_sec: u64) -> Option<f64> {
+        perf::bandwidth_gbps(self.num_bus_bits, transfers_per_sec)
+    }
+
+    /// Typical memory transfer rate of the database entry
+    fn memory_transfer_rate_mts(&self) -> Option<u32> {
+        match self.vendor {
+            #[cfg(feature = "adreno-core")]
+            GpuVendor::Adreno => crate::adreno::resolve_adreno_specs(self.adreno_data.as_ref()?.chip_id)
+                .map(|specs| specs.memory.transfer_rate_mts),
+            _ => None,
+        }
+    }
+
+    /// Check if GPU supports FP16 operations
+    ///
+    /// Shorthand for [`capabilities.fp16`](GpuCapabilities::fp16).
+    pub fn supports_fp16(&self) -> bool {
+        self.capabilities.fp16
+    }
+
+    /// Current GPU clock in Hz from devfreq
+    ///
+    /// Uses the first GPU devfreq device, so on systems with several GPUs
+    /// the value is not necessarily this GPU's.
+    pub fn current_frequency(&self) -> Option<u64> {
+        telemetry::devfreq::read_frequency_info()?.current_hz
+    }
+
+    /// Current, limit and OPP frequencies from devfreq, see
+    /// [`current_frequency`](Self::current_frequency)
+    pub fn frequency_info(&self) -> Option<telemetry::FrequencyInfo> {
+        telemetry::devfreq::read_frequency_info()
+    }
+
+    /// Confidence of a database-backed field
+    ///
+    /// Fields listed in [`AdrenoData::confirmed`] are
+    /// [`Measured`](SpecConfidence::Measured); everything else carries the
+    /// confidence of the database entry. `None` without vendor data.
+    pub fn field_confidence(&self, field: FieldId) -> Option<SpecConfidence> {
+        match (&self.mali_data, &self.adreno_data) {
+            (Some(mali), _) => mali.spec_confidence,
+            (_, Some(adreno)) if adreno.confirmed.contains(&field) => Some(SpecConfidence::Measured),
+            (_, Some(adreno)) => SpecConfidence::from_label(&adreno.spec_confidence),
+            (None, None) => None,
+        }
+    }
+
+    /// Confidence of `field` as listed in [`provenance`](Self::provenance),
+    /// `Unknown` if it is not listed
+    ///
+    /// ```
+    /// use armgpuinfo::fields::FieldId;
+    /// use armgpuinfo::{DataConfidence, GpuInfo};
+    ///
+    /// let mut info = GpuInfo::adreno_builder()
+    ///     .chip_id(0x0706_0001)
+    ///     .gpu_name("Adreno 740")
+    ///     .architecture("Adreno 7xx")
+    ///     .num_shader_cores(6)
+    ///     .gmem_bytes(2 << 20)
+    ///     .build()?;
+    /// assert_eq!(info.confidence, DataConfidence::Unknown);
+    ///
+    /// info.provenance.push((FieldId::GmemBytes, DataConfidence::Measured));
+    /// assert_eq!(info.data_confidence(FieldId::GmemBytes), DataConfidence::Measured);
+    /// assert_eq!(info.data_confidence(FieldId::NumShaderCores), DataConfidence::Unknown);
+    /// # Ok::<(), armgpuinfo::BuilderError>(())
+    /// ```
+    pub fn data_confidence(&self, field: FieldId) -> DataConfidence {
+        self.provenance
+            .iter()
+            .find(|(listed, _)| *listed == field)
+            .map_or(DataConfidence::Unknown, |&(_, confidence)| confidence)
+    }
+
+    /// Record `confidence` for `field`, replacing an existing entry
+    pub(crate) fn set_data_confidence(&mut self, field: FieldId, confidence: DataConfidence) {
+        match self.provenance.iter_mut().find(|(listed, _)| *listed == field) {
+            Some(entry) => entry.1 = confidence,
+            None => self.provenance.push((field, confidence)),
         }
     }
 
+    /// Check database values against the running system, see
+    /// [`telemetry::confirm`]
+    pub fn confirm_specs(&mut self) -> Vec<telemetry::confirm::Confirmation> {
+        telemetry::confirm::confirm(self)
+    }
+
+    /// [`calculate_fp32_flops`](Self::calculate_fp32_flops) at the current clock
+    pub fn current_fp32_flops(&self) -> Option<u64> {
+        self.current_frequency().map(|hz| self.calculate_fp32_flops(hz))
+    }
+
+    /// [`calculate_fp32_flops`](Self::calculate_fp32_flops) at the peak clock
+    /// recorded at query time: the top Mali OPP or the Adreno database clock
+    ///
+    /// ```
+    /// use armgpuinfo::GpuInfo;
+    ///
+    /// let mut info = GpuInfo::builder()
+    ///     .gpu_id(0xa867)
+    ///     .raw_gpu_id(0xa867_0001)
+    ///     .gpu_name("Mali-G610")
+    ///     .architecture("Valhall")
+    ///     .architecture_major(10)
+    ///     .architecture_minor(8)
+    ///     .num_shader_cores(4)
+    ///     .num_l2_bytes(256 << 10)
+    ///     .num_fp32_fmas_per_core(64)
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(info.peak_fp32_flops(), None);
+    ///
+    /// info.mali_data.as_mut().unwrap().freq_table = vec![300, 1000];
+    /// assert_eq!(info.peak_fp32_flops(), Some(512_000_000_000));
+    /// ```
+    pub fn peak_fp32_flops(&self) -> Option<u64> {
+        let mhz = match (&self.mali_data, &self.adreno_data) {
+            (Some(mali), _) => mali.peak_freq_mhz()?,
+            (_, Some(adreno)) => adreno.max_freq_mhz,
+            (None, None) => return None,
+        };
+        (mhz > 0).then(|| self.calculate_fp32_flops(u64::from(mhz) * 1_000_000))
+    }
+
+    /// Peak FP32/FP16, texture, pixel and memory rates at `freq_hz`, see
+    /// [`perf`](crate::perf)
+    pub fn performance_estimate(&self, freq_hz: u64) -> PerfEstimate {
+        perf::estimate(self, freq_hz)
+    }
+
     /// Calculate total FP32 FLOPS at given frequency (in Hz)
     pub fn calculate_fp32_flops(&self, frequency_hz: u64) -> u64 {
         match self.vendor {
@@ -116,8 +923,173 @@ impl GpuInfo {
         }
     }
 
-    /// Get GPU information as a formatted string
-    pub fn to_string(&self) -> String {
+    /// Machine-stable identifier for use as a database key
+    ///
+    /// `mali:0x<gpu_id, 4 hex digits>:mc<cores>` or
+    /// `adreno:0x<chip_id, 8 hex digits>`, always lowercase. The format is
+    /// covered by the [`compat`](crate::compat) guarantees; `None` if the
+    /// vendor data is missing.
+    ///
+    /// ```
+    /// use armgpuinfo::GpuInfo;
+    ///
+    /// let info = GpuInfo::builder()
+    ///     .gpu_id(0xa002)
+    ///     .raw_gpu_id(0xa002_0010)
+    ///     .gpu_name("Mali-G710")
+    ///     .architecture("Valhall")
+    ///     .architecture_major(10)
+    ///     .architecture_minor(0)
+    ///     .num_shader_cores(16)
+    ///     .num_l2_bytes(2 << 20)
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(info.stable_id().as_deref(), Some("mali:0xa002:mc16"));
+    /// ```
+    pub fn stable_id(&self) -> Option<String> {
+        match self.vendor {
+            GpuVendor::Mali => {
+                let mali = self.mali_data.as_ref()?;
+                Some(format!("mali:0x{:04x}:mc{}", mali.gpu_id, self.num_shader_cores))
+            }
+            GpuVendor::Adreno => {
+                let adreno = self.adreno_data.as_ref()?;
+                Some(format!("adreno:0x{:08x}", adreno.chip_id))
+            }
+            GpuVendor::Vivante => {
+                let vivante = self.vivante_data.as_ref()?;
+                Some(format!("vivante:0x{:04x}:0x{:04x}", vivante.model, vivante.revision))
+            }
+            GpuVendor::Unknown => None,
+        }
+    }
+
+    /// Stable vendor code, see [`GpuVendor::code`]
+    pub fn vendor_code(&self) -> &'static str {
+        self.vendor.code()
+    }
+
+    /// Stable architecture code, `None` if the generation is unknown
+    ///
+    /// Mali uses the database generation, falling back to the
+    /// `architecture` string; Adreno uses the chip ID's top byte.
+    ///
+    /// ```
+    /// use armgpuinfo::{ArchitectureCode, GpuInfo};
+    ///
+    /// let info = GpuInfo::builder()
+    ///     .gpu_id(0xa002)
+    ///     .raw_gpu_id(0xa002_0010)
+    ///     .gpu_name("Mali-G710")
+    ///     .architecture("Valhall")
+    ///     .architecture_major(10)
+    ///     .architecture_minor(0)
+    ///     .num_shader_cores(16)
+    ///     .num_l2_bytes(2 << 20)
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(info.vendor_code(), "mali");
+    /// assert_eq!(info.architecture_code(), Some(ArchitectureCode::MaliValhall));
+    /// ```
+    pub fn architecture_code(&self) -> Option<ArchitectureCode> {
+        match self.vendor {
+            GpuVendor::Mali => self
+                .mali_data
+                .as_ref()
+                .and_then(|m| m.arch)
+                .or_else(|| {
+                    [MaliArch::Midgard, MaliArch::Bifrost, MaliArch::Valhall, MaliArch::Gen5]
+                        .into_iter()
+                        .find(|a| a.as_str().eq_ignore_ascii_case(&self.architecture))
+                })
+                .map(ArchitectureCode::from),
+            GpuVendor::Adreno => {
+                let major = match &self.adreno_data {
+                    Some(adreno) => (adreno.chip_id >> 24) as u8,
+                    None => self.architecture_major,
+                };
+                ArchitectureCode::from_adreno_major(major)
+            }
+            GpuVendor::Vivante | GpuVendor::Unknown => None,
+        }
+    }
+
+    /// Sectioned report of the common and vendor fields, see
+    /// [`REPORT_VERSION`](crate::report::REPORT_VERSION)
+    ///
+    /// ```
+    /// use armgpuinfo::report::ReportFormat;
+    /// use armgpuinfo::GpuInfo;
+    ///
+    /// let info = GpuInfo::adreno_builder()
+    ///     .chip_id(0x0706_0001)
+    ///     .gpu_name("Adreno 740")
+    ///     .architecture("Adreno 7xx")
+    ///     .num_shader_cores(6)
+    ///     .gmem_bytes(2 << 20)
+    ///     .build()?;
+    ///
+    /// let text = info.to_report(ReportFormat::PlainText);
+    /// assert!(text.contains("Adreno\n  chip_id: 0x7060001\n"));
+    /// let markdown = info.to_report(ReportFormat::Markdown);
+    /// assert!(markdown.contains("| gpu_name | Adreno 740 |"));
+    /// # Ok::<(), armgpuinfo::BuilderError>(())
+    /// ```
+    pub fn to_report(&self, format: ReportFormat) -> String {
+        report::render(self, format)
+    }
+
+    /// [`to_report`](Self::to_report) as JSON
+    ///
+    /// ```
+    /// use armgpuinfo::GpuInfo;
+    ///
+    /// let info = GpuInfo::adreno_builder()
+    ///     .chip_id(0x0706_0001)
+    ///     .gpu_name("Adreno 740")
+    ///     .architecture("Adreno 7xx")
+    ///     .num_shader_cores(6)
+    ///     .gmem_bytes(2 << 20)
+    ///     .build()?;
+    /// assert!(info.to_json().starts_with(r#"{"report_version":1,"general":{"vendor":"adreno","gpu_name":"Adreno 740""#));
+    /// # Ok::<(), armgpuinfo::BuilderError>(())
+    /// ```
+    pub fn to_json(&self) -> String {
+        self.to_report(ReportFormat::Json)
+    }
+
+    /// Formatter for `style`; the [`Display`](fmt::Display) impl uses
+    /// [`DisplayStyle::Compact`]
+    ///
+    /// Human-readable; the layout may change between releases. Use
+    /// [`stable_id`](Self::stable_id) for keys and
+    /// [`to_report`](Self::to_report) for machine-readable output.
+    ///
+    /// ```
+    /// use armgpuinfo::{DisplayStyle, GpuInfo};
+    ///
+    /// let info = GpuInfo::builder()
+    ///     .gpu_id(0xa867)
+    ///     .raw_gpu_id(0xa867_0001)
+    ///     .gpu_name("Mali-G610")
+    ///     .architecture("Valhall")
+    ///     .architecture_major(10)
+    ///     .architecture_minor(8)
+    ///     .num_shader_cores(4)
+    ///     .num_l2_bytes(256 << 10)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(info.to_string(), "Mali-G610 (Valhall), Architecture: 10.8, Cores: 4, L2: 256 KB");
+    /// let detailed = info.display(DisplayStyle::Detailed).to_string();
+    /// assert!(detailed.starts_with("Mali-G610 (Valhall), Architecture: 10.8"));
+    /// assert!(detailed.contains("\n  gpu_id: 0xa867\n"));
+    /// ```
+    pub fn display(&self, style: DisplayStyle) -> GpuInfoDisplay<'_> {
+        GpuInfoDisplay { info: self, style }
+    }
+
+    fn compact(&self) -> String {
         match self.vendor {
             GpuVendor::Mali => {
                 if !self.gpu_name.is_empty() {
@@ -176,9 +1148,44 @@ impl GpuInfo {
     }
 }
 
+/// Layout of [`GpuInfo::display`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DisplayStyle {
+    /// One summary line, as written by `Display`
+    #[default]
+    Compact,
+    /// The summary line followed by the vendor fields, one per line
+    Detailed,
+    /// Every field, as the pretty-printed `Debug` output
+    Debug,
+}
+
+/// [`GpuInfo`] formatted in a [`DisplayStyle`], see [`GpuInfo::display`]
+#[derive(Debug, Clone, Copy)]
+pub struct GpuInfoDisplay<'a> {
+    info: &'a GpuInfo,
+    style: DisplayStyle,
+}
+
+impl fmt::Display for GpuInfoDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.style {
+            DisplayStyle::Compact => f.write_str(&self.info.compact()),
+            DisplayStyle::Detailed => {
+                f.write_str(&self.info.compact())?;
+                for (key, value) in report::vendor_entries(self.info) {
+                    write!(f, "\n  {key}: {value}")?;
+                }
+                Ok(())
+            }
+            DisplayStyle::Debug => write!(f, "{:#?}", self.info),
+        }
+    }
+}
+
 impl fmt::Display for GpuInfo {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.to_string())
+        self.display(DisplayStyle::Compact).fmt(f)
     }
 }
 
@@ -204,6 +1211,10 @@ pub struct GpuInfoBuilder {
     num_fp16_fmas_per_core: Option<u32>,
     num_texels_per_core: Option<u32>,
     num_pixels_per_core: Option<u32>,
+    flavor: Option<MaliFlavor>,
+    driver_version: Option<(u16, u16)>,
+    arch: Option<MaliArch>,
+    spec_confidence: Option<SpecConfidence>,
 }
 
 impl GpuInfoBuilder {
@@ -288,31 +1299,375 @@ impl GpuInfoBuilder {
         self
     }
 
+    pub fn flavor(mut self, flavor: MaliFlavor) -> Self {
+        self.flavor = Some(flavor);
+        self
+    }
+
+    pub fn driver_version(mut self, major: u16, minor: u16) -> Self {
+        self.driver_version = Some((major, minor));
+        self
+    }
+
+    pub fn mali_arch(mut self, arch: MaliArch) -> Self {
+        self.arch = Some(arch);
+        self
+    }
+
+    pub fn spec_confidence(mut self, confidence: SpecConfidence) -> Self {
+        self.spec_confidence = Some(confidence);
+        self
+    }
+
+    /// Check cross-field consistency of the values set so far
+    ///
+    /// [`build`](Self::build) only checks that required fields are present;
+    /// call this first to also reject contradictory input.
+    ///
+    /// ```
+    /// use armgpuinfo::{BuilderError, GpuInfo};
+    /// use armgpuinfo::fields::FieldId;
+    ///
+    /// let builder = GpuInfo::builder().num_shader_cores(4).shader_core_mask(0b111);
+    /// assert!(matches!(
+    ///     builder.validate(),
+    ///     Err(BuilderError::InvalidValue { field: FieldId::ShaderCoreMask, .. })
+    /// ));
+    /// ```
+    pub fn validate(&self) -> Result<(), BuilderError> {
+        let invalid = |field, reason: String| Err(BuilderError::InvalidValue { field, reason });
+
+        if self.num_shader_cores == Some(0) {
+            return invalid(FieldId::NumShaderCores, "must be non-zero".into());
+        }
+        if let (Some(cores), Some(mask)) = (self.num_shader_cores, self.shader_core_mask) {
+            if mask != 0 && mask.count_ones() != cores {
+                return invalid(
+                    FieldId::ShaderCoreMask,
+                    format!("{mask:#x} has {} cores set, expected {cores}", mask.count_ones()),
+                );
+            }
+        }
+        if let Some(bits) = self.num_bus_bits.filter(|&bits| bits != 0) {
+            if !bits.is_power_of_two() {
+                return invalid(FieldId::NumBusBits, format!("{bits} is not a power of two"));
+            }
+        }
+        if let (Some(slices), Some(bytes)) = (self.num_l2_slices, self.num_l2_bytes) {
+            if slices != 0 && bytes % slices != 0 {
+                return invalid(
+                    FieldId::NumL2Bytes,
+                    format!("{bytes} bytes do not split evenly across {slices} slices"),
+                );
+            }
+        }
+        if let (Some(fp32), Some(fp16)) = (self.num_fp32_fmas_per_core, self.num_fp16_fmas_per_core) {
+            if fp16 != 0 && fp16 < fp32 {
+                return invalid(
+                    FieldId::NumFp16FmasPerCore,
+                    format!("{fp16} is below the FP32 rate of {fp32}"),
+                );
+            }
+        }
+        Ok(())
+    }
+
     /// Build GpuInfo (Mali-specific builder)
-    pub fn build(self) -> Result<GpuInfo, &'static str> {
+    pub fn build(self) -> Result<GpuInfo, BuilderError> {
+        let missing = BuilderError::MissingField;
         let mali_data = MaliData {
-            gpu_id: self.gpu_id.ok_or("GPU ID required")?,
-            raw_gpu_id: self.raw_gpu_id.ok_or("Raw GPU ID required")?,
+            gpu_id: self.gpu_id.ok_or(missing(FieldId::GpuId))?,
+            raw_gpu_id: self.raw_gpu_id.ok_or(missing(FieldId::RawGpuId))?,
             shader_core_mask: self.shader_core_mask.unwrap_or(0),
+            core_groups: self.shader_core_mask.filter(|&mask| mask != 0).map(CoreGroup::new).into_iter().collect(),
             num_l2_slices: self.num_l2_slices.unwrap_or(0),
             num_exec_engines: self.num_exec_engines.unwrap_or(0),
             num_fp32_fmas_per_core: self.num_fp32_fmas_per_core.unwrap_or(0),
             num_fp16_fmas_per_core: self.num_fp16_fmas_per_core.unwrap_or(0),
             num_texels_per_core: self.num_texels_per_core.unwrap_or(0),
             num_pixels_per_core: self.num_pixels_per_core.unwrap_or(0),
+            flavor: self.flavor,
+            driver_version: self.driver_version,
+            arch: self.arch,
+            spec_confidence: self.spec_confidence,
+            power_model: None,
+            freq_table: Vec::new(),
+            current_freq_mhz: None,
+            min_freq_mhz: None,
+            max_freq_mhz: None,
+            measured_max_freq_mhz: None,
+            hw_config: None,
+            l2_features: None,
+            extended: None,
+            instance: None,
         };
 
-        Ok(GpuInfo {
+        let mut info = GpuInfo {
             vendor: GpuVendor::Mali,
-            gpu_name: self.gpu_name.ok_or("GPU name required")?,
-            architecture: self.architecture.ok_or("Architecture required")?,
-            architecture_major: self.architecture_major.ok_or("Architecture major required")?,
-            architecture_minor: self.architecture_minor.ok_or("Architecture minor required")?,
-            num_shader_cores: self.num_shader_cores.ok_or("Number of shader cores required")?,
-            num_l2_bytes: self.num_l2_bytes.ok_or("L2 cache size required")?,
+            gpu_name: self.gpu_name.ok_or(missing(FieldId::GpuName))?,
+            architecture: self.architecture.ok_or(missing(FieldId::Architecture))?,
+            architecture_major: self.architecture_major.ok_or(missing(FieldId::ArchitectureMajor))?,
+            architecture_minor: self.architecture_minor.ok_or(missing(FieldId::ArchitectureMinor))?,
+            num_shader_cores: self.num_shader_cores.ok_or(missing(FieldId::NumShaderCores))?,
+            num_l2_bytes: self.num_l2_bytes.ok_or(missing(FieldId::NumL2Bytes))?,
             num_bus_bits: self.num_bus_bits.unwrap_or(0),
             mali_data: Some(mali_data),
             adreno_data: None,
-        })
+            vivante_data: None,
+            capabilities: GpuCapabilities::default(),
+            confidence: DataConfidence::Unknown,
+            provenance: Vec::new(),
+            device_path: None,
+        };
+        info.capabilities = GpuCapabilities::of(&info);
+        Ok(info)
+    }
+}
+/// Builder for Adreno-flavored GpuInfo
+///
+/// `architecture_major`/`architecture_minor` default to the core and major
+/// bytes of the chip ID, and `num_l2_bytes` carries the GMEM size like the
+/// KGSL path does.
+///
+/// ```
+/// use armgpuinfo::GpuInfo;
+///
+/// let info = GpuInfo::adreno_builder()
+///     .chip_id(0x0703_0001)
+///     .gpu_name("Adreno 730")
+///     .architecture("Adreno 7xx")
+///     .num_shader_cores(4)
+///     .gmem_bytes(2 * 1024 * 1024)
+///     .build()?;
+/// assert_eq!(info.architecture_major, 7);
+/// assert_eq!(info.num_l2_bytes, 2 * 1024 * 1024);
+/// # Ok::<(), armgpuinfo::BuilderError>(())
+/// ```
+#[derive(Debug, Default)]
+pub struct AdrenoInfoBuilder {
+    // Common fields
+    gpu_name: Option<Cow<'static, str>>,
+    architecture: Option<Cow<'static, str>>,
+    architecture_major: Option<u8>,
+    architecture_minor: Option<u8>,
+    num_shader_cores: Option<u32>,
+    num_bus_bits: Option<u64>,
+
+    // Adreno-specific fields
+    chip_id: Option<u32>,
+    gpu_model_code: Option<u32>,
+    mmu_enabled: bool,
+    gmem_bytes: Option<u64>,
+    driver_model_name: Option<Cow<'static, str>>,
+    driver_version: Option<(u32, u32)>,
+    speed_bin: Option<u32>,
+    ubwc_mode: Option<u32>,
+    stream_processors: Option<u32>,
+    slices: Option<AdrenoSlices>,
+    max_freq_mhz: Option<u32>,
+    process_nm: Option<u32>,
+    release_year: Option<u32>,
+    spec_confidence: Option<SpecConfidence>,
+}
+
+impl AdrenoInfoBuilder {
+    pub fn gpu_name(mut self, name: impl Into<Cow<'static, str>>) -> Self {
+        self.gpu_name = Some(name.into());
+        self
+    }
+
+    pub fn architecture(mut self, arch: impl Into<Cow<'static, str>>) -> Self {
+        self.architecture = Some(arch.into());
+        self
     }
-}
\ No newline at end of file
+
+    pub fn architecture_major(mut self, major: u8) -> Self {
+        self.architecture_major = Some(major);
+        self
+    }
+
+    pub fn architecture_minor(mut self, minor: u8) -> Self {
+        self.architecture_minor = Some(minor);
+        self
+    }
+
+    pub fn num_shader_cores(mut self, cores: u32) -> Self {
+        self.num_shader_cores = Some(cores);
+        self
+    }
+
+    pub fn num_bus_bits(mut self, bits: u64) -> Self {
+        self.num_bus_bits = Some(bits);
+        self
+    }
+
+    pub fn chip_id(mut self, chip_id: u32) -> Self {
+        self.chip_id = Some(chip_id);
+        self
+    }
+
+    pub fn gpu_model_code(mut self, code: u32) -> Self {
+        self.gpu_model_code = Some(code);
+        self
+    }
+
+    pub fn mmu_enabled(mut self, enabled: bool) -> Self {
+        self.mmu_enabled = enabled;
+        self
+    }
+
+    pub fn gmem_bytes(mut self, bytes: u64) -> Self {
+        self.gmem_bytes = Some(bytes);
+        self
+    }
+
+    pub fn driver_model_name(mut self, name: impl Into<Cow<'static, str>>) -> Self {
+        self.driver_model_name = Some(name.into());
+        self
+    }
+
+    pub fn driver_version(mut self, major: u32, minor: u32) -> Self {
+        self.driver_version = Some((major, minor));
+        self
+    }
+
+    pub fn speed_bin(mut self, bin: u32) -> Self {
+        self.speed_bin = Some(bin);
+        self
+    }
+
+    pub fn ubwc_mode(mut self, mode: u32) -> Self {
+        self.ubwc_mode = Some(mode);
+        self
+    }
+
+    pub fn stream_processors(mut self, sps: u32) -> Self {
+        self.stream_processors = Some(sps);
+        self
+    }
+
+    pub fn slices(mut self, slices: AdrenoSlices) -> Self {
+        self.slices = Some(slices);
+        self
+    }
+
+    pub fn max_freq_mhz(mut self, mhz: u32) -> Self {
+        self.max_freq_mhz = Some(mhz);
+        self
+    }
+
+    pub fn process_nm(mut self, nm: u32) -> Self {
+        self.process_nm = Some(nm);
+        self
+    }
+
+    pub fn release_year(mut self, year: u32) -> Self {
+        self.release_year = Some(year);
+        self
+    }
+
+    pub fn spec_confidence(mut self, confidence: SpecConfidence) -> Self {
+        self.spec_confidence = Some(confidence);
+        self
+    }
+
+    /// Check cross-field consistency of the values set so far
+    ///
+    /// ```
+    /// use armgpuinfo::{BuilderError, GpuInfo};
+    /// use armgpuinfo::fields::FieldId;
+    ///
+    /// let builder = GpuInfo::adreno_builder().chip_id(0x0703_0001).architecture_major(6);
+    /// assert!(matches!(
+    ///     builder.validate(),
+    ///     Err(BuilderError::InvalidValue { field: FieldId::ArchitectureMajor, .. })
+    /// ));
+    /// ```
+    pub fn validate(&self) -> Result<(), BuilderError> {
+        let invalid = |field, reason: String| Err(BuilderError::InvalidValue { field, reason });
+
+        if self.num_shader_cores == Some(0) {
+            return invalid(FieldId::NumShaderCores, "must be non-zero".into());
+        }
+        if let Some(bits) = self.num_bus_bits.filter(|&bits| bits != 0) {
+            if !bits.is_power_of_two() {
+                return invalid(FieldId::NumBusBits, format!("{bits} is not a power of two"));
+            }
+        }
+        if self.gmem_bytes == Some(0) {
+            return invalid(FieldId::GmemBytes, "must be non-zero".into());
+        }
+        if let (Some(slices), Some(cores)) = (self.slices, self.num_shader_cores) {
+            if slices.usptps() != cores {
+                return invalid(
+                    FieldId::NumShaderCores,
+                    format!("{cores} contradicts {} slices x {} uSPTPs", slices.slices, slices.usptps_per_slice),
+                );
+            }
+        }
+        if let (Some(chip_id), Some(major)) = (self.chip_id, self.architecture_major) {
+            let series = (chip_id >> 24) as u8;
+            if series != 0 && series != major {
+                return invalid(
+                    FieldId::ArchitectureMajor,
+                    format!("{major} contradicts chip ID {chip_id:#010x} (series {series})"),
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Build GpuInfo (Adreno-specific builder)
+    pub fn build(self) -> Result<GpuInfo, BuilderError> {
+        let missing = BuilderError::MissingField;
+        let chip_id = self.chip_id.ok_or(missing(FieldId::ChipId))?;
+        let gmem_bytes = self.gmem_bytes.ok_or(missing(FieldId::GmemBytes))?;
+        let adreno_data = AdrenoData {
+            chip_id,
+            gpu_model_code: self.gpu_model_code.unwrap_or(0),
+            mmu_enabled: self.mmu_enabled,
+            gmem_bytes,
+            driver_model_name: self.driver_model_name,
+            driver_version: self.driver_version,
+            speed_bin: self.speed_bin,
+            ubwc_mode: self.ubwc_mode,
+            uche_gmem_vaddr: None,
+            highest_bank_bit: None,
+            min_access_length: None,
+            qdss_stm: None,
+            // Hand-built values are not backed by a driver query
+            spec_confidence: self.spec_confidence.unwrap_or(SpecConfidence::Heuristic).as_cow(),
+            stream_processors: self.stream_processors.unwrap_or(0),
+            slices: self.slices,
+            max_freq_mhz: self.max_freq_mhz.unwrap_or(0),
+            measured_max_freq_mhz: None,
+            freq_table: Vec::new(),
+            current_pwrlevel: None,
+            process_nm: self.process_nm.unwrap_or(0),
+            release_year: self.release_year.unwrap_or(0),
+            snapdragon_models: Vec::new(),
+            gmu: None,
+            confirmed: Vec::new(),
+        };
+
+        let mut info = GpuInfo {
+            vendor: GpuVendor::Adreno,
+            gpu_name: self.gpu_name.ok_or(missing(FieldId::GpuName))?,
+            architecture: self.architecture.ok_or(missing(FieldId::Architecture))?,
+            architecture_major: self.architecture_major.unwrap_or((chip_id >> 24) as u8),
+            architecture_minor: self.architecture_minor.unwrap_or((chip_id >> 16) as u8),
+            num_shader_cores: self.num_shader_cores.ok_or(missing(FieldId::NumShaderCores))?,
+            num_l2_bytes: gmem_bytes,
+            num_bus_bits: self.num_bus_bits.unwrap_or(0),
+            mali_data: None,
+            adreno_data: Some(adreno_data),
+            vivante_data: None,
+            capabilities: GpuCapabilities::default(),
+            confidence: DataConfidence::Unknown,
+            provenance: Vec::new(),
+            device_path: None,
+        };
+        info.capabilities = GpuCapabilities::of(&info);
+        Ok(info)
+    }
+}
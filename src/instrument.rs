@@ -0,0 +1,157 @@
+//! Per-query timings and byte counts
+//!
+//! An [`Instrumentation`] hook set with
+//! [`QueryOptions::instrument`](crate::QueryOptions::instrument) receives a
+//! [`QueryTrace`] once per query: where the time went, how many bytes the
+//! driver returned and which fallbacks were taken. The hook runs on the
+//! thread that ran the query, a background thread if a timeout is set.
+//!
+//! ```
+//! # #[cfg(feature = "mali-kbase")]
+//! # {
+//! use armgpuinfo::{Instrumentation, Mode, QueryOptions};
+//!
+//! let options = QueryOptions::new(Mode::Parity).instrument(Instrumentation::new(|trace| {
+//!     eprintln!("ioctl {:?}, {} bytes", trace.ioctl, trace.bytes);
+//! }));
+//! let _ = armgpuinfo::mali::query_mali_with_opts("/dev/mali0", options);
+//! # }
+//! ```
+
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::Mode;
+
+#[cfg(any(feature = "mali-core", feature = "adreno-core"))]
+use std::cell::RefCell;
+#[cfg(any(feature = "mali-core", feature = "adreno-core"))]
+use std::time::Instant;
+
+/// Where the time of one query went
+///
+/// Phases repeated by a mode fallback are summed. Queries through an
+/// already open [`GpuDevice`](crate::device::GpuDevice) report no open or
+/// handshake time.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct QueryTrace {
+    pub device_path: Option<PathBuf>,
+    /// Mode that answered, `None` if every mode failed
+    pub mode: Option<Mode>,
+    pub open: Duration,
+    /// kbase version check and context setup
+    pub handshake: Duration,
+    /// Property ioctls
+    pub ioctl: Duration,
+    /// Decoding of the returned buffers
+    pub parse: Duration,
+    /// Product database lookup
+    pub lookup: Duration,
+    /// Whole query, including phases not listed above
+    pub total: Duration,
+    /// Bytes returned by the driver
+    pub bytes: usize,
+    /// The sysfs nodes were read after the ioctls failed
+    pub sysfs_fallback: bool,
+    pub succeeded: bool,
+}
+
+/// Hook receiving a [`QueryTrace`] after every query
+#[derive(Clone)]
+pub struct Instrumentation {
+    // Only the ioctl backends run instrumented queries
+    #[cfg_attr(not(any(feature = "mali-kbase", feature = "adreno-kgsl")), allow(dead_code))]
+    hook: Arc<dyn Fn(&QueryTrace) + Send + Sync>,
+}
+
+impl Instrumentation {
+    pub fn new(hook: impl Fn(&QueryTrace) + Send + Sync + 'static) -> Self {
+        Self { hook: Arc::new(hook) }
+    }
+}
+
+impl fmt::Debug for Instrumentation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Instrumentation").finish_non_exhaustive()
+    }
+}
+
+/// Query phases timed separately
+#[cfg(any(feature = "mali-core", feature = "adreno-core"))]
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Phase {
+    #[cfg(any(feature = "mali-kbase", feature = "adreno-kgsl"))]
+    Open,
+    #[cfg(feature = "mali-kbase")]
+    Handshake,
+    #[cfg(any(feature = "mali-kbase", feature = "adreno-kgsl"))]
+    Ioctl,
+    Parse,
+    Lookup,
+}
+
+#[cfg(any(feature = "mali-core", feature = "adreno-core"))]
+thread_local! {
+    /// Trace of the query running on this thread, if it is instrumented
+    static ACTIVE: RefCell<Option<QueryTrace>> = const { RefCell::new(None) };
+}
+
+/// Run `query` on `path`, reporting its trace to the hook in `instrumentation`
+#[cfg(any(feature = "mali-kbase", feature = "adreno-kgsl"))]
+pub(crate) fn traced<T>(
+    instrumentation: Option<&Instrumentation>,
+    path: &std::path::Path,
+    query: impl FnOnce() -> crate::GpuResult<T>,
+) -> crate::GpuResult<T> {
+    let Some(instrumentation) = instrumentation else {
+        return query();
+    };
+    let start = Instant::now();
+    let trace = QueryTrace { device_path: Some(path.to_path_buf()), ..Default::default() };
+    let outer = ACTIVE.with(|active| active.replace(Some(trace)));
+    let result = query();
+    let mut trace = ACTIVE.with(|active| active.replace(outer)).unwrap_or_default();
+    trace.total = start.elapsed();
+    trace.succeeded = result.is_ok();
+    if !trace.succeeded {
+        trace.mode = None;
+    }
+    (instrumentation.hook)(&trace);
+    result
+}
+
+/// Update the active trace, if any
+#[cfg(any(feature = "mali-core", feature = "adreno-core"))]
+pub(crate) fn record(update: impl FnOnce(&mut QueryTrace)) {
+    ACTIVE.with(|active| {
+        if let Some(trace) = active.borrow_mut().as_mut() {
+            update(trace);
+        }
+    });
+}
+
+/// Run `f`, adding its duration to `phase` of the active trace
+#[cfg(any(feature = "mali-core", feature = "adreno-core"))]
+pub(crate) fn timed<T>(phase: Phase, f: impl FnOnce() -> T) -> T {
+    if ACTIVE.with(|active| active.borrow().is_none()) {
+        return f();
+    }
+    let start = Instant::now();
+    let value = f();
+    let elapsed = start.elapsed();
+    record(|trace| {
+        *match phase {
+            #[cfg(any(feature = "mali-kbase", feature = "adreno-kgsl"))]
+            Phase::Open => &mut trace.open,
+            #[cfg(feature = "mali-kbase")]
+            Phase::Handshake => &mut trace.handshake,
+            #[cfg(any(feature = "mali-kbase", feature = "adreno-kgsl"))]
+            Phase::Ioctl => &mut trace.ioctl,
+            Phase::Parse => &mut trace.parse,
+            Phase::Lookup => &mut trace.lookup,
+        } += elapsed;
+    });
+    value
+}
@@ -0,0 +1,57 @@
+//! JNI bindings for Android apps
+//!
+//! Exports the native method of
+//!
+//! ```java
+//! package dev.gpuinfo;
+//!
+//! public final class GpuInfo {
+//!     static { System.loadLibrary("armgpuinfo"); }
+//!
+//!     /** The detected GPU as JSON; throws IOException if none answers */
+//!     public static native String query();
+//! }
+//! ```
+//!
+//! The JSON is the serde form of [`GpuInfo`](crate::GpuInfo), as printed
+//! by `gpuinfo --json`. Build the shared library with
+//!
+//! ```text
+//! cargo rustc --release --target aarch64-linux-android --features jni --crate-type cdylib
+//! ```
+
+use ::jni::objects::JClass;
+use ::jni::sys::jstring;
+use ::jni::JNIEnv;
+
+use crate::error::{GpuError, GpuResult};
+
+/// Exception class thrown when the query fails
+const EXCEPTION_CLASS: &str = "java/io/IOException";
+
+/// Detect and query the GPU, returning the JSON `GpuInfo.query()` returns
+pub fn query_json() -> GpuResult<String> {
+    let info = crate::detect::query_gpu_auto(None::<&str>)?;
+    serde_json::to_string(&info).map_err(|e| GpuError::Export(e.to_string()))
+}
+
+/// `dev.gpuinfo.GpuInfo.query()`
+///
+/// Returns null with a pending `IOException` on failure.
+#[no_mangle]
+pub extern "system" fn Java_dev_gpuinfo_GpuInfo_query<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass<'local>,
+) -> jstring {
+    let result = query_json().and_then(|json| {
+        env.new_string(json).map_err(|e| GpuError::Export(e.to_string()))
+    });
+    match result {
+        Ok(json) => json.into_raw(),
+        Err(error) => {
+            // Nothing more can be reported if even the throw fails
+            let _ = env.throw_new(EXCEPTION_CLASS, error.to_string());
+            std::ptr::null_mut()
+        }
+    }
+}
@@ -2,25 +2,99 @@
 //!
 //! This library provides a unified interface to query GPU information
 //! for both ARM Mali and Qualcomm Adreno GPUs on Linux/Android systems.
-pub use info::GpuInfoBuilder;  
+pub use info::{AdrenoInfoBuilder, GpuInfoBuilder};
 // Common modules
+// Only the ioctl backends emit diagnostics
+#[cfg_attr(
+    not(any(feature = "mali-kbase", feature = "adreno-kgsl")),
+    allow(unused_macros, unused_imports)
+)]
+mod diag;
+pub mod abi;
+pub mod budget;
+pub mod capabilities;
+pub mod compat;
+pub mod display;
+pub mod drm;
+pub mod dump;
 pub mod error;
+pub mod fields;
 pub mod info;
+pub mod instrument;
+pub mod monitor;
+pub mod options;
+pub mod perf;
+pub mod prelude;
+pub mod quirks;
+pub mod report;
+pub mod snapshot;
+pub mod soc;
+pub mod telemetry;
 
 // Conditionally compiled modules
-#[cfg(feature = "mali")]
+#[cfg(feature = "mali-core")]
 pub mod mali;
 
-#[cfg(feature = "adreno")]
+#[cfg(feature = "adreno-core")]
 pub mod adreno;
 
+#[cfg(feature = "vivante")]
+pub mod vivante;
 
-#[cfg(feature = "auto-detect")]
+#[cfg(all(feature = "auto-detect", any(feature = "mali-kbase", feature = "adreno-kgsl")))]
 pub mod detect;
 
+#[cfg(all(feature = "auto-detect", any(feature = "mali-kbase", feature = "adreno-kgsl")))]
+pub mod cache;
+
+#[cfg(any(feature = "mali-kbase", feature = "adreno-kgsl"))]
+pub mod device;
+
+#[cfg(any(feature = "mali-core", feature = "adreno-core"))]
+pub mod resolve;
+
+#[cfg(any(feature = "mali-core", feature = "adreno-core"))]
+pub mod decode;
+
+#[cfg(all(feature = "database-file", any(feature = "mali-core", feature = "adreno-core")))]
+pub mod database;
+
+#[cfg(all(feature = "egl", any(feature = "mali-core", feature = "adreno-core")))]
+pub mod egl;
+
+#[cfg(all(feature = "android-props", any(feature = "mali-core", feature = "adreno-core")))]
+pub mod android_props;
+
+#[cfg(feature = "sysinfo-adapter")]
+pub mod adapter;
+
+#[cfg(feature = "agent")]
+pub mod agent;
+
+#[cfg(feature = "mock")]
+pub mod mock;
+
+#[cfg(feature = "tokio")]
+pub mod nonblocking;
+
+#[cfg(all(feature = "ffi", any(feature = "mali-kbase", feature = "adreno-kgsl")))]
+pub mod ffi;
+
+#[cfg(all(feature = "jni", any(feature = "mali-kbase", feature = "adreno-kgsl")))]
+pub mod jni;
+
 // Re-export common types
-pub use error::{GpuError, GpuResult};
-pub use info::{GpuInfo, GpuVendor, MaliData, AdrenoData};
+pub use budget::{BudgetProfile, MemoryBudget};
+pub use capabilities::GpuCapabilities;
+pub use error::{BuilderError, GpuError, GpuErrorKind, GpuResult};
+pub use instrument::{Instrumentation, QueryTrace};
+pub use options::{Fallback, QueryOptions, Validation};
+pub use perf::PerfEstimate;
+pub use report::ReportFormat;
+pub use info::{
+    AdrenoData, AdrenoSlices, ApiLevels, ApiVersion, ArchitectureCode, CoreGroup, DataConfidence, DisplayStyle, DriverSupport, GmuFirmwareVersion, GmuInfo, GpuInfo, GpuInfoDisplay, GpuVendor, MaliArch,
+    ExtendedMaliData, MaliData, MaliFlavor, MaliHwConfig, MaliL2Features, MaliPowerModel, SpecConfidence, SupportStatus,
+};
 
 /// Operation mode for Mali GPUs
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -31,25 +105,69 @@ pub enum Mode {
     Extended,
 }
 
+impl Mode {
+    /// Which `GpuInfo` fields this mode populates for `vendor`
+    ///
+    /// ```
+    /// use armgpuinfo::fields::Availability;
+    /// use armgpuinfo::{GpuVendor, Mode};
+    ///
+    /// let engines = |mode: Mode| {
+    ///     mode.capabilities(GpuVendor::Mali)
+    ///         .into_iter()
+    ///         .find(|c| c.field.name == "mali_data.num_exec_engines")
+    ///         .unwrap()
+    ///         .availability
+    /// };
+    /// assert_eq!(engines(Mode::Parity), Availability::Never);
+    /// assert_eq!(engines(Mode::Extended), Availability::Always);
+    /// ```
+    pub fn capabilities(self, vendor: GpuVendor) -> Vec<fields::FieldCapability> {
+        fields::capabilities(self, vendor)
+    }
+}
+
 // Mali-specific API (conditionally compiled)
-#[cfg(feature = "mali")]
-pub use mali::{query_mali, query_mali_with_mode};
+#[cfg(feature = "mali-kbase")]
+pub use mali::{query_mali, query_mali_with_mode, query_mali_with_opts};
+#[cfg(feature = "mali-drm")]
+pub use mali::query_mali_drm;
 
 // Adreno-specific API (conditionally compiled)
-#[cfg(feature = "adreno")]
+#[cfg(feature = "adreno-core")]
+pub use adreno::{Mode as AdrenoMode, QueryOptions as AdrenoQueryOptions};
+#[cfg(feature = "adreno-kgsl")]
 pub use adreno::query_adreno;
+#[cfg(feature = "adreno-drm")]
+pub use adreno::query_adreno_drm;
+
+#[cfg(feature = "vivante")]
+pub use vivante::query_vivante_drm;
+
+// Persistent device handle
+#[cfg(any(feature = "mali-kbase", feature = "adreno-kgsl"))]
+pub use device::GpuDevice;
+
+// Name-based reverse lookup
+#[cfg(any(feature = "mali-core", feature = "adreno-core"))]
+pub use resolve::{parse_renderer_string, resolve_name};
 
 // Auto-detection API (conditionally compiled)
-#[cfg(feature = "auto-detect")]
-pub use detect::query_gpu_auto;
+#[cfg(all(feature = "auto-detect", any(feature = "mali-kbase", feature = "adreno-kgsl")))]
+pub use detect::{
+    enumerate_gpus, query_gpu_auto, query_gpu_with_config, query_gpu_with_options, wait_for_gpu,
+    DetectConfig, DetectOptions, DeviceEvent, DeviceWatch, GpuReadiness,
+};
+#[cfg(all(feature = "auto-detect", any(feature = "mali-kbase", feature = "adreno-kgsl")))]
+pub use cache::query_gpu_cached;
 
 // Legacy API for backward compatibility (Mali-specific)
-#[cfg(feature = "mali")]
+#[cfg(feature = "mali-kbase")]
 pub fn query_gpu<P: AsRef<std::path::Path>>(device_path: P) -> GpuResult<GpuInfo> {
     query_mali(device_path)
 }
 
-#[cfg(feature = "mali")]
+#[cfg(feature = "mali-kbase")]
 pub fn query_gpu_with_mode<P: AsRef<std::path::Path>>(
     device_path: P,
     mode: Mode
@@ -58,7 +176,7 @@ pub fn query_gpu_with_mode<P: AsRef<std::path::Path>>(
 }
 
 /// Unified query function (requires auto-detect feature)
-#[cfg(feature = "auto-detect")]
+#[cfg(all(feature = "auto-detect", any(feature = "mali-kbase", feature = "adreno-kgsl")))]
 pub fn query_gpu_unified<P: AsRef<std::path::Path>>(
     device_path: Option<P>
 ) -> GpuResult<GpuInfo> {
@@ -0,0 +1,435 @@
+//! GpuInfo assembly from a kbase property buffer
+//!
+//! Shared by the kbase backend and offline replay of dumped buffers, which
+//! have no device node: without a path the power model, devfreq clocks and
+//! instance index are left empty.
+
+use std::borrow::Cow;
+use std::path::Path;
+
+use crate::capabilities::GpuCapabilities;
+use crate::error::{GpuError, GpuResult};
+use crate::fields::FieldId;
+use crate::info::{
+    CoreGroup, DataConfidence, ExtendedMaliData, GpuInfo, GpuVendor, MaliData, MaliFlavor, MaliL2Features,
+    SpecConfidence,
+};
+use crate::instrument::{timed, Phase};
+use crate::Mode;
+
+use super::database::{
+    extract_architecture, extrapolate_product, resolve_gpu_id, resolve_product, soc_variant, validate_gpu_info,
+    ProductEntry,
+};
+use super::parser::{parse_properties, ParsedProperties, ParserConfig};
+use super::power_model::read_power_model_for_device;
+use super::sysfs::{apply_clocks, read_clocks_for_device};
+
+/// How a property buffer is turned into a [`GpuInfo`]
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Assembly<'a> {
+    pub mode: Mode,
+    pub config: ParserConfig,
+    pub validate: bool,
+    pub flavor: Option<MaliFlavor>,
+    /// UK version from the handshake
+    pub driver_version: Option<(u16, u16)>,
+    pub device_path: Option<&'a Path>,
+}
+
+impl<'a> Assembly<'a> {
+    /// Offline assembly with the defaults of `mode`
+    pub fn offline(mode: Mode) -> Self {
+        Self {
+            mode,
+            config: match mode {
+                Mode::Parity => ParserConfig::PARITY,
+                Mode::Extended => ParserConfig::EXTENDED,
+            },
+            validate: mode == Mode::Extended,
+            flavor: None,
+            driver_version: None,
+            device_path: None,
+        }
+    }
+
+    pub fn build(&self, props: &[u8]) -> GpuResult<GpuInfo> {
+        let info = match self.mode {
+            Mode::Parity => self.parity(props)?,
+            Mode::Extended => self.extended(props)?,
+        };
+        if self.validate {
+            validate_gpu_info(&info)?;
+        }
+        Ok(info)
+    }
+
+    /// Minimal decoding like libgpuinfo; unknown products keep empty names
+    fn parity(&self, props: &[u8]) -> GpuResult<GpuInfo> {
+        let parsed = timed(Phase::Parse, || parse_properties(props, self.config))
+            .unwrap_or_else(|_| ParsedProperties::empty());
+
+        // A buffer without PRODUCT_ID means the driver did not answer the
+        // query we think it did; don't report an all-zero GPU
+        if parsed.gpu_id == 0 {
+            return Err(GpuError::InvalidGpuProperties(
+                "GET_GPUPROPS returned no product id".into(),
+            ));
+        }
+
+        let num_l2_bytes = if parsed.l2_log2_cache_size > 0 && parsed.num_l2_slices > 0 {
+            (1u64 << parsed.l2_log2_cache_size) * parsed.num_l2_slices
+        } else {
+            0
+        };
+
+        // Bus width is log2-encoded in L2_FEATURES[31:24]; zero means not reported
+        let num_bus_bits = if parsed.raw_l2_features != 0 {
+            1u64.checked_shl(((parsed.raw_l2_features >> 24) & 0xFF) as u32).unwrap_or(0)
+        } else {
+            0
+        };
+
+        // Try to get product info from database
+        let product =
+            timed(Phase::Lookup, || self.product(resolve_gpu_id(parsed.gpu_id), parsed.num_shader_cores));
+        let (gpu_name_cow, architecture_cow, arch_major, arch_minor) = match product {
+            Some(product_info) => {
+                let (major, minor) = extract_architecture(parsed.raw_gpu_id);
+                (product_info.name_as_cow(), product_info.architecture_as_cow(), major, minor)
+            }
+            None => (Cow::Borrowed(""), Cow::Borrowed(""), 0, 0),
+        };
+
+        let mut mali_data = MaliData {
+            // The product ID as the driver reported it, like libgpuinfo
+            gpu_id: parsed.gpu_id,
+            raw_gpu_id: parsed.raw_gpu_id,
+            shader_core_mask: parsed.shader_core_mask,
+            core_groups: core_groups(&parsed.core_group_masks, parsed.shader_core_mask),
+            num_l2_slices: parsed.num_l2_slices,
+            num_exec_engines: 0,
+            num_fp32_fmas_per_core: 0,
+            num_fp16_fmas_per_core: 0,
+            num_texels_per_core: 0,
+            num_pixels_per_core: 0,
+            flavor: self.flavor,
+            driver_version: self.driver_version,
+            arch: product.map(|p| p.architecture),
+            spec_confidence: product.map(|_| SpecConfidence::Measured),
+            power_model: self.device_path.and_then(read_power_model_for_device),
+            freq_table: Vec::new(),
+            current_freq_mhz: None,
+            min_freq_mhz: None,
+            max_freq_mhz: None,
+            measured_max_freq_mhz: None,
+            hw_config: Some(parsed.hw_config),
+            l2_features: None,
+            extended: None,
+            instance: self.device_path.and_then(instance_index),
+        };
+        if let Some(clocks) = self.device_path.and_then(read_clocks_for_device) {
+            apply_clocks(&mut mali_data, &clocks);
+        }
+
+        let mut info = GpuInfo {
+            vendor: GpuVendor::Mali,
+            gpu_name: gpu_name_cow,
+            architecture: architecture_cow,
+            architecture_major: arch_major,
+            architecture_minor: arch_minor,
+            num_shader_cores: parsed.num_shader_cores,
+            num_l2_bytes,
+            num_bus_bits,
+            mali_data: Some(mali_data),
+            adreno_data: None,
+            vivante_data: None,
+            capabilities: GpuCapabilities::default(),
+            confidence: DataConfidence::reported(parsed.num_shader_cores.into())
+                .max(DataConfidence::reported(num_l2_bytes)),
+            provenance: Vec::new(),
+            device_path: self.device_path.map(Path::to_path_buf),
+        };
+        info.capabilities = GpuCapabilities::of(&info);
+        Ok(info)
+    }
+
+    /// Database product; live queries take the variant the running SoC
+    /// ships
+    fn product(&self, gpu_id: u32, num_cores: u32) -> Option<&'static ProductEntry> {
+        let product = resolve_product(gpu_id, num_cores);
+        match self.device_path {
+            Some(_) => product.map(soc_variant),
+            None => product,
+        }
+    }
+
+    /// Full decoding with per-core throughput from the product database
+    fn extended(&self, props: &[u8]) -> GpuResult<GpuInfo> {
+        let parsed = timed(Phase::Parse, || parse_properties(props, self.config))?;
+
+        let gpu_id = resolve_gpu_id(parsed.gpu_id);
+        let (product_info, spec_confidence) = timed(Phase::Lookup, || {
+            match self.product(gpu_id, parsed.num_shader_cores) {
+                Some(product) => Ok((product, SpecConfidence::Measured)),
+                None => extrapolate_product(gpu_id, parsed.num_shader_cores)
+                    .map(|product| (product, SpecConfidence::Extrapolated))
+                    .ok_or(GpuError::UnsupportedGpu {
+                        id: parsed.gpu_id,
+                        cores: parsed.num_shader_cores,
+                    }),
+            }
+        })?;
+
+        let num_exec_engines = (product_info.get_num_exec_engines)(
+            parsed.num_shader_cores,
+            parsed.raw_core_features,
+            parsed.raw_thread_features,
+        );
+
+        let num_fp32_fmas_per_engine = (product_info.get_num_fp32_fmas_per_engine)(
+            parsed.num_shader_cores,
+            parsed.raw_core_features,
+            parsed.raw_thread_features,
+        );
+
+        let num_fp32_fmas_per_core = num_fp32_fmas_per_engine * num_exec_engines;
+
+        let num_texels_per_core = (product_info.get_num_texels)(
+            parsed.num_shader_cores,
+            parsed.raw_core_features,
+            parsed.raw_thread_features,
+        );
+
+        let num_pixels_per_core = (product_info.get_num_pixels)(
+            parsed.num_shader_cores,
+            parsed.raw_core_features,
+            parsed.raw_thread_features,
+        );
+
+        let (arch_major, arch_minor) = extract_architecture(parsed.raw_gpu_id);
+
+        let num_l2_bytes = (1u64 << parsed.l2_log2_cache_size) * parsed.num_l2_slices;
+        let num_bus_bits = 1u64 << ((parsed.raw_l2_features >> 24) & 0xFF);
+
+        let mut mali_data = MaliData {
+            gpu_id,
+            raw_gpu_id: parsed.raw_gpu_id,
+            shader_core_mask: parsed.shader_core_mask,
+            core_groups: core_groups(&parsed.core_group_masks, parsed.shader_core_mask),
+            num_l2_slices: parsed.num_l2_slices,
+            num_exec_engines,
+            num_fp32_fmas_per_core,
+            num_fp16_fmas_per_core: num_fp32_fmas_per_core * 2,
+            num_texels_per_core,
+            num_pixels_per_core,
+            flavor: self.flavor,
+            driver_version: self.driver_version,
+            arch: Some(product_info.architecture),
+            spec_confidence: Some(spec_confidence),
+            power_model: self.device_path.and_then(read_power_model_for_device),
+            freq_table: Vec::new(),
+            current_freq_mhz: None,
+            min_freq_mhz: None,
+            max_freq_mhz: None,
+            measured_max_freq_mhz: None,
+            hw_config: Some(parsed.hw_config),
+            l2_features: MaliL2Features::decode(parsed.raw_l2_features),
+            extended: Some(extended_data(&parsed)),
+            instance: self.device_path.and_then(instance_index),
+        };
+        if let Some(clocks) = self.device_path.and_then(read_clocks_for_device) {
+            apply_clocks(&mut mali_data, &clocks);
+        }
+
+        // Never present a neighbour's marketing name as this GPU's
+        let gpu_name = match spec_confidence {
+            SpecConfidence::Extrapolated => Cow::Owned(format!(
+                "Mali ({} unknown, 0x{:04x})",
+                product_info.architecture, gpu_id
+            )),
+            _ => product_info.name_as_cow(),
+        };
+
+        let mut info = GpuInfo {
+            vendor: GpuVendor::Mali,
+            gpu_name,
+            architecture: product_info.architecture_as_cow(),
+            architecture_major: arch_major,
+            architecture_minor: arch_minor,
+            num_shader_cores: parsed.num_shader_cores,
+            num_l2_bytes,
+            num_bus_bits,
+            mali_data: Some(mali_data),
+            adreno_data: None,
+            vivante_data: None,
+            capabilities: GpuCapabilities::default(),
+            confidence: DataConfidence::reported(parsed.num_shader_cores.into())
+                .max(DataConfidence::reported(num_l2_bytes)),
+            provenance: Vec::new(),
+            device_path: self.device_path.map(Path::to_path_buf),
+        };
+        info.provenance = provenance(&info);
+        info.capabilities = GpuCapabilities::of(&info);
+        Ok(info)
+    }
+}
+
+/// [`GpuInfo`] of a database product with `cores` shader cores and nothing
+/// read from hardware, for backends that only know the product name
+#[cfg(any(feature = "egl", feature = "android-props"))]
+pub(crate) fn product_gpu_info(entry: &'static ProductEntry, cores: u32) -> GpuInfo {
+    // GPU_ID register with revision r0p0, as the sysfs fallback rebuilds it
+    let raw_gpu_id = u64::from(entry.id) << 16;
+    let (architecture_major, architecture_minor) = extract_architecture(raw_gpu_id);
+    let shader_core_mask = if cores > 0 { (1u64 << cores) - 1 } else { 0 };
+    let mut info = GpuInfo {
+        vendor: GpuVendor::Mali,
+        gpu_name: entry.name_as_cow(),
+        architecture: entry.architecture_as_cow(),
+        architecture_major,
+        architecture_minor,
+        num_shader_cores: cores,
+        num_l2_bytes: 0,
+        num_bus_bits: 0,
+        mali_data: Some(MaliData {
+            gpu_id: entry.id,
+            raw_gpu_id,
+            shader_core_mask,
+            core_groups: core_groups(&[], shader_core_mask),
+            num_l2_slices: 0,
+            num_exec_engines: 0,
+            num_fp32_fmas_per_core: 0,
+            num_fp16_fmas_per_core: 0,
+            num_texels_per_core: 0,
+            num_pixels_per_core: 0,
+            flavor: None,
+            driver_version: None,
+            arch: Some(entry.architecture),
+            spec_confidence: Some(SpecConfidence::Measured),
+            power_model: None,
+            freq_table: Vec::new(),
+            current_freq_mhz: None,
+            min_freq_mhz: None,
+            max_freq_mhz: None,
+            measured_max_freq_mhz: None,
+            hw_config: None,
+            l2_features: None,
+            extended: None,
+            instance: None,
+        }),
+        adreno_data: None,
+        vivante_data: None,
+        capabilities: GpuCapabilities::default(),
+        confidence: DataConfidence::DatabaseDerived,
+        provenance: Vec::new(),
+        device_path: None,
+    };
+    info.capabilities = GpuCapabilities::of(&info);
+    info
+}
+
+/// Core groups with an accepted mask; a single group of every present core
+/// if the driver reported none
+pub(crate) fn core_groups(group_masks: &[u64], shader_core_mask: u64) -> Vec<CoreGroup> {
+    let groups: Vec<CoreGroup> =
+        group_masks.iter().filter(|&&mask| mask != 0).map(|&mask| CoreGroup::new(mask)).collect();
+    if groups.is_empty() && shader_core_mask != 0 {
+        return vec![CoreGroup::new(shader_core_mask)];
+    }
+    groups
+}
+
+/// Limits the kernel decoded, falling back to the raw registers
+pub(crate) fn extended_data(parsed: &ParsedProperties) -> ExtendedMaliData {
+    let hw = &parsed.hw_config;
+    let or_raw = |decoded: u32, raw: u32| if decoded != 0 { decoded } else { raw };
+    let mut texture_features = parsed.texture_features;
+    for (decoded, raw) in texture_features.iter_mut().zip(hw.texture_features) {
+        *decoded = or_raw(*decoded, raw);
+    }
+    ExtendedMaliData {
+        tiler_bin_size_bytes: or_raw(parsed.tiler_bin_size_bytes, hw.tiler_bin_size_bytes()),
+        tiler_max_active_levels: or_raw(parsed.tiler_max_active_levels, hw.tiler_max_active_levels()),
+        max_threads: or_raw(parsed.max_threads, hw.thread_max_threads),
+        max_workgroup_size: or_raw(parsed.max_workgroup_size, hw.thread_max_workgroup_size),
+        max_barrier_size: or_raw(parsed.max_barrier_size, hw.thread_max_barrier_size),
+        max_registers: parsed.max_registers,
+        num_address_spaces: hw.num_address_spaces(),
+        num_coherency_groups: parsed.num_coherency_groups,
+        coherency_mode: or_raw(parsed.coherency, hw.coherency_mode),
+        texture_features,
+    }
+}
+
+/// Per-field confidence of a result decoded from the property registers
+///
+/// Register values are measured; names and per-core rates carry the
+/// confidence of the database entry.
+pub(crate) fn provenance(info: &GpuInfo) -> Vec<(FieldId, DataConfidence)> {
+    let Some(mali) = &info.mali_data else {
+        return Vec::new();
+    };
+    let measured = DataConfidence::Measured;
+    let database = mali.spec_confidence.map_or(DataConfidence::Unknown, DataConfidence::from);
+
+    let mut provenance = vec![
+        (FieldId::GpuId, measured),
+        (FieldId::RawGpuId, measured),
+        (FieldId::ArchitectureMajor, measured),
+        (FieldId::ArchitectureMinor, measured),
+        (FieldId::ShaderCoreMask, DataConfidence::reported(mali.shader_core_mask)),
+        (FieldId::NumShaderCores, DataConfidence::reported(info.num_shader_cores.into())),
+        (FieldId::NumL2Slices, DataConfidence::reported(mali.num_l2_slices)),
+        (FieldId::NumL2Bytes, DataConfidence::reported(info.num_l2_bytes)),
+        (FieldId::NumBusBits, DataConfidence::reported(info.num_bus_bits)),
+        (FieldId::GpuName, database),
+        (FieldId::Architecture, database),
+        (FieldId::Arch, database),
+        (FieldId::NumExecEngines, database),
+        (FieldId::NumFp32FmasPerCore, database),
+        (FieldId::NumFp16FmasPerCore, database),
+        (FieldId::NumTexelsPerCore, database),
+        (FieldId::NumPixelsPerCore, database),
+    ];
+    if mali.flavor.is_some() {
+        provenance.push((FieldId::Flavor, measured));
+    }
+    provenance
+}
+
+/// Instance index of a kbase node, `N` of `/dev/maliN`
+pub fn instance_index(device_path: &Path) -> Option<u32> {
+    device_path.file_name()?.to_str()?.strip_prefix("mali")?.parse().ok()
+}
+
+/// Decode a dumped `GET_GPUPROPS` buffer as a query in `mode` would
+///
+/// Lets buffers dumped with `dump_raw_properties` on unsupported devices be
+/// replayed without the hardware. Extended mode validates the result.
+///
+/// ```
+/// use armgpuinfo::mali::parse_snapshot;
+/// use armgpuinfo::Mode;
+///
+/// // kbase key: property ID << 2 | size code (2 = u32, 3 = u64)
+/// let u32_prop = |id: u32, value: u32| [((id << 2) | 2).to_le_bytes(), value.to_le_bytes()].concat();
+/// let raw = [
+///     u32_prop(1, 0xa867),      // PRODUCT_ID: Mali-G610
+///     u32_prop(14, 18),         // L2_LOG2_CACHE_SIZE: 256 KB per slice
+///     u32_prop(15, 2),          // L2_NUM_L2_SLICES
+///     u32_prop(62, 1),          // COHERENCY_NUM_CORE_GROUPS
+///     u32_prop(64, 0b1111),     // COHERENCY_GROUP_0: 4 cores
+/// ]
+/// .concat();
+///
+/// let info = parse_snapshot(&raw, Mode::Parity)?;
+/// assert_eq!(info.gpu_name, "Mali-G610");
+/// assert_eq!(info.num_shader_cores, 4);
+/// assert_eq!(info.num_l2_bytes, 512 * 1024);
+/// assert_eq!(info.device_path, None);
+/// # Ok::<(), armgpuinfo::GpuError>(())
+/// ```
+pub fn parse_snapshot(raw: &[u8], mode: Mode) -> GpuResult<GpuInfo> {
+    Assembly::offline(mode).build(raw)
+}
@@ -0,0 +1,446 @@
+//! Hardware performance counters (HWCNT)
+//!
+//! kbase exposes the counter dumps through a reader fd created with
+//! `KBASE_IOCTL_HWCNT_READER_SETUP`. A dump is a sequence of blocks of 64
+//! 32-bit counters: the front end (job manager or CSF), the tiler, one
+//! memory-system block per L2 slice and one shader-core block per bit up to
+//! the highest bit of the core mask (layout version 5, Bifrost onwards).
+//! The first four counters of each block are a header.
+//!
+//! Counter positions within a block differ per architecture; [`Counter`]
+//! names the commonly used ones and [`counter_index`] maps them. Decoding
+//! ([`CounterDump::from_bytes`]) works offline; [`HwcntReader`] needs the
+//! `mali-kbase` feature and access to the device node.
+
+use crate::info::{GpuInfo, MaliArch, MaliFlavor};
+
+/// Counters per block
+pub const BLOCK_COUNTERS: usize = 64;
+/// Bytes per block
+pub const BLOCK_BYTES: usize = BLOCK_COUNTERS * 4;
+/// Header counters at the start of each block
+pub const BLOCK_HEADER_COUNTERS: usize = 4;
+/// Dump layout version this module decodes
+pub const SUPPORTED_HW_VERSION: u32 = 5;
+
+/// Counter block type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CounterBlock {
+    /// Job manager (JM) or command stream front end (CSF)
+    FrontEnd,
+    Tiler,
+    /// L2 cache and MMU, one block per L2 slice
+    MemorySystem,
+    /// One block per shader core
+    ShaderCore,
+}
+
+/// Named hardware counters
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Counter {
+    /// Cycles the GPU had any work queued or running
+    GpuActive,
+    /// Cycles the tiler was active
+    TilerActive,
+    /// Cycles a shader core ran fragment work
+    FragActive,
+    /// Cycles a shader core ran compute or vertex work
+    ComputeActive,
+    /// Cycles the shader core execution engines were active
+    ExecCoreActive,
+    /// L2 read lookups
+    L2ReadLookup,
+    /// L2 write lookups
+    L2WriteLookup,
+    /// External reads issued by the L2, i.e. read misses
+    L2ExtRead,
+}
+
+impl Counter {
+    /// Every named counter
+    pub const ALL: [Counter; 8] = [
+        Counter::GpuActive,
+        Counter::TilerActive,
+        Counter::FragActive,
+        Counter::ComputeActive,
+        Counter::ExecCoreActive,
+        Counter::L2ReadLookup,
+        Counter::L2WriteLookup,
+        Counter::L2ExtRead,
+    ];
+
+    /// Name as used in Arm's counter documentation
+    pub const fn name(self) -> &'static str {
+        match self {
+            Counter::GpuActive => "GPU_ACTIVE",
+            Counter::TilerActive => "TILER_ACTIVE",
+            Counter::FragActive => "FRAG_ACTIVE",
+            Counter::ComputeActive => "COMPUTE_ACTIVE",
+            Counter::ExecCoreActive => "EXEC_CORE_ACTIVE",
+            Counter::L2ReadLookup => "L2_READ_LOOKUP",
+            Counter::L2WriteLookup => "L2_WRITE_LOOKUP",
+            Counter::L2ExtRead => "L2_EXT_READ",
+        }
+    }
+
+    /// Block the counter lives in
+    pub const fn block(self) -> CounterBlock {
+        match self {
+            Counter::GpuActive => CounterBlock::FrontEnd,
+            Counter::TilerActive => CounterBlock::Tiler,
+            Counter::FragActive | Counter::ComputeActive | Counter::ExecCoreActive => {
+                CounterBlock::ShaderCore
+            }
+            Counter::L2ReadLookup | Counter::L2WriteLookup | Counter::L2ExtRead => {
+                CounterBlock::MemorySystem
+            }
+        }
+    }
+}
+
+/// Position of `counter` within its block, `None` if the architecture has
+/// no such counter or its position is not known
+///
+/// CSF front ends use a different block layout from job managers.
+pub const fn counter_index(arch: MaliArch, flavor: MaliFlavor, counter: Counter) -> Option<usize> {
+    let csf = matches!(flavor, MaliFlavor::Csf);
+    match (arch, counter) {
+        (_, Counter::GpuActive) => Some(if csf { 4 } else { 6 }),
+        (_, Counter::FragActive) => Some(4),
+        (_, Counter::ComputeActive) => Some(22),
+        // Midgard has no tiler/execution-engine activity or L2 lookup
+        // counters at these positions
+        (MaliArch::Midgard, _) => None,
+        (_, Counter::TilerActive) => Some(4),
+        (_, Counter::ExecCoreActive) => Some(26),
+        (_, Counter::L2ReadLookup) => Some(26),
+        (_, Counter::L2WriteLookup) => Some(27),
+        (_, Counter::L2ExtRead) => Some(29),
+    }
+}
+
+/// Block arrangement of a version 5 dump
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockLayout {
+    pub num_l2_slices: usize,
+    /// Shader-core blocks, highest set bit of the core mask plus one
+    pub num_core_blocks: usize,
+}
+
+impl BlockLayout {
+    pub fn new(num_l2_slices: u64, shader_core_mask: u64) -> Self {
+        Self {
+            num_l2_slices: num_l2_slices as usize,
+            num_core_blocks: (u64::BITS - shader_core_mask.leading_zeros()) as usize,
+        }
+    }
+
+    /// Layout for a queried Mali GPU, `None` without Mali data
+    pub fn from_info(info: &GpuInfo) -> Option<Self> {
+        let mali = info.mali_data.as_ref()?;
+        Some(Self::new(mali.num_l2_slices, mali.shader_core_mask))
+    }
+
+    /// Number of instances of `block`
+    pub fn instances(&self, block: CounterBlock) -> usize {
+        match block {
+            CounterBlock::FrontEnd | CounterBlock::Tiler => 1,
+            CounterBlock::MemorySystem => self.num_l2_slices,
+            CounterBlock::ShaderCore => self.num_core_blocks,
+        }
+    }
+
+    /// Total number of blocks in a dump
+    pub fn block_count(&self) -> usize {
+        2 + self.num_l2_slices + self.num_core_blocks
+    }
+
+    /// Index of the `instance`-th block of type `block` within the dump
+    pub fn block_index(&self, block: CounterBlock, instance: usize) -> Option<usize> {
+        if instance >= self.instances(block) {
+            return None;
+        }
+        Some(match block {
+            CounterBlock::FrontEnd => 0,
+            CounterBlock::Tiler => 1,
+            CounterBlock::MemorySystem => 2 + instance,
+            CounterBlock::ShaderCore => 2 + self.num_l2_slices + instance,
+        })
+    }
+}
+
+/// One decoded counter dump
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CounterDump {
+    /// Dump time in nanoseconds, as reported by the driver
+    pub timestamp_ns: u64,
+    pub arch: MaliArch,
+    pub flavor: MaliFlavor,
+    pub layout: BlockLayout,
+    values: Vec<u32>,
+}
+
+impl CounterDump {
+    /// Decode a raw little-endian dump buffer
+    ///
+    /// Returns `None` if the buffer is shorter than the layout requires.
+    ///
+    /// ```
+    /// use armgpuinfo::mali::counters::{BlockLayout, Counter, CounterDump, BLOCK_BYTES};
+    /// use armgpuinfo::{MaliArch, MaliFlavor};
+    ///
+    /// // One L2 slice, cores 0 and 2 present: FE, tiler, L2, SC0, SC1, SC2
+    /// let layout = BlockLayout::new(1, 0b101);
+    /// let mut bytes = vec![0u8; layout.block_count() * BLOCK_BYTES];
+    /// bytes[6 * 4] = 100; // FE GPU_ACTIVE
+    /// bytes[3 * BLOCK_BYTES + 4 * 4] = 30; // SC0 FRAG_ACTIVE
+    /// bytes[5 * BLOCK_BYTES + 4 * 4] = 40; // SC2 FRAG_ACTIVE
+    ///
+    /// let dump =
+    ///     CounterDump::from_bytes(&bytes, MaliArch::Valhall, MaliFlavor::JobManager, layout, 0)
+    ///         .unwrap();
+    /// assert_eq!(dump.get(Counter::GpuActive), Some(100));
+    /// assert_eq!(dump.get(Counter::FragActive), Some(70));
+    /// ```
+    pub fn from_bytes(
+        bytes: &[u8],
+        arch: MaliArch,
+        flavor: MaliFlavor,
+        layout: BlockLayout,
+        timestamp_ns: u64,
+    ) -> Option<Self> {
+        let needed = layout.block_count() * BLOCK_BYTES;
+        let values = bytes
+            .get(..needed)?
+            .chunks_exact(4)
+            .map(|c| u32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+            .collect();
+        Some(Self { timestamp_ns, arch, flavor, layout, values })
+    }
+
+    /// All counters of one block instance, header included
+    pub fn block(&self, block: CounterBlock, instance: usize) -> Option<&[u32]> {
+        let start = self.layout.block_index(block, instance)? * BLOCK_COUNTERS;
+        self.values.get(start..start + BLOCK_COUNTERS)
+    }
+
+    /// Value of `counter` in one block instance
+    pub fn get_instance(&self, counter: Counter, instance: usize) -> Option<u32> {
+        let index = counter_index(self.arch, self.flavor, counter)?;
+        self.block(counter.block(), instance)?.get(index).copied()
+    }
+
+    /// Value of `counter` summed over all instances of its block
+    pub fn get(&self, counter: Counter) -> Option<u64> {
+        counter_index(self.arch, self.flavor, counter)?;
+        let instances = self.layout.instances(counter.block());
+        (0..instances)
+            .map(|i| self.get_instance(counter, i).map(u64::from))
+            .sum()
+    }
+
+    /// Every named counter the architecture has, summed over instances
+    pub fn named(&self) -> Vec<(Counter, u64)> {
+        Counter::ALL
+            .into_iter()
+            .filter_map(|c| self.get(c).map(|v| (c, v)))
+            .collect()
+    }
+
+    /// Share of L2 read lookups (0.0 - 1.0) that did not go to memory
+    pub fn l2_read_hit_rate(&self) -> Option<f64> {
+        let lookups = self.get(Counter::L2ReadLookup)?;
+        let misses = self.get(Counter::L2ExtRead)?;
+        (lookups > 0).then(|| lookups.saturating_sub(misses) as f64 / lookups as f64)
+    }
+}
+
+#[cfg(feature = "mali-kbase")]
+pub use reader::HwcntReader;
+
+#[cfg(feature = "mali-kbase")]
+mod reader {
+    use std::fs::File;
+    use std::os::unix::io::{AsRawFd, FromRawFd};
+    use std::path::Path;
+    use std::ptr::NonNull;
+    use std::time::Duration;
+
+    use crate::error::{GpuError, GpuResult};
+    use crate::info::{MaliArch, MaliFlavor};
+    use crate::Mode;
+
+    use super::super::ioctl::{
+        hwcnt_reader_clear, hwcnt_reader_dump, hwcnt_reader_get_buffer,
+        hwcnt_reader_get_buffer_size, hwcnt_reader_get_hwver, hwcnt_reader_put_buffer,
+        kbase_hwcnt_reader_setup, nr, reader_nr, KbaseHwcntReaderMetadata,
+        KbaseIoctlHwcntReaderSetup,
+    };
+    use super::super::query::{open_session, query_session};
+    use super::{BlockLayout, CounterDump, SUPPORTED_HW_VERSION};
+
+    /// Buffers the kernel rotates through
+    const BUFFER_COUNT: u32 = 4;
+    /// How long to wait for a requested dump
+    const DUMP_TIMEOUT: Duration = Duration::from_secs(1);
+
+    /// An open HWCNT reader on one kbase device
+    ///
+    /// ```no_run
+    /// use armgpuinfo::mali::counters::{Counter, HwcntReader};
+    ///
+    /// let mut reader = HwcntReader::open("/dev/mali0")?;
+    /// reader.clear()?;
+    /// std::thread::sleep(std::time::Duration::from_millis(100));
+    /// let dump = reader.dump()?;
+    /// println!("GPU_ACTIVE: {:?}", dump.get(Counter::GpuActive));
+    /// # Ok::<(), armgpuinfo::GpuError>(())
+    /// ```
+    #[derive(Debug)]
+    pub struct HwcntReader {
+        reader: File,
+        // The kbase context owns the counter session and must outlive the
+        // reader fd
+        _context: File,
+        mapping: NonNull<u8>,
+        buffer_size: usize,
+        arch: MaliArch,
+        flavor: MaliFlavor,
+        layout: BlockLayout,
+    }
+
+    // SAFETY: the mapping is read-only and owned exclusively by the reader
+    unsafe impl Send for HwcntReader {}
+
+    impl HwcntReader {
+        /// Open `device_path`, query the block layout and enable all counters
+        pub fn open<P: AsRef<Path>>(device_path: P) -> GpuResult<Self> {
+            let device_path = device_path.as_ref();
+            let (context, handshake) = open_session(device_path)?;
+            let info = query_session(context.as_raw_fd(), handshake, device_path, &Mode::Parity.into())?;
+            let mali = info.mali_data.as_ref().ok_or(GpuError::DeviceNotFound)?;
+            let arch = mali.arch.ok_or(GpuError::UnsupportedGpu {
+                id: mali.gpu_id,
+                cores: info.num_shader_cores,
+            })?;
+            let flavor = handshake.map_or(MaliFlavor::JobManager, |(flavor, _)| flavor);
+            let layout = BlockLayout::new(mali.num_l2_slices, mali.shader_core_mask);
+
+            let setup = KbaseIoctlHwcntReaderSetup {
+                buffer_count: BUFFER_COUNT,
+                fe_bm: u32::MAX,
+                shader_bm: u32::MAX,
+                tiler_bm: u32::MAX,
+                mmu_l2_bm: u32::MAX,
+            };
+            let fd = unsafe { kbase_hwcnt_reader_setup(context.as_raw_fd(), &setup) }
+                .map_err(|e| ioctl_error(nr::HWCNT_READER_SETUP, e))?;
+            // SAFETY: the ioctl returned a new fd that nothing else owns
+            let reader = unsafe { File::from_raw_fd(fd) };
+
+            let mut hw_version = 0u32;
+            unsafe { hwcnt_reader_get_hwver(reader.as_raw_fd(), &mut hw_version) }
+                .map_err(|e| ioctl_error(reader_nr::GET_HWVER, e))?;
+            if hw_version != SUPPORTED_HW_VERSION {
+                return Err(GpuError::VersionMismatch {
+                    required: format!("HWCNT layout {SUPPORTED_HW_VERSION}"),
+                    found: hw_version.to_string(),
+                });
+            }
+
+            let mut buffer_size = 0u32;
+            unsafe { hwcnt_reader_get_buffer_size(reader.as_raw_fd(), &mut buffer_size) }
+                .map_err(|e| ioctl_error(reader_nr::GET_BUFFER_SIZE, e))?;
+            let buffer_size = buffer_size as usize;
+            let needed = layout.block_count() * super::BLOCK_BYTES;
+            if buffer_size < needed {
+                return Err(GpuError::BufferTooSmall { expected: needed, actual: buffer_size });
+            }
+
+            // SAFETY: maps the kernel's dump buffers read-only; unmapped in Drop
+            let mapping = unsafe {
+                libc::mmap(
+                    std::ptr::null_mut(),
+                    buffer_size * BUFFER_COUNT as usize,
+                    libc::PROT_READ,
+                    libc::MAP_PRIVATE,
+                    reader.as_raw_fd(),
+                    0,
+                )
+            };
+            if mapping == libc::MAP_FAILED {
+                return Err(GpuError::Io(std::io::Error::last_os_error()));
+            }
+            let mapping = NonNull::new(mapping.cast()).ok_or(GpuError::DeviceNotFound)?;
+
+            Ok(Self { reader, _context: context, mapping, buffer_size, arch, flavor, layout })
+        }
+
+        /// Block layout of the dumps
+        pub fn layout(&self) -> BlockLayout {
+            self.layout
+        }
+
+        /// Reset all counters to zero
+        pub fn clear(&mut self) -> GpuResult<()> {
+            unsafe { hwcnt_reader_clear(self.reader.as_raw_fd(), &0) }
+                .map_err(|e| ioctl_error(reader_nr::CLEAR, e))?;
+            Ok(())
+        }
+
+        /// Dump the counters accumulated since the previous dump or clear
+        pub fn dump(&mut self) -> GpuResult<CounterDump> {
+            let fd = self.reader.as_raw_fd();
+            unsafe { hwcnt_reader_dump(fd, &0) }.map_err(|e| ioctl_error(reader_nr::DUMP, e))?;
+
+            let mut pollfd = libc::pollfd { fd, events: libc::POLLIN, revents: 0 };
+            // SAFETY: one valid pollfd
+            let ready = unsafe { libc::poll(&mut pollfd, 1, DUMP_TIMEOUT.as_millis() as i32) };
+            if ready <= 0 {
+                return Err(GpuError::Io(if ready == 0 {
+                    std::io::ErrorKind::TimedOut.into()
+                } else {
+                    std::io::Error::last_os_error()
+                }));
+            }
+
+            let mut meta = KbaseHwcntReaderMetadata::default();
+            unsafe { hwcnt_reader_get_buffer(fd, &mut meta) }
+                .map_err(|e| ioctl_error(reader_nr::GET_BUFFER, e))?;
+
+            let dump = if meta.buffer_idx < BUFFER_COUNT {
+                // SAFETY: buffer_idx is in range of the mapping, and the
+                // kernel does not write the buffer until it is put back
+                let bytes = unsafe {
+                    std::slice::from_raw_parts(
+                        self.mapping.as_ptr().add(meta.buffer_idx as usize * self.buffer_size),
+                        self.buffer_size,
+                    )
+                };
+                CounterDump::from_bytes(bytes, self.arch, self.flavor, self.layout, meta.timestamp)
+            } else {
+                None
+            };
+
+            unsafe { hwcnt_reader_put_buffer(fd, &meta) }
+                .map_err(|e| ioctl_error(reader_nr::PUT_BUFFER, e))?;
+            dump.ok_or_else(|| {
+                GpuError::InvalidData(format!("HWCNT buffer index {} out of range", meta.buffer_idx))
+            })
+        }
+    }
+
+    impl Drop for HwcntReader {
+        fn drop(&mut self) {
+            // SAFETY: unmaps exactly the region mapped in open
+            unsafe {
+                libc::munmap(self.mapping.as_ptr().cast(), self.buffer_size * BUFFER_COUNT as usize);
+            }
+        }
+    }
+
+    fn ioctl_error(request: u8, e: nix::Error) -> GpuError {
+        GpuError::IoctlFailed { request: u64::from(request), source: e.into() }
+    }
+}
@@ -1,563 +1,54 @@
-use std::collections::HashMap;
-use std::borrow::Cow; 
-use std::sync::OnceLock;
-use std::fs::OpenOptions;
-// Product database structures
-pub struct ProductEntry {
-    pub id: u32,
-    pub mask: u32,
-    pub min_cores: u32,
-    pub name: &'static str,
-    pub architecture: &'static str,
-    pub get_num_fp32_fmas_per_engine: fn(u32, u32, u32) -> u32,
-    pub get_num_texels: fn(u32, u32, u32) -> u32,
-    pub get_num_pixels: fn(u32, u32, u32) -> u32,
-    pub get_num_exec_engines: fn(u32, u32, u32) -> u32,
-}
-
-// Helper für Cow-Konvertierung
-impl ProductEntry {
-    pub fn name_as_cow(&self) -> Cow<'static, str> {
-        Cow::Borrowed(self.name)
-    }
-    
-    pub fn architecture_as_cow(&self) -> Cow<'static, str> {
-        Cow::Borrowed(self.architecture)
-    }
-}
-
-const MASK_OLD: u32 = 0xFFFF;
-const MASK_NEW: u32 = 0xF00F;
-
-// Helper functions for product database
-pub fn get_num_1(_: u32, _: u32, _: u32) -> u32 { 1 }
-pub fn get_num_2(_: u32, _: u32, _: u32) -> u32 { 2 }
-pub fn get_num_3(_: u32, _: u32, _: u32) -> u32 { 3 }
-pub fn get_num_4(_: u32, _: u32, _: u32) -> u32 { 4 }
-pub fn get_num_8(_: u32, _: u32, _: u32) -> u32 { 8 }
-pub fn get_num_16(_: u32, _: u32, _: u32) -> u32 { 16 }
-pub fn get_num_32(_: u32, _: u32, _: u32) -> u32 { 32 }
-pub fn get_num_64(_: u32, _: u32, _: u32) -> u32 { 64 }
-
-pub fn get_num_eng_g31(core_count: u32, _: u32, thread_features: u32) -> u32 {
-    if core_count == 1 && (thread_features & 0xFFFF) == 0x2000 { 1 } else { 2 }
-}
-
-pub fn get_num_eng_g51(core_count: u32, _: u32, thread_features: u32) -> u32 {
-    if core_count == 1 && (thread_features & 0xFFFF) == 0x2000 { 1 } else { 3 }
-}
-
-pub fn get_num_eng_g52(_: u32, core_features: u32, _: u32) -> u32 { core_features & 0xF }
-
-pub fn get_num_fma_g510(_: u32, core_features: u32, _: u32) -> u32 {
-    let variant = core_features & 0xF;
-    match variant { 0 => 16, 2 | 3 => 24, _ => 32 }
-}
-
-pub fn get_num_tex_g510(_: u32, core_features: u32, _: u32) -> u32 {
-    let variant = core_features & 0xF;
-    match variant { 0 | 5 => 2, 1 | 2 | 6 => 4, _ => 8 }
-}
-
-pub fn get_num_pix_g510(_: u32, core_features: u32, _: u32) -> u32 {
-    let variant = core_features & 0xF;
-    match variant { 0 | 1 | 5 | 6 => 2, _ => 4 }
-}
-
-pub fn get_num_eng_g510(_: u32, core_features: u32, _: u32) -> u32 {
-    let variant = core_features & 0xF;
-    match variant { 0 | 1 | 5 | 6 => 1, _ => 2 }
-}
-
-const PRODUCT_VERSIONS: [ProductEntry; 38] = [
-    // Mali-T600 series
-    ProductEntry {
-        id: 0x6956,
-        mask: MASK_OLD,
-        min_cores: 1,
-        name: "Mali-T600",
-        architecture: "Midgard",
-        get_num_fp32_fmas_per_engine: get_num_4,
-        get_num_texels: get_num_1,
-        get_num_pixels: get_num_1,
-        get_num_exec_engines: get_num_2,
-    },
-    ProductEntry {
-        id: 0x0620,
-        mask: MASK_OLD,
-        min_cores: 1,
-        name: "Mali-T620",
-        architecture: "Midgard",
-        get_num_fp32_fmas_per_engine: get_num_4,
-        get_num_texels: get_num_1,
-        get_num_pixels: get_num_1,
-        get_num_exec_engines: get_num_2,
-    },
-    ProductEntry {
-        id: 0x0720,
-        mask: MASK_OLD,
-        min_cores: 1,
-        name: "Mali-T720",
-        architecture: "Midgard",
-        get_num_fp32_fmas_per_engine: get_num_4,
-        get_num_texels: get_num_1,
-        get_num_pixels: get_num_1,
-        get_num_exec_engines: get_num_1,
-    },
-    ProductEntry {
-        id: 0x0750,
-        mask: MASK_OLD,
-        min_cores: 1,
-        name: "Mali-T760",
-        architecture: "Midgard",
-        get_num_fp32_fmas_per_engine: get_num_4,
-        get_num_texels: get_num_1,
-        get_num_pixels: get_num_1,
-        get_num_exec_engines: get_num_2,
-    },
-    ProductEntry {
-        id: 0x0820,
-        mask: MASK_OLD,
-        min_cores: 1,
-        name: "Mali-T820",
-        architecture: "Midgard",
-        get_num_fp32_fmas_per_engine: get_num_4,
-        get_num_texels: get_num_1,
-        get_num_pixels: get_num_1,
-        get_num_exec_engines: get_num_1,
-    },
-    ProductEntry {
-        id: 0x0830,
-        mask: MASK_OLD,
-        min_cores: 1,
-        name: "Mali-T830",
-        architecture: "Midgard",
-        get_num_fp32_fmas_per_engine: get_num_4,
-        get_num_texels: get_num_1,
-        get_num_pixels: get_num_1,
-        get_num_exec_engines: get_num_2,
-    },
-    ProductEntry {
-        id: 0x0860,
-        mask: MASK_OLD,
-        min_cores: 1,
-        name: "Mali-T860",
-        architecture: "Midgard",
-        get_num_fp32_fmas_per_engine: get_num_4,
-        get_num_texels: get_num_1,
-        get_num_pixels: get_num_1,
-        get_num_exec_engines: get_num_2,
-    },
-    ProductEntry {
-        id: 0x0880,
-        mask: MASK_OLD,
-        min_cores: 1,
-        name: "Mali-T880",
-        architecture: "Midgard",
-        get_num_fp32_fmas_per_engine: get_num_4,
-        get_num_texels: get_num_1,
-        get_num_pixels: get_num_1,
-        get_num_exec_engines: get_num_3,
-    },
-
-    // Mali-G71/G72 (Bifrost)
-    ProductEntry {
-        id: 0x6000,
-        mask: MASK_NEW,
-        min_cores: 1,
-        name: "Mali-G71",
-        architecture: "Bifrost",
-        get_num_fp32_fmas_per_engine: get_num_4,
-        get_num_texels: get_num_1,
-        get_num_pixels: get_num_1,
-        get_num_exec_engines: get_num_3,
-    },
-    ProductEntry {
-        id: 0x6001,
-        mask: MASK_NEW,
-        min_cores: 1,
-        name: "Mali-G72",
-        architecture: "Bifrost",
-        get_num_fp32_fmas_per_engine: get_num_4,
-        get_num_texels: get_num_1,
-        get_num_pixels: get_num_1,
-        get_num_exec_engines: get_num_3,
-    },
-
-    // Mali-G51/G76/G52/G31 (Bifrost)
-    ProductEntry {
-        id: 0x7000,
-        mask: MASK_NEW,
-        min_cores: 1,
-        name: "Mali-G51",
-        architecture: "Bifrost",
-        get_num_fp32_fmas_per_engine: get_num_4,
-        get_num_texels: get_num_2,
-        get_num_pixels: get_num_2,
-        get_num_exec_engines: get_num_eng_g51,
-    },
-    ProductEntry {
-        id: 0x7001,
-        mask: MASK_NEW,
-        min_cores: 1,
-        name: "Mali-G76",
-        architecture: "Bifrost",
-        get_num_fp32_fmas_per_engine: get_num_8,
-        get_num_texels: get_num_2,
-        get_num_pixels: get_num_2,
-        get_num_exec_engines: get_num_3,
-    },
-    ProductEntry {
-        id: 0x7002,
-        mask: MASK_NEW,
-        min_cores: 1,
-        name: "Mali-G52",
-        architecture: "Bifrost",
-        get_num_fp32_fmas_per_engine: get_num_8,
-        get_num_texels: get_num_2,
-        get_num_pixels: get_num_2,
-        get_num_exec_engines: get_num_eng_g52,
-    },
-    ProductEntry {
-        id: 0x7003,
-        mask: MASK_NEW,
-        min_cores: 1,
-        name: "Mali-G31",
-        architecture: "Bifrost",
-        get_num_fp32_fmas_per_engine: get_num_4,
-        get_num_texels: get_num_2,
-        get_num_pixels: get_num_2,
-        get_num_exec_engines: get_num_eng_g31,
-    },
-
-    // Mali-G77/G57/G68/G78 (Valhall)
-    ProductEntry {
-        id: 0x9000,
-        mask: MASK_NEW,
-        min_cores: 1,
-        name: "Mali-G77",
-        architecture: "Valhall",
-        get_num_fp32_fmas_per_engine: get_num_16,
-        get_num_texels: get_num_4,
-        get_num_pixels: get_num_2,
-        get_num_exec_engines: get_num_2,
-    },
-    ProductEntry {
-        id: 0x9001,
-        mask: MASK_NEW,
-        min_cores: 1,
-        name: "Mali-G57",
-        architecture: "Valhall",
-        get_num_fp32_fmas_per_engine: get_num_16,
-        get_num_texels: get_num_4,
-        get_num_pixels: get_num_2,
-        get_num_exec_engines: get_num_2,
-    },
-    ProductEntry {
-        id: 0x9003,
-        mask: MASK_NEW,
-        min_cores: 1,
-        name: "Mali-G57",
-        architecture: "Valhall",
-        get_num_fp32_fmas_per_engine: get_num_16,
-        get_num_texels: get_num_4,
-        get_num_pixels: get_num_2,
-        get_num_exec_engines: get_num_2,
-    },
-    ProductEntry {
-        id: 0x9004,
-        mask: MASK_NEW,
-        min_cores: 1,
-        name: "Mali-G68",
-        architecture: "Valhall",
-        get_num_fp32_fmas_per_engine: get_num_16,
-        get_num_texels: get_num_4,
-        get_num_pixels: get_num_2,
-        get_num_exec_engines: get_num_2,
-    },
-    ProductEntry {
-        id: 0x9002,
-        mask: MASK_NEW,
-        min_cores: 1,
-        name: "Mali-G78",
-        architecture: "Valhall",
-        get_num_fp32_fmas_per_engine: get_num_16,
-        get_num_texels: get_num_4,
-        get_num_pixels: get_num_2,
-        get_num_exec_engines: get_num_2,
-    },
-    ProductEntry {
-        id: 0x9005,
-        mask: MASK_NEW,
-        min_cores: 1,
-        name: "Mali-G78AE",
-        architecture: "Valhall",
-        get_num_fp32_fmas_per_engine: get_num_16,
-        get_num_texels: get_num_4,
-        get_num_pixels: get_num_2,
-        get_num_exec_engines: get_num_2,
-    },
-
-    // Mali-G710/G610 (Valhall)
-    ProductEntry {
-        id: 0xa002,
-        mask: MASK_NEW,
-        min_cores: 1,
-        name: "Mali-G710",
-        architecture: "Valhall",
-        get_num_fp32_fmas_per_engine: get_num_32,
-        get_num_texels: get_num_8,
-        get_num_pixels: get_num_4,
-        get_num_exec_engines: get_num_2,
-    },
-    ProductEntry {
-        id: 0xa007,
-        mask: MASK_NEW,
-        min_cores: 1,
-        name: "Mali-G610",
-        architecture: "Valhall",
-        get_num_fp32_fmas_per_engine: get_num_32,
-        get_num_texels: get_num_8,
-        get_num_pixels: get_num_4,
-        get_num_exec_engines: get_num_2,
-    },
-
-    // Mali-G510/G310 (Valhall)
-    ProductEntry {
-        id: 0xa003,
-        mask: MASK_NEW,
-        min_cores: 1,
-        name: "Mali-G510",
-        architecture: "Valhall",
-        get_num_fp32_fmas_per_engine: get_num_fma_g510,
-        get_num_texels: get_num_tex_g510,
-        get_num_pixels: get_num_pix_g510,
-        get_num_exec_engines: get_num_eng_g510,
-    },
-    ProductEntry {
-        id: 0xa004,
-        mask: MASK_NEW,
-        min_cores: 1,
-        name: "Mali-G310",
-        architecture: "Valhall",
-        get_num_fp32_fmas_per_engine: get_num_fma_g510,
-        get_num_texels: get_num_tex_g510,
-        get_num_pixels: get_num_pix_g510,
-        get_num_exec_engines: get_num_eng_g510,
-    },
+//! Runtime additions to the static product table of `armgpuinfo-core`
 
-    // Immortalis-G715/Mali-G715/G615
-    ProductEntry {
-        id: 0xb002,
-        mask: MASK_NEW,
-        min_cores: 10,
-        name: "Immortalis-G715",
-        architecture: "Valhall",
-        get_num_fp32_fmas_per_engine: get_num_64,
-        get_num_texels: get_num_8,
-        get_num_pixels: get_num_4,
-        get_num_exec_engines: get_num_2,
-    },
-    ProductEntry {
-        id: 0xb002,
-        mask: MASK_NEW,
-        min_cores: 7,
-        name: "Mali-G715",
-        architecture: "Valhall",
-        get_num_fp32_fmas_per_engine: get_num_64,
-        get_num_texels: get_num_8,
-        get_num_pixels: get_num_4,
-        get_num_exec_engines: get_num_2,
-    },
-    ProductEntry {
-        id: 0xb002,
-        mask: MASK_NEW,
-        min_cores: 1,
-        name: "Mali-G615",
-        architecture: "Valhall",
-        get_num_fp32_fmas_per_engine: get_num_64,
-        get_num_texels: get_num_8,
-        get_num_pixels: get_num_4,
-        get_num_exec_engines: get_num_2,
-    },
-    ProductEntry {
-        id: 0xb003,
-        mask: MASK_NEW,
-        min_cores: 1,
-        name: "Mali-G615",
-        architecture: "Valhall",
-        get_num_fp32_fmas_per_engine: get_num_64,
-        get_num_texels: get_num_8,
-        get_num_pixels: get_num_4,
-        get_num_exec_engines: get_num_2,
-    },
+use std::sync::RwLock;
 
-    // Immortalis-G720/Mali-G720/G620
-    ProductEntry {
-        id: 0xc000,
-        mask: MASK_NEW,
-        min_cores: 10,
-        name: "Immortalis-G720",
-        architecture: "Arm 5th Gen",
-        get_num_fp32_fmas_per_engine: get_num_64,
-        get_num_texels: get_num_8,
-        get_num_pixels: get_num_4,
-        get_num_exec_engines: get_num_2,
-    },
-    ProductEntry {
-        id: 0xc000,
-        mask: MASK_NEW,
-        min_cores: 6,
-        name: "Mali-G720",
-        architecture: "Arm 5th Gen",
-        get_num_fp32_fmas_per_engine: get_num_64,
-        get_num_texels: get_num_8,
-        get_num_pixels: get_num_4,
-        get_num_exec_engines: get_num_2,
-    },
-    ProductEntry {
-        id: 0xc000,
-        mask: MASK_NEW,
-        min_cores: 1,
-        name: "Mali-G620",
-        architecture: "Arm 5th Gen",
-        get_num_fp32_fmas_per_engine: get_num_64,
-        get_num_texels: get_num_8,
-        get_num_pixels: get_num_4,
-        get_num_exec_engines: get_num_2,
-    },
-    ProductEntry {
-        id: 0xc001,
-        mask: MASK_NEW,
-        min_cores: 1,
-        name: "Mali-G620",
-        architecture: "Arm 5th Gen",
-        get_num_fp32_fmas_per_engine: get_num_64,
-        get_num_texels: get_num_8,
-        get_num_pixels: get_num_4,
-        get_num_exec_engines: get_num_2,
-    },
+pub use armgpuinfo_core::mali::{
+    extract_architecture, extrapolate_product, get_gpu_id, lookup_product, products, ProductEntry,
+};
+#[cfg(feature = "database-file")]
+pub(crate) use armgpuinfo_core::mali::{constant_rate, default_apis, default_mask, default_support};
 
-    // Immortalis-G925/Mali-G725/G625
-    ProductEntry {
-        id: 0xd000,
-        mask: MASK_NEW,
-        min_cores: 10,
-        name: "Immortalis-G925",
-        architecture: "Arm 5th Gen",
-        get_num_fp32_fmas_per_engine: get_num_64,
-        get_num_texels: get_num_8,
-        get_num_pixels: get_num_4,
-        get_num_exec_engines: get_num_2,
-    },
-    ProductEntry {
-        id: 0xd000,
-        mask: MASK_NEW,
-        min_cores: 6,
-        name: "Mali-G725",
-        architecture: "Arm 5th Gen",
-        get_num_fp32_fmas_per_engine: get_num_64,
-        get_num_texels: get_num_8,
-        get_num_pixels: get_num_4,
-        get_num_exec_engines: get_num_2,
-    },
-    ProductEntry {
-        id: 0xd001,
-        mask: MASK_NEW,
-        min_cores: 1,
-        name: "Mali-G625",
-        architecture: "Arm 5th Gen",
-        get_num_fp32_fmas_per_engine: get_num_64,
-        get_num_texels: get_num_8,
-        get_num_pixels: get_num_4,
-        get_num_exec_engines: get_num_2,
-    },
+/// Products loaded at runtime, see [`crate::database`]; they take
+/// precedence over the static table
+static EXTRA_PRODUCTS: RwLock<Vec<&'static ProductEntry>> = RwLock::new(Vec::new());
 
-    // Mali G1 series
-    ProductEntry {
-        id: 0xe000,
-        mask: MASK_NEW,
-        min_cores: 10,
-        name: "Mali G1-Ultra",
-        architecture: "Arm 5th Gen",
-        get_num_fp32_fmas_per_engine: get_num_64,
-        get_num_texels: get_num_8,
-        get_num_pixels: get_num_4,
-        get_num_exec_engines: get_num_2,
-    },
-    ProductEntry {
-        id: 0xe001,
-        mask: MASK_NEW,
-        min_cores: 6,
-        name: "Mali G1-Premium",
-        architecture: "Arm 5th Gen",
-        get_num_fp32_fmas_per_engine: get_num_64,
-        get_num_texels: get_num_8,
-        get_num_pixels: get_num_4,
-        get_num_exec_engines: get_num_2,
-    },
-    ProductEntry {
-        id: 0xe003,
-        mask: MASK_NEW,
-        min_cores: 1,
-        name: "Mali G1-Pro",
-        architecture: "Arm 5th Gen",
-        get_num_fp32_fmas_per_engine: get_num_64,
-        get_num_texels: get_num_8,
-        get_num_pixels: get_num_4,
-        get_num_exec_engines: get_num_2,
-    },
-];
-
-// Lazy-initialized product lookup map
-fn product_map() -> &'static HashMap<u32, Vec<&'static ProductEntry>> {
-    static MAP: OnceLock<HashMap<u32, Vec<&'static ProductEntry>>> = OnceLock::new();
-
-    MAP.get_or_init(|| {
-        // Iterator-Kette mit collect() - am idiomatischsten!
-        PRODUCT_VERSIONS
-            .iter()
-            .fold(HashMap::new(), |mut map, entry| {
-                map.entry(entry.id).or_default().push(entry);
-                map
-            })
-    })
+/// Add runtime products; entries live for the rest of the process
+#[cfg(feature = "database-file")]
+pub(crate) fn register_products(entries: Vec<ProductEntry>) {
+    let mut extra = EXTRA_PRODUCTS.write().unwrap_or_else(|e| e.into_inner());
+    extra.extend(entries.into_iter().map(|entry| &*Box::leak(Box::new(entry))));
 }
 
-pub(crate) fn get_gpu_id(input_id: u32) -> u32 {
-    PRODUCT_VERSIONS
-        .iter()
-        .find(|entry| (input_id & entry.mask) == entry.id)
-        .map(|entry| entry.id)
-        .unwrap_or(input_id)
+/// [`get_gpu_id`], consulting runtime products first
+pub(crate) fn resolve_gpu_id(input_id: u32) -> u32 {
+    let extra = EXTRA_PRODUCTS.read().unwrap_or_else(|e| e.into_inner());
+    match extra.iter().find(|entry| input_id & entry.mask == entry.id) {
+        Some(entry) => entry.id,
+        None => get_gpu_id(input_id),
+    }
 }
 
-pub(crate) fn lookup_product(gpu_id: u32, core_count: u32) -> Option<&'static ProductEntry> {
-    product_map()
-        .get(&gpu_id)?
+/// [`lookup_product`], consulting runtime products first
+pub(crate) fn resolve_product(gpu_id: u32, core_count: u32) -> Option<&'static ProductEntry> {
+    let extra = EXTRA_PRODUCTS.read().unwrap_or_else(|e| e.into_inner());
+    extra
         .iter()
-        .filter(|e| core_count >= e.min_cores)
-        .max_by_key(|e| e.min_cores)
         .copied()
+        .filter(|entry| entry.id == gpu_id && core_count >= entry.min_cores)
+        .max_by_key(|entry| entry.min_cores)
+        .or_else(|| lookup_product(gpu_id, core_count))
 }
 
-pub(crate) fn extract_architecture(raw_gpu_id: u64) -> (u8, u8) {
-    const COMPAT_SHIFT: u64 = 28;
-    const COMPAT_MASK: u64 = 0xF;
-
-    let is_64bit_id = ((raw_gpu_id >> COMPAT_SHIFT) & COMPAT_MASK) == COMPAT_MASK;
-
-    if !is_64bit_id {
-        (
-            ((raw_gpu_id >> 28) & 0xF) as u8,
-            ((raw_gpu_id >> 24) & 0xF) as u8,
-        )
-    } else {
-        (
-            ((raw_gpu_id >> 56) & 0xFF) as u8,
-            ((raw_gpu_id >> 48) & 0xFF) as u8,
-        )
-    }
+/// The product sharing `entry`'s ID that the running SoC is known to ship
+///
+/// Products with the same ID are told apart by core count, which names a
+/// Mali-G715 binned to six cores a Mali-G615. Only for live queries: the
+/// SoC is the local system's.
+pub(crate) fn soc_variant(entry: &'static ProductEntry) -> &'static ProductEntry {
+    crate::soc::system_soc()
+        .mali_gpu()
+        .and_then(|name| products().iter().find(|product| product.id == entry.id && product.name == name))
+        .unwrap_or(entry)
 }
 
 pub(crate) fn validate_gpu_info(info: &crate::info::GpuInfo) -> crate::error::GpuResult<()> {
@@ -570,4 +61,4 @@ pub(crate) fn validate_gpu_info(info: &crate::info::GpuInfo) -> crate::error::Gp
     }
 
     Ok(())
-}
\ No newline at end of file
+}
@@ -0,0 +1,241 @@
+//! Mali GPU discovery from the device tree
+//!
+//! `/proc/device-tree` is world-readable and present whether or not a GPU
+//! driver is bound. The GPU node (`compatible = "arm,mali-..."`) describes
+//! the register window, interrupts and operating points, so the GPU can be
+//! found without permission to open `/dev/mali0`. The GPU ID and core count
+//! are not in the tree; a specific compatible such as `"arm,mali-g610"`
+//! still names the product.
+//!
+//! ```
+//! use std::fs;
+//! use armgpuinfo::mali::devicetree::find_mali_nodes_in;
+//!
+//! let root = tempfile::tempdir()?;
+//! fs::write(root.path().join("#size-cells"), 2u32.to_be_bytes())?;
+//! let gpu = root.path().join("gpu@fb000000");
+//! fs::create_dir(&gpu)?;
+//! fs::write(gpu.join("compatible"), "rockchip,rk3588-mali\0arm,mali-valhall-csf\0")?;
+//! fs::write(gpu.join("reg"), [0, 0, 0, 0, 0xfb, 0, 0, 0, 0, 0, 0, 0, 0, 0x20, 0, 0])?;
+//!
+//! let nodes = find_mali_nodes_in(root.path());
+//! assert_eq!(nodes[0].reg, [(0xfb00_0000, 0x20_0000)]);
+//! assert_eq!(nodes[0].architecture(), Some(armgpuinfo::MaliArch::Valhall));
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::info::MaliArch;
+
+use super::database::{products, ProductEntry};
+
+/// Device tree as the kernel exports it
+pub const DEVICE_TREE_ROOT: &str = "/proc/device-tree";
+
+/// Cell counts the device-tree specification assumes when a parent leaves
+/// them out
+const DEFAULT_ADDRESS_CELLS: usize = 2;
+const DEFAULT_SIZE_CELLS: usize = 1;
+
+/// A Mali GPU node
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MaliDtNode {
+    /// Node directory, e.g. `/proc/device-tree/gpu@fb000000`
+    pub path: PathBuf,
+    /// `compatible` strings, most specific first
+    pub compatible: Vec<String>,
+    /// `status` is absent, "okay" or "ok"
+    pub enabled: bool,
+    /// Register windows as (address, size)
+    pub reg: Vec<(u64, u64)>,
+    /// Raw `interrupts` cells, `#interrupt-cells` per interrupt (three on
+    /// the GIC)
+    pub interrupts: Vec<u32>,
+    /// `interrupt-names`, e.g. "job", "mmu", "gpu"
+    pub interrupt_names: Vec<String>,
+    /// Operating points, ascending by frequency
+    pub opps: Vec<DtOpp>,
+}
+
+/// An operating point of the GPU clock
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DtOpp {
+    pub freq_hz: u64,
+    /// First `opp-microvolt` value, the target voltage
+    pub microvolt: Option<u32>,
+}
+
+impl MaliDtNode {
+    /// Product named by a specific compatible such as `"arm,mali-g610"`
+    ///
+    /// `None` for the generic `arm,mali-bifrost` style compatibles. Products
+    /// sharing a name differ by core count, which the tree does not give;
+    /// the first database entry is returned.
+    pub fn product(&self) -> Option<&'static ProductEntry> {
+        self.compatible.iter().filter_map(|c| c.strip_prefix("arm,mali-")).find_map(|model| {
+            products()
+                .iter()
+                .find(|product| product.name.strip_prefix("Mali-").is_some_and(|name| name.eq_ignore_ascii_case(model)))
+        })
+    }
+
+    /// Architecture from the product or a generic compatible
+    pub fn architecture(&self) -> Option<MaliArch> {
+        self.product().map(|product| product.architecture).or_else(|| {
+            self.compatible.iter().find_map(|c| match c.strip_prefix("arm,mali-")? {
+                "midgard" => Some(MaliArch::Midgard),
+                "bifrost" => Some(MaliArch::Bifrost),
+                family if family.starts_with("valhall") => Some(MaliArch::Valhall),
+                _ => None,
+            })
+        })
+    }
+
+    /// Highest operating point frequency
+    pub fn max_freq_hz(&self) -> Option<u64> {
+        self.opps.iter().map(|opp| opp.freq_hz).max()
+    }
+}
+
+/// Mali GPU nodes in [`DEVICE_TREE_ROOT`]
+pub fn find_mali_nodes() -> Vec<MaliDtNode> {
+    find_mali_nodes_in(DEVICE_TREE_ROOT)
+}
+
+/// Mali GPU nodes in the device tree at `root`, in path order
+///
+/// Empty if the tree is missing or has no node with an `arm,mali`
+/// compatible.
+pub fn find_mali_nodes_in<P: AsRef<Path>>(root: P) -> Vec<MaliDtNode> {
+    let root = root.as_ref();
+    let mut found = Vec::new();
+    walk(root, &mut |dir| {
+        let compatible = read_strings(&dir.join("compatible"));
+        if compatible.iter().any(|c| c.starts_with("arm,mali")) {
+            found.push(read_node(root, dir, compatible));
+        }
+    });
+    found.sort_by(|a, b| a.path.cmp(&b.path));
+    found
+}
+
+fn read_node(root: &Path, dir: &Path, compatible: Vec<String>) -> MaliDtNode {
+    let parent = dir.parent().unwrap_or(root);
+    let address_cells = read_cells(&parent.join("#address-cells"))
+        .first()
+        .map_or(DEFAULT_ADDRESS_CELLS, |&n| n as usize);
+    let size_cells = read_cells(&parent.join("#size-cells"))
+        .first()
+        .map_or(DEFAULT_SIZE_CELLS, |&n| n as usize);
+    let reg = read_cells(&dir.join("reg"))
+        .chunks_exact((address_cells + size_cells).max(1))
+        .map(|entry| {
+            let (address, size) = entry.split_at(address_cells);
+            (join_cells(address), join_cells(size))
+        })
+        .collect();
+    let status = read_strings(&dir.join("status"));
+
+    MaliDtNode {
+        path: dir.to_path_buf(),
+        compatible,
+        enabled: status.first().is_none_or(|s| s == "okay" || s == "ok"),
+        reg,
+        interrupts: read_cells(&dir.join("interrupts")),
+        interrupt_names: read_strings(&dir.join("interrupt-names")),
+        opps: read_opps(root, dir),
+    }
+}
+
+/// `operating-points-v2` table, else the legacy `operating-points` pairs
+fn read_opps(root: &Path, dir: &Path) -> Vec<DtOpp> {
+    let mut opps: Vec<DtOpp> = match read_cells(&dir.join("operating-points-v2")).first() {
+        Some(&phandle) => find_phandle(root, phandle)
+            .map(|table| {
+                let mut opps = Vec::new();
+                for entry in fs::read_dir(table).into_iter().flatten().flatten() {
+                    let path = entry.path();
+                    let freq = read_cells(&path.join("opp-hz"));
+                    if !freq.is_empty() {
+                        opps.push(DtOpp {
+                            freq_hz: join_cells(&freq),
+                            microvolt: read_cells(&path.join("opp-microvolt")).first().copied(),
+                        });
+                    }
+                }
+                opps
+            })
+            .unwrap_or_default(),
+        // Pairs of kHz and microvolts
+        None => read_cells(&dir.join("operating-points"))
+            .chunks_exact(2)
+            .map(|pair| DtOpp { freq_hz: u64::from(pair[0]) * 1000, microvolt: Some(pair[1]) })
+            .collect(),
+    };
+    opps.sort_by_key(|opp| opp.freq_hz);
+    opps.dedup_by_key(|opp| opp.freq_hz);
+    opps
+}
+
+/// Node whose `phandle` is `phandle`
+fn find_phandle(root: &Path, phandle: u32) -> Option<PathBuf> {
+    let mut found = None;
+    walk(root, &mut |dir| {
+        if found.is_none() {
+            let cells = read_cells(&dir.join("phandle"));
+            let cells = if cells.is_empty() { read_cells(&dir.join("linux,phandle")) } else { cells };
+            if cells.first() == Some(&phandle) {
+                found = Some(dir.to_path_buf());
+            }
+        }
+    });
+    found
+}
+
+/// Visit `dir` and every node below it
+fn walk(dir: &Path, visit: &mut dyn FnMut(&Path)) {
+    visit(dir);
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        // Properties are files; only real directories are child nodes
+        if entry.file_type().is_ok_and(|kind| kind.is_dir()) {
+            walk(&entry.path(), visit);
+        }
+    }
+}
+
+/// Big-endian 32-bit cells of a property, empty if missing
+fn read_cells(path: &Path) -> Vec<u32> {
+    fs::read(path)
+        .map(|bytes| {
+            bytes
+                .chunks_exact(4)
+                .map(|cell| u32::from_be_bytes([cell[0], cell[1], cell[2], cell[3]]))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// NUL-separated strings of a property, empty if missing
+fn read_strings(path: &Path) -> Vec<String> {
+    fs::read(path)
+        .map(|bytes| {
+            bytes
+                .split(|&b| b == 0)
+                .filter(|s| !s.is_empty())
+                .map(|s| String::from_utf8_lossy(s).into_owned())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Most significant cell first
+fn join_cells(cells: &[u32]) -> u64 {
+    cells.iter().fold(0, |value, &cell| (value << 32) | u64::from(cell))
+}
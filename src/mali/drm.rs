@@ -0,0 +1,384 @@
+//! Upstream panfrost/panthor DRM backend
+//!
+//! Mainline kernels drive Mali through panfrost (Midgard to Valhall JM) or
+//! panthor (CSF) render nodes instead of `/dev/mali0`. Both export the raw
+//! GPU registers that kbase reports through `GET_GPUPROPS`, so the product
+//! database applies unchanged.
+
+use std::borrow::Cow;
+use std::os::unix::io::{AsRawFd, RawFd};
+use std::path::{Path, PathBuf};
+
+use crate::capabilities::GpuCapabilities;
+use crate::drm::{driver_name, drm_iowr, ioctl, open_render_node, DRM_COMMAND_BASE};
+use crate::error::{GpuError, GpuResult};
+use crate::telemetry::devfreq::find_gpu_devfreq;
+use crate::telemetry::FrequencyInfo;
+use crate::info::{
+    DataConfidence, GpuInfo, GpuVendor, MaliData, MaliFlavor, MaliHwConfig, MaliL2Features, SpecConfidence,
+};
+
+use super::assemble::{core_groups, extended_data, provenance};
+use super::database::{extract_architecture, extrapolate_product, resolve_gpu_id, resolve_product, soc_variant};
+use super::parser::ParsedProperties;
+use super::power_model::read_power_model;
+use super::sysfs::{apply_clocks, find_device_devfreq};
+
+const PANFROST_DRIVER_NAME: &str = "panfrost";
+const PANTHOR_DRIVER_NAME: &str = "panthor";
+
+/// `COHERENCY_FEATURES` value for "no coherency"; neither driver reports
+/// the mode it selected, so non-coherent is assumed
+const MALI_COHERENCY_NONE: u32 = 31;
+
+/// `enum drm_panfrost_param`, in header order
+#[allow(dead_code)]
+#[repr(u32)]
+#[derive(Debug, Clone, Copy)]
+enum PanfrostParam {
+    GpuProdId,
+    GpuRevision,
+    ShaderPresent,
+    TilerPresent,
+    L2Present,
+    StackPresent,
+    AsPresent,
+    JsPresent,
+    L2Features,
+    CoreFeatures,
+    TilerFeatures,
+    MemFeatures,
+    MmuFeatures,
+    ThreadFeatures,
+    MaxThreads,
+    ThreadMaxWorkgroupSz,
+    ThreadMaxBarrierSz,
+    CoherencyFeatures,
+    TextureFeatures0,
+    TextureFeatures1,
+    TextureFeatures2,
+    TextureFeatures3,
+    JsFeatures0,
+    JsFeatures1,
+    JsFeatures2,
+    JsFeatures3,
+    JsFeatures4,
+    JsFeatures5,
+    JsFeatures6,
+    JsFeatures7,
+    JsFeatures8,
+    JsFeatures9,
+    JsFeatures10,
+    JsFeatures11,
+    JsFeatures12,
+    JsFeatures13,
+    JsFeatures14,
+    JsFeatures15,
+    NrCoreGroups,
+    ThreadTlsAlloc,
+    AfbcFeatures,
+    SystemTimestamp,
+    SystemTimestampFrequency,
+    AllowedJmCtxPriorities,
+}
+
+// Values from panfrost_drm.h, so an edit of the enum above fails the build
+const _: () = {
+    let table = [
+        (PanfrostParam::GpuProdId, 0),
+        (PanfrostParam::GpuRevision, 1),
+        (PanfrostParam::ShaderPresent, 2),
+        (PanfrostParam::AsPresent, 6),
+        (PanfrostParam::JsPresent, 7),
+        (PanfrostParam::L2Features, 8),
+        (PanfrostParam::CoreFeatures, 9),
+        (PanfrostParam::TilerFeatures, 10),
+        (PanfrostParam::ThreadFeatures, 13),
+        (PanfrostParam::ThreadMaxBarrierSz, 16),
+        (PanfrostParam::CoherencyFeatures, 17),
+        (PanfrostParam::TextureFeatures0, 18),
+        (PanfrostParam::JsFeatures0, 22),
+        (PanfrostParam::JsFeatures15, 37),
+        (PanfrostParam::NrCoreGroups, 38),
+        (PanfrostParam::ThreadTlsAlloc, 39),
+        (PanfrostParam::AllowedJmCtxPriorities, 43),
+    ];
+    let mut i = 0;
+    while i < table.len() {
+        assert!(table[i].0 as u32 == table[i].1, "drm_panfrost_param numbering");
+        i += 1;
+    }
+};
+
+/// `DRM_PANTHOR_DEV_QUERY_GPU_INFO`
+const PANTHOR_DEV_QUERY_GPU_INFO: u32 = 0;
+
+/// `struct drm_panfrost_get_param`
+#[repr(C)]
+#[derive(Default)]
+struct DrmPanfrostGetParam {
+    param: u32,
+    pad: u32,
+    value: u64,
+}
+
+/// `struct drm_panthor_dev_query`
+#[repr(C)]
+struct DrmPanthorDevQuery {
+    query_type: u32,
+    size: u32,
+    pointer: u64,
+}
+
+/// `struct drm_panthor_gpu_info`
+#[repr(C)]
+#[derive(Default)]
+struct DrmPanthorGpuInfo {
+    gpu_id: u32,
+    gpu_rev: u32,
+    csf_id: u32,
+    l2_features: u32,
+    tiler_features: u32,
+    mem_features: u32,
+    mmu_features: u32,
+    thread_features: u32,
+    max_threads: u32,
+    thread_max_workgroup_size: u32,
+    thread_max_barrier_size: u32,
+    coherency_features: u32,
+    texture_features: [u32; 4],
+    as_present: u32,
+    shader_present: u64,
+    l2_present: u64,
+    tiler_present: u64,
+    core_features: u32,
+    pad: u32,
+}
+
+crate::abi::layouts! {
+    pub(crate) fn drm_layouts {
+        DrmPanfrostGetParam as "drm_panfrost_get_param": size 16, value @ 8;
+        DrmPanthorDevQuery as "drm_panthor_dev_query": size 16, pointer @ 8;
+        DrmPanthorGpuInfo as "drm_panthor_gpu_info":
+            size 104, texture_features @ 48, as_present @ 64, shader_present @ 72, core_features @ 96;
+    }
+}
+
+/// `DRM_COMMAND_BASE + DRM_PANFROST_GET_PARAM`
+const DRM_IOCTL_PANFROST_GET_PARAM: u64 =
+    drm_iowr(DRM_COMMAND_BASE + 0x04, std::mem::size_of::<DrmPanfrostGetParam>());
+/// `DRM_COMMAND_BASE + DRM_PANTHOR_DEV_QUERY`
+const DRM_IOCTL_PANTHOR_DEV_QUERY: u64 =
+    drm_iowr(DRM_COMMAND_BASE, std::mem::size_of::<DrmPanthorDevQuery>());
+
+/// Query a panfrost or panthor render node
+///
+/// Returns [`GpuError::DriverNotSupported`] for render nodes of other
+/// drivers.
+pub fn query_mali_drm<P: AsRef<Path>>(device_path: P) -> GpuResult<GpuInfo> {
+    let device_path = device_path.as_ref();
+    let file = open_render_node(device_path)?;
+    let fd = file.as_raw_fd();
+
+    let (parsed, flavor) = match driver_name(fd)?.as_str() {
+        PANFROST_DRIVER_NAME => (panfrost_properties(fd)?, MaliFlavor::JobManager),
+        PANTHOR_DRIVER_NAME => (panthor_properties(fd)?, MaliFlavor::Csf),
+        _ => return Err(GpuError::DriverNotSupported),
+    };
+    if parsed.gpu_id == 0 {
+        return Err(GpuError::InvalidGpuProperties("driver reported no product id".into()));
+    }
+
+    let gpu_id = resolve_gpu_id(parsed.gpu_id);
+    let (product, confidence) = match resolve_product(gpu_id, parsed.num_shader_cores).map(soc_variant) {
+        Some(entry) => (Some(entry), Some(SpecConfidence::Measured)),
+        None => match extrapolate_product(gpu_id, parsed.num_shader_cores) {
+            Some(entry) => (Some(entry), Some(SpecConfidence::Extrapolated)),
+            None => (None, None),
+        },
+    };
+    let per_core = |f: fn(u32, u32, u32) -> u32| {
+        f(parsed.num_shader_cores, parsed.raw_core_features, parsed.raw_thread_features)
+    };
+    let num_exec_engines = product.map_or(0, |entry| per_core(entry.get_num_exec_engines));
+    let num_fp32_fmas_per_core = product.map_or(0, |entry| {
+        per_core(entry.get_num_fp32_fmas_per_engine) * num_exec_engines
+    });
+    let (architecture_major, architecture_minor) = extract_architecture(parsed.raw_gpu_id);
+
+    let l2_features = MaliL2Features::decode(parsed.raw_l2_features);
+    let num_l2_bytes = match parsed.l2_log2_cache_size {
+        0 => 0,
+        log2 => (1u64 << log2) * parsed.num_l2_slices,
+    };
+
+    // Never present a neighbour's marketing name as this GPU's
+    let gpu_name = match (product, confidence) {
+        (Some(entry), Some(SpecConfidence::Measured)) => entry.name_as_cow(),
+        (Some(entry), _) => {
+            Cow::Owned(format!("Mali ({} unknown, 0x{:04x})", entry.architecture, gpu_id))
+        }
+        (None, _) => Cow::Borrowed(""),
+    };
+
+    let device_dir = device_path
+        .file_name()
+        .map(|name| PathBuf::from("/sys/class/drm").join(name).join("device"));
+    let of_node = device_dir.as_ref().map(|dir| dir.join("of_node"));
+
+    let mut info = GpuInfo {
+        vendor: GpuVendor::Mali,
+        gpu_name,
+        architecture: product.map_or(Cow::Borrowed(""), |entry| entry.architecture_as_cow()),
+        architecture_major,
+        architecture_minor,
+        num_shader_cores: parsed.num_shader_cores,
+        num_l2_bytes,
+        num_bus_bits: l2_features.map_or(0, |l2| u64::from(l2.external_bus_bits)),
+        mali_data: Some(MaliData {
+            gpu_id,
+            raw_gpu_id: parsed.raw_gpu_id,
+            shader_core_mask: parsed.shader_core_mask,
+            core_groups: core_groups(&parsed.core_group_masks, parsed.shader_core_mask),
+            num_l2_slices: parsed.num_l2_slices,
+            num_exec_engines,
+            num_fp32_fmas_per_core,
+            num_fp16_fmas_per_core: num_fp32_fmas_per_core * 2,
+            num_texels_per_core: product.map_or(0, |entry| per_core(entry.get_num_texels)),
+            num_pixels_per_core: product.map_or(0, |entry| per_core(entry.get_num_pixels)),
+            flavor: Some(flavor),
+            driver_version: None,
+            arch: product.map(|entry| entry.architecture),
+            spec_confidence: confidence,
+            power_model: of_node.and_then(|node| read_power_model(&node)),
+            freq_table: Vec::new(),
+            current_freq_mhz: None,
+            min_freq_mhz: None,
+            max_freq_mhz: None,
+            measured_max_freq_mhz: None,
+            hw_config: Some(parsed.hw_config),
+            l2_features,
+            extended: Some(extended_data(&parsed)),
+            instance: None,
+        }),
+        adreno_data: None,
+        vivante_data: None,
+        capabilities: GpuCapabilities::default(),
+        confidence: DataConfidence::reported(parsed.num_shader_cores.into())
+            .max(DataConfidence::reported(num_l2_bytes)),
+        provenance: Vec::new(),
+        device_path: Some(device_path.to_path_buf()),
+    };
+    let devfreq = device_dir.as_deref().and_then(find_device_devfreq).or_else(find_gpu_devfreq);
+    if let (Some(mali), Some(devfreq)) = (info.mali_data.as_mut(), devfreq) {
+        apply_clocks(mali, &FrequencyInfo::read(&devfreq));
+    }
+    info.provenance = provenance(&info);
+    info.capabilities = GpuCapabilities::of(&info);
+    Ok(info)
+}
+
+/// Registers from `DRM_IOCTL_PANFROST_GET_PARAM`
+fn panfrost_properties(fd: RawFd) -> GpuResult<ParsedProperties> {
+    let param = |param: u32| -> GpuResult<u64> {
+        let mut req = DrmPanfrostGetParam { param, ..DrmPanfrostGetParam::default() };
+        ioctl(fd, DRM_IOCTL_PANFROST_GET_PARAM, &mut req)?;
+        Ok(req.value)
+    };
+    // Registers added in later kernels than the GPU ID read as zero
+    let optional = |id: PanfrostParam| param(id as u32).unwrap_or(0);
+    let optional_u32 = |id: PanfrostParam| optional(id) as u32;
+
+    let product_id = param(PanfrostParam::GpuProdId as u32)?;
+    let revision = optional(PanfrostParam::GpuRevision);
+    let shader_present = param(PanfrostParam::ShaderPresent as u32)?;
+    let l2_present = optional(PanfrostParam::L2Present);
+    let l2_features = optional(PanfrostParam::L2Features);
+
+    let mut js_features = [0u32; 16];
+    for (slot, id) in js_features.iter_mut().zip(PanfrostParam::JsFeatures0 as u32..) {
+        *slot = param(id).unwrap_or(0) as u32;
+    }
+    let mut texture_features = [0u32; 3];
+    for (slot, id) in texture_features.iter_mut().zip(PanfrostParam::TextureFeatures0 as u32..) {
+        *slot = param(id).unwrap_or(0) as u32;
+    }
+
+    // ParsedProperties is non-exhaustive outside armgpuinfo-core
+    let mut props = ParsedProperties::default();
+    props.gpu_id = product_id as u32;
+    props.raw_gpu_id = (product_id << 16) | (revision & 0xFFFF);
+    props.l2_log2_cache_size = (l2_features >> 16) & 0xFF;
+    props.raw_l2_features = l2_features;
+    props.raw_core_features = optional_u32(PanfrostParam::CoreFeatures);
+    props.raw_thread_features = optional_u32(PanfrostParam::ThreadFeatures);
+    props.num_shader_cores = shader_present.count_ones();
+    props.shader_core_mask = shader_present;
+    props.num_l2_slices = u64::from(l2_present.count_ones());
+    props.hw_config = MaliHwConfig {
+        shader_present,
+        tiler_present: optional(PanfrostParam::TilerPresent),
+        l2_present,
+        stack_present: optional(PanfrostParam::StackPresent),
+        as_present: optional_u32(PanfrostParam::AsPresent),
+        js_present: optional_u32(PanfrostParam::JsPresent),
+        js_features,
+        mem_features: optional_u32(PanfrostParam::MemFeatures),
+        mmu_features: optional_u32(PanfrostParam::MmuFeatures),
+        tiler_features: optional_u32(PanfrostParam::TilerFeatures),
+        texture_features,
+        thread_max_threads: optional_u32(PanfrostParam::MaxThreads),
+        thread_max_workgroup_size: optional_u32(PanfrostParam::ThreadMaxWorkgroupSz),
+        thread_max_barrier_size: optional_u32(PanfrostParam::ThreadMaxBarrierSz),
+        thread_tls_alloc: optional_u32(PanfrostParam::ThreadTlsAlloc),
+        coherency_mode: MALI_COHERENCY_NONE,
+        ..MaliHwConfig::default()
+    };
+    Ok(props)
+}
+
+/// Registers from `DRM_IOCTL_PANTHOR_DEV_QUERY(GPU_INFO)`
+fn panthor_properties(fd: RawFd) -> GpuResult<ParsedProperties> {
+    let mut gpu_info = DrmPanthorGpuInfo::default();
+    let mut query = DrmPanthorDevQuery {
+        query_type: PANTHOR_DEV_QUERY_GPU_INFO,
+        size: std::mem::size_of::<DrmPanthorGpuInfo>() as u32,
+        pointer: &mut gpu_info as *mut DrmPanthorGpuInfo as u64,
+    };
+    ioctl(fd, DRM_IOCTL_PANTHOR_DEV_QUERY, &mut query)?;
+
+    let l2_features = u64::from(gpu_info.l2_features);
+    let texture_features = [
+        gpu_info.texture_features[0],
+        gpu_info.texture_features[1],
+        gpu_info.texture_features[2],
+    ];
+
+    // ParsedProperties is non-exhaustive outside armgpuinfo-core
+    let mut props = ParsedProperties::default();
+    props.gpu_id = gpu_info.gpu_id >> 16;
+    props.raw_gpu_id = u64::from(gpu_info.gpu_id);
+    props.l2_log2_cache_size = (l2_features >> 16) & 0xFF;
+    props.num_l2_slices = u64::from(gpu_info.l2_present.count_ones());
+    props.raw_l2_features = l2_features;
+    props.raw_core_features = gpu_info.core_features;
+    props.raw_thread_features = gpu_info.thread_features;
+    props.num_shader_cores = gpu_info.shader_present.count_ones();
+    props.shader_core_mask = gpu_info.shader_present;
+    props.hw_config = MaliHwConfig {
+        shader_present: gpu_info.shader_present,
+        tiler_present: gpu_info.tiler_present,
+        l2_present: gpu_info.l2_present,
+        as_present: gpu_info.as_present,
+        mem_features: gpu_info.mem_features,
+        mmu_features: gpu_info.mmu_features,
+        tiler_features: gpu_info.tiler_features,
+        texture_features,
+        thread_max_threads: gpu_info.max_threads,
+        thread_max_workgroup_size: gpu_info.thread_max_workgroup_size,
+        thread_max_barrier_size: gpu_info.thread_max_barrier_size,
+        coherency_mode: MALI_COHERENCY_NONE,
+        ..MaliHwConfig::default()
+    };
+    Ok(props)
+}
@@ -0,0 +1,160 @@
+//! kbase ioctl definitions
+//!
+//! Layouts and numbers mirror `mali_kbase_ioctl.h` (Job Manager) and
+//! `mali_kbase_csf_ioctl.h` (CSF) from the kernel UAPI.
+
+use nix::{ioctl_read, ioctl_readwrite, ioctl_write_ptr};
+
+/// `KBASE_IOCTL_TYPE`
+pub const KBASE_IOCTL_TYPE: u8 = 0x80;
+
+/// Ioctl request numbers
+pub mod nr {
+    /// `KBASE_IOCTL_VERSION_CHECK` on Job Manager kernels
+    ///
+    /// CSF kernels keep this number as `KBASE_IOCTL_VERSION_CHECK_RESERVED`
+    /// and reject it with EPERM.
+    pub const VERSION_CHECK_JM: u8 = 0x00;
+    /// `KBASE_IOCTL_SET_FLAGS`
+    pub const SET_FLAGS: u8 = 0x01;
+    /// `KBASE_IOCTL_GET_GPUPROPS`
+    pub const GET_GPUPROPS: u8 = 0x03;
+    /// `KBASE_IOCTL_HWCNT_READER_SETUP`, returns the reader fd
+    pub const HWCNT_READER_SETUP: u8 = 0x08;
+    /// `KBASE_IOCTL_VERSION_CHECK` on CSF kernels
+    pub const VERSION_CHECK_CSF: u8 = 0x34;
+}
+
+/// `BASE_CONTEXT_SYSTEM_MONITOR_SUBMIT_DISABLED`
+///
+/// The only create flag an unprivileged query context needs.
+pub const BASE_CONTEXT_SYSTEM_MONITOR_SUBMIT_DISABLED: u32 = 1 << 1;
+
+/// `struct kbase_ioctl_version_check`
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct KbaseIoctlVersionCheck {
+    pub major: u16,
+    pub minor: u16,
+}
+
+/// `struct kbase_ioctl_set_flags`
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct KbaseIoctlSetFlags {
+    pub create_flags: u32,
+}
+
+/// `struct kbase_ioctl_get_gpuprops`
+///
+/// With `size == 0` the driver returns the required buffer size; `flags`
+/// must be zero.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct KbaseIoctlGetGpuprops {
+    pub buffer: u64,
+    pub size: u32,
+    pub flags: u32,
+}
+
+/// `struct kbase_ioctl_hwcnt_reader_setup`
+///
+/// The bitmasks select counter groups of four per block type; all ones
+/// enables everything.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct KbaseIoctlHwcntReaderSetup {
+    pub buffer_count: u32,
+    pub fe_bm: u32,
+    pub shader_bm: u32,
+    pub tiler_bm: u32,
+    pub mmu_l2_bm: u32,
+}
+
+/// `KBASE_HWCNT_READER`, ioctl type of the reader fd
+pub const KBASE_HWCNT_READER: u8 = 0xBE;
+
+/// Reader fd request numbers (`mali_kbase_hwcnt_reader.h`)
+pub mod reader_nr {
+    pub const GET_HWVER: u8 = 0x00;
+    pub const GET_BUFFER_SIZE: u8 = 0x01;
+    pub const DUMP: u8 = 0x10;
+    pub const CLEAR: u8 = 0x11;
+    pub const GET_BUFFER: u8 = 0x20;
+    pub const PUT_BUFFER: u8 = 0x21;
+}
+
+/// `struct kbase_hwcnt_reader_metadata`
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct KbaseHwcntReaderMetadata {
+    /// Dump time in nanoseconds
+    pub timestamp: u64,
+    pub event_id: u32,
+    /// Index of the filled buffer in the reader mapping
+    pub buffer_idx: u32,
+}
+
+crate::abi::layouts! {
+    pub(crate) fn kbase_layouts {
+        KbaseIoctlVersionCheck as "kbase_ioctl_version_check": size 4, major @ 0, minor @ 2;
+        KbaseIoctlSetFlags as "kbase_ioctl_set_flags": size 4, create_flags @ 0;
+        KbaseIoctlGetGpuprops as "kbase_ioctl_get_gpuprops": size 16, buffer @ 0, size @ 8, flags @ 12;
+        KbaseIoctlHwcntReaderSetup as "kbase_ioctl_hwcnt_reader_setup":
+            size 20, buffer_count @ 0, fe_bm @ 4, shader_bm @ 8, tiler_bm @ 12, mmu_l2_bm @ 16;
+        KbaseHwcntReaderMetadata as "kbase_hwcnt_reader_metadata":
+            size 16, timestamp @ 0, event_id @ 8, buffer_idx @ 12;
+    }
+}
+
+/// Kernel user/kernel (UK) API version
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct UkVersion {
+    pub major: u16,
+    pub minor: u16,
+}
+
+impl UkVersion {
+    pub const fn new(major: u16, minor: u16) -> Self {
+        Self { major, minor }
+    }
+
+    /// Whether this version falls inside a range we have layouts for
+    pub fn is_known(self) -> bool {
+        KNOWN_UK_VERSIONS
+            .iter()
+            .any(|(min, max)| self >= *min && self <= *max)
+    }
+}
+
+impl std::fmt::Display for UkVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+/// UK API ranges whose GET_GPUPROPS/SET_FLAGS layouts match the structs above
+///
+/// JM 11.x covers r21p0 onwards, CSF 1.x all released CSF drivers.
+pub const KNOWN_UK_VERSIONS: &[(UkVersion, UkVersion)] = &[
+    (UkVersion::new(11, 0), UkVersion::new(11, 40)),
+    (UkVersion::new(1, 0), UkVersion::new(1, 22)),
+];
+
+/// Versions we offer during the handshake; the kernel answers with the
+/// lowest common minor
+pub const CSF_UK_VERSION: UkVersion = UkVersion::new(1, 22);
+pub const JM_UK_VERSION: UkVersion = UkVersion::new(11, 40);
+
+ioctl_readwrite!(kbase_version_check_jm, KBASE_IOCTL_TYPE, nr::VERSION_CHECK_JM, KbaseIoctlVersionCheck);
+ioctl_readwrite!(kbase_version_check_csf, KBASE_IOCTL_TYPE, nr::VERSION_CHECK_CSF, KbaseIoctlVersionCheck);
+ioctl_write_ptr!(kbase_set_flags, KBASE_IOCTL_TYPE, nr::SET_FLAGS, KbaseIoctlSetFlags);
+ioctl_write_ptr!(kbase_get_gpuprops, KBASE_IOCTL_TYPE, nr::GET_GPUPROPS, KbaseIoctlGetGpuprops);
+ioctl_write_ptr!(kbase_hwcnt_reader_setup, KBASE_IOCTL_TYPE, nr::HWCNT_READER_SETUP, KbaseIoctlHwcntReaderSetup);
+
+ioctl_read!(hwcnt_reader_get_hwver, KBASE_HWCNT_READER, reader_nr::GET_HWVER, u32);
+ioctl_read!(hwcnt_reader_get_buffer_size, KBASE_HWCNT_READER, reader_nr::GET_BUFFER_SIZE, u32);
+ioctl_write_ptr!(hwcnt_reader_dump, KBASE_HWCNT_READER, reader_nr::DUMP, u32);
+ioctl_write_ptr!(hwcnt_reader_clear, KBASE_HWCNT_READER, reader_nr::CLEAR, u32);
+ioctl_read!(hwcnt_reader_get_buffer, KBASE_HWCNT_READER, reader_nr::GET_BUFFER, KbaseHwcntReaderMetadata);
+ioctl_write_ptr!(hwcnt_reader_put_buffer, KBASE_HWCNT_READER, reader_nr::PUT_BUFFER, KbaseHwcntReaderMetadata);
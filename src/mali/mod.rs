@@ -2,13 +2,65 @@
 //!
 //! This module provides functionality to query ARM Mali GPU information
 //! via kernel ioctls on Linux/Android systems.
+//!
+//! The parser and product database are always available with `mali-core`;
+//! the kbase ioctl backend additionally requires the `mali-kbase` feature,
+//! the upstream panfrost/panthor DRM backend the `mali-drm` feature.
 
+#[cfg(feature = "mali-kbase")]
+mod ioctl;
+#[cfg(feature = "mali-kbase")]
 mod query;
+#[cfg(feature = "mali-drm")]
+mod drm;
+mod assemble;
 mod database;
+pub mod counters;
+pub mod devicetree;
 mod parser;
+mod power_model;
+pub mod sysfs;
+
+#[cfg(feature = "mali-kbase")]
+pub use query::{
+    dump_raw_properties, query_mali, query_mali_robust, query_mali_with_mode,
+    query_mali_with_opts,
+};
+#[cfg(feature = "mali-kbase")]
+pub(crate) use query::{open_session, query_session, Handshake};
+#[cfg(all(feature = "mali-kbase", feature = "agent"))]
+pub(crate) use query::get_properties_into;
+#[cfg(feature = "mali-drm")]
+pub use drm::query_mali_drm;
+#[cfg(feature = "mali-drm")]
+pub(crate) use drm::drm_layouts;
+#[cfg(feature = "mali-kbase")]
+pub(crate) use ioctl::kbase_layouts;
+#[cfg(feature = "mali-kbase")]
+pub use ioctl::{UkVersion, KNOWN_UK_VERSIONS};
+pub use assemble::{instance_index, parse_snapshot};
+#[cfg(all(feature = "mock", feature = "mali-kbase"))]
+pub(crate) use assemble::Assembly;
+#[cfg(all(feature = "mock", feature = "mali-kbase"))]
+pub(crate) use query::negotiate_version;
+#[cfg(any(feature = "egl", feature = "android-props"))]
+pub(crate) use assemble::product_gpu_info;
+pub use database::{
+    extract_architecture, extrapolate_product, get_gpu_id, lookup_product, products, ProductEntry,
+};
+pub(crate) use database::{resolve_gpu_id, resolve_product};
+#[cfg(feature = "database-file")]
+pub(crate) use database::{constant_rate, default_apis, default_mask, default_support, register_products};
+pub use parser::{
+    parse_properties, parse_properties_lenient, parse_properties_strict, property_name,
+    raw_properties, ParserConfig, ParsedProperties,
+};
+
+pub use power_model::{read_power_model, read_power_model_for_device};
+pub use sysfs::{query_mali_sysfs, query_mali_sysfs_in};
+pub use devicetree::{find_mali_nodes, MaliDtNode};
 
-pub use query::{query_mali, query_mali_with_mode};
-pub use parser::{parse_properties, parse_properties_lenient, ParserConfig, ParsedProperties};
+pub use crate::info::{CoreGroup, ExtendedMaliData, MaliArch, MaliHwConfig, MaliL2Features, MaliPowerModel};
 
 // Re-export the Mode enum for compatibility
 pub use crate::Mode;
\ No newline at end of file
@@ -0,0 +1,65 @@
+//! Device tree IPA power model
+//!
+//! Boards tuned for thermal management carry a `power_model` (or
+//! `power-model`) child under the GPU node with Arm-provided coefficients.
+//! Properties are raw big-endian cells, as exposed under
+//! `/sys/firmware/devicetree` or `/proc/device-tree`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::info::MaliPowerModel;
+
+/// Power model coefficients from a GPU device tree node directory
+///
+/// Looks for a `power_model` or `power-model` child carrying
+/// `static-coefficient` and `dynamic-coefficient`.
+pub fn read_power_model(of_node: &Path) -> Option<MaliPowerModel> {
+    ["power_model", "power-model"]
+        .iter()
+        .find_map(|name| parse_model_node(&of_node.join(name)))
+}
+
+/// Power model for a kbase device node such as `/dev/mali0`
+///
+/// Resolves the device tree node through
+/// `/sys/class/misc/<name>/device/of_node`.
+pub fn read_power_model_for_device(device_path: &Path) -> Option<MaliPowerModel> {
+    let name = device_path.file_name()?;
+    let of_node = PathBuf::from("/sys/class/misc").join(name).join("device/of_node");
+    read_power_model(&of_node)
+}
+
+fn parse_model_node(node: &Path) -> Option<MaliPowerModel> {
+    let static_coefficient = read_u32(&node.join("static-coefficient"))?;
+    let dynamic_coefficient = read_u32(&node.join("dynamic-coefficient"))?;
+
+    let mut temperature_scaling = [0i32; 4];
+    if let Ok(raw) = fs::read(node.join("ts")) {
+        for (slot, cell) in temperature_scaling.iter_mut().zip(raw.chunks_exact(4)) {
+            *slot = i32::from_be_bytes([cell[0], cell[1], cell[2], cell[3]]);
+        }
+    }
+
+    Some(MaliPowerModel {
+        compatible: read_string(&node.join("compatible")).unwrap_or_default(),
+        static_coefficient,
+        dynamic_coefficient,
+        temperature_scaling,
+        thermal_zone: read_string(&node.join("thermal-zone")),
+    })
+}
+
+/// Single big-endian cell
+fn read_u32(path: &Path) -> Option<u32> {
+    let raw = fs::read(path).ok()?;
+    let cell: [u8; 4] = raw.get(..4)?.try_into().ok()?;
+    Some(u32::from_be_bytes(cell))
+}
+
+/// First NUL-terminated string of a property
+fn read_string(path: &Path) -> Option<String> {
+    let raw = fs::read(path).ok()?;
+    let s = raw.split(|&b| b == 0).next()?;
+    (!s.is_empty()).then(|| String::from_utf8_lossy(s).into_owned())
+}
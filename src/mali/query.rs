@@ -1,56 +1,99 @@
-use std::borrow::Cow; 
-use std::fs::OpenOptions;
+use std::fs::{File, OpenOptions};
 use std::os::unix::io::{AsRawFd, RawFd};
 use std::path::Path;
 
-use nix::{ioctl_readwrite, ioctl_write_ptr};
-
+use crate::diag::diag;
 use crate::error::{GpuError, GpuResult};
-use crate::info::{GpuInfo, GpuVendor, MaliData};
+use crate::info::{GpuInfo, MaliFlavor};
+use crate::instrument::{record, timed, traced, Phase};
+use crate::options::{with_timeout, Fallback, QueryOptions};
 use crate::Mode;
 
-use super::parser::{parse_properties, parse_properties_lenient, ParserConfig};
-use super::database::{get_gpu_id, lookup_product, extract_architecture, validate_gpu_info};
-
-// Constants
-const MALI_IOC_MAGIC: u8 = 0x80;
+use super::assemble::Assembly;
+use super::sysfs::query_mali_sysfs;
+use super::parser::ParserConfig;
+use super::ioctl::{
+    kbase_get_gpuprops, kbase_set_flags, kbase_version_check_csf, kbase_version_check_jm, nr,
+    KbaseIoctlGetGpuprops, KbaseIoctlSetFlags, KbaseIoctlVersionCheck, UkVersion,
+    BASE_CONTEXT_SYSTEM_MONITOR_SUBMIT_DISABLED, CSF_UK_VERSION, JM_UK_VERSION,
+};
 
-// Ioctl request numbers
-mod ioctl_num {
-    pub const SET_FLAGS: u64 = 0x01;
-    pub const GET_PROPS: u64 = 0x03;
-    pub const VERSION_CHECK_CSF: u64 = 0x34;
-}
+/// Driver flavor and UK version negotiated by [`handshake`], if any
+pub(crate) type Handshake = Option<(MaliFlavor, UkVersion)>;
 
-// Ioctl structures
-#[repr(C)]
-struct VersionCheck {
-    major: u16,
-    minor: u16,
+/// Query Mali GPU information with mode selection
+pub fn query_mali_with_mode<P: AsRef<Path>>(device_path: P, mode: Mode) -> GpuResult<GpuInfo> {
+    query_mali_with_opts(device_path, mode)
 }
 
-#[repr(C)]
-struct SetFlags {
-    create_flags: u32,
+/// Query Mali GPU information with explicit options
+///
+/// Modes are tried on one session as [`QueryOptions::modes`] lists them;
+/// with [`Fallback::ParityThenSysfs`] the sysfs nodes are read when all of
+/// them fail, and the ioctl error is returned if that fails too.
+pub fn query_mali_with_opts<P: AsRef<Path>>(
+    device_path: P,
+    options: impl Into<QueryOptions>,
+) -> GpuResult<GpuInfo> {
+    let options = options.into();
+    #[cfg(feature = "mock")]
+    if let Some(result) = crate::mock::query_mali(device_path.as_ref(), &options) {
+        return result;
+    }
+    let device_path = device_path.as_ref().to_path_buf();
+    with_timeout(options.timeout, move || {
+        traced(options.instrumentation.as_ref(), &device_path, || {
+            let result = open_session(&device_path).and_then(|(file, handshake)| {
+                query_session(file.as_raw_fd(), handshake, &device_path, &options)
+            });
+            match result {
+                Err(e) if options.uses_sysfs() => {
+                    record(|trace| trace.sysfs_fallback = true);
+                    match query_mali_sysfs() {
+                        Ok(info) => {
+                            diag!(info, "{} not usable, using sysfs fallback", device_path.display());
+                            Ok(info)
+                        }
+                        Err(sysfs_error) => {
+                            diag!(debug, "sysfs fallback failed: {}", sysfs_error);
+                            Err(e)
+                        }
+                    }
+                }
+                result => result,
+            }
+        })
+    })
 }
 
-#[repr(C)]
-struct MaliPropsQuery {
-    buffer: u64,
-    size: u32,
-    flags: u32,
+/// Open a kbase device node and set up its context
+///
+/// The handshake can only be done once per file, so callers that query
+/// repeatedly must keep the file and pass the result to [`query_session`].
+pub(crate) fn open_session(device_path: &Path) -> GpuResult<(File, Handshake)> {
+    let file = timed(Phase::Open, || {
+        OpenOptions::new().read(true).write(true).open(device_path)
+    })
+    .map_err(GpuError::Io)?;
+    let handshake = timed(Phase::Handshake, || handshake(file.as_raw_fd()))?;
+    Ok((file, handshake))
 }
 
-ioctl_readwrite!(mali_version_check_csf, MALI_IOC_MAGIC, 0x34, VersionCheck);
-ioctl_write_ptr!(mali_set_flags, MALI_IOC_MAGIC, 0x01, SetFlags);
-ioctl_write_ptr!(mali_get_props, MALI_IOC_MAGIC, 0x03, MaliPropsQuery);
-
-/// Query Mali GPU information with mode selection
-pub fn query_mali_with_mode<P: AsRef<Path>>(device_path: P, mode: Mode) -> GpuResult<GpuInfo> {
-    match mode {
-        Mode::Parity => ParityStrategy.query(device_path),
-        Mode::Extended => ExtendedStrategy.query(device_path),
-    }
+/// Query a device opened with [`open_session`]
+pub(crate) fn query_session(
+    fd: RawFd,
+    handshake: Handshake,
+    device_path: &Path,
+    options: &QueryOptions,
+) -> GpuResult<GpuInfo> {
+    options.try_modes(|mode| {
+        let config = options.mali_config(mode);
+        let validate = options.validates(mode);
+        match mode {
+            Mode::Parity => ParityStrategy { config, validate }.query(fd, handshake, device_path),
+            Mode::Extended => ExtendedStrategy { config, validate }.query(fd, handshake, device_path),
+        }
+    })
 }
 
 /// Query Mali GPU information (defaults to Parity mode)
@@ -58,83 +101,56 @@ pub fn query_mali<P: AsRef<Path>>(device_path: P) -> GpuResult<GpuInfo> {
     query_mali_with_mode(device_path, Mode::Parity)
 }
 
+/// Try Extended, then Parity mode, then the sysfs nodes
+///
+/// The sysfs fallback covers devices where the kbase node is not
+/// accessible to the caller; the ioctl error is returned if it fails too.
+pub fn query_mali_robust<P: AsRef<Path>>(device_path: P) -> GpuResult<GpuInfo> {
+    query_mali_with_opts(
+        device_path,
+        QueryOptions::new(Mode::Extended).fallback(Fallback::ParityThenSysfs),
+    )
+}
+
+/// Dump the raw `GET_GPUPROPS` buffer of `device_path`
+///
+/// The bytes can be attached to a bug report and decoded later with
+/// [`parse_snapshot`](super::parse_snapshot).
+pub fn dump_raw_properties<P: AsRef<Path>>(device_path: P) -> GpuResult<Vec<u8>> {
+    let (file, _) = open_session(device_path.as_ref())?;
+    get_properties_common(file.as_raw_fd())
+}
+
 /// Trait defining the strategy for querying Mali GPU information
 trait QueryStrategy {
-    fn query<P: AsRef<Path>>(&self, device_path: P) -> GpuResult<GpuInfo>;
+    fn query(&self, fd: RawFd, handshake: Handshake, device_path: &Path) -> GpuResult<GpuInfo>;
     fn parser_config(&self) -> ParserConfig;
     fn get_properties(&self, fd: RawFd) -> GpuResult<Vec<u8>>;
     fn should_validate(&self) -> bool;
-    fn use_product_db(&self) -> bool;
 }
 
 /// Parity strategy - minimal like libgpuinfo
-struct ParityStrategy;
+struct ParityStrategy {
+    config: ParserConfig,
+    validate: bool,
+}
 
 impl QueryStrategy for ParityStrategy {
-    fn query<P: AsRef<Path>>(&self, device_path: P) -> GpuResult<GpuInfo> {
-        let file = OpenOptions::new()
-            .read(true)
-            .write(true)
-            .open(device_path)
-            .map_err(GpuError::Io)?;
-
-        let fd = file.as_raw_fd();
+    fn query(&self, fd: RawFd, handshake: Handshake, device_path: &Path) -> GpuResult<GpuInfo> {
         let props = self.get_properties(fd)?;
-        let parsed = parse_properties_lenient(&props);
-
-        let num_l2_bytes = if parsed.l2_log2_cache_size > 0 && parsed.num_l2_slices > 0 {
-            (1u64 << parsed.l2_log2_cache_size) * parsed.num_l2_slices
-        } else {
-            0
-        };
-
-        // Try to get product info from database
-        let (gpu_name_cow, architecture_cow, arch_major, arch_minor, gpu_id) =
-            if self.use_product_db() {
-                if let Some(product_info) = lookup_product(get_gpu_id(parsed.gpu_id), parsed.num_shader_cores) {
-                    let (major, minor) = extract_architecture(parsed.raw_gpu_id);
-                    (
-                        Cow::Borrowed(product_info.name),      // Direkt Cow erstellen
-                        Cow::Borrowed(product_info.architecture),
-                        major,
-                        minor,
-                        get_gpu_id(parsed.gpu_id)
-                    )
-                } else {
-                    (Cow::Borrowed(""), Cow::Borrowed(""), 0, 0, parsed.gpu_id)
-                }
-            } else {
-                (Cow::Borrowed(""), Cow::Borrowed(""), 0, 0, parsed.gpu_id)
-            };
-
-        let mali_data = MaliData {
-            gpu_id: parsed.gpu_id,
-            raw_gpu_id: parsed.raw_gpu_id,
-            shader_core_mask: parsed.shader_core_mask,
-            num_l2_slices: parsed.num_l2_slices,
-            num_exec_engines: 0,
-            num_fp32_fmas_per_core: 0,
-            num_fp16_fmas_per_core: 0,
-            num_texels_per_core: 0,
-            num_pixels_per_core: 0,
-        };
-
-        Ok(GpuInfo {
-            vendor: GpuVendor::Mali,
-            gpu_name: gpu_name_cow,        
-            architecture: architecture_cow, 
-            architecture_major: arch_major,
-            architecture_minor: arch_minor,
-            num_shader_cores: parsed.num_shader_cores,
-            num_l2_bytes,
-            num_bus_bits: 0,
-            mali_data: Some(mali_data),
-            adreno_data: None,
-        })
+        Assembly {
+            mode: Mode::Parity,
+            config: self.parser_config(),
+            validate: self.should_validate(),
+            flavor: handshake.map(|(flavor, _)| flavor),
+            driver_version: handshake.map(|(_, version)| (version.major, version.minor)),
+            device_path: Some(device_path),
+        }
+        .build(&props)
     }
 
     fn parser_config(&self) -> ParserConfig {
-        ParserConfig::PARITY
+        self.config
     }
 
     fn get_properties(&self, fd: RawFd) -> GpuResult<Vec<u8>> {
@@ -142,108 +158,41 @@ impl QueryStrategy for ParityStrategy {
     }
 
     fn should_validate(&self) -> bool {
-        false
-    }
-
-    fn use_product_db(&self) -> bool {
-        true
+        self.validate
     }
 }
 
 /// Extended strategy - full features
-struct ExtendedStrategy;
+struct ExtendedStrategy {
+    config: ParserConfig,
+    validate: bool,
+}
 
 impl QueryStrategy for ExtendedStrategy {
-    fn query<P: AsRef<Path>>(&self, device_path: P) -> GpuResult<GpuInfo> {
-        let file = OpenOptions::new()
-            .read(true)
-            .write(true)
-            .open(device_path)
-            .map_err(GpuError::Io)?;
-
-        let fd = file.as_raw_fd();
-
-        // Check version (ignore errors)
-        let _ = check_version_optional(fd);
-
-        // Set flags (ignore errors)
-        let _ = set_flags_optional(fd);
+    fn query(&self, fd: RawFd, handshake: Handshake, device_path: &Path) -> GpuResult<GpuInfo> {
+        if let Some((flavor, version)) = handshake {
+            if !version.is_known() {
+                return Err(GpuError::VersionMismatch {
+                    required: "kbase UK 11.x (JM) or 1.x (CSF)".into(),
+                    found: format!("{version} ({flavor})"),
+                });
+            }
+        }
 
-        // Get properties
         let props = self.get_properties(fd)?;
-        let parsed = parse_properties(&props, self.parser_config())?;
-
-        let product_info = lookup_product(get_gpu_id(parsed.gpu_id), parsed.num_shader_cores)
-            .ok_or_else(|| GpuError::UnsupportedGpu {
-                id: parsed.gpu_id,
-                cores: parsed.num_shader_cores,
-            })?;
-
-        let num_exec_engines = (product_info.get_num_exec_engines)(
-            parsed.num_shader_cores,
-            parsed.raw_core_features,
-            parsed.raw_thread_features,
-        );
-
-        let num_fp32_fmas_per_engine = (product_info.get_num_fp32_fmas_per_engine)(
-            parsed.num_shader_cores,
-            parsed.raw_core_features,
-            parsed.raw_thread_features,
-        );
-
-        let num_fp32_fmas_per_core = num_fp32_fmas_per_engine * num_exec_engines;
-
-        let num_texels_per_core = (product_info.get_num_texels)(
-            parsed.num_shader_cores,
-            parsed.raw_core_features,
-            parsed.raw_thread_features,
-        );
-
-        let num_pixels_per_core = (product_info.get_num_pixels)(
-            parsed.num_shader_cores,
-            parsed.raw_core_features,
-            parsed.raw_thread_features,
-        );
-
-        let (arch_major, arch_minor) = extract_architecture(parsed.raw_gpu_id);
-
-        let num_l2_bytes = (1u64 << parsed.l2_log2_cache_size) * parsed.num_l2_slices;
-        let num_bus_bits = 1u64 << ((parsed.raw_l2_features >> 24) & 0xFF);
-
-        let mali_data = MaliData {
-            gpu_id: get_gpu_id(parsed.gpu_id),
-            raw_gpu_id: parsed.raw_gpu_id,
-            shader_core_mask: parsed.shader_core_mask,
-            num_l2_slices: parsed.num_l2_slices,
-            num_exec_engines,
-            num_fp32_fmas_per_core,
-            num_fp16_fmas_per_core: num_fp32_fmas_per_core * 2,
-            num_texels_per_core,
-            num_pixels_per_core,
-        };
-
-        let info = GpuInfo {
-            vendor: GpuVendor::Mali,
-            gpu_name: Cow::Borrowed(product_info.name),  
-            architecture: Cow::Borrowed(product_info.architecture), 
-            architecture_major: arch_major,
-            architecture_minor: arch_minor,
-            num_shader_cores: parsed.num_shader_cores,
-            num_l2_bytes,
-            num_bus_bits,
-            mali_data: Some(mali_data),
-            adreno_data: None,
-        };
-
-        if self.should_validate() {
-            validate_gpu_info(&info)?;
+        Assembly {
+            mode: Mode::Extended,
+            config: self.parser_config(),
+            validate: self.should_validate(),
+            flavor: handshake.map(|(flavor, _)| flavor),
+            driver_version: handshake.map(|(_, version)| (version.major, version.minor)),
+            device_path: Some(device_path),
         }
-
-        Ok(info)
+        .build(&props)
     }
 
     fn parser_config(&self) -> ParserConfig {
-        ParserConfig::EXTENDED
+        self.config
     }
 
     fn get_properties(&self, fd: RawFd) -> GpuResult<Vec<u8>> {
@@ -251,28 +200,27 @@ impl QueryStrategy for ExtendedStrategy {
     }
 
     fn should_validate(&self) -> bool {
-        true
-    }
-
-    fn use_product_db(&self) -> bool {
-        true
+        self.validate
     }
 }
 
 /// Common function to get properties
-fn get_properties_common(fd: RawFd) -> GpuResult<Vec<u8>> {
-    let mut query = MaliPropsQuery {
-        buffer: 0,
-        size: 0,
-        flags: 0,
+pub(crate) fn get_properties_common(fd: RawFd) -> GpuResult<Vec<u8>> {
+    let buffer = timed(Phase::Ioctl, || read_properties(fd))?;
+    record(|trace| trace.bytes += buffer.len());
+    Ok(buffer)
+}
+
+/// Two-step `GET_GPUPROPS`: query the size, then fill a buffer of that size
+fn read_properties(fd: RawFd) -> GpuResult<Vec<u8>> {
+    let map_err = |e: nix::Error| GpuError::IoctlFailed {
+        request: u64::from(nr::GET_GPUPROPS),
+        source: e.into(),
     };
 
-    let needed_size = unsafe {
-        mali_get_props(fd, &mut query).map_err(|e| GpuError::IoctlFailed {
-            request: ioctl_num::GET_PROPS,
-            source: e.into(),
-        })?
-    } as usize;
+    // First call with an empty buffer returns the required size
+    let mut query = KbaseIoctlGetGpuprops::default();
+    let needed_size = unsafe { kbase_get_gpuprops(fd, &query).map_err(map_err)? } as usize;
 
     if needed_size == 0 {
         return Err(GpuError::InvalidData("Driver returned zero buffer size".into()));
@@ -280,46 +228,138 @@ fn get_properties_common(fd: RawFd) -> GpuResult<Vec<u8>> {
 
     let mut buffer = vec![0u8; needed_size];
     query.buffer = buffer.as_mut_ptr() as u64;
-    query.size = needed_size as u32;
+    query.size = u32::try_from(needed_size)
+        .map_err(|_| GpuError::InvalidData(format!("Property buffer too large: {needed_size}")))?;
 
-    unsafe {
-        mali_get_props(fd, &mut query).map_err(|e| GpuError::IoctlFailed {
-            request: ioctl_num::GET_PROPS,
-            source: e.into(),
-        })?;
-    }
+    let written = unsafe { kbase_get_gpuprops(fd, &query).map_err(map_err)? } as usize;
+    buffer.truncate(written.min(needed_size));
 
     Ok(buffer)
 }
 
-/// Optional version check (errors ignored)
-fn check_version_optional(fd: RawFd) -> GpuResult<()> {
-    let mut ver = VersionCheck { major: 0, minor: 0 };
-    match unsafe { mali_version_check_csf(fd, &mut ver) } {
-        Ok(_) => Ok(()),
-        Err(nix::Error::EACCES) | Err(nix::Error::EPERM) | Err(nix::Error::ENOTTY) => {
-            // Permission denied or not supported - that's okay
-            Ok(())
+/// Fill `buffer` with the property buffer, returning the used length
+///
+/// Same two-step query as [`get_properties_common`], for callers that keep
+/// the buffer on the stack.
+#[cfg(feature = "agent")]
+pub(crate) fn get_properties_into(fd: RawFd, buffer: &mut [u8]) -> GpuResult<usize> {
+    let map_err = |e: nix::Error| GpuError::IoctlFailed {
+        request: u64::from(nr::GET_GPUPROPS),
+        source: e.into(),
+    };
+
+    let mut query = KbaseIoctlGetGpuprops::default();
+    let needed_size = unsafe { kbase_get_gpuprops(fd, &query).map_err(map_err)? } as usize;
+
+    if needed_size == 0 {
+        return Err(GpuError::InvalidData("Driver returned zero buffer size".into()));
+    }
+    if needed_size > buffer.len() {
+        return Err(GpuError::InvalidData(format!(
+            "Property buffer needs {needed_size} bytes, {} available",
+            buffer.len()
+        )));
+    }
+
+    query.buffer = buffer.as_mut_ptr() as u64;
+    query.size = needed_size as u32;
+    let written = unsafe { kbase_get_gpuprops(fd, &query).map_err(map_err)? } as usize;
+    Ok(written.min(needed_size))
+}
+
+/// Context setup the kernel requires before GET_GPUPROPS
+///
+/// kbase rejects every other ioctl with EPERM until VERSION_CHECK and then
+/// SET_FLAGS have been issued on the file, in exactly that order. Returns the
+/// driver flavor and UK version negotiated with the kernel, if the version
+/// check went through.
+fn handshake(fd: RawFd) -> GpuResult<Handshake> {
+    let version = check_version_optional(fd)?;
+    set_flags_optional(fd)?;
+    Ok(version)
+}
+
+/// kbase version handshake, tolerating kernels that refuse it
+///
+/// Probes the CSF variant first and falls back to the Job Manager one: JM
+/// kernels don't know the CSF number (ENOTTY), while CSF kernels reject the
+/// JM number with EPERM, so whichever succeeds identifies the flavor. We
+/// offer the newest UK version we know; the kernel replies with its own
+/// major and the lowest common minor. A different major means the kernel
+/// refused the handshake and fails with [`GpuError::VersionMismatch`].
+/// Refused probes yield `None`; other ioctl errors are returned, see
+/// [`negotiate_version`].
+fn check_version_optional(fd: RawFd) -> GpuResult<Handshake> {
+    negotiate_version(|request, ver| {
+        match request {
+            nr::VERSION_CHECK_CSF => unsafe { kbase_version_check_csf(fd, ver) },
+            _ => unsafe { kbase_version_check_jm(fd, ver) },
+        }
+        .map(drop)
+    })
+}
+
+/// Version check over `probe`, which issues the ioctl with the given number
+///
+/// Some JM kernels answer the CSF number with EPERM rather than ENOTTY, so
+/// a refused probe only means "not this flavor". `None` comes back when
+/// every probe was refused (also the case when the handshake was already
+/// done on the file); any other error is reported once both have failed.
+pub(crate) fn negotiate_version(
+    mut probe: impl FnMut(u8, &mut KbaseIoctlVersionCheck) -> nix::Result<()>,
+) -> GpuResult<Handshake> {
+    let probes: [(MaliFlavor, u8, UkVersion); 2] = [
+        (MaliFlavor::Csf, nr::VERSION_CHECK_CSF, CSF_UK_VERSION),
+        (MaliFlavor::JobManager, nr::VERSION_CHECK_JM, JM_UK_VERSION),
+    ];
+
+    let mut failure = None;
+    for (flavor, request, offered) in probes {
+        let mut ver = KbaseIoctlVersionCheck {
+            major: offered.major,
+            minor: offered.minor,
+        };
+        match probe(request, &mut ver) {
+            // The kernel answers with its own major if it cannot serve ours
+            Ok(()) if ver.major != offered.major => {
+                return Err(GpuError::VersionMismatch {
+                    required: format!("kbase UK {}.x ({flavor})", offered.major),
+                    found: UkVersion::new(ver.major, ver.minor).to_string(),
+                })
+            }
+            Ok(()) => return Ok(Some((flavor, UkVersion::new(ver.major, ver.minor)))),
+            // Not this flavor, or permission denied / handshake already done
+            Err(nix::Error::ENOTTY | nix::Error::EINVAL | nix::Error::EACCES | nix::Error::EPERM) => continue,
+            Err(e) => {
+                failure.get_or_insert(GpuError::IoctlFailed {
+                    request: u64::from(request),
+                    source: e.into(),
+                });
+            }
         }
-        Err(e) => Err(GpuError::IoctlFailed {
-            request: ioctl_num::VERSION_CHECK_CSF,
-            source: e.into(),
-        }),
     }
+
+    failure.map_or(Ok(None), Err)
 }
 
-/// Optional set flags (errors ignored)
+/// Create the kbase context, tolerating kernels that refuse it
+///
+/// EACCES, EPERM and ENOTTY (no permission, context already set up, no
+/// such ioctl) are not errors; anything else fails with
+/// [`GpuError::IoctlFailed`].
 fn set_flags_optional(fd: RawFd) -> GpuResult<()> {
-    let flags = SetFlags { create_flags: 2 };
-    match unsafe { mali_set_flags(fd, &flags) } {
+    let flags = KbaseIoctlSetFlags {
+        create_flags: BASE_CONTEXT_SYSTEM_MONITOR_SUBMIT_DISABLED,
+    };
+    match unsafe { kbase_set_flags(fd, &flags) } {
         Ok(_) => Ok(()),
         Err(nix::Error::EACCES) | Err(nix::Error::EPERM) | Err(nix::Error::ENOTTY) => {
-            // Permission denied or not supported - that's okay
+            // Permission denied, already set up or not supported - that's okay
             Ok(())
         }
         Err(e) => Err(GpuError::IoctlFailed {
-            request: ioctl_num::SET_FLAGS,
+            request: u64::from(nr::SET_FLAGS),
             source: e.into(),
         }),
     }
-}
\ No newline at end of file
+}
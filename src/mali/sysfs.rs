@@ -0,0 +1,207 @@
+//! sysfs fallback for devices where `/dev/mali0` is not accessible
+//!
+//! Production Android builds often restrict the kbase node to the graphics
+//! HAL, while the kbase platform device still exposes `gpuinfo` (name, core
+//! count, revision and product ID) and `core_mask` world-readable. The
+//! result carries no property-register data: `hw_config`, L2 and bus width
+//! stay empty.
+
+use std::borrow::Cow;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::capabilities::GpuCapabilities;
+use crate::error::{GpuError, GpuResult};
+use crate::info::{DataConfidence, GpuInfo, GpuVendor, MaliData, SpecConfidence};
+use crate::telemetry::devfreq::find_gpu_devfreq;
+use crate::telemetry::mali_nodes::MaliGpuinfo;
+use crate::telemetry::{read_node, FrequencyInfo};
+
+use super::assemble::core_groups;
+use super::database::{extract_architecture, extrapolate_product, resolve_gpu_id, resolve_product, soc_variant};
+
+/// kbase device directory behind the misc device
+pub const MALI_SYSFS_DEVICE_DIR: &str = "/sys/class/misc/mali0/device";
+
+/// Platform bus scanned when the misc class link is missing
+pub const PLATFORM_DEVICES_DIR: &str = "/sys/devices/platform";
+
+/// Directory holding a readable kbase `gpuinfo` node
+///
+/// Checks [`MALI_SYSFS_DEVICE_DIR`] first, then platform devices whose name
+/// contains `mali` or `gpu`.
+pub fn find_sysfs_device() -> Option<PathBuf> {
+    let preferred = PathBuf::from(MALI_SYSFS_DEVICE_DIR);
+    if preferred.join("gpuinfo").is_file() {
+        return Some(preferred);
+    }
+
+    let mut candidates: Vec<PathBuf> = fs::read_dir(PLATFORM_DEVICES_DIR)
+        .ok()?
+        .flatten()
+        .filter(|entry| {
+            let name = entry.file_name().to_string_lossy().to_lowercase();
+            name.contains("mali") || name.contains("gpu")
+        })
+        .map(|entry| entry.path())
+        .filter(|dir| dir.join("gpuinfo").is_file())
+        .collect();
+    candidates.sort();
+    candidates.into_iter().next()
+}
+
+/// devfreq directory registered by a kbase device directory, e.g.
+/// `<device>/devfreq/13000000.mali`
+pub fn find_device_devfreq(device_dir: &Path) -> Option<PathBuf> {
+    let mut candidates: Vec<PathBuf> = fs::read_dir(device_dir.join("devfreq"))
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        .collect();
+    candidates.sort();
+    candidates.into_iter().next()
+}
+
+/// devfreq clocks for a kbase device node such as `/dev/mali0`
+///
+/// Reads the devfreq device of `/sys/class/misc/<name>/device`, falling
+/// back to the first GPU-like device of the devfreq class.
+pub(crate) fn read_clocks_for_device(device_path: &Path) -> Option<FrequencyInfo> {
+    let name = device_path.file_name()?;
+    let device_dir = PathBuf::from("/sys/class/misc").join(name).join("device");
+    let devfreq = find_device_devfreq(&device_dir).or_else(find_gpu_devfreq)?;
+    Some(FrequencyInfo::read(&devfreq))
+}
+
+/// Fill the clock fields of `mali` from a devfreq reading
+pub(crate) fn apply_clocks(mali: &mut MaliData, clocks: &FrequencyInfo) {
+    let mhz = |hz: u64| (hz / 1_000_000) as u32;
+    mali.freq_table = clocks.available_hz.iter().copied().map(mhz).collect();
+    mali.current_freq_mhz = clocks.current_hz.map(mhz);
+    mali.min_freq_mhz = clocks.min_hz.map(mhz);
+    mali.max_freq_mhz = clocks.max_hz.map(mhz);
+}
+
+/// Query the first kbase device found in sysfs
+pub fn query_mali_sysfs() -> GpuResult<GpuInfo> {
+    let dir = find_sysfs_device().ok_or(GpuError::DeviceNotFound)?;
+    query_mali_sysfs_in(dir)
+}
+
+/// Query a kbase sysfs device directory
+///
+/// ```
+/// use armgpuinfo::mali::query_mali_sysfs_in;
+///
+/// let dir = tempfile::tempdir()?;
+/// std::fs::write(dir.path().join("gpuinfo"), "Mali-G610 4 cores r0p0 0xA867\n")?;
+/// std::fs::write(dir.path().join("core_mask"), "Current core mask : 0xF\n")?;
+/// let devfreq = dir.path().join("devfreq/fb000000.gpu");
+/// std::fs::create_dir_all(&devfreq)?;
+/// std::fs::write(devfreq.join("available_frequencies"), "1000000000 300000000 600000000\n")?;
+/// std::fs::write(devfreq.join("cur_freq"), "600000000\n")?;
+///
+/// let info = query_mali_sysfs_in(dir.path())?;
+/// assert_eq!(info.gpu_name, "Mali-G610");
+/// assert_eq!(info.num_shader_cores, 4);
+/// let mali = info.mali_data.unwrap();
+/// assert_eq!(mali.shader_core_mask, 0xF);
+/// assert_eq!(mali.freq_table, [300, 600, 1000]);
+/// assert_eq!(mali.current_freq_mhz, Some(600));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn query_mali_sysfs_in<P: AsRef<Path>>(device_dir: P) -> GpuResult<GpuInfo> {
+    let device_dir = device_dir.as_ref();
+    let line = read_node(&device_dir.join("gpuinfo")).ok_or(GpuError::DeviceNotFound)?;
+    let gpuinfo = MaliGpuinfo::parse(&line)
+        .ok_or_else(|| GpuError::InvalidData(format!("unrecognized gpuinfo: {line}")))?;
+    let product_id = gpuinfo
+        .product_id
+        .ok_or_else(|| GpuError::InvalidData(format!("gpuinfo without product ID: {line}")))?;
+
+    let shader_core_mask = read_node(&device_dir.join("core_mask"))
+        .and_then(|text| parse_core_mask(&text))
+        .filter(|mask| mask.count_ones() == gpuinfo.num_cores)
+        .unwrap_or(0);
+
+    // Rebuild the 32-bit GPU_ID register so revision and architecture
+    // decode as on the ioctl path
+    let (major, minor) = gpuinfo.revision.unwrap_or((0, 0));
+    let raw_gpu_id = (u64::from(product_id) << 16)
+        | (u64::from(major & 0xF) << 12)
+        | (u64::from(minor & 0xFF) << 4);
+    let gpu_id = resolve_gpu_id(product_id);
+
+    let (product, confidence) = match resolve_product(gpu_id, gpuinfo.num_cores).map(soc_variant) {
+        Some(entry) => (Some(entry), Some(SpecConfidence::Measured)),
+        None => match extrapolate_product(gpu_id, gpuinfo.num_cores) {
+            Some(entry) => (Some(entry), Some(SpecConfidence::Extrapolated)),
+            None => (None, None),
+        },
+    };
+    let (architecture_major, architecture_minor) = extract_architecture(raw_gpu_id);
+
+    let gpu_name = match product {
+        Some(entry) if confidence == Some(SpecConfidence::Measured) => entry.name_as_cow(),
+        _ => Cow::Owned(gpuinfo.name),
+    };
+
+    let mut info = GpuInfo {
+        vendor: GpuVendor::Mali,
+        gpu_name,
+        architecture: product.map_or(Cow::Borrowed(""), |entry| entry.architecture_as_cow()),
+        architecture_major,
+        architecture_minor,
+        num_shader_cores: gpuinfo.num_cores,
+        num_l2_bytes: 0,
+        num_bus_bits: 0,
+        mali_data: Some(MaliData {
+            gpu_id,
+            raw_gpu_id,
+            shader_core_mask,
+            core_groups: core_groups(&[], shader_core_mask),
+            num_l2_slices: 0,
+            num_exec_engines: 0,
+            num_fp32_fmas_per_core: 0,
+            num_fp16_fmas_per_core: 0,
+            num_texels_per_core: 0,
+            num_pixels_per_core: 0,
+            flavor: None,
+            driver_version: None,
+            arch: product.map(|entry| entry.architecture),
+            spec_confidence: confidence,
+            power_model: None,
+            freq_table: Vec::new(),
+            current_freq_mhz: None,
+            min_freq_mhz: None,
+            max_freq_mhz: None,
+            measured_max_freq_mhz: None,
+            hw_config: None,
+            l2_features: None,
+            extended: None,
+            instance: None,
+        }),
+        adreno_data: None,
+        vivante_data: None,
+        capabilities: GpuCapabilities::default(),
+        // The L2 size is not exported through sysfs
+        confidence: DataConfidence::Unknown,
+        provenance: Vec::new(),
+        device_path: Some(device_dir.to_path_buf()),
+    };
+    if let (Some(mali), Some(devfreq)) = (info.mali_data.as_mut(), find_device_devfreq(device_dir)) {
+        apply_clocks(mali, &FrequencyInfo::read(&devfreq));
+    }
+    info.capabilities = GpuCapabilities::of(&info);
+    Ok(info)
+}
+
+/// Parse kbase `core_mask`, e.g. "Current core mask : 0xF" or the per-slot
+/// CSF listing; the first hex value wins
+fn parse_core_mask(text: &str) -> Option<u64> {
+    text.split(|c: char| c.is_whitespace() || c == ':' || c == ',')
+        .find_map(|token| {
+            let hex = token.strip_prefix("0x").or_else(|| token.strip_prefix("0X"))?;
+            u64::from_str_radix(hex, 16).ok()
+        })
+}
@@ -0,0 +1,265 @@
+//! Canned GPUs for tests on machines without one
+//!
+//! A [`MockGpuBackend`] maps device paths to dumped property buffers. While
+//! it is installed on the current thread, the `query_mali*`,
+//! `query_adreno*`, `query_gpu_*` and `enumerate_gpus` entry points decode
+//! those buffers instead of opening device nodes, and report every path
+//! without a fixture as missing. Buffers come from
+//! [`dump_raw_properties`](crate::mali::dump_raw_properties) or a stored
+//! [`Snapshot`].
+//!
+//! ```
+//! # #[cfg(all(feature = "adreno-kgsl", feature = "auto-detect"))]
+//! # {
+//! use armgpuinfo::adreno::KgslDeviceInfo;
+//! use armgpuinfo::mock::MockGpuBackend;
+//!
+//! let info = KgslDeviceInfo {
+//!     chip_id: 0x0706_0001,
+//!     gmem_sizebytes: 2 << 20,
+//!     ..Default::default()
+//! };
+//! let _mock = MockGpuBackend::new().adreno_device_info("/dev/kgsl-3d0", info).install();
+//!
+//! let gpu = armgpuinfo::query_gpu_auto(None::<&str>)?;
+//! assert_eq!(gpu.gpu_name, "Adreno 740");
+//! # }
+//! # Ok::<(), armgpuinfo::GpuError>(())
+//! ```
+
+use std::cell::RefCell;
+use std::path::PathBuf;
+
+use crate::info::GpuVendor;
+use crate::snapshot::Snapshot;
+
+#[cfg(any(feature = "mali-kbase", feature = "adreno-kgsl"))]
+use crate::error::{GpuError, GpuResult};
+#[cfg(any(feature = "mali-kbase", feature = "adreno-kgsl"))]
+use crate::info::GpuInfo;
+#[cfg(any(feature = "mali-kbase", feature = "adreno-kgsl"))]
+use crate::instrument::traced;
+#[cfg(any(feature = "mali-kbase", feature = "adreno-kgsl"))]
+use crate::options::QueryOptions;
+#[cfg(any(feature = "mali-kbase", feature = "adreno-kgsl"))]
+use std::path::Path;
+
+thread_local! {
+    /// Installed backends, innermost last
+    static INSTALLED: RefCell<Vec<MockGpuBackend>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Device paths and the buffers their drivers answer with
+#[derive(Debug, Clone, Default)]
+pub struct MockGpuBackend {
+    /// `None` for nodes that exist but cannot be opened
+    devices: Vec<(PathBuf, Option<Snapshot>)>,
+    /// kbase `VERSION_CHECK` answers by node and ioctl number
+    #[cfg(feature = "mali-kbase")]
+    version_checks: Vec<(PathBuf, u8, Result<crate::mali::UkVersion, i32>)>,
+    /// Nodes whose query panics
+    #[cfg(any(feature = "mali-kbase", feature = "adreno-kgsl"))]
+    panics: Vec<PathBuf>,
+}
+
+impl MockGpuBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Serve a kbase `GET_GPUPROPS` buffer at `path`
+    pub fn mali(self, path: impl Into<PathBuf>, raw: impl Into<Vec<u8>>) -> Self {
+        self.snapshot(path, Snapshot::from_raw(GpuVendor::Mali, raw))
+    }
+
+    /// Answer the kbase `VERSION_CHECK` ioctl number `request` on `path`
+    /// with the kernel's UK version or the errno it fails with
+    ///
+    /// Numbers without an answer fail with ENOTTY. Nodes without any answer
+    /// skip the handshake and report no driver flavor.
+    #[cfg(feature = "mali-kbase")]
+    pub fn mali_version_check(
+        mut self,
+        path: impl Into<PathBuf>,
+        request: u8,
+        reply: Result<crate::mali::UkVersion, i32>,
+    ) -> Self {
+        self.version_checks.push((path.into(), request, reply));
+        self
+    }
+
+    /// Serve a KGSL device info record (`KgslDeviceInfo::to_bytes`) at `path`
+    pub fn adreno(self, path: impl Into<PathBuf>, raw: impl Into<Vec<u8>>) -> Self {
+        self.snapshot(path, Snapshot::from_raw(GpuVendor::Adreno, raw))
+    }
+
+    /// Serve `info` at `path`
+    #[cfg(feature = "adreno-kgsl")]
+    pub fn adreno_device_info(
+        self,
+        path: impl Into<PathBuf>,
+        info: crate::adreno::KgslDeviceInfo,
+    ) -> Self {
+        self.adreno(path, info.to_bytes())
+    }
+
+    /// Serve a stored snapshot at `path`
+    pub fn snapshot(mut self, path: impl Into<PathBuf>, snapshot: Snapshot) -> Self {
+        self.devices.push((path.into(), Some(snapshot)));
+        self
+    }
+
+    /// A node at `path` that fails with [`PermissionDenied`](crate::GpuError::PermissionDenied)
+    pub fn denied(mut self, path: impl Into<PathBuf>) -> Self {
+        self.devices.push((path.into(), None));
+        self
+    }
+
+    /// A node at `path` whose query panics, to exercise unwind handling in
+    /// callers
+    #[cfg(any(feature = "mali-kbase", feature = "adreno-kgsl"))]
+    pub fn panicking(mut self, path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        self.panics.push(path.clone());
+        self.denied(path)
+    }
+
+    /// Serve queries on this thread until the guard is dropped
+    ///
+    /// Installing again nests: the newest backend is used until its guard
+    /// is dropped.
+    pub fn install(self) -> MockGuard {
+        INSTALLED.with(|installed| installed.borrow_mut().push(self));
+        MockGuard { _not_send: std::marker::PhantomData }
+    }
+}
+
+/// Keeps a [`MockGpuBackend`] installed, see [`MockGpuBackend::install`]
+#[derive(Debug)]
+#[must_use = "the mock backend is uninstalled when the guard is dropped"]
+pub struct MockGuard {
+    // Installed per thread, so it must be dropped on the same thread
+    _not_send: std::marker::PhantomData<*const ()>,
+}
+
+impl Drop for MockGuard {
+    fn drop(&mut self) {
+        INSTALLED.with(|installed| installed.borrow_mut().pop());
+    }
+}
+
+/// Copy of the active backend, for queries handed to another thread
+#[cfg(all(feature = "tokio", any(feature = "mali-kbase", feature = "adreno-kgsl")))]
+pub(crate) fn active() -> Option<MockGpuBackend> {
+    INSTALLED.with(|installed| installed.borrow().last().cloned())
+}
+
+/// Run `f` with the active backend, `None` if none is installed
+#[cfg(any(feature = "mali-kbase", feature = "adreno-kgsl"))]
+fn with_active<T>(f: impl FnOnce(&MockGpuBackend) -> T) -> Option<T> {
+    INSTALLED.with(|installed| installed.borrow().last().map(f))
+}
+
+/// Buffer of a `vendor` node at `path`, with the error a real open or
+/// ioctl would fail with otherwise
+#[cfg(any(feature = "mali-kbase", feature = "adreno-kgsl"))]
+fn fixture(mock: &MockGpuBackend, path: &Path, vendor: GpuVendor) -> GpuResult<Vec<u8>> {
+    if mock.panics.iter().any(|device| device == path) {
+        panic!("mock query of {} panicked", path.display());
+    }
+    match mock.devices.iter().find(|(device, _)| device == path) {
+        None => Err(GpuError::DeviceNotFound),
+        Some((_, None)) => Err(GpuError::PermissionDenied),
+        Some((_, Some(snapshot))) if snapshot.vendor == vendor => {
+            crate::instrument::record(|trace| trace.bytes += snapshot.raw.len());
+            Ok(snapshot.raw.clone())
+        }
+        Some(_) => Err(GpuError::DriverNotSupported),
+    }
+}
+
+/// Mock answer for a kbase query, `None` if no backend is installed
+#[cfg(feature = "mali-kbase")]
+pub(crate) fn query_mali(path: &Path, options: &QueryOptions) -> Option<GpuResult<GpuInfo>> {
+    with_active(|mock| {
+        traced(options.instrumentation.as_ref(), path, || {
+            let raw = fixture(mock, path, GpuVendor::Mali)?;
+            let handshake = version_check(mock, path)?;
+            options.try_modes(|mode| {
+                crate::mali::Assembly {
+                    mode,
+                    config: options.mali_config(mode),
+                    validate: options.validates(mode),
+                    flavor: handshake.map(|(flavor, _)| flavor),
+                    driver_version: handshake.map(|(_, version)| (version.major, version.minor)),
+                    device_path: Some(path),
+                }
+                .build(&raw)
+            })
+        })
+    })
+}
+
+/// kbase handshake against the registered `VERSION_CHECK` answers
+#[cfg(feature = "mali-kbase")]
+fn version_check(mock: &MockGpuBackend, path: &Path) -> GpuResult<crate::mali::Handshake> {
+    let replies: Vec<_> = mock.version_checks.iter().filter(|(device, ..)| device == path).collect();
+    if replies.is_empty() {
+        return Ok(None);
+    }
+    crate::mali::negotiate_version(|request, ver| {
+        match replies.iter().find(|(_, number, _)| *number == request) {
+            Some((.., Ok(version))) => {
+                ver.major = version.major;
+                ver.minor = version.minor;
+                Ok(())
+            }
+            Some((.., Err(errno))) => Err(nix::Error::from_raw(*errno)),
+            None => Err(nix::Error::ENOTTY),
+        }
+    })
+}
+
+/// Mock answer for a KGSL query, `None` if no backend is installed
+#[cfg(feature = "adreno-kgsl")]
+pub(crate) fn query_adreno(path: &Path, options: &QueryOptions) -> Option<GpuResult<GpuInfo>> {
+    with_active(|mock| {
+        traced(options.instrumentation.as_ref(), path, || {
+            let raw = fixture(mock, path, GpuVendor::Adreno)?;
+            let mut info = options.try_modes(|mode| {
+                crate::adreno::assemble_device_info(
+                    &raw,
+                    mode.into(),
+                    options.adreno_config(mode),
+                    options.validates(mode),
+                    None,
+                )
+            })?;
+            info.device_path = Some(path.to_path_buf());
+            Ok(info)
+        })
+    })
+}
+
+/// Mock device nodes in `dir` whose name starts with `prefix`, sorted
+#[cfg(all(feature = "auto-detect", any(feature = "mali-kbase", feature = "adreno-kgsl")))]
+pub(crate) fn nodes_with_prefix(dir: &Path, prefix: &str) -> Option<Vec<PathBuf>> {
+    with_active(|mock| {
+        let mut nodes: Vec<PathBuf> = mock
+            .devices
+            .iter()
+            .map(|(path, _)| path)
+            .filter(|path| path.parent() == Some(dir))
+            .filter(|path| path.file_name().is_some_and(|n| n.to_string_lossy().starts_with(prefix)))
+            .cloned()
+            .collect();
+        nodes.sort();
+        nodes
+    })
+}
+
+/// Whether the active backend has a node at `path`, `None` if none is installed
+#[cfg(all(feature = "auto-detect", any(feature = "mali-kbase", feature = "adreno-kgsl")))]
+pub(crate) fn node_exists(path: &Path) -> Option<bool> {
+    with_active(|mock| mock.devices.iter().any(|(device, _)| device == path))
+}
@@ -0,0 +1,161 @@
+//! Arrow IPC and Parquet export of monitoring data
+//!
+//! Samples become one row each; summaries become one row per metric. Both
+//! keep nulls for metrics that were not available instead of writing zeros,
+//! so pandas/DuckDB see the same gaps the monitor did.
+
+use std::fs::File;
+use std::path::Path;
+use std::sync::Arc;
+
+use arrow_array::builder::{BooleanBuilder, Float32Builder, StringBuilder, UInt32Builder};
+use arrow_array::{ArrayRef, Float64Array, RecordBatch, StringArray, UInt64Array};
+use arrow_schema::{DataType, Field, Schema};
+
+use crate::error::{GpuError, GpuResult};
+use crate::telemetry::Reading;
+
+use super::{MetricSummary, MonitorSummary, Sample};
+
+/// Schema of [`samples_to_record_batch`]
+pub fn sample_schema() -> Schema {
+    Schema::new(vec![
+        Field::new("elapsed_us", DataType::UInt64, false),
+        Field::new("freq_mhz", DataType::UInt32, true),
+        Field::new("utilization", DataType::Float32, true),
+        Field::new("utilization_source", DataType::Utf8, true),
+        Field::new("temperature_c", DataType::Float32, true),
+        Field::new("temperature_source", DataType::Utf8, true),
+        Field::new("power_watts", DataType::Float32, true),
+        Field::new("power_source", DataType::Utf8, true),
+        Field::new("throttled", DataType::Boolean, true),
+        Field::new("power_state", DataType::Utf8, false),
+    ])
+}
+
+/// Schema of [`summary_to_record_batch`]
+pub fn summary_schema() -> Schema {
+    Schema::new(vec![
+        Field::new("metric", DataType::Utf8, false),
+        Field::new("count", DataType::UInt64, false),
+        Field::new("min", DataType::Float64, false),
+        Field::new("mean", DataType::Float64, false),
+        Field::new("p50", DataType::Float64, false),
+        Field::new("p95", DataType::Float64, false),
+        Field::new("max", DataType::Float64, false),
+    ])
+}
+
+/// Convert samples to a record batch
+pub fn samples_to_record_batch<'a>(
+    samples: impl IntoIterator<Item = &'a Sample>,
+) -> GpuResult<RecordBatch> {
+    let mut elapsed = Vec::new();
+    let mut freq = UInt32Builder::new();
+    let mut util = ReadingColumns::default();
+    let mut temp = ReadingColumns::default();
+    let mut power = ReadingColumns::default();
+    let mut throttled = BooleanBuilder::new();
+    let mut power_state = StringBuilder::new();
+
+    for sample in samples {
+        elapsed.push(sample.elapsed.as_micros() as u64);
+        freq.append_option(sample.freq_mhz);
+        util.append(sample.utilization);
+        temp.append(sample.temperature_c);
+        power.append(sample.power_watts);
+        throttled.append_option(sample.throttled);
+        power_state.append_value(sample.power_state.as_str());
+    }
+
+    let (util, util_src) = util.finish();
+    let (temp, temp_src) = temp.finish();
+    let (power, power_src) = power.finish();
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(UInt64Array::from(elapsed)),
+        Arc::new(freq.finish()),
+        util,
+        util_src,
+        temp,
+        temp_src,
+        power,
+        power_src,
+        Arc::new(throttled.finish()),
+        Arc::new(power_state.finish()),
+    ];
+    RecordBatch::try_new(Arc::new(sample_schema()), columns).map_err(export_error)
+}
+
+/// Convert a summary to a record batch with one row per available metric
+///
+/// Throttle time is emitted as metric `throttle_pct` with all statistics set
+/// to the percentage.
+pub fn summary_to_record_batch(summary: &MonitorSummary) -> GpuResult<RecordBatch> {
+    let mut rows: Vec<(&str, MetricSummary)> = [
+        ("freq_mhz", summary.freq_mhz),
+        ("utilization", summary.utilization),
+        ("temperature_c", summary.temperature_c),
+        ("power_watts", summary.power_watts),
+    ]
+    .into_iter()
+    .filter_map(|(name, metric)| metric.map(|m| (name, m)))
+    .collect();
+    if let Some(pct) = summary.throttle_pct {
+        rows.push((
+            "throttle_pct",
+            MetricSummary { count: summary.samples, min: pct, mean: pct, p50: pct, p95: pct, max: pct },
+        ));
+    }
+
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(StringArray::from_iter_values(rows.iter().map(|(name, _)| *name))),
+        Arc::new(UInt64Array::from_iter_values(rows.iter().map(|(_, m)| m.count as u64))),
+        Arc::new(Float64Array::from_iter_values(rows.iter().map(|(_, m)| m.min))),
+        Arc::new(Float64Array::from_iter_values(rows.iter().map(|(_, m)| m.mean))),
+        Arc::new(Float64Array::from_iter_values(rows.iter().map(|(_, m)| m.p50))),
+        Arc::new(Float64Array::from_iter_values(rows.iter().map(|(_, m)| m.p95))),
+        Arc::new(Float64Array::from_iter_values(rows.iter().map(|(_, m)| m.max))),
+    ];
+    RecordBatch::try_new(Arc::new(summary_schema()), columns).map_err(export_error)
+}
+
+/// Write a batch as an Arrow IPC file
+pub fn write_ipc<P: AsRef<Path>>(path: P, batch: &RecordBatch) -> GpuResult<()> {
+    let file = File::create(path)?;
+    let mut writer =
+        arrow_ipc::writer::FileWriter::try_new(file, &batch.schema()).map_err(export_error)?;
+    writer.write(batch).map_err(export_error)?;
+    writer.finish().map_err(export_error)
+}
+
+/// Write a batch as a Parquet file
+pub fn write_parquet<P: AsRef<Path>>(path: P, batch: &RecordBatch) -> GpuResult<()> {
+    let file = File::create(path)?;
+    let mut writer =
+        parquet::arrow::ArrowWriter::try_new(file, batch.schema(), None).map_err(export_error)?;
+    writer.write(batch).map_err(export_error)?;
+    writer.close().map_err(export_error)?;
+    Ok(())
+}
+
+fn export_error(e: impl std::fmt::Display) -> GpuError {
+    GpuError::Export(e.to_string())
+}
+
+/// Value and provenance columns for one [`Reading`] metric
+#[derive(Default)]
+struct ReadingColumns {
+    values: Float32Builder,
+    sources: StringBuilder,
+}
+
+impl ReadingColumns {
+    fn append(&mut self, reading: Option<Reading<f32>>) {
+        self.values.append_option(reading.map(|r| r.value));
+        self.sources.append_option(reading.map(|r| r.provenance.to_string()));
+    }
+
+    fn finish(mut self) -> (ArrayRef, ArrayRef) {
+        (Arc::new(self.values.finish()), Arc::new(self.sources.finish()))
+    }
+}
@@ -0,0 +1,100 @@
+//! Battery and charger state from sysfs `power_supply`
+
+use std::fs;
+use std::path::Path;
+
+use crate::telemetry::read_node;
+
+/// Default power_supply class directory
+pub const POWER_SUPPLY_ROOT: &str = "/sys/class/power_supply";
+
+/// Whether the device currently runs from its battery
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PowerState {
+    /// On battery and discharging
+    Battery,
+    /// Charging, full, or on an external supply
+    External,
+    /// No battery found (dev boards, TVs) or state unreadable
+    Unknown,
+}
+
+impl PowerState {
+    /// Stable lowercase label
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PowerState::Battery => "battery",
+            PowerState::External => "external",
+            PowerState::Unknown => "unknown",
+        }
+    }
+
+    /// Read the current state from [`POWER_SUPPLY_ROOT`]
+    pub fn read() -> Self {
+        Self::read_from(Path::new(POWER_SUPPLY_ROOT))
+    }
+
+    /// Read the current state from a custom class directory
+    ///
+    /// Any online mains/USB supply counts as external power; otherwise the
+    /// first battery's `status` decides.
+    pub fn read_from(root: &Path) -> Self {
+        let Ok(entries) = fs::read_dir(root) else {
+            return PowerState::Unknown;
+        };
+        let mut supplies: Vec<_> = entries.flatten().map(|e| e.path()).collect();
+        supplies.sort();
+
+        let mut battery = PowerState::Unknown;
+        for supply in supplies {
+            let kind = read_node(&supply.join("type")).unwrap_or_default();
+            match kind.as_str() {
+                "Mains" | "USB" | "USB_PD" | "USB_C" | "Wireless"
+                    if read_node(&supply.join("online")).as_deref() == Some("1") =>
+                {
+                    return PowerState::External;
+                }
+                "Battery" if battery == PowerState::Unknown => {
+                    battery = match read_node(&supply.join("status")).as_deref() {
+                        Some("Discharging") | Some("Not charging") => PowerState::Battery,
+                        Some("Charging") | Some("Full") => PowerState::External,
+                        _ => PowerState::Unknown,
+                    };
+                }
+                _ => {}
+            }
+        }
+        battery
+    }
+}
+
+/// How the monitor reacts to running on battery
+#[derive(Debug, Clone)]
+pub struct BatteryPolicy {
+    /// Adapt sampling to the power state at all
+    pub enabled: bool,
+    /// Sampling interval multiplier while on battery
+    pub interval_multiplier: u32,
+    /// Skip non-essential metrics (power, temperature) on battery
+    pub pause_nonessential: bool,
+}
+
+impl Default for BatteryPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_multiplier: 4,
+            pause_nonessential: true,
+        }
+    }
+}
+
+impl BatteryPolicy {
+    /// Battery-aware policy with default throttling
+    pub fn battery_aware() -> Self {
+        Self {
+            enabled: true,
+            ..Self::default()
+        }
+    }
+}
@@ -0,0 +1,373 @@
+//! Periodic GPU monitoring
+//!
+//! [`GpuMonitor`] samples frequency, utilization and the other
+//! [`telemetry`](crate::telemetry) sources at a fixed interval and keeps the
+//! samples for later inspection. [`GpuMonitor::start`] runs it on a
+//! background thread and hands out statistics while sampling continues.
+
+#[cfg(feature = "arrow")]
+pub mod arrow;
+pub mod battery;
+pub mod summary;
+
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use crate::telemetry::busy::{BusyCounters, BusyTime};
+use crate::telemetry::devfreq::{
+    find_gpu_devfreq, read_available_frequencies_hz, read_cur_freq_hz, read_max_freq_hz,
+};
+use crate::telemetry::hwmon::{find_gpu_power_sensors, PowerSensor};
+use crate::telemetry::{read_temperature, read_utilization, Reading};
+
+pub use battery::{BatteryPolicy, PowerState};
+pub use summary::{MetricSummary, MonitorSummary};
+
+/// How samples are retained
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Storage {
+    /// Keep every sample until [`GpuMonitor::clear`]
+    #[default]
+    Unbounded,
+    /// Keep at most `capacity` samples, overwriting the oldest
+    ///
+    /// Memory stays constant, which suits always-on agents that only dump
+    /// recent history when something goes wrong.
+    Ring { capacity: usize },
+}
+
+/// Samples [`GpuMonitor::start`] retains: one minute at 100 ms
+pub const DEFAULT_HISTORY: usize = 600;
+
+/// Monitor configuration
+#[derive(Debug, Clone)]
+pub struct MonitorConfig {
+    /// Sampling interval at full rate
+    pub interval: Duration,
+    /// Sample retention
+    pub storage: Storage,
+    /// Behaviour on battery power
+    pub battery: BatteryPolicy,
+    /// devfreq directory to sample; auto-detected if `None`
+    pub devfreq: Option<PathBuf>,
+}
+
+impl Default for MonitorConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_millis(100),
+            storage: Storage::Unbounded,
+            battery: BatteryPolicy::default(),
+            devfreq: None,
+        }
+    }
+}
+
+/// One monitoring sample
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sample {
+    /// Time since the monitor was created
+    pub elapsed: Duration,
+    /// Current GPU clock in MHz
+    pub freq_mhz: Option<u32>,
+    /// GPU busy percentage (0.0 - 100.0)
+    pub utilization: Option<Reading<f32>>,
+    /// Active and idle time since the previous sample (or over the driver's
+    /// last window for non-cumulative counters)
+    pub busy_time: Option<Reading<BusyTime>>,
+    /// GPU temperature in degrees Celsius
+    pub temperature_c: Option<Reading<f32>>,
+    /// GPU power draw in watts
+    pub power_watts: Option<Reading<f32>>,
+    /// Whether the clock ceiling is below the highest OPP
+    pub throttled: Option<bool>,
+    /// Power state the sample was taken in
+    pub power_state: PowerState,
+}
+
+/// Periodic GPU sampler
+#[derive(Debug)]
+pub struct GpuMonitor {
+    config: MonitorConfig,
+    devfreq: Option<PathBuf>,
+    peak_freq_hz: Option<u64>,
+    power_sensor: Option<PowerSensor>,
+    busy_counters: Option<BusyCounters>,
+    last_busy: Option<BusyTime>,
+    full_rate_override: bool,
+    start: Instant,
+    samples: VecDeque<Sample>,
+    overwritten: u64,
+}
+
+impl GpuMonitor {
+    /// Create a monitor, locating devfreq and power sensors once up front
+    pub fn new(config: MonitorConfig) -> Self {
+        let devfreq = config.devfreq.clone().or_else(find_gpu_devfreq);
+        let peak_freq_hz = devfreq
+            .as_deref()
+            .and_then(|d| read_available_frequencies_hz(d).last().copied());
+        let samples = match config.storage {
+            Storage::Ring { capacity } => VecDeque::with_capacity(capacity),
+            Storage::Unbounded => VecDeque::new(),
+        };
+        Self {
+            config,
+            devfreq,
+            peak_freq_hz,
+            power_sensor: find_gpu_power_sensors().into_iter().next(),
+            busy_counters: BusyCounters::find(),
+            last_busy: None,
+            full_rate_override: false,
+            start: Instant::now(),
+            samples,
+            overwritten: 0,
+        }
+    }
+
+    /// Sample every `interval` on a background thread, retaining the last
+    /// [`DEFAULT_HISTORY`] samples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use armgpuinfo::monitor::GpuMonitor;
+    ///
+    /// let handle = GpuMonitor::start(Duration::from_millis(10));
+    /// std::thread::sleep(Duration::from_millis(50));
+    /// let stats = handle.stats();
+    /// if let Some(freq) = stats.freq_mhz {
+    ///     println!("{} - {} MHz, p95 {}", freq.min, freq.max, freq.p95);
+    /// }
+    ///
+    /// let monitor = handle.stop();
+    /// assert!(monitor.samples().len() > 0);
+    /// ```
+    pub fn start(interval: Duration) -> MonitorHandle {
+        Self::new(MonitorConfig {
+            interval,
+            storage: Storage::Ring { capacity: DEFAULT_HISTORY },
+            ..MonitorConfig::default()
+        })
+        .spawn()
+    }
+
+    /// Move the monitor onto a background thread sampling at
+    /// [`next_interval`](Self::next_interval)
+    ///
+    /// The first sample is taken right away.
+    pub fn spawn(self) -> MonitorHandle {
+        let monitor = Arc::new(Mutex::new(self));
+        let (stop, stopped) = mpsc::channel::<()>();
+        let shared = Arc::clone(&monitor);
+        let thread = thread::spawn(move || loop {
+            let interval = {
+                let mut monitor = lock(&shared);
+                monitor.sample();
+                monitor.next_interval()
+            };
+            // A message or a dropped sender both end sampling
+            if !matches!(stopped.recv_timeout(interval), Err(RecvTimeoutError::Timeout)) {
+                break;
+            }
+        });
+        MonitorHandle { monitor, stop: Some(stop), thread: Some(thread) }
+    }
+
+    /// Force full-rate sampling regardless of the power state
+    pub fn set_full_rate_override(&mut self, enabled: bool) {
+        self.full_rate_override = enabled;
+    }
+
+    /// Whether the battery policy currently throttles sampling
+    fn reduced(&self, state: PowerState) -> bool {
+        self.config.battery.enabled && !self.full_rate_override && state == PowerState::Battery
+    }
+
+    /// Interval to wait before the next sample under the current policy
+    pub fn next_interval(&self) -> Duration {
+        if self.reduced(PowerState::read()) {
+            self.config.interval * self.config.battery.interval_multiplier.max(1)
+        } else {
+            self.config.interval
+        }
+    }
+
+    /// Take and store one sample
+    pub fn sample(&mut self) -> &Sample {
+        let power_state = if self.config.battery.enabled {
+            PowerState::read()
+        } else {
+            PowerState::Unknown
+        };
+        let essential_only = self.reduced(power_state) && self.config.battery.pause_nonessential;
+
+        let devfreq = self.devfreq.as_deref();
+        let freq_mhz = devfreq
+            .and_then(read_cur_freq_hz)
+            .map(|hz| (hz / 1_000_000) as u32);
+        let throttled = match (devfreq.and_then(read_max_freq_hz), self.peak_freq_hz) {
+            (Some(ceiling), Some(peak)) => Some(ceiling < peak),
+            _ => None,
+        };
+
+        let busy_time = self.read_busy_delta();
+        let utilization = read_utilization().or_else(|| {
+            let busy = busy_time?;
+            busy.value.busy_percent().map(|p| Reading::new(p, busy.provenance))
+        });
+
+        let (temperature_c, power_watts) = if essential_only {
+            (None, None)
+        } else {
+            (read_temperature(), self.power_sensor.as_ref().and_then(PowerSensor::read))
+        };
+
+        if let Storage::Ring { capacity } = self.config.storage {
+            while self.samples.len() >= capacity.max(1) {
+                self.samples.pop_front();
+                self.overwritten += 1;
+            }
+        }
+
+        self.samples.push_back(Sample {
+            elapsed: self.start.elapsed(),
+            freq_mhz,
+            utilization,
+            busy_time,
+            temperature_c,
+            power_watts,
+            throttled,
+            power_state,
+        });
+        self.samples.back().expect("sample just pushed")
+    }
+
+    /// Busy time since the last call; the first read of cumulative
+    /// counters only primes the baseline
+    fn read_busy_delta(&mut self) -> Option<Reading<BusyTime>> {
+        let counters = self.busy_counters.as_ref()?;
+        let current = counters.read()?;
+        if !counters.format.is_cumulative() {
+            return Some(current);
+        }
+        let previous = self.last_busy.replace(current.value)?;
+        let delta = current.value.since(&previous)?;
+        Some(Reading::new(delta, current.provenance))
+    }
+
+    /// Sample until `duration` has passed, honouring the battery policy
+    pub fn run_for(&mut self, duration: Duration) {
+        let end = Instant::now() + duration;
+        while Instant::now() < end {
+            self.sample();
+            thread::sleep(self.next_interval().min(end.saturating_duration_since(Instant::now())));
+        }
+    }
+
+    /// Stored samples, oldest first
+    pub fn samples(&self) -> impl DoubleEndedIterator<Item = &Sample> + ExactSizeIterator + Clone {
+        self.samples.iter()
+    }
+
+    /// Owned copy of the stored history, oldest first
+    pub fn snapshot(&self) -> Vec<Sample> {
+        self.samples.iter().cloned().collect()
+    }
+
+    /// Number of samples dropped by the ring buffer so far
+    pub fn overwritten(&self) -> u64 {
+        self.overwritten
+    }
+
+    /// Statistics over the most recent `window` of samples
+    pub fn summary(&self, window: Duration) -> MonitorSummary {
+        MonitorSummary::from_samples(self.samples.iter(), window)
+    }
+
+    /// Write the stored samples to an Arrow IPC file
+    #[cfg(feature = "arrow")]
+    pub fn export_arrow_ipc<P: AsRef<std::path::Path>>(&self, path: P) -> crate::GpuResult<()> {
+        arrow::write_ipc(path, &arrow::samples_to_record_batch(self.samples.iter())?)
+    }
+
+    /// Write the stored samples to a Parquet file
+    #[cfg(feature = "arrow")]
+    pub fn export_parquet<P: AsRef<std::path::Path>>(&self, path: P) -> crate::GpuResult<()> {
+        arrow::write_parquet(path, &arrow::samples_to_record_batch(self.samples.iter())?)
+    }
+
+    /// Drop all stored samples
+    pub fn clear(&mut self) {
+        self.samples.clear();
+    }
+}
+
+/// A [`GpuMonitor`] sampling on a background thread, see
+/// [`GpuMonitor::start`]
+///
+/// Sampling stops when the handle is dropped.
+#[derive(Debug)]
+pub struct MonitorHandle {
+    monitor: Arc<Mutex<GpuMonitor>>,
+    stop: Option<mpsc::Sender<()>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl MonitorHandle {
+    /// Min, max, mean and percentiles over the retained history
+    pub fn stats(&self) -> MonitorSummary {
+        self.summary(Duration::MAX)
+    }
+
+    /// Statistics over the most recent `window` of samples
+    pub fn summary(&self, window: Duration) -> MonitorSummary {
+        lock(&self.monitor).summary(window)
+    }
+
+    /// Most recent sample
+    pub fn latest(&self) -> Option<Sample> {
+        lock(&self.monitor).samples().next_back().cloned()
+    }
+
+    /// Owned copy of the retained history, oldest first
+    pub fn snapshot(&self) -> Vec<Sample> {
+        lock(&self.monitor).snapshot()
+    }
+
+    /// Stop sampling and take back the monitor with its history
+    pub fn stop(mut self) -> GpuMonitor {
+        self.join();
+        let monitor = Arc::clone(&self.monitor);
+        drop(self);
+        // The thread has exited, so no other reference is left
+        Arc::try_unwrap(monitor)
+            .expect("monitor thread exited")
+            .into_inner()
+            .unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Signal the thread and wait for it to exit
+    fn join(&mut self) {
+        if let Some(stop) = self.stop.take() {
+            let _ = stop.send(());
+        }
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Drop for MonitorHandle {
+    fn drop(&mut self) {
+        self.join();
+    }
+}
+
+/// Lock the shared monitor; a panicked sample leaves it usable
+fn lock(monitor: &Mutex<GpuMonitor>) -> MutexGuard<'_, GpuMonitor> {
+    monitor.lock().unwrap_or_else(|e| e.into_inner())
+}
@@ -0,0 +1,106 @@
+//! Aggregate statistics over monitor samples
+
+use std::time::Duration;
+
+use super::Sample;
+
+/// Distribution of one metric over a window
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MetricSummary {
+    /// Number of samples that carried this metric
+    pub count: usize,
+    pub min: f64,
+    pub mean: f64,
+    pub p50: f64,
+    pub p95: f64,
+    pub max: f64,
+}
+
+impl MetricSummary {
+    /// Summarize a set of values, `None` if empty
+    pub fn from_values(mut values: Vec<f64>) -> Option<Self> {
+        if values.is_empty() {
+            return None;
+        }
+        values.sort_by(f64::total_cmp);
+        let count = values.len();
+        Some(Self {
+            count,
+            min: values[0],
+            mean: values.iter().sum::<f64>() / count as f64,
+            p50: percentile(&values, 50.0),
+            p95: percentile(&values, 95.0),
+            max: values[count - 1],
+        })
+    }
+}
+
+/// Aggregates over a monitoring window
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MonitorSummary {
+    /// Time span actually covered by the samples
+    pub window: Duration,
+    /// Number of samples in the window
+    pub samples: usize,
+    pub freq_mhz: Option<MetricSummary>,
+    pub utilization: Option<MetricSummary>,
+    pub temperature_c: Option<MetricSummary>,
+    pub power_watts: Option<MetricSummary>,
+    /// Share of samples (0.0 - 100.0) taken while the clock was capped
+    pub throttle_pct: Option<f64>,
+}
+
+impl MonitorSummary {
+    /// Summarize samples (oldest first) whose timestamps fall within
+    /// `window` of the newest one
+    pub fn from_samples<'a, I>(samples: I, window: Duration) -> Self
+    where
+        I: IntoIterator<Item = &'a Sample>,
+        I::IntoIter: DoubleEndedIterator + Clone,
+    {
+        let samples = samples.into_iter();
+        let newest = samples.clone().next_back().map(|s| s.elapsed).unwrap_or_default();
+        let start = newest.saturating_sub(window);
+        let in_window: Vec<&Sample> = samples.filter(|s| s.elapsed >= start).collect();
+
+        let collect = |f: &dyn Fn(&Sample) -> Option<f64>| -> Option<MetricSummary> {
+            MetricSummary::from_values(in_window.iter().filter_map(|s| f(s)).collect())
+        };
+
+        let throttle: Vec<bool> = in_window.iter().filter_map(|s| s.throttled).collect();
+        let throttle_pct = (!throttle.is_empty()).then(|| {
+            throttle.iter().filter(|&&t| t).count() as f64 * 100.0 / throttle.len() as f64
+        });
+
+        Self {
+            window: in_window
+                .first()
+                .map(|first| newest - first.elapsed)
+                .unwrap_or_default(),
+            samples: in_window.len(),
+            freq_mhz: collect(&|s| s.freq_mhz.map(f64::from)),
+            utilization: collect(&|s| s.utilization.map(|r| f64::from(r.value))),
+            temperature_c: collect(&|s| s.temperature_c.map(|r| f64::from(r.value))),
+            power_watts: collect(&|s| s.power_watts.map(|r| f64::from(r.value))),
+            throttle_pct,
+        }
+    }
+
+    /// Mean GPU clock in MHz
+    pub fn mean_freq_mhz(&self) -> Option<f64> {
+        self.freq_mhz.map(|m| m.mean)
+    }
+
+    /// Mean GPU utilization in percent
+    pub fn mean_utilization(&self) -> Option<f64> {
+        self.utilization.map(|m| m.mean)
+    }
+}
+
+/// Nearest-rank percentile of sorted values
+fn percentile(sorted: &[f64], pct: f64) -> f64 {
+    let rank = ((pct / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
@@ -0,0 +1,192 @@
+//! Async queries and monitoring for tokio runtimes
+//!
+//! The queries open device nodes and issue ioctls, which block. The
+//! functions here run them on tokio's blocking thread pool and return
+//! futures, so they can be awaited from async code without stalling a
+//! worker thread. A [`MockGpuBackend`](crate::mock::MockGpuBackend)
+//! installed on the calling thread serves the spawned query as well.
+//!
+//! ```no_run
+//! # #[cfg(all(feature = "auto-detect", any(feature = "mali-kbase", feature = "adreno-kgsl")))]
+//! # async fn run() -> armgpuinfo::GpuResult<()> {
+//! use armgpuinfo::monitor::MonitorConfig;
+//! use armgpuinfo::nonblocking::{monitor_stream, query_gpu_auto_async};
+//!
+//! let gpu = query_gpu_auto_async(None::<&str>).await?;
+//! println!("{}", gpu.gpu_name);
+//!
+//! let mut samples = monitor_stream(MonitorConfig::default());
+//! while let Some(sample) = samples.next().await {
+//!     println!("{:?} MHz", sample.freq_mhz);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use tokio::sync::mpsc;
+
+use crate::monitor::{GpuMonitor, MonitorConfig, Sample, Storage};
+
+#[cfg(any(feature = "mali-kbase", feature = "adreno-kgsl"))]
+use std::future::Future;
+#[cfg(any(feature = "mali-kbase", feature = "adreno-kgsl"))]
+use std::path::Path;
+
+#[cfg(any(feature = "mali-kbase", feature = "adreno-kgsl"))]
+use crate::error::{GpuError, GpuResult};
+#[cfg(any(feature = "mali-kbase", feature = "adreno-kgsl"))]
+use crate::info::GpuInfo;
+#[cfg(any(feature = "mali-kbase", feature = "adreno-kgsl"))]
+use crate::options::QueryOptions;
+
+/// Samples buffered ahead of a slow consumer
+const SAMPLE_BUFFER: usize = 16;
+
+/// Run `query` on the blocking thread pool
+///
+/// The mock backend is captured when this is called, not when the future
+/// is first polled, which may happen on another thread.
+#[cfg(any(feature = "mali-kbase", feature = "adreno-kgsl"))]
+fn blocking<T, F>(query: F) -> impl Future<Output = GpuResult<T>>
+where
+    T: Send + 'static,
+    F: FnOnce() -> GpuResult<T> + Send + 'static,
+{
+    #[cfg(feature = "mock")]
+    let query = {
+        let mock = crate::mock::active();
+        move || {
+            let _mock = mock.map(crate::mock::MockGpuBackend::install);
+            query()
+        }
+    };
+    spawn_query(query)
+}
+
+#[cfg(any(feature = "mali-kbase", feature = "adreno-kgsl"))]
+async fn spawn_query<T, F>(query: F) -> GpuResult<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> GpuResult<T> + Send + 'static,
+{
+    tokio::task::spawn_blocking(query)
+        .await
+        .unwrap_or_else(|_| Err(GpuError::InvalidData("query task panicked".into())))
+}
+
+/// [`query_mali`](crate::mali::query_mali) on the blocking thread pool
+#[cfg(feature = "mali-kbase")]
+pub fn query_mali_async<P: AsRef<Path>>(device_path: P) -> impl Future<Output = GpuResult<GpuInfo>> {
+    let device_path = device_path.as_ref().to_path_buf();
+    blocking(move || crate::mali::query_mali(device_path))
+}
+
+/// [`query_mali_with_opts`](crate::mali::query_mali_with_opts) on the
+/// blocking thread pool
+#[cfg(feature = "mali-kbase")]
+pub fn query_mali_with_opts_async<P: AsRef<Path>>(
+    device_path: P,
+    options: impl Into<QueryOptions>,
+) -> impl Future<Output = GpuResult<GpuInfo>> {
+    let device_path = device_path.as_ref().to_path_buf();
+    let options = options.into();
+    blocking(move || crate::mali::query_mali_with_opts(device_path, options))
+}
+
+/// [`query_adreno`](crate::adreno::query_adreno) on the blocking thread pool
+#[cfg(feature = "adreno-kgsl")]
+pub fn query_adreno_async<P: AsRef<Path>>(device_path: P) -> impl Future<Output = GpuResult<GpuInfo>> {
+    let device_path = device_path.as_ref().to_path_buf();
+    blocking(move || crate::adreno::query_adreno(device_path))
+}
+
+/// [`query_adreno_with_opts`](crate::adreno::query_adreno_with_opts) on the
+/// blocking thread pool
+#[cfg(feature = "adreno-kgsl")]
+pub fn query_adreno_with_opts_async<P: AsRef<Path>>(
+    device_path: P,
+    options: impl Into<QueryOptions>,
+) -> impl Future<Output = GpuResult<GpuInfo>> {
+    let device_path = device_path.as_ref().to_path_buf();
+    let options = options.into();
+    blocking(move || crate::adreno::query_adreno_with_opts(device_path, options))
+}
+
+/// [`query_gpu_auto`](crate::query_gpu_auto) on the blocking thread pool
+#[cfg(all(feature = "auto-detect", any(feature = "mali-kbase", feature = "adreno-kgsl")))]
+pub fn query_gpu_auto_async<P: AsRef<Path>>(
+    device_path: Option<P>,
+) -> impl Future<Output = GpuResult<GpuInfo>> {
+    let device_path = device_path.map(|path| path.as_ref().to_path_buf());
+    blocking(move || crate::detect::query_gpu_auto(device_path))
+}
+
+/// [`query_gpu_with_options`](crate::query_gpu_with_options) on the
+/// blocking thread pool
+#[cfg(all(feature = "auto-detect", any(feature = "mali-kbase", feature = "adreno-kgsl")))]
+pub fn query_gpu_with_options_async(
+    options: crate::detect::DetectOptions,
+) -> impl Future<Output = GpuResult<GpuInfo>> {
+    blocking(move || crate::detect::query_gpu_with_options(&options))
+}
+
+/// [`enumerate_gpus`](crate::enumerate_gpus) on the blocking thread pool
+#[cfg(all(feature = "auto-detect", any(feature = "mali-kbase", feature = "adreno-kgsl")))]
+pub fn enumerate_gpus_async() -> impl Future<Output = GpuResult<Vec<GpuInfo>>> {
+    blocking(crate::detect::enumerate_gpus)
+}
+
+/// Stream of [`GpuMonitor`] samples, see [`monitor_stream`]
+#[derive(Debug)]
+pub struct MonitorStream {
+    receiver: mpsc::Receiver<Sample>,
+}
+
+impl MonitorStream {
+    /// Wait for the next sample; `None` once sampling stopped
+    pub async fn next(&mut self) -> Option<Sample> {
+        self.receiver.recv().await
+    }
+}
+
+impl futures_core::Stream for MonitorStream {
+    type Item = Sample;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Sample>> {
+        self.receiver.poll_recv(cx)
+    }
+}
+
+/// Sample the GPU on a background task at the monitor's interval
+///
+/// Samples are taken on the blocking thread pool and handed over rather
+/// than retained, so `config.storage` is ignored. Sampling stops when the
+/// stream is dropped. Must be called from within a tokio runtime.
+pub fn monitor_stream(config: MonitorConfig) -> MonitorStream {
+    let (sender, receiver) = mpsc::channel(SAMPLE_BUFFER);
+    let config = MonitorConfig { storage: Storage::Ring { capacity: 1 }, ..config };
+    tokio::spawn(async move {
+        let Ok(mut monitor) = tokio::task::spawn_blocking(move || GpuMonitor::new(config)).await else {
+            return;
+        };
+        loop {
+            let step = tokio::task::spawn_blocking(move || {
+                let sample = monitor.sample().clone();
+                let interval = monitor.next_interval();
+                (monitor, sample, interval)
+            });
+            let Ok((returned, sample, interval)) = step.await else {
+                return;
+            };
+            monitor = returned;
+            if sender.send(sample).await.is_err() {
+                return;
+            }
+            tokio::time::sleep(interval).await;
+        }
+    });
+    MonitorStream { receiver }
+}
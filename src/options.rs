@@ -0,0 +1,217 @@
+//! Query options shared by the Mali and Adreno backends
+//!
+//! ```
+//! use std::time::Duration;
+//! use armgpuinfo::{Fallback, Mode, QueryOptions, Validation};
+//!
+//! let options = QueryOptions::new(Mode::Extended)
+//!     .validation(Validation::Off)
+//!     .fallback(Fallback::Parity)
+//!     .timeout(Duration::from_secs(2));
+//! assert_eq!(options.modes(), [Mode::Extended, Mode::Parity]);
+//! assert!(!options.validates(Mode::Extended));
+//! ```
+
+use std::time::Duration;
+
+use crate::instrument::Instrumentation;
+use crate::Mode;
+
+/// When a parsed result is checked for consistency before it is returned
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Validation {
+    /// Extended mode validates, Parity mode does not
+    #[default]
+    ByMode,
+    /// Never reject a parsed result
+    Off,
+    /// Validate in every mode
+    Always,
+}
+
+/// What to try when the requested mode fails
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Fallback {
+    /// Return the error of the requested mode
+    #[default]
+    None,
+    /// Retry an Extended query in Parity mode
+    Parity,
+    /// Retry in Parity mode, then read the vendor's sysfs nodes
+    ParityThenSysfs,
+}
+
+/// Options for `query_mali_with_opts` and `query_adreno_with_opts`
+///
+/// `Mode` and the vendor `Mode` enums convert into options with every other
+/// setting at its default.
+#[derive(Debug, Clone)]
+pub struct QueryOptions {
+    pub mode: Mode,
+    /// Overrides the parser configuration of `mode` for kbase buffers
+    #[cfg(feature = "mali-core")]
+    pub mali_parser_config: Option<crate::mali::ParserConfig>,
+    /// Overrides the parser configuration of `mode` for KGSL device info
+    #[cfg(feature = "adreno-core")]
+    pub adreno_parser_config: Option<crate::adreno::ParserConfig>,
+    pub validation: Validation,
+    pub fallback: Fallback,
+    /// Give up on a driver that does not answer within this time; the
+    /// blocked ioctl is left to finish on a background thread
+    pub timeout: Option<Duration>,
+    /// Called with the timings of every query
+    pub instrumentation: Option<Instrumentation>,
+}
+
+impl QueryOptions {
+    /// Options for `mode` with its default parser configuration
+    pub fn new(mode: impl Into<Mode>) -> Self {
+        Self {
+            mode: mode.into(),
+            #[cfg(feature = "mali-core")]
+            mali_parser_config: None,
+            #[cfg(feature = "adreno-core")]
+            adreno_parser_config: None,
+            validation: Validation::default(),
+            fallback: Fallback::default(),
+            timeout: None,
+            instrumentation: None,
+        }
+    }
+
+    /// Override the kbase parser configuration
+    #[cfg(feature = "mali-core")]
+    pub fn mali_parser_config(mut self, config: crate::mali::ParserConfig) -> Self {
+        self.mali_parser_config = Some(config);
+        self
+    }
+
+    /// Override the KGSL parser configuration
+    #[cfg(feature = "adreno-core")]
+    pub fn adreno_parser_config(mut self, config: crate::adreno::ParserConfig) -> Self {
+        self.adreno_parser_config = Some(config);
+        self
+    }
+
+    pub fn validation(mut self, validation: Validation) -> Self {
+        self.validation = validation;
+        self
+    }
+
+    pub fn fallback(mut self, fallback: Fallback) -> Self {
+        self.fallback = fallback;
+        self
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Report timings and byte counts of every query to `instrumentation`
+    pub fn instrument(mut self, instrumentation: Instrumentation) -> Self {
+        self.instrumentation = Some(instrumentation);
+        self
+    }
+
+    /// Modes to try on the device node, in order
+    pub fn modes(&self) -> Vec<Mode> {
+        match (self.mode, self.fallback) {
+            (Mode::Extended, Fallback::Parity | Fallback::ParityThenSysfs) => {
+                vec![Mode::Extended, Mode::Parity]
+            }
+            (mode, _) => vec![mode],
+        }
+    }
+
+    /// Whether a result queried in `mode` is validated
+    pub fn validates(&self, mode: Mode) -> bool {
+        match self.validation {
+            Validation::ByMode => mode == Mode::Extended,
+            Validation::Off => false,
+            Validation::Always => true,
+        }
+    }
+
+    /// Whether the sysfs nodes are read when every mode failed
+    pub fn uses_sysfs(&self) -> bool {
+        self.fallback == Fallback::ParityThenSysfs
+    }
+
+    /// Run `query` for each of [`modes`](Self::modes) until one succeeds,
+    /// returning the last error otherwise
+    #[cfg(any(feature = "mali-kbase", feature = "adreno-kgsl"))]
+    pub(crate) fn try_modes<T>(
+        &self,
+        mut query: impl FnMut(Mode) -> crate::GpuResult<T>,
+    ) -> crate::GpuResult<T> {
+        let mut last_error = None;
+        for mode in self.modes() {
+            match query(mode) {
+                Ok(value) => {
+                    crate::instrument::record(|trace| trace.mode = Some(mode));
+                    return Ok(value);
+                }
+                Err(e) => {
+                    crate::diag::diag!(debug, "{:?} mode failed: {}", mode, e);
+                    last_error = Some(e);
+                }
+            }
+        }
+        Err(last_error.unwrap_or(crate::GpuError::DeviceNotFound))
+    }
+
+    /// Parser configuration for a kbase query in `mode`
+    #[cfg(feature = "mali-kbase")]
+    pub(crate) fn mali_config(&self, mode: Mode) -> crate::mali::ParserConfig {
+        self.mali_parser_config.unwrap_or(match mode {
+            Mode::Parity => crate::mali::ParserConfig::PARITY,
+            Mode::Extended => crate::mali::ParserConfig::EXTENDED,
+        })
+    }
+
+    /// Parser configuration for a KGSL query in `mode`
+    #[cfg(feature = "adreno-kgsl")]
+    pub(crate) fn adreno_config(&self, mode: Mode) -> crate::adreno::ParserConfig {
+        self.adreno_parser_config
+            .unwrap_or_else(|| crate::adreno::Mode::from(mode).parser_config())
+    }
+}
+
+impl Default for QueryOptions {
+    fn default() -> Self {
+        Self::new(Mode::Parity)
+    }
+}
+
+impl From<Mode> for QueryOptions {
+    fn from(mode: Mode) -> Self {
+        Self::new(mode)
+    }
+}
+
+/// Run `query`, abandoning it after `timeout`
+#[cfg(any(feature = "mali-kbase", feature = "adreno-kgsl"))]
+pub(crate) fn with_timeout<T, F>(timeout: Option<Duration>, query: F) -> crate::GpuResult<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> crate::GpuResult<T> + Send + 'static,
+{
+    use std::sync::mpsc::{self, RecvTimeoutError};
+
+    let Some(timeout) = timeout else {
+        return query();
+    };
+    let (sender, receiver) = mpsc::channel();
+    std::thread::spawn(move || {
+        // The receiver is gone if the caller already timed out
+        let _ = sender.send(query());
+    });
+    match receiver.recv_timeout(timeout) {
+        Ok(result) => result,
+        Err(RecvTimeoutError::Timeout) => Err(crate::GpuError::Timeout(timeout)),
+        Err(RecvTimeoutError::Disconnected) => {
+            Err(crate::GpuError::InvalidData("query thread panicked".into()))
+        }
+    }
+}
@@ -0,0 +1,103 @@
+//! Theoretical peak throughput
+//!
+//! Peak rates are per-core rates times core count times clock: what the
+//! hardware could do with every unit busy, not what a workload achieves.
+//! Mali rates need the per-core values Extended mode fills in; Adreno
+//! rates come from the database's stream processor count.
+//!
+//! ```
+//! use armgpuinfo::GpuInfo;
+//!
+//! let info = GpuInfo::builder()
+//!     .gpu_id(0xa867)
+//!     .raw_gpu_id(0xa867_0001)
+//!     .gpu_name("Mali-G610")
+//!     .architecture("Valhall")
+//!     .architecture_major(10)
+//!     .architecture_minor(8)
+//!     .num_shader_cores(4)
+//!     .num_l2_bytes(256 << 10)
+//!     .num_bus_bits(128)
+//!     .num_fp32_fmas_per_core(64)
+//!     .num_fp16_fmas_per_core(128)
+//!     .num_texels_per_core(8)
+//!     .num_pixels_per_core(4)
+//!     .build()
+//!     .unwrap();
+//!
+//! let estimate = info.performance_estimate(1_000_000_000).memory_clock(3_200_000_000);
+//! assert_eq!(estimate.fp32_gflops, Some(512.0));
+//! assert_eq!(estimate.fp16_gflops, Some(1024.0));
+//! assert_eq!(estimate.texel_rate_gtexels, Some(32.0));
+//! assert_eq!(estimate.pixel_rate_gpixels, Some(16.0));
+//! assert_eq!(estimate.memory_bandwidth_gb_per_sec, Some(51.2));
+//! ```
+
+use crate::info::{GpuInfo, GpuVendor};
+
+/// Peak rates at one GPU clock, `None` where the per-core rate is unknown
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PerfEstimate {
+    /// GPU clock the rates were computed for
+    pub freq_hz: u64,
+    pub fp32_gflops: Option<f64>,
+    /// Adreno GPUs with FP16 support are assumed to run it at twice the
+    /// FP32 rate
+    pub fp16_gflops: Option<f64>,
+    /// Bilinear texels per second, in billions
+    pub texel_rate_gtexels: Option<f64>,
+    /// Pixels written per second, in billions
+    pub pixel_rate_gpixels: Option<f64>,
+    /// Peak memory bandwidth, set by [`memory_clock`](Self::memory_clock)
+    pub memory_bandwidth_gb_per_sec: Option<f64>,
+    bus_bits: u64,
+}
+
+impl PerfEstimate {
+    /// Derive the memory bandwidth from the bus width and the memory's
+    /// effective transfer rate, e.g. 6_400_000_000 for LPDDR5-6400
+    ///
+    /// Left unset if the bus width is unknown.
+    pub fn memory_clock(mut self, transfers_per_sec: u64) -> Self {
+        self.memory_bandwidth_gb_per_sec = bandwidth_gbps(self.bus_bits, transfers_per_sec);
+        self
+    }
+}
+
+/// Peak bandwidth in GB/s of a `bus_bits` wide bus at `transfers_per_sec`,
+/// `None` if the width is unknown
+pub(crate) fn bandwidth_gbps(bus_bits: u64, transfers_per_sec: u64) -> Option<f64> {
+    (bus_bits > 0).then(|| (bus_bits / 8) as f64 * transfers_per_sec as f64 / 1e9)
+}
+
+/// Peak rates of `info` at `freq_hz`
+pub fn estimate(info: &GpuInfo, freq_hz: u64) -> PerfEstimate {
+    let per_second = |per_clock: u64| (per_clock > 0).then(|| (per_clock * freq_hz) as f64 / 1e9);
+    let cores = info.num_shader_cores as u64;
+
+    let (fp32_ops, fp16_ops, texels, pixels) = match (info.vendor, &info.mali_data, &info.adreno_data) {
+        // One FMA counts as two operations
+        (GpuVendor::Mali, Some(mali), _) => (
+            mali.num_fp32_fmas_per_core as u64 * cores * 2,
+            mali.num_fp16_fmas_per_core as u64 * cores * 2,
+            mali.num_texels_per_core as u64 * cores,
+            mali.num_pixels_per_core as u64 * cores,
+        ),
+        (GpuVendor::Adreno, _, Some(adreno)) => {
+            let fp32 = adreno.stream_processors as u64 * 2;
+            let fp16 = if info.supports_fp16() { fp32 * 2 } else { fp32 };
+            (fp32, fp16, 0, 0)
+        }
+        _ => (0, 0, 0, 0),
+    };
+
+    PerfEstimate {
+        freq_hz,
+        fp32_gflops: per_second(fp32_ops),
+        fp16_gflops: per_second(fp16_ops),
+        texel_rate_gtexels: per_second(texels),
+        pixel_rate_gpixels: per_second(pixels),
+        memory_bandwidth_gb_per_sec: None,
+        bus_bits: info.num_bus_bits,
+    }
+}
@@ -0,0 +1,43 @@
+//! Commonly used types and functions
+//!
+//! ```
+//! use armgpuinfo::prelude::*;
+//! ```
+//!
+//! Everything here is also reachable from the crate root or its module.
+//! Vendor-specific types whose names clash with crate-level ones are
+//! exported under a vendor prefix (`adreno::Mode` as [`AdrenoMode`]).
+
+pub use crate::capabilities::GpuCapabilities;
+pub use crate::error::{BuilderError, GpuError, GpuErrorKind, GpuResult};
+pub use crate::info::{
+    AdrenoData, AdrenoInfoBuilder, ArchitectureCode, DataConfidence, DisplayStyle, GpuInfo, GpuInfoBuilder, GpuVendor, MaliArch,
+    MaliData, MaliFlavor, SpecConfidence,
+};
+pub use crate::instrument::{Instrumentation, QueryTrace};
+pub use crate::options::{Fallback, QueryOptions, Validation};
+pub use crate::Mode;
+
+#[cfg(feature = "adreno-core")]
+pub use crate::{AdrenoMode, AdrenoQueryOptions};
+
+#[cfg(feature = "mali-kbase")]
+pub use crate::mali::{query_mali, query_mali_with_mode, query_mali_with_opts};
+#[cfg(feature = "mali-drm")]
+pub use crate::mali::query_mali_drm;
+
+#[cfg(feature = "adreno-kgsl")]
+pub use crate::adreno::{query_adreno, query_adreno_with_mode, query_adreno_with_opts};
+#[cfg(feature = "adreno-drm")]
+pub use crate::adreno::query_adreno_drm;
+
+#[cfg(feature = "vivante")]
+pub use crate::vivante::query_vivante_drm;
+
+#[cfg(any(feature = "mali-kbase", feature = "adreno-kgsl"))]
+pub use crate::device::GpuDevice;
+
+#[cfg(all(feature = "auto-detect", any(feature = "mali-kbase", feature = "adreno-kgsl")))]
+pub use crate::cache::query_gpu_cached;
+#[cfg(all(feature = "auto-detect", any(feature = "mali-kbase", feature = "adreno-kgsl")))]
+pub use crate::detect::{enumerate_gpus, query_gpu_auto, query_gpu_with_options, DetectOptions};
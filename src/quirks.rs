@@ -0,0 +1,133 @@
+//! Silicon revision quirk database
+//!
+//! Known hardware errata and driver workarounds keyed by vendor, product and
+//! revision. Entries mirror what open drivers (panfrost, freedreno) and the
+//! vendor kernels apply, so engines can consult one list next to the device
+//! database instead of keeping their own.
+
+use std::fmt;
+
+use crate::info::GpuVendor;
+
+/// Hardware revision (`rXpY` on Mali, `minor.patch` of the chip ID on Adreno)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Revision {
+    pub major: u8,
+    pub minor: u8,
+}
+
+impl Revision {
+    pub const fn new(major: u8, minor: u8) -> Self {
+        Self { major, minor }
+    }
+}
+
+impl fmt::Display for Revision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "r{}p{}", self.major, self.minor)
+    }
+}
+
+/// Kind of erratum or missing capability
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum QuirkFlag {
+    /// Hierarchical tiling is unsupported; tiler must use a single level
+    NoHierarchicalTiling,
+    /// Anisotropic filtering is unsupported or broken
+    NoAnisotropicFiltering,
+}
+
+/// A known quirk
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Quirk {
+    pub flag: QuirkFlag,
+    pub description: &'static str,
+}
+
+/// Which revisions an entry applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RevisionMatch {
+    /// Every revision
+    Any,
+    /// Exactly this revision
+    Exact(Revision),
+    /// All revisions older than this one (fixed in it)
+    Before(Revision),
+}
+
+impl RevisionMatch {
+    /// Whether `revision` is affected; unknown revisions only match `Any`
+    pub fn matches(self, revision: Option<Revision>) -> bool {
+        match (self, revision) {
+            (RevisionMatch::Any, _) => true,
+            (RevisionMatch::Exact(r), Some(rev)) => rev == r,
+            (RevisionMatch::Before(r), Some(rev)) => rev < r,
+            (_, None) => false,
+        }
+    }
+}
+
+/// One row of the quirk table
+#[derive(Debug, Clone, Copy)]
+pub struct QuirkEntry {
+    pub vendor: GpuVendor,
+    /// Normalized Mali product ID, or Adreno chip ID with the revision bytes
+    /// masked off (`chip_id & 0xFFFF0000`)
+    pub product: u32,
+    pub revision: RevisionMatch,
+    pub quirk: Quirk,
+}
+
+const NO_HIERARCHICAL_TILING: Quirk = Quirk {
+    flag: QuirkFlag::NoHierarchicalTiling,
+    description: "Tiler lacks hierarchical tiling; panfrost disables it",
+};
+
+const NO_ANISO: Quirk = Quirk {
+    flag: QuirkFlag::NoAnisotropicFiltering,
+    description: "Anisotropic filtering not supported by the texture unit",
+};
+
+const fn mali(product: u32, revision: RevisionMatch, quirk: Quirk) -> QuirkEntry {
+    QuirkEntry {
+        vendor: GpuVendor::Mali,
+        product,
+        revision,
+        quirk,
+    }
+}
+
+/// Known quirks
+pub static QUIRKS: &[QuirkEntry] = &[
+    // Midgard
+    mali(0x6956, RevisionMatch::Any, NO_ANISO), // T600
+    mali(0x0620, RevisionMatch::Any, NO_ANISO), // T620
+    mali(0x0720, RevisionMatch::Any, NO_ANISO), // T720
+    mali(0x0720, RevisionMatch::Any, NO_HIERARCHICAL_TILING), // T720
+    mali(0x0750, RevisionMatch::Any, NO_ANISO), // T760
+    mali(0x0820, RevisionMatch::Any, NO_ANISO), // T820
+    mali(0x0820, RevisionMatch::Any, NO_HIERARCHICAL_TILING), // T820
+    mali(0x0830, RevisionMatch::Any, NO_ANISO), // T830
+    mali(0x0830, RevisionMatch::Any, NO_HIERARCHICAL_TILING), // T830
+    mali(0x0860, RevisionMatch::Any, NO_ANISO), // T860
+    mali(0x0880, RevisionMatch::Any, NO_ANISO), // T880
+    // Bifrost
+    mali(0x6000, RevisionMatch::Any, NO_ANISO), // G71
+    mali(0x6001, RevisionMatch::Before(Revision::new(0, 3)), NO_ANISO), // G72
+    mali(0x7000, RevisionMatch::Before(Revision::new(1, 1)), NO_ANISO), // G51
+    mali(0x7003, RevisionMatch::Any, NO_ANISO), // G31
+];
+
+/// Quirks affecting a given product and revision
+pub fn known_quirks(
+    vendor: GpuVendor,
+    product: u32,
+    revision: Option<Revision>,
+) -> Vec<&'static Quirk> {
+    QUIRKS
+        .iter()
+        .filter(|e| e.vendor == vendor && e.product == product && e.revision.matches(revision))
+        .map(|e| &e.quirk)
+        .collect()
+}
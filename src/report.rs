@@ -0,0 +1,428 @@
+//! Delimited reports of GPU info and monitoring data
+//!
+//! Numbers are written by Rust's formatting machinery, which never consults
+//! the C locale, so neither these reports nor any `Display` impl in the
+//! crate change with `LC_NUMERIC`. The separators are chosen explicitly
+//! instead: [`Separators::CSV`] for tools expecting `1.5,2.0`,
+//! [`Separators::SEMICOLON`] for spreadsheets in comma-decimal locales.
+//!
+//! ```
+//! use armgpuinfo::report::{parse_decimal, ReportRenderer, Separators};
+//!
+//! let text = ReportRenderer::new(Separators::SEMICOLON).decimal(2.5);
+//! assert_eq!(text, "2,5");
+//! assert_eq!(parse_decimal(&text, Separators::SEMICOLON), Some(2.5));
+//! ```
+//!
+//! [`GpuInfo::to_report`] renders a single GPU as a sectioned report
+//! instead: the common fields, then a section for the vendor.
+
+use std::fmt::Write as _;
+
+use crate::info::GpuInfo;
+use crate::monitor::{MetricSummary, MonitorSummary, Sample};
+
+/// Field and decimal separators of a report
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Separators {
+    field: char,
+    decimal: char,
+}
+
+impl Separators {
+    /// `,` between fields, `.` as decimal point
+    pub const CSV: Self = Self { field: ',', decimal: '.' };
+    /// `;` between fields, `,` as decimal point
+    pub const SEMICOLON: Self = Self { field: ';', decimal: ',' };
+    /// Tab between fields, `.` as decimal point
+    pub const TSV: Self = Self { field: '\t', decimal: '.' };
+
+    /// Custom separators, `None` if they could be confused with each other
+    /// or with the characters of a number
+    pub fn new(field: char, decimal: char) -> Option<Self> {
+        let numeric = |c: char| c.is_ascii_digit() || matches!(c, '-' | '+' | 'e' | 'E' | '"');
+        (field != decimal && !numeric(field) && !numeric(decimal)).then_some(Self { field, decimal })
+    }
+
+    pub fn field(&self) -> char {
+        self.field
+    }
+
+    pub fn decimal(&self) -> char {
+        self.decimal
+    }
+}
+
+impl Default for Separators {
+    fn default() -> Self {
+        Self::CSV
+    }
+}
+
+/// Renders reports with fixed separators
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ReportRenderer {
+    separators: Separators,
+    header: bool,
+}
+
+impl ReportRenderer {
+    /// Renderer writing a header line
+    pub fn new(separators: Separators) -> Self {
+        Self { separators, header: true }
+    }
+
+    /// Whether the column names are written as the first line
+    pub fn header(mut self, header: bool) -> Self {
+        self.header = header;
+        self
+    }
+
+    pub fn separators(&self) -> Separators {
+        self.separators
+    }
+
+    /// One line per GPU
+    pub fn gpus(&self, gpus: &[GpuInfo]) -> String {
+        let rows = gpus.iter().map(|info| {
+            vec![
+                self.text(&info.vendor.to_string()),
+                self.text(&info.gpu_name),
+                self.text(&info.architecture),
+                info.architecture_major.to_string(),
+                info.architecture_minor.to_string(),
+                info.num_shader_cores.to_string(),
+                info.num_l2_bytes.to_string(),
+                info.num_bus_bits.to_string(),
+                info.device_path
+                    .as_ref()
+                    .map(|path| self.text(&path.to_string_lossy()))
+                    .unwrap_or_default(),
+            ]
+        });
+        self.table(
+            &[
+                "vendor", "gpu_name", "architecture", "architecture_major",
+                "architecture_minor", "num_shader_cores", "num_l2_bytes", "num_bus_bits",
+                "device_path",
+            ],
+            rows,
+        )
+    }
+
+    /// One line per monitor sample; missing readings are left empty
+    pub fn samples(&self, samples: &[Sample]) -> String {
+        let rows = samples.iter().map(|sample| {
+            vec![
+                self.decimal(sample.elapsed.as_secs_f64()),
+                sample.freq_mhz.map(|mhz| mhz.to_string()).unwrap_or_default(),
+                self.reading(sample.utilization.map(|r| r.value)),
+                self.reading(sample.temperature_c.map(|r| r.value)),
+                self.reading(sample.power_watts.map(|r| r.value)),
+                sample.throttled.map(|t| t.to_string()).unwrap_or_default(),
+            ]
+        });
+        self.table(
+            &["elapsed_s", "freq_mhz", "utilization", "temperature_c", "power_watts", "throttled"],
+            rows,
+        )
+    }
+
+    /// One line per metric of `summary` that has values
+    pub fn summary(&self, summary: &MonitorSummary) -> String {
+        let metrics = [
+            ("freq_mhz", summary.freq_mhz),
+            ("utilization", summary.utilization),
+            ("temperature_c", summary.temperature_c),
+            ("power_watts", summary.power_watts),
+        ];
+        let rows = metrics.into_iter().filter_map(|(name, metric)| {
+            let MetricSummary { count, min, mean, p50, p95, max } = metric?;
+            Some(vec![
+                name.to_string(),
+                count.to_string(),
+                self.decimal(min),
+                self.decimal(mean),
+                self.decimal(p50),
+                self.decimal(p95),
+                self.decimal(max),
+            ])
+        });
+        self.table(&["metric", "count", "min", "mean", "p50", "p95", "max"], rows)
+    }
+
+    /// Format `value` with the configured decimal separator
+    ///
+    /// The shortest representation that parses back to the same `f64` is
+    /// used, so [`parse_decimal`] round-trips every finite value.
+    pub fn decimal(&self, value: f64) -> String {
+        let text = value.to_string();
+        if self.separators.decimal == '.' {
+            text
+        } else {
+            text.replace('.', &self.separators.decimal.to_string())
+        }
+    }
+
+    fn reading(&self, value: Option<f32>) -> String {
+        value.map(|v| self.decimal(f64::from(v))).unwrap_or_default()
+    }
+
+    /// Quote `text` if it contains a separator, a quote or a line break
+    fn text(&self, text: &str) -> String {
+        let special = |c: char| {
+            c == self.separators.field || c == self.separators.decimal || matches!(c, '"' | '\n' | '\r')
+        };
+        if text.contains(special) {
+            format!("\"{}\"", text.replace('"', "\"\""))
+        } else {
+            text.to_string()
+        }
+    }
+
+    fn table(&self, columns: &[&str], rows: impl Iterator<Item = Vec<String>>) -> String {
+        let field = self.separators.field.to_string();
+        let mut out = String::new();
+        if self.header {
+            out.push_str(&columns.join(&field));
+            out.push('\n');
+        }
+        for row in rows {
+            out.push_str(&row.join(&field));
+            out.push('\n');
+        }
+        out
+    }
+}
+
+/// Parse a number written by [`ReportRenderer::decimal`] with `separators`
+pub fn parse_decimal(text: &str, separators: Separators) -> Option<f64> {
+    let text = text.trim();
+    if separators.decimal != '.' && text.contains('.') {
+        return None;
+    }
+    text.replace(separators.decimal, ".").parse().ok()
+}
+
+/// Layout version of [`GpuInfo::to_report`] output, bumped when sections
+/// or keys are renamed or removed
+pub const REPORT_VERSION: u32 = 1;
+
+/// Output format of [`GpuInfo::to_report`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    /// One object per section, plus `report_version`
+    Json,
+    /// A heading and a two-column table per section
+    Markdown,
+    /// A title line and indented `key: value` lines per section
+    PlainText,
+}
+
+/// A report value, written unquoted in JSON if numeric
+enum Value {
+    Number(u64),
+    Text(String),
+}
+
+impl Value {
+    fn text(&self) -> String {
+        match self {
+            Value::Number(n) => n.to_string(),
+            Value::Text(text) => text.clone(),
+        }
+    }
+}
+
+/// Named section of key/value entries; unknown values are left out
+struct Section {
+    key: &'static str,
+    title: &'static str,
+    entries: Vec<(&'static str, Value)>,
+}
+
+impl Section {
+    fn new(key: &'static str, title: &'static str) -> Self {
+        Self { key, title, entries: Vec::new() }
+    }
+
+    fn number(&mut self, key: &'static str, value: impl Into<u64>) -> &mut Self {
+        self.entries.push((key, Value::Number(value.into())));
+        self
+    }
+
+    fn text(&mut self, key: &'static str, value: impl ToString) -> &mut Self {
+        self.entries.push((key, Value::Text(value.to_string())));
+        self
+    }
+
+    fn hex(&mut self, key: &'static str, value: impl Into<u64>) -> &mut Self {
+        self.text(key, format!("{:#x}", value.into()))
+    }
+}
+
+fn sections(info: &GpuInfo) -> Vec<Section> {
+    let mut general = Section::new("general", "General");
+    general
+        .text("vendor", info.vendor_code())
+        .text("gpu_name", &info.gpu_name)
+        .text("architecture", &info.architecture)
+        .text("architecture_version", format!("{}.{}", info.architecture_major, info.architecture_minor))
+        .number("num_shader_cores", info.num_shader_cores)
+        .number("num_l2_bytes", info.num_l2_bytes)
+        .number("num_bus_bits", info.num_bus_bits)
+        .text("capabilities", info.capabilities.names().join(","))
+        .text("confidence", format!("{:?}", info.confidence));
+    if let Some(version) = info.max_vulkan_version() {
+        general.text("max_vulkan_version", version);
+    }
+    if let Some(version) = info.max_gles_version() {
+        general.text("max_gles_version", version);
+    }
+    if let Some(path) = &info.device_path {
+        general.text("device_path", path.display());
+    }
+    let mut sections = vec![general];
+
+    if let Some(mali) = &info.mali_data {
+        let mut section = Section::new("mali", "Mali");
+        section
+            .hex("gpu_id", mali.gpu_id)
+            .hex("raw_gpu_id", mali.raw_gpu_id)
+            .hex("shader_core_mask", mali.shader_core_mask)
+            .number("num_l2_slices", mali.num_l2_slices)
+            .number("num_exec_engines", mali.num_exec_engines)
+            .number("num_fp32_fmas_per_core", mali.num_fp32_fmas_per_core)
+            .number("num_fp16_fmas_per_core", mali.num_fp16_fmas_per_core)
+            .number("num_texels_per_core", mali.num_texels_per_core)
+            .number("num_pixels_per_core", mali.num_pixels_per_core);
+        if let Some(flavor) = mali.flavor {
+            section.text("flavor", flavor);
+        }
+        if let Some((major, minor)) = mali.driver_version {
+            section.text("driver_version", format!("{major}.{minor}"));
+        }
+        if let Some(confidence) = mali.spec_confidence {
+            section.text("spec_confidence", confidence);
+        }
+        if let Some(mhz) = mali.peak_freq_mhz() {
+            section.number("peak_freq_mhz", mhz);
+        }
+        sections.push(section);
+    }
+
+    if let Some(adreno) = &info.adreno_data {
+        let mut section = Section::new("adreno", "Adreno");
+        section
+            .hex("chip_id", adreno.chip_id)
+            .number("gpu_model_code", adreno.gpu_model_code)
+            .number("gmem_bytes", adreno.gmem_bytes)
+            .number("stream_processors", adreno.stream_processors)
+            .number("max_freq_mhz", adreno.max_freq_mhz)
+            .number("process_nm", adreno.process_nm)
+            .number("release_year", adreno.release_year)
+            .text("spec_confidence", &adreno.spec_confidence);
+        if let Some(name) = &adreno.driver_model_name {
+            section.text("driver_model_name", name);
+        }
+        if let Some((major, minor)) = adreno.driver_version {
+            section.text("driver_version", format!("{major}.{minor}"));
+        }
+        if let Some(bin) = adreno.speed_bin {
+            section.number("speed_bin", bin);
+        }
+        sections.push(section);
+    }
+
+    if let Some(vivante) = &info.vivante_data {
+        let mut section = Section::new("vivante", "Vivante");
+        section
+            .hex("model", vivante.model)
+            .hex("revision", vivante.revision)
+            .hex("product_id", vivante.product_id)
+            .hex("customer_id", vivante.customer_id)
+            .hex("eco_id", vivante.eco_id)
+            .number("stream_count", vivante.stream_count)
+            .number("thread_count", vivante.thread_count)
+            .number("pixel_pipes", vivante.pixel_pipes)
+            .number("instruction_count", vivante.instruction_count)
+            .number("num_constants", vivante.num_constants)
+            .number("num_varyings", vivante.num_varyings);
+        sections.push(section);
+    }
+    sections
+}
+
+/// Key/value pairs of the vendor sections of `info`, for
+/// [`DisplayStyle::Detailed`](crate::DisplayStyle::Detailed)
+pub(crate) fn vendor_entries(info: &GpuInfo) -> Vec<(&'static str, String)> {
+    sections(info)
+        .into_iter()
+        .skip(1)
+        .flat_map(|section| section.entries)
+        .map(|(key, value)| (key, value.text()))
+        .collect()
+}
+
+/// Render `info` in `format`, see [`GpuInfo::to_report`]
+pub fn render(info: &GpuInfo, format: ReportFormat) -> String {
+    let sections = sections(info);
+    let mut out = String::new();
+    match format {
+        ReportFormat::Json => {
+            let _ = write!(out, "{{\"report_version\":{REPORT_VERSION}");
+            for section in &sections {
+                let _ = write!(out, ",{}:{{", json_string(section.key));
+                for (i, (key, value)) in section.entries.iter().enumerate() {
+                    let value = match value {
+                        Value::Number(n) => n.to_string(),
+                        Value::Text(text) => json_string(text),
+                    };
+                    let _ = write!(out, "{}{}:{value}", if i > 0 { "," } else { "" }, json_string(key));
+                }
+                out.push('}');
+            }
+            out.push('}');
+        }
+        ReportFormat::Markdown => {
+            let _ = writeln!(out, "# GPU report (version {REPORT_VERSION})");
+            for section in &sections {
+                let _ = write!(out, "\n## {}\n\n| Field | Value |\n| --- | --- |\n", section.title);
+                for (key, value) in &section.entries {
+                    let _ = writeln!(out, "| {key} | {} |", value.text().replace('|', "\\|"));
+                }
+            }
+        }
+        ReportFormat::PlainText => {
+            let _ = writeln!(out, "GPU report (version {REPORT_VERSION})");
+            for section in &sections {
+                let _ = writeln!(out, "{}", section.title);
+                for (key, value) in &section.entries {
+                    let _ = writeln!(out, "  {key}: {}", value.text());
+                }
+            }
+        }
+    }
+    out
+}
+
+/// `text` as a quoted, escaped JSON string
+fn json_string(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() + 2);
+    out.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if u32::from(c) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", u32::from(c));
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
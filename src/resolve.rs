@@ -0,0 +1,210 @@
+//! Name-based reverse lookup
+//!
+//! Crash reporters and analytics backends usually only have the renderer
+//! string (`GL_RENDERER`, `VkPhysicalDeviceProperties::deviceName`), not the
+//! product or chip ID. [`resolve_name`] normalizes the usual vendor and
+//! runtime naming quirks and maps such strings back onto database entries;
+//! [`parse_renderer_string`] additionally digs the GPU name out of wrapped
+//! strings (ANGLE, driver suffixes) and still reports the vendor for products
+//! the databases do not know yet.
+
+use crate::info::GpuVendor;
+
+#[cfg(feature = "adreno-core")]
+use crate::adreno::{AdrenoSpecs, ADRENO_CHIPS};
+#[cfg(feature = "mali-core")]
+use crate::mali::{lookup_product, products, ProductEntry};
+
+/// Database entry a name resolved to
+#[derive(Debug, Clone, Copy)]
+pub enum DatabaseEntry {
+    #[cfg(feature = "mali-core")]
+    Mali(&'static ProductEntry),
+    #[cfg(feature = "adreno-core")]
+    Adreno {
+        /// Chip ID the entry is keyed by
+        chip_id: u32,
+        specs: &'static AdrenoSpecs,
+    },
+}
+
+/// Result of [`resolve_name`]
+#[derive(Debug, Clone, Copy)]
+pub struct ResolvedName {
+    pub vendor: GpuVendor,
+    /// Canonical database name (e.g. "Mali-G68", "Adreno 640")
+    pub canonical_name: &'static str,
+    /// Core count from an `MCn`/`MPn` suffix, if present
+    pub core_count: Option<u32>,
+    pub entry: DatabaseEntry,
+}
+
+/// Resolve a marketing or renderer name to its database entry
+///
+/// Handles trademark markers ("Adreno (TM) 640", "ARM Mali™"), `MCn`/`MPn`
+/// core-count suffixes, execution engine counts ("3EE") and variant letters
+/// ("642L"). Returns `None` for names without a matching entry; series
+/// fallbacks are never returned.
+pub fn resolve_name(name: &str) -> Option<ResolvedName> {
+    let normalized = normalize(name);
+    let tokens: Vec<&str> = normalized.split_whitespace().collect();
+
+    match tokens.first().copied() {
+        #[cfg(feature = "mali-core")]
+        Some("mali") | Some("immortalis") => resolve_mali(&tokens),
+        #[cfg(feature = "adreno-core")]
+        Some("adreno") => resolve_adreno(&tokens),
+        _ => None,
+    }
+}
+
+/// Result of [`parse_renderer_string`]
+#[derive(Debug, Clone, Copy)]
+pub struct ParsedRenderer {
+    pub vendor: GpuVendor,
+    /// Canonical database name, `None` for products newer than the database
+    pub product: Option<&'static str>,
+    /// Core count from an `MCn`/`MPn` suffix, if present
+    pub core_count: Option<u32>,
+    pub entry: Option<DatabaseEntry>,
+}
+
+/// Parse a raw `GL_RENDERER` / Vulkan `deviceName` string offline
+///
+/// Unlike [`resolve_name`], surrounding text is tolerated, so ANGLE strings
+/// and driver-appended suffixes work too. Returns `None` only when no Mali,
+/// Immortalis or Adreno name is present at all.
+///
+/// ```
+/// use armgpuinfo::{parse_renderer_string, GpuVendor};
+///
+/// let parsed = parse_renderer_string("ANGLE (ARM, Mali-G78 MP20, OpenGL ES 3.2)").unwrap();
+/// assert_eq!(parsed.vendor, GpuVendor::Mali);
+/// assert_eq!(parsed.core_count, Some(20));
+/// # #[cfg(feature = "mali-core")]
+/// assert_eq!(parsed.product, Some("Mali-G78"));
+///
+/// let parsed = parse_renderer_string("Adreno (TM) 730").unwrap();
+/// assert_eq!(parsed.vendor, GpuVendor::Adreno);
+/// # #[cfg(feature = "adreno-core")]
+/// assert_eq!(parsed.product, Some("Adreno 730"));
+/// ```
+pub fn parse_renderer_string(renderer: &str) -> Option<ParsedRenderer> {
+    let normalized = normalize(renderer);
+    let tokens: Vec<&str> = normalized.split_whitespace().collect();
+    let start = tokens
+        .iter()
+        .position(|t| matches!(*t, "mali" | "immortalis" | "adreno"))?;
+    let tokens = &tokens[start..];
+    let vendor = if tokens[0] == "adreno" {
+        GpuVendor::Adreno
+    } else {
+        GpuVendor::Mali
+    };
+
+    // Longest prefix that resolves wins, so trailing API/driver text is dropped
+    let resolved = (2..=tokens.len()).rev().find_map(|end| {
+        let window = &tokens[..end];
+        match vendor {
+            #[cfg(feature = "mali-core")]
+            GpuVendor::Mali => resolve_mali(window),
+            #[cfg(feature = "adreno-core")]
+            GpuVendor::Adreno => resolve_adreno(window),
+            _ => None,
+        }
+    });
+
+    Some(match resolved {
+        Some(resolved) => ParsedRenderer {
+            vendor,
+            product: Some(resolved.canonical_name),
+            core_count: resolved.core_count,
+            entry: Some(resolved.entry),
+        },
+        None => ParsedRenderer {
+            vendor,
+            product: None,
+            core_count: tokens.iter().find_map(|t| core_count_token(t)),
+            entry: None,
+        },
+    })
+}
+
+/// Lowercase, drop trademark markers and vendor prefixes, and turn
+/// separators into single spaces
+fn normalize(name: &str) -> String {
+    let mut s = name.to_ascii_lowercase();
+    for marker in ["(tm)", "(r)", "\u{2122}", "\u{ae}"] {
+        s = s.replace(marker, " ");
+    }
+    let s: String = s
+        .chars()
+        .map(|c| if matches!(c, '-' | '_' | ',' | '(' | ')') { ' ' } else { c })
+        .collect();
+    s.split_whitespace()
+        .filter(|t| !matches!(*t, "arm" | "qualcomm" | "gpu"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(feature = "mali-core")]
+fn resolve_mali(tokens: &[&str]) -> Option<ResolvedName> {
+    let mut core_count = None;
+    let mut name_tokens = Vec::new();
+    for token in tokens {
+        if let Some(count) = core_count_token(token) {
+            core_count = Some(count);
+        } else if token.strip_suffix("ee").is_some_and(|n| n.parse::<u32>().is_ok()) {
+            // Execution engine count, not part of the product name
+        } else {
+            name_tokens.push(*token);
+        }
+    }
+    let wanted = name_tokens.join(" ");
+
+    let first = products().iter().find(|p| normalize(p.name) == wanted)?;
+    // Same-named products differ by core count (e.g. Mali-G57 pre/post r1)
+    let entry = core_count
+        .and_then(|cores| lookup_product(first.id, cores))
+        .filter(|p| p.name == first.name)
+        .unwrap_or(first);
+
+    Some(ResolvedName {
+        vendor: GpuVendor::Mali,
+        canonical_name: entry.name,
+        core_count,
+        entry: DatabaseEntry::Mali(entry),
+    })
+}
+
+#[cfg(feature = "adreno-core")]
+fn resolve_adreno(tokens: &[&str]) -> Option<ResolvedName> {
+    // "642l" -> "642"
+    let number = tokens.get(1)?.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+    if number.is_empty() || !number.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    ADRENO_CHIPS
+        .iter()
+        .find(|(_, specs)| {
+            specs
+                .name
+                .strip_prefix("Adreno ")
+                .is_some_and(|models| models.split('/').any(|m| m == number))
+        })
+        .map(|(chip_id, specs)| ResolvedName {
+            vendor: GpuVendor::Adreno,
+            canonical_name: specs.name,
+            core_count: None,
+            entry: DatabaseEntry::Adreno { chip_id: *chip_id, specs },
+        })
+}
+
+/// Core count from an `mcN`/`mpN` token
+fn core_count_token(token: &str) -> Option<u32> {
+    token
+        .strip_prefix("mc")
+        .or_else(|| token.strip_prefix("mp"))
+        .and_then(|n| n.parse::<u32>().ok())
+}
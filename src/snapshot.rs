@@ -0,0 +1,227 @@
+//! Raw property snapshots and diffs across kernel upgrades
+//!
+//! A [`Snapshot`] keeps the bytes the driver returned (the kbase
+//! `GET_GPUPROPS` buffer or the KGSL device info structure) so it can be
+//! stored before a BSP update and compared after it. [`Snapshot::compare`]
+//! decodes both sides into named properties and reports what was added,
+//! removed or changed.
+//!
+//! ```
+//! # #[cfg(feature = "mali-core")]
+//! # {
+//! use armgpuinfo::snapshot::{ChangeKind, Snapshot};
+//! use armgpuinfo::GpuVendor;
+//!
+//! // kbase key: property ID << 2 | size code (2 = u32)
+//! let prop = |id: u32, value: u32| [((id << 2) | 2).to_le_bytes(), value.to_le_bytes()].concat();
+//! let old = Snapshot::from_raw(GpuVendor::Mali, [prop(1, 0xa867), prop(24, 1)].concat());
+//! let new = Snapshot::from_raw(GpuVendor::Mali, [prop(1, 0xa867), prop(24, 2), prop(82, 2)].concat());
+//!
+//! let diff = Snapshot::compare(&old, &new)?;
+//! assert_eq!(diff.changes.len(), 2);
+//! assert_eq!(diff.changes[0].name, Some("IMPL_TECH"));
+//! assert_eq!(diff.changes[1].kind(), ChangeKind::Added);
+//! assert_eq!(diff.to_string(), "~ IMPL_TECH: 0x1 -> 0x2\n+ NUM_EXEC_ENGINES: 0x2\n");
+//! # }
+//! # Ok::<(), armgpuinfo::GpuError>(())
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::error::{GpuError, GpuResult};
+use crate::info::GpuVendor;
+
+#[cfg(any(feature = "mali-kbase", feature = "adreno-kgsl"))]
+use std::path::Path;
+
+/// KGSL device info fields, one `u32` word each
+pub(crate) const KGSL_DEVICE_INFO_FIELDS: [&str; 8] = [
+    "device_id",
+    "chip_id",
+    "mmu_enabled",
+    "gmem_gpubaseaddr",
+    "gmem_sizebytes",
+    "unknown1",
+    "unknown2",
+    "gpu_model",
+];
+
+/// Raw driver property dump
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Snapshot {
+    pub vendor: GpuVendor,
+    /// Bytes as returned by the driver
+    pub raw: Vec<u8>,
+}
+
+/// How a property differs between two snapshots
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+/// One differing property
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PropertyChange {
+    /// kbase property ID or KGSL device info word index
+    pub id: u64,
+    /// Decoded name, `None` for IDs unknown to this crate
+    pub name: Option<&'static str>,
+    pub old: Option<u64>,
+    pub new: Option<u64>,
+}
+
+impl PropertyChange {
+    pub fn kind(&self) -> ChangeKind {
+        match (self.old, self.new) {
+            (None, _) => ChangeKind::Added,
+            (_, None) => ChangeKind::Removed,
+            _ => ChangeKind::Changed,
+        }
+    }
+}
+
+impl fmt::Display for PropertyChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let marker = match self.kind() {
+            ChangeKind::Added => '+',
+            ChangeKind::Removed => '-',
+            ChangeKind::Changed => '~',
+        };
+        match self.name {
+            Some(name) => write!(f, "{marker} {name}")?,
+            None => write!(f, "{marker} #{}", self.id)?,
+        }
+        match (self.old, self.new) {
+            (Some(old), Some(new)) => write!(f, ": {old:#x} -> {new:#x}"),
+            (Some(value), None) | (None, Some(value)) => write!(f, ": {value:#x}"),
+            (None, None) => Ok(()),
+        }
+    }
+}
+
+/// Result of [`Snapshot::compare`], ordered by property ID
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SnapshotDiff {
+    pub changes: Vec<PropertyChange>,
+}
+
+impl SnapshotDiff {
+    /// Whether the driver reports exactly the same properties
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Changes of one kind
+    pub fn of_kind(&self, kind: ChangeKind) -> impl Iterator<Item = &PropertyChange> {
+        self.changes.iter().filter(move |change| change.kind() == kind)
+    }
+}
+
+impl fmt::Display for SnapshotDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for change in &self.changes {
+            writeln!(f, "{change}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Snapshot {
+    /// Wrap a previously stored dump
+    pub fn from_raw(vendor: GpuVendor, raw: impl Into<Vec<u8>>) -> Self {
+        Self { vendor, raw: raw.into() }
+    }
+
+    /// Dump the kbase property buffer of `device_path`
+    #[cfg(feature = "mali-kbase")]
+    pub fn capture_mali<P: AsRef<Path>>(device_path: P) -> GpuResult<Self> {
+        let raw = crate::mali::dump_raw_properties(device_path)?;
+        Ok(Self::from_raw(GpuVendor::Mali, raw))
+    }
+
+    /// Dump the KGSL device info of `device_path`
+    #[cfg(feature = "adreno-kgsl")]
+    pub fn capture_adreno<P: AsRef<Path>>(device_path: P) -> GpuResult<Self> {
+        let raw = crate::adreno::dump_raw_properties(device_path)?;
+        Ok(Self::from_raw(GpuVendor::Adreno, raw))
+    }
+
+    /// Decode the dump as a query in `mode` would, without the device
+    ///
+    /// See `mali::parse_snapshot` and `adreno::parse_snapshot`.
+    #[cfg(any(feature = "mali-core", feature = "adreno-core"))]
+    pub fn parse(&self, mode: crate::Mode) -> GpuResult<crate::info::GpuInfo> {
+        match self.vendor {
+            #[cfg(feature = "mali-core")]
+            GpuVendor::Mali => crate::mali::parse_snapshot(&self.raw, mode),
+            #[cfg(feature = "adreno-core")]
+            GpuVendor::Adreno => crate::adreno::parse_snapshot(&self.raw, mode.into()),
+            vendor => Err(GpuError::InvalidData(format!("cannot decode {vendor:?} property dumps"))),
+        }
+    }
+
+    /// Annotated hex dump, see [`crate::dump::pretty_hex`]
+    pub fn pretty_hex(&self) -> String {
+        crate::dump::pretty_hex(self.vendor, &self.raw)
+    }
+
+    /// Decoded properties keyed by ID
+    pub fn properties(&self) -> GpuResult<BTreeMap<u64, u64>> {
+        match self.vendor {
+            #[cfg(feature = "mali-core")]
+            GpuVendor::Mali => Ok(crate::mali::raw_properties(&self.raw)?.into_iter().collect()),
+            GpuVendor::Adreno => Ok(self
+                .raw
+                .chunks_exact(4)
+                .enumerate()
+                .map(|(i, word)| (i as u64, u64::from(u32::from_le_bytes(word.try_into().unwrap()))))
+                .collect()),
+            vendor => Err(GpuError::InvalidData(format!("cannot decode {vendor:?} property dumps"))),
+        }
+    }
+
+    /// Name of property `id` in this snapshot's encoding
+    pub fn property_name(&self, id: u64) -> Option<&'static str> {
+        match self.vendor {
+            #[cfg(feature = "mali-core")]
+            GpuVendor::Mali => crate::mali::property_name(id),
+            GpuVendor::Adreno => KGSL_DEVICE_INFO_FIELDS.get(id as usize).copied(),
+            _ => None,
+        }
+    }
+
+    /// Diff two dumps of the same vendor
+    pub fn compare(old: &Snapshot, new: &Snapshot) -> GpuResult<SnapshotDiff> {
+        if old.vendor != new.vendor {
+            return Err(GpuError::InvalidData(format!(
+                "cannot compare {:?} and {:?} snapshots",
+                old.vendor, new.vendor
+            )));
+        }
+        let old_props = old.properties()?;
+        let new_props = new.properties()?;
+
+        let mut ids: Vec<u64> = old_props.keys().chain(new_props.keys()).copied().collect();
+        ids.sort_unstable();
+        ids.dedup();
+
+        let changes = ids
+            .into_iter()
+            .filter_map(|id| {
+                let (before, after) = (old_props.get(&id).copied(), new_props.get(&id).copied());
+                (before != after).then(|| PropertyChange {
+                    id,
+                    name: new.property_name(id),
+                    old: before,
+                    new: after,
+                })
+            })
+            .collect();
+        Ok(SnapshotDiff { changes })
+    }
+}
@@ -0,0 +1,118 @@
+//! SoC references and GPU/SoC linkage
+//!
+//! The Adreno database lists the SoCs each GPU ships in as [`SocRef`]s
+//! defined here (re-exported from `armgpuinfo-core`), so the relationship
+//! can be walked both ways: from a chip ID to its SoCs ([`socs_using`]) and
+//! from a SoC part number or marketing name back to the GPU
+//! ([`gpu_for_soc`]).
+//!
+//! [`identify_soc`] reports the SoC of the running system from
+//! `/sys/devices/soc0`, the device-tree `compatible` strings and
+//! `/proc/cpuinfo`. Mali products sharing a product ID (Mali-G715 and
+//! Mali-G615) are otherwise told apart by core count alone, which misnames
+//! binned parts; the Mali query paths ask the running SoC first.
+
+use std::fs;
+use std::path::Path;
+
+#[cfg(feature = "mali-core")]
+use once_cell::sync::Lazy;
+
+pub use armgpuinfo_core::soc::*;
+
+/// Identifiers of the running system's SoC
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SocInfo {
+    /// Known SoC the identifiers below match
+    pub soc: Option<SocRef>,
+    /// `/sys/devices/soc0/machine`, e.g. "SM8550"
+    pub machine: Option<String>,
+    /// `/sys/devices/soc0/family`, e.g. "Snapdragon"
+    pub family: Option<String>,
+    /// `/sys/devices/soc0/soc_id`, numeric on Qualcomm, a JEP106 string
+    /// elsewhere
+    pub soc_id: Option<String>,
+    /// Device-tree root `compatible` strings, most specific first
+    pub compatible: Vec<String>,
+    /// `Hardware` line of `/proc/cpuinfo`
+    pub hardware: Option<String>,
+}
+
+impl SocInfo {
+    /// Marketing name of the known SoC, else the reported machine name
+    pub fn name(&self) -> Option<&str> {
+        self.soc.as_ref().map(|soc| soc.market_name).or(self.machine.as_deref())
+    }
+
+    /// Database name of the Mali GPU the SoC ships, see [`mali_gpu_for_soc`]
+    pub fn mali_gpu(&self) -> Option<&'static str> {
+        self.soc.as_ref().and_then(mali_gpu_for_soc)
+    }
+}
+
+/// Identify the running system's SoC
+///
+/// Every source is optional; a SoC that matches none of [`KNOWN_SOCS`]
+/// still reports its raw identifiers.
+pub fn identify_soc() -> SocInfo {
+    identify_soc_in(Path::new("/"))
+}
+
+/// [`identify_soc`] against a filesystem rooted at `root`
+///
+/// ```
+/// use std::fs;
+/// use armgpuinfo::soc::identify_soc_in;
+///
+/// let root = tempfile::tempdir()?;
+/// fs::create_dir_all(root.path().join("proc/device-tree"))?;
+/// fs::write(root.path().join("proc/device-tree/compatible"), "mediatek,mt6985-evb\0mediatek,mt6985\0")?;
+///
+/// let soc = identify_soc_in(root.path());
+/// assert_eq!(soc.name(), Some("Dimensity 9200"));
+/// assert_eq!(soc.mali_gpu(), Some("Immortalis-G715"));
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn identify_soc_in(root: &Path) -> SocInfo {
+    let read = |path: &str| {
+        fs::read_to_string(root.join(path))
+            .ok()
+            .map(|text| text.trim_matches(|c: char| c.is_whitespace() || c == '\0').to_string())
+            .filter(|text| !text.is_empty())
+    };
+    let compatible: Vec<String> = read("proc/device-tree/compatible")
+        .map(|text| text.split('\0').filter(|s| !s.is_empty()).map(str::to_string).collect())
+        .unwrap_or_default();
+    let hardware = read("proc/cpuinfo").and_then(|cpuinfo| {
+        cpuinfo.lines().find_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            (key.trim() == "Hardware").then(|| value.trim().to_string())
+        })
+    });
+    let mut info = SocInfo {
+        soc: None,
+        machine: read("sys/devices/soc0/machine"),
+        family: read("sys/devices/soc0/family"),
+        soc_id: read("sys/devices/soc0/soc_id"),
+        compatible,
+        hardware,
+    };
+
+    // "qcom,sm8550" -> "sm8550"; "Qualcomm Technologies, Inc SM8550" -> "SM8550"
+    let candidates = info
+        .machine
+        .iter()
+        .map(String::as_str)
+        .chain(info.compatible.iter().filter_map(|c| c.split_once(',').map(|(_, model)| model)))
+        .chain(info.hardware.iter().flat_map(|hw| hw.split_whitespace()));
+    info.soc = candidates.filter_map(find_soc).next().copied();
+    info
+}
+
+/// [`identify_soc`], read once per process
+#[cfg(feature = "mali-core")]
+pub(crate) fn system_soc() -> &'static SocInfo {
+    static SOC: Lazy<SocInfo> = Lazy::new(identify_soc);
+    &SOC
+}
@@ -0,0 +1,101 @@
+//! GPU busy/idle time counters
+//!
+//! KGSL's `gpubusy` reports busy and total time in microseconds for the last
+//! DVFS window. kbase's `dvfs_utilisation` reports busy and idle time in
+//! nanoseconds accumulated since power-on, so its deltas have to be taken
+//! between two reads.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use super::{read_node, Provenance, Reading};
+
+/// KGSL busy/total counters
+pub const KGSL_GPUBUSY_PATH: &str = "/sys/class/kgsl/kgsl-3d0/gpubusy";
+/// kbase busy/idle counters
+pub const MALI_DVFS_UTILISATION_PATH: &str = "/sys/class/misc/mali0/device/dvfs_utilisation";
+
+/// Layout of a busy-time node
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BusyFormat {
+    /// `<busy_us> <total_us>` over the driver's last window (KGSL `gpubusy`)
+    KgslGpubusy,
+    /// `<busy_ns> <idle_ns>` since power-on, optionally with `busy_time`/
+    /// `idle_time` labels (kbase `dvfs_utilisation`)
+    MaliDvfsUtilisation,
+}
+
+impl BusyFormat {
+    /// Whether the counters only ever grow between reads
+    pub fn is_cumulative(self) -> bool {
+        matches!(self, BusyFormat::MaliDvfsUtilisation)
+    }
+
+    /// Parse the node contents into active and idle time
+    pub fn parse(self, text: &str) -> Option<BusyTime> {
+        let mut numbers = text.split_whitespace().filter_map(|t| {
+            t.trim_end_matches(|c: char| !c.is_ascii_digit()).parse::<u64>().ok()
+        });
+        let (first, second) = (numbers.next()?, numbers.next()?);
+        Some(match self {
+            BusyFormat::KgslGpubusy => BusyTime {
+                active: Duration::from_micros(first),
+                idle: Duration::from_micros(second.saturating_sub(first)),
+            },
+            BusyFormat::MaliDvfsUtilisation => BusyTime {
+                active: Duration::from_nanos(first),
+                idle: Duration::from_nanos(second),
+            },
+        })
+    }
+}
+
+/// Active and idle GPU time
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct BusyTime {
+    pub active: Duration,
+    pub idle: Duration,
+}
+
+impl BusyTime {
+    /// Busy percentage (0.0 - 100.0), `None` if no time has passed
+    pub fn busy_percent(&self) -> Option<f32> {
+        let total = (self.active + self.idle).as_secs_f64();
+        (total > 0.0).then(|| (self.active.as_secs_f64() / total * 100.0) as f32)
+    }
+
+    /// Time elapsed between `earlier` and `self`, `None` if a counter went
+    /// backwards (driver reload or wrap)
+    pub fn since(&self, earlier: &BusyTime) -> Option<BusyTime> {
+        Some(BusyTime {
+            active: self.active.checked_sub(earlier.active)?,
+            idle: self.idle.checked_sub(earlier.idle)?,
+        })
+    }
+}
+
+/// A readable busy-time node
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BusyCounters {
+    pub path: PathBuf,
+    pub format: BusyFormat,
+}
+
+impl BusyCounters {
+    /// First readable node among KGSL `gpubusy` and kbase `dvfs_utilisation`
+    pub fn find() -> Option<Self> {
+        [
+            (KGSL_GPUBUSY_PATH, BusyFormat::KgslGpubusy),
+            (MALI_DVFS_UTILISATION_PATH, BusyFormat::MaliDvfsUtilisation),
+        ]
+        .into_iter()
+        .map(|(path, format)| Self { path: PathBuf::from(path), format })
+        .find(|counters| counters.read().is_some())
+    }
+
+    /// Read the raw counter values
+    pub fn read(&self) -> Option<Reading<BusyTime>> {
+        let text = read_node(&self.path)?;
+        self.format.parse(&text).map(|t| Reading::new(t, Provenance::Sysfs))
+    }
+}
@@ -0,0 +1,118 @@
+//! Measured max-frequency calibration
+//!
+//! Database boost clocks are what the SoC vendor advertises, not what a given
+//! device ever reaches once its DVFS table, thermal limits and governor are
+//! applied. Calibration watches devfreq for a short window and records the
+//! highest frequency that was actually held.
+
+use std::path::PathBuf;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::error::{GpuError, GpuResult};
+use crate::info::GpuInfo;
+
+use super::devfreq::{find_gpu_devfreq, read_cur_freq_hz};
+use super::{Provenance, Reading};
+
+/// Calibration parameters
+#[derive(Debug, Clone)]
+pub struct CalibrationConfig {
+    /// Total observation window
+    pub window: Duration,
+    /// Delay between devfreq samples
+    pub interval: Duration,
+    /// Minimum time a frequency must be held to count as sustained
+    pub sustain: Duration,
+    /// devfreq directory to watch; auto-detected if `None`
+    pub devfreq: Option<PathBuf>,
+}
+
+impl Default for CalibrationConfig {
+    fn default() -> Self {
+        Self {
+            window: Duration::from_secs(5),
+            interval: Duration::from_millis(20),
+            sustain: Duration::from_millis(200),
+            devfreq: None,
+        }
+    }
+}
+
+/// Outcome of a calibration run
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MaxFreqCalibration {
+    /// Highest frequency held for at least [`CalibrationConfig::sustain`]
+    pub sustained_max_mhz: Reading<u32>,
+    /// Highest frequency seen in any single sample
+    pub peak_mhz: u32,
+    /// Number of samples taken
+    pub samples: usize,
+}
+
+impl MaxFreqCalibration {
+    /// Store the measured value next to the database `max_freq_mhz`
+    pub fn apply(&self, info: &mut GpuInfo) {
+        let measured = Some(self.sustained_max_mhz.value);
+        if let Some(mali) = info.mali_data.as_mut() {
+            mali.measured_max_freq_mhz = measured;
+        }
+        if let Some(adreno) = info.adreno_data.as_mut() {
+            adreno.measured_max_freq_mhz = measured;
+        }
+    }
+}
+
+/// Calibrate under whatever load the system is currently running
+pub fn calibrate_max_freq(config: &CalibrationConfig) -> GpuResult<MaxFreqCalibration> {
+    let devfreq = config
+        .devfreq
+        .clone()
+        .or_else(find_gpu_devfreq)
+        .ok_or(GpuError::DeviceNotFound)?;
+
+    let mut samples_mhz = Vec::new();
+    let start = Instant::now();
+    while start.elapsed() < config.window {
+        if let Some(hz) = read_cur_freq_hz(&devfreq) {
+            samples_mhz.push((hz / 1_000_000) as u32);
+        }
+        thread::sleep(config.interval);
+    }
+
+    let run = (config.sustain.as_nanos() / config.interval.as_nanos().max(1)).max(1) as usize;
+    let sustained = sustained_max(&samples_mhz, run)
+        .ok_or_else(|| GpuError::InvalidData("No devfreq samples during calibration".into()))?;
+
+    Ok(MaxFreqCalibration {
+        sustained_max_mhz: Reading::new(sustained, Provenance::Measured),
+        peak_mhz: samples_mhz.iter().copied().max().unwrap_or(0),
+        samples: samples_mhz.len(),
+    })
+}
+
+/// Calibrate while `load` runs on a separate thread
+///
+/// `load` should keep the GPU busy (e.g. submit a compute workload) for about
+/// the configured window; sampling does not wait for it to finish early.
+pub fn calibrate_max_freq_with_load<F>(config: &CalibrationConfig, load: F) -> GpuResult<MaxFreqCalibration>
+where
+    F: FnOnce() + Send,
+{
+    thread::scope(|scope| {
+        scope.spawn(load);
+        calibrate_max_freq(config)
+    })
+}
+
+/// Highest value held by `run` consecutive samples
+fn sustained_max(samples: &[u32], run: usize) -> Option<u32> {
+    let run = run.min(samples.len());
+    if run == 0 {
+        return None;
+    }
+    samples
+        .windows(run)
+        .filter_map(|window| window.iter().copied().min())
+        .max()
+}
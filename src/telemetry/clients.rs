@@ -0,0 +1,163 @@
+//! Processes holding GPU contexts
+//!
+//! KGSL creates a sysfs directory per process with an open context under
+//! `/sys/class/kgsl/kgsl/proc/<pid>`; kbase names a debugfs directory after
+//! every context, `<tgid>_<id>`, under `/sys/kernel/debug/mali0/ctx`. The
+//! kbase directories are root-only on production builds, in which case its
+//! `gpu_memory` accounting is used instead.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::{read_node, Provenance};
+
+/// A process with at least one GPU context
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GpuClient {
+    /// Process (thread group) ID
+    pub pid: u32,
+    /// `/proc/<pid>/comm`, `None` if the process exited or is hidden
+    pub name: Option<String>,
+    /// Number of GPU contexts, `None` if the source does not count them
+    pub contexts: Option<u32>,
+    /// GPU memory allocated by the process
+    pub gpu_memory_bytes: Option<u64>,
+    pub provenance: Provenance,
+}
+
+/// Directories [`list_gpu_clients_in`] reads
+#[derive(Debug, Clone)]
+pub struct ClientPaths {
+    /// KGSL per-process sysfs directory
+    pub kgsl_proc: PathBuf,
+    /// kbase debugfs directory
+    pub mali_debugfs: PathBuf,
+    /// procfs mount, for process names
+    pub procfs: PathBuf,
+}
+
+impl Default for ClientPaths {
+    fn default() -> Self {
+        Self {
+            kgsl_proc: PathBuf::from("/sys/class/kgsl/kgsl/proc"),
+            mali_debugfs: PathBuf::from("/sys/kernel/debug/mali0"),
+            procfs: PathBuf::from("/proc"),
+        }
+    }
+}
+
+/// Processes currently holding GPU contexts, sorted by PID
+///
+/// Empty if no source is readable.
+pub fn list_gpu_clients() -> Vec<GpuClient> {
+    list_gpu_clients_in(&ClientPaths::default())
+}
+
+/// [`list_gpu_clients`] under custom roots
+///
+/// ```
+/// use std::fs;
+/// use armgpuinfo::telemetry::clients::{list_gpu_clients_in, ClientPaths};
+///
+/// let root = tempfile::tempdir()?;
+/// let kgsl = root.path().join("kgsl/proc/1234");
+/// fs::create_dir_all(&kgsl)?;
+/// fs::write(kgsl.join("gpumem_mapped"), "4096\n")?;
+/// fs::write(kgsl.join("gpumem_unmapped"), "8192\n")?;
+/// fs::create_dir_all(root.path().join("proc/1234"))?;
+/// fs::write(root.path().join("proc/1234/comm"), "surfaceflinger\n")?;
+///
+/// let clients = list_gpu_clients_in(&ClientPaths {
+///     kgsl_proc: root.path().join("kgsl/proc"),
+///     mali_debugfs: root.path().join("mali0"),
+///     procfs: root.path().join("proc"),
+/// });
+/// assert_eq!(clients[0].pid, 1234);
+/// assert_eq!(clients[0].name.as_deref(), Some("surfaceflinger"));
+/// assert_eq!(clients[0].gpu_memory_bytes, Some(12288));
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn list_gpu_clients_in(paths: &ClientPaths) -> Vec<GpuClient> {
+    let mut clients = kgsl_clients(&paths.kgsl_proc);
+    #[cfg(feature = "mali-core")]
+    clients.extend(mali_clients(&paths.mali_debugfs));
+
+    for client in &mut clients {
+        client.name = read_node(&paths.procfs.join(client.pid.to_string()).join("comm"));
+    }
+    clients.sort_by_key(|client| client.pid);
+    clients
+}
+
+/// One client per numeric directory; memory is the sum of the mapped and
+/// unmapped allocations where the kernel reports them
+fn kgsl_clients(proc_dir: &Path) -> Vec<GpuClient> {
+    numeric_entries(proc_dir)
+        .map(|(pid, dir)| {
+            let bytes = ["gpumem_mapped", "gpumem_unmapped"]
+                .iter()
+                .filter_map(|node| read_node(&dir.join(node))?.parse::<u64>().ok())
+                .reduce(|a, b| a + b);
+            GpuClient {
+                pid,
+                name: None,
+                contexts: None,
+                gpu_memory_bytes: bytes,
+                provenance: Provenance::Sysfs,
+            }
+        })
+        .collect()
+}
+
+/// Clients from the `ctx` directories, with memory from `gpu_memory`; only
+/// `gpu_memory` if the directories are not readable
+#[cfg(feature = "mali-core")]
+fn mali_clients(debugfs: &Path) -> Vec<GpuClient> {
+    let memory = read_node(&debugfs.join("gpu_memory"))
+        .and_then(|text| super::mali_nodes::parse_gpu_memory(&text, super::page_size()));
+    let bytes_of = |pid| memory.as_ref().and_then(|m| m.for_pid(pid));
+
+    let mut clients: Vec<GpuClient> = Vec::new();
+    let contexts = fs::read_dir(debugfs.join("ctx")).into_iter().flatten().flatten();
+    for entry in contexts {
+        // "<tgid>_<context id>"
+        let name = entry.file_name();
+        let Some(pid) = name.to_str().and_then(|n| n.split_once('_')?.0.parse::<u32>().ok()) else {
+            continue;
+        };
+        match clients.iter_mut().find(|client| client.pid == pid) {
+            Some(client) => client.contexts = client.contexts.map(|n| n + 1),
+            None => clients.push(GpuClient {
+                pid,
+                name: None,
+                contexts: Some(1),
+                gpu_memory_bytes: bytes_of(pid),
+                provenance: Provenance::Debugfs,
+            }),
+        }
+    }
+
+    if clients.is_empty() {
+        clients = memory
+            .iter()
+            .flat_map(|m| &m.processes)
+            .map(|process| GpuClient {
+                pid: process.pid,
+                name: None,
+                contexts: None,
+                gpu_memory_bytes: Some(process.bytes),
+                provenance: Provenance::Debugfs,
+            })
+            .collect();
+    }
+    clients
+}
+
+/// Entries of `dir` named by a PID
+fn numeric_entries(dir: &Path) -> impl Iterator<Item = (u32, PathBuf)> {
+    fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| Some((entry.file_name().to_str()?.parse().ok()?, entry.path())))
+}
@@ -0,0 +1,97 @@
+//! Runtime confirmation of database specs
+//!
+//! Adreno clocks come from the chip database, which is only
+//! [`Heuristic`](crate::SpecConfidence::Heuristic) for parts Qualcomm does
+//! not document. [`confirm`] reads the values the running system exposes,
+//! replaces the database value and lists the field in
+//! [`AdrenoData::confirmed`](crate::AdrenoData::confirmed), so
+//! [`GpuInfo::field_confidence`] and [`GpuInfo::data_confidence`] report it
+//! as measured. GMEM size is
+//! confirmed by the query itself whenever the driver reports it.
+
+use crate::fields::FieldId;
+use crate::info::{DataConfidence, GpuInfo};
+
+use super::devfreq::{read_frequency_info, FrequencyInfo};
+use super::{Provenance, Reading};
+
+/// One field checked against the running system
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Confirmation {
+    pub field: FieldId,
+    /// Value before confirmation
+    pub database: u64,
+    /// Value found on the device, now stored in the field
+    pub observed: Reading<u64>,
+}
+
+impl Confirmation {
+    /// Whether the database value was wrong for this device
+    pub fn corrected(&self) -> bool {
+        self.database != self.observed.value
+    }
+}
+
+/// Confirm what the GPU devfreq device allows
+pub fn confirm(info: &mut GpuInfo) -> Vec<Confirmation> {
+    confirm_with(info, read_frequency_info().as_ref())
+}
+
+/// [`confirm`] against an already read devfreq state
+///
+/// ```
+/// use armgpuinfo::fields::FieldId;
+/// use armgpuinfo::telemetry::confirm::confirm_with;
+/// use armgpuinfo::telemetry::FrequencyInfo;
+/// use armgpuinfo::{DataConfidence, GpuInfo, SpecConfidence};
+///
+/// let mut info = GpuInfo::adreno_builder()
+///     .chip_id(0x0706_0001)
+///     .gpu_name("Adreno 740")
+///     .architecture("Adreno 7xx")
+///     .num_shader_cores(6)
+///     .gmem_bytes(2 << 20)
+///     .max_freq_mhz(719)
+///     .build()?;
+/// let devfreq = FrequencyInfo {
+///     devfreq: "/sys/class/devfreq/3d00000.qcom,kgsl-3d0".into(),
+///     current_hz: Some(220_000_000),
+///     min_hz: Some(220_000_000),
+///     max_hz: Some(680_000_000),
+///     available_hz: vec![220_000_000, 680_000_000, 719_000_000],
+/// };
+///
+/// let confirmed = confirm_with(&mut info, Some(&devfreq));
+/// assert!(!confirmed[0].corrected());
+/// assert_eq!(info.field_confidence(FieldId::MaxFreqMhz), Some(SpecConfidence::Measured));
+/// assert_eq!(info.field_confidence(FieldId::GmemBytes), Some(SpecConfidence::Heuristic));
+/// assert_eq!(info.data_confidence(FieldId::MaxFreqMhz), DataConfidence::Measured);
+/// # Ok::<(), armgpuinfo::BuilderError>(())
+/// ```
+pub fn confirm_with(info: &mut GpuInfo, frequency: Option<&FrequencyInfo>) -> Vec<Confirmation> {
+    let mut confirmed = Vec::new();
+    let Some(adreno) = info.adreno_data.as_mut() else {
+        return confirmed;
+    };
+
+    // The top OPP rather than the policy limit, which drops while throttled
+    let peak_mhz = frequency.and_then(FrequencyInfo::peak_hz).map(|hz| hz / 1_000_000);
+    if let Some(mhz) = peak_mhz.filter(|&mhz| mhz > 0) {
+        confirmed.push(Confirmation {
+            field: FieldId::MaxFreqMhz,
+            database: u64::from(adreno.max_freq_mhz),
+            observed: Reading::new(mhz, Provenance::Sysfs),
+        });
+        adreno.max_freq_mhz = mhz as u32;
+    }
+
+    for confirmation in &confirmed {
+        if !adreno.confirmed.contains(&confirmation.field) {
+            adreno.confirmed.push(confirmation.field);
+        }
+    }
+    for confirmation in &confirmed {
+        info.set_data_confidence(confirmation.field, DataConfidence::Measured);
+    }
+    confirmed
+}
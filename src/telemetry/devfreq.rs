@@ -0,0 +1,104 @@
+//! devfreq access for the GPU
+//!
+//! Both kbase and KGSL register the GPU with the devfreq framework, which
+//! exposes the current clock under `/sys/class/devfreq/<device>/cur_freq`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::read_node;
+
+/// Default devfreq class directory
+pub const DEVFREQ_ROOT: &str = "/sys/class/devfreq";
+
+/// Find the devfreq directory belonging to the GPU
+///
+/// Devices are named after their platform node (e.g. `fb000000.gpu`,
+/// `13000000.mali`, `3d00000.qcom,kgsl-3d0`), so the name is matched against
+/// the usual GPU markers.
+pub fn find_gpu_devfreq() -> Option<PathBuf> {
+    find_gpu_devfreq_in(Path::new(DEVFREQ_ROOT))
+}
+
+/// [`find_gpu_devfreq`] under a custom class directory
+pub fn find_gpu_devfreq_in(root: &Path) -> Option<PathBuf> {
+    let mut candidates: Vec<PathBuf> = fs::read_dir(root)
+        .ok()?
+        .flatten()
+        .filter(|entry| {
+            let name = entry.file_name().to_string_lossy().to_ascii_lowercase();
+            name.contains("gpu") || name.contains("mali") || name.contains("kgsl")
+        })
+        .map(|entry| entry.path())
+        .collect();
+    // read_dir order is unspecified; keep the choice stable across runs
+    candidates.sort();
+    candidates.into_iter().next()
+}
+
+/// Current GPU clock in Hz
+pub fn read_cur_freq_hz(devfreq: &Path) -> Option<u64> {
+    read_node(&devfreq.join("cur_freq"))?.parse().ok()
+}
+
+/// Current lower clock limit in Hz
+pub fn read_min_freq_hz(devfreq: &Path) -> Option<u64> {
+    read_node(&devfreq.join("min_freq"))?.parse().ok()
+}
+
+/// Current upper clock limit in Hz (lowered by thermal or policy caps)
+pub fn read_max_freq_hz(devfreq: &Path) -> Option<u64> {
+    read_node(&devfreq.join("max_freq"))?.parse().ok()
+}
+
+/// OPP frequencies in Hz, ascending
+pub fn read_available_frequencies_hz(devfreq: &Path) -> Vec<u64> {
+    let mut freqs: Vec<u64> = read_node(&devfreq.join("available_frequencies"))
+        .map(|s| s.split_whitespace().filter_map(|f| f.parse().ok()).collect())
+        .unwrap_or_default();
+    freqs.sort_unstable();
+    freqs
+}
+
+/// Clock state of a devfreq device, all values in Hz
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FrequencyInfo {
+    /// devfreq directory the values were read from
+    pub devfreq: PathBuf,
+    /// Clock the GPU is running at right now
+    pub current_hz: Option<u64>,
+    /// Lower policy limit
+    pub min_hz: Option<u64>,
+    /// Upper policy limit, below the top OPP while thermally capped
+    pub max_hz: Option<u64>,
+    /// OPP table, ascending
+    pub available_hz: Vec<u64>,
+}
+
+impl FrequencyInfo {
+    /// Read every node of `devfreq`
+    pub fn read(devfreq: &Path) -> Self {
+        Self {
+            devfreq: devfreq.to_path_buf(),
+            current_hz: read_cur_freq_hz(devfreq),
+            min_hz: read_min_freq_hz(devfreq),
+            max_hz: read_max_freq_hz(devfreq),
+            available_hz: read_available_frequencies_hz(devfreq),
+        }
+    }
+
+    /// Highest OPP, regardless of the current policy limit
+    pub fn peak_hz(&self) -> Option<u64> {
+        self.available_hz.last().copied().or(self.max_hz)
+    }
+
+    /// Whether the policy limit is below the top OPP (thermal or power cap)
+    pub fn is_capped(&self) -> bool {
+        matches!((self.max_hz, self.available_hz.last()), (Some(max), Some(&top)) if max < top)
+    }
+}
+
+/// [`FrequencyInfo`] of the GPU's devfreq device, if one is found
+pub fn read_frequency_info() -> Option<FrequencyInfo> {
+    find_gpu_devfreq().map(|devfreq| FrequencyInfo::read(&devfreq))
+}
@@ -0,0 +1,136 @@
+//! hwmon power-rail sensors
+//!
+//! Dev boards and some phones carry current/power monitors (INA2xx, INA3221,
+//! PMIC fuel gauges) on the GPU rail. hwmon reports power in microwatts,
+//! current in milliamps and voltage in millivolts.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::{read_node, Provenance, Reading};
+
+/// Default hwmon class directory
+pub const HWMON_ROOT: &str = "/sys/class/hwmon";
+
+/// How a sensor's power is obtained
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PowerChannel {
+    /// `powerN_input` (microwatts)
+    Power(u32),
+    /// `inN_input` (millivolts) times `currN_input` (milliamps)
+    VoltageCurrent { voltage: u32, current: u32 },
+}
+
+/// A GPU-related power sensor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PowerSensor {
+    /// hwmon device directory
+    pub path: PathBuf,
+    /// Channel label, or the chip name if the channel has none
+    pub label: String,
+    pub channel: PowerChannel,
+}
+
+impl PowerSensor {
+    /// Current rail power in watts
+    pub fn read_watts(&self) -> Option<f32> {
+        let read = |node: String| -> Option<f64> { read_node(&self.path.join(node))?.parse().ok() };
+        match self.channel {
+            PowerChannel::Power(n) => {
+                let microwatts = read(format!("power{n}_input"))?;
+                Some((microwatts / 1e6) as f32)
+            }
+            PowerChannel::VoltageCurrent { voltage, current } => {
+                let millivolts = read(format!("in{voltage}_input"))?;
+                let milliamps = read(format!("curr{current}_input"))?;
+                Some((millivolts * milliamps / 1e6) as f32)
+            }
+        }
+    }
+
+    /// Current rail power as a telemetry reading
+    pub fn read(&self) -> Option<Reading<f32>> {
+        self.read_watts().map(|w| Reading::new(w, Provenance::Hwmon))
+    }
+}
+
+/// Find all GPU power sensors under [`HWMON_ROOT`]
+pub fn find_gpu_power_sensors() -> Vec<PowerSensor> {
+    find_gpu_power_sensors_in(Path::new(HWMON_ROOT))
+}
+
+/// [`find_gpu_power_sensors`] under a custom class directory
+pub fn find_gpu_power_sensors_in(root: &Path) -> Vec<PowerSensor> {
+    let Ok(entries) = fs::read_dir(root) else {
+        return Vec::new();
+    };
+    let mut dirs: Vec<PathBuf> = entries.flatten().map(|e| e.path()).collect();
+    dirs.sort();
+
+    let mut sensors = Vec::new();
+    for dir in dirs {
+        let chip = read_node(&dir.join("name")).unwrap_or_default();
+        let chip_is_gpu = is_gpu_label(&chip);
+
+        // Direct power channels
+        for n in channel_numbers(&dir, "power", "_input") {
+            let label = read_node(&dir.join(format!("power{n}_label")));
+            if chip_is_gpu || label.as_deref().is_some_and(is_gpu_label) {
+                sensors.push(PowerSensor {
+                    path: dir.clone(),
+                    label: label.unwrap_or_else(|| chip.clone()),
+                    channel: PowerChannel::Power(n),
+                });
+            }
+        }
+
+        // Voltage/current pairs sharing a label (INA3221 style)
+        for n in channel_numbers(&dir, "curr", "_input") {
+            let label = read_node(&dir.join(format!("curr{n}_label")))
+                .or_else(|| read_node(&dir.join(format!("in{n}_label"))));
+            if !(chip_is_gpu || label.as_deref().is_some_and(is_gpu_label)) {
+                continue;
+            }
+            if !dir.join(format!("in{n}_input")).exists() {
+                continue;
+            }
+            let label = label.unwrap_or_else(|| chip.clone());
+            // A direct power channel for the same rail wins
+            if sensors.iter().any(|s| s.path == dir && s.label == label) {
+                continue;
+            }
+            sensors.push(PowerSensor {
+                path: dir.clone(),
+                label,
+                channel: PowerChannel::VoltageCurrent { voltage: n, current: n },
+            });
+        }
+    }
+    sensors
+}
+
+/// Measured GPU power from the first readable sensor
+pub fn read_gpu_power() -> Option<Reading<f32>> {
+    find_gpu_power_sensors().iter().find_map(PowerSensor::read)
+}
+
+fn is_gpu_label(label: &str) -> bool {
+    let label = label.to_ascii_lowercase();
+    label.contains("gpu") || label.contains("mali") || label.contains("gfx")
+}
+
+/// Channel numbers N for which `<prefix>N<suffix>` exists, ascending
+fn channel_numbers(dir: &Path, prefix: &str, suffix: &str) -> Vec<u32> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut numbers: Vec<u32> = entries
+        .flatten()
+        .filter_map(|e| {
+            let name = e.file_name().into_string().ok()?;
+            name.strip_prefix(prefix)?.strip_suffix(suffix)?.parse().ok()
+        })
+        .collect();
+    numbers.sort_unstable();
+    numbers
+}
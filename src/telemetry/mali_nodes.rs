@@ -0,0 +1,203 @@
+//! Vendor procfs/debugfs ingestion for Mali
+//!
+//! Some kernels expose data through `/proc/mali/*`, the kbase sysfs device
+//! directory or kbase debugfs that unprivileged callers cannot get via
+//! ioctls. Nothing here is read unless [`MaliNodes::read`] is called.
+
+use std::borrow::Cow;
+use std::path::PathBuf;
+
+use crate::capabilities::GpuCapabilities;
+use crate::info::{GpuInfo, GpuVendor};
+
+use super::{
+    page_size, read_node, GpuMemoryUsage, ProcessGpuMemory, Provenance, Reading, Telemetry,
+};
+
+/// Root directories searched for vendor nodes
+#[derive(Debug, Clone)]
+pub struct MaliNodePaths {
+    /// kbase sysfs device directory
+    pub sysfs: PathBuf,
+    /// kbase debugfs directory
+    pub debugfs: PathBuf,
+    /// Vendor procfs directory
+    pub procfs: PathBuf,
+}
+
+impl Default for MaliNodePaths {
+    fn default() -> Self {
+        Self {
+            sysfs: PathBuf::from("/sys/class/misc/mali0/device"),
+            debugfs: PathBuf::from("/sys/kernel/debug/mali0"),
+            procfs: PathBuf::from("/proc/mali"),
+        }
+    }
+}
+
+/// Parsed kbase `gpuinfo` line, e.g. "Mali-G610 4 cores r0p0 0xA867"
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MaliGpuinfo {
+    pub name: String,
+    pub num_cores: u32,
+    /// Hardware revision (major, minor) from the `rXpY` token
+    pub revision: Option<(u32, u32)>,
+    pub product_id: Option<u32>,
+}
+
+impl MaliGpuinfo {
+    /// Parse the `gpuinfo` sysfs format
+    pub fn parse(line: &str) -> Option<Self> {
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        let cores_idx = tokens.iter().position(|t| *t == "cores" || *t == "core")?;
+        if cores_idx < 2 {
+            return None;
+        }
+
+        let num_cores = tokens[cores_idx - 1].parse().ok()?;
+        let name = tokens[..cores_idx - 1].join(" ");
+
+        let revision = tokens.iter().skip(cores_idx + 1).find_map(|t| {
+            let (major, minor) = t.strip_prefix('r')?.split_once('p')?;
+            Some((major.parse().ok()?, minor.parse().ok()?))
+        });
+        let product_id = tokens.iter().skip(cores_idx + 1).find_map(|t| {
+            u32::from_str_radix(t.strip_prefix("0x").or_else(|| t.strip_prefix("0X"))?, 16).ok()
+        });
+
+        Some(Self { name, num_cores, revision, product_id })
+    }
+}
+
+/// Everything found in the vendor nodes of one device
+#[derive(Debug, Clone, Default)]
+pub struct MaliNodes {
+    pub gpuinfo: Option<Reading<MaliGpuinfo>>,
+    pub telemetry: Telemetry,
+}
+
+impl MaliNodes {
+    /// Read all nodes under the default roots
+    pub fn read() -> Self {
+        Self::read_from(&MaliNodePaths::default())
+    }
+
+    /// Read all nodes under the given roots; unreadable nodes are skipped
+    pub fn read_from(paths: &MaliNodePaths) -> Self {
+        let gpuinfo = first_readable(&[
+            (paths.sysfs.join("gpuinfo"), Provenance::Sysfs),
+            (paths.procfs.join("gpuinfo"), Provenance::Procfs),
+        ])
+        .and_then(|r| MaliGpuinfo::parse(&r.value).map(|g| Reading::new(g, r.provenance)));
+
+        let utilization = first_readable(&[
+            (paths.sysfs.join("utilization"), Provenance::Sysfs),
+            (paths.procfs.join("utilization"), Provenance::Procfs),
+            (paths.debugfs.join("utilization"), Provenance::Debugfs),
+        ])
+        .and_then(|r| parse_percent(&r.value).map(|v| Reading::new(v, r.provenance)));
+
+        Self {
+            gpuinfo,
+            telemetry: Telemetry {
+                utilization,
+                memory: read_gpu_memory(paths),
+                power_watts: None,
+            },
+        }
+    }
+
+    /// Fill fields of a Mali `GpuInfo` the ioctl path left empty
+    ///
+    /// Returns the names of the fields that were filled together with their
+    /// provenance. Values the driver already reported are never overwritten.
+    pub fn merge_into(&self, info: &mut GpuInfo) -> Vec<(&'static str, Provenance)> {
+        let mut merged = Vec::new();
+        let Some(Reading { value: gpuinfo, provenance }) = &self.gpuinfo else {
+            return merged;
+        };
+        if info.vendor != GpuVendor::Mali {
+            return merged;
+        }
+
+        if info.gpu_name.is_empty() && !gpuinfo.name.is_empty() {
+            info.gpu_name = Cow::Owned(gpuinfo.name.clone());
+            merged.push(("gpu_name", *provenance));
+        }
+        if info.num_shader_cores == 0 && gpuinfo.num_cores > 0 {
+            info.num_shader_cores = gpuinfo.num_cores;
+            merged.push(("num_shader_cores", *provenance));
+        }
+        if let (Some(mali), Some(product_id)) = (info.mali_data.as_mut(), gpuinfo.product_id) {
+            if mali.gpu_id == 0 {
+                mali.gpu_id = product_id;
+                merged.push(("gpu_id", *provenance));
+            }
+        }
+        if !merged.is_empty() {
+            info.capabilities = GpuCapabilities::of(info);
+        }
+        merged
+    }
+}
+
+fn first_readable(candidates: &[(PathBuf, Provenance)]) -> Option<Reading<String>> {
+    candidates
+        .iter()
+        .find_map(|(path, provenance)| read_node(path).map(|s| Reading::new(s, *provenance)))
+}
+
+/// Parse utilization in the formats vendor kernels use:
+/// "42", "42%", "gpu=42" and MediaTek's "gpu/cljs0/cljs1=42/0/0"
+fn parse_percent(text: &str) -> Option<f32> {
+    let value = text.rsplit('=').next()?;
+    let first = value.split('/').next()?.trim().trim_end_matches('%').trim();
+    first.parse::<f32>().ok().filter(|v| (0.0..=100.0).contains(v))
+}
+
+/// kbase `gpu_memory` accounting from debugfs or the vendor procfs copy
+pub fn read_gpu_memory(paths: &MaliNodePaths) -> Option<Reading<GpuMemoryUsage>> {
+    first_readable(&[
+        (paths.debugfs.join("gpu_memory"), Provenance::Debugfs),
+        (paths.procfs.join("gpu_memory"), Provenance::Procfs),
+    ])
+    .and_then(|r| parse_gpu_memory(&r.value, page_size()).map(|m| Reading::new(m, r.provenance)))
+}
+
+/// Parse kbase `gpu_memory`:
+///
+/// ```text
+/// mali0                  12345
+///   kctx-0x00000000deadbeef       1024       1234
+/// ```
+///
+/// The first line holds the device-wide page count, each following line the
+/// pages and TGID of one context. Contexts of the same process are summed.
+pub(crate) fn parse_gpu_memory(text: &str, page_size: u64) -> Option<GpuMemoryUsage> {
+    let mut lines = text.lines();
+    let total_pages: u64 = lines.next()?.split_whitespace().last()?.parse().ok()?;
+
+    let mut processes: Vec<ProcessGpuMemory> = Vec::new();
+    for line in lines {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let [ctx, pages, tgid] = fields[..] else {
+            continue;
+        };
+        if !ctx.starts_with("kctx") {
+            continue;
+        }
+        let (Ok(pages), Ok(pid)) = (pages.parse::<u64>(), tgid.parse::<u32>()) else {
+            continue;
+        };
+        match processes.iter_mut().find(|p| p.pid == pid) {
+            Some(entry) => entry.bytes += pages * page_size,
+            None => processes.push(ProcessGpuMemory { pid, bytes: pages * page_size }),
+        }
+    }
+    processes.sort_by_key(|p| p.pid);
+
+    Some(GpuMemoryUsage {
+        total_bytes: total_pages * page_size,
+        processes,
+    })
+}
@@ -0,0 +1,218 @@
+//! Runtime GPU telemetry
+//!
+//! Unlike [`GpuInfo`](crate::GpuInfo), which describes the hardware, telemetry
+//! values change over time and come from a mix of sources. Every value is
+//! wrapped in a [`Reading`] recording where it came from, so consumers can
+//! tell a driver-reported number from one scraped out of debugfs.
+
+pub mod busy;
+pub mod calibration;
+pub mod clients;
+pub mod confirm;
+pub mod devfreq;
+pub mod hwmon;
+#[cfg(feature = "mali-core")]
+pub mod mali_nodes;
+pub mod power;
+pub mod thermal;
+
+pub use busy::{BusyCounters, BusyTime};
+pub use clients::{list_gpu_clients, GpuClient};
+pub use devfreq::FrequencyInfo;
+pub use power::{estimate_gpu_power, GpuPowerEstimate};
+pub use thermal::gpu_thermal_zones;
+
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+const KGSL_BUSY_PATH: &str = "/sys/class/kgsl/kgsl-3d0/gpu_busy_percentage";
+const MEMINFO_PATH: &str = "/proc/meminfo";
+
+/// Where a value was obtained from
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Provenance {
+    /// Kernel driver ioctl
+    Ioctl,
+    /// Built-in product database
+    Database,
+    /// sysfs node
+    Sysfs,
+    /// debugfs node (usually root-only)
+    Debugfs,
+    /// procfs node (vendor kernels)
+    Procfs,
+    /// Observed on this device over time (e.g. by calibration)
+    Measured,
+    /// hwmon sensor
+    Hwmon,
+    /// Model-based estimate
+    Estimated,
+}
+
+impl fmt::Display for Provenance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Provenance::Ioctl => write!(f, "ioctl"),
+            Provenance::Database => write!(f, "database"),
+            Provenance::Sysfs => write!(f, "sysfs"),
+            Provenance::Debugfs => write!(f, "debugfs"),
+            Provenance::Procfs => write!(f, "procfs"),
+            Provenance::Measured => write!(f, "measured"),
+            Provenance::Hwmon => write!(f, "hwmon"),
+            Provenance::Estimated => write!(f, "estimated"),
+        }
+    }
+}
+
+/// A value tagged with its [`Provenance`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Reading<T> {
+    pub value: T,
+    pub provenance: Provenance,
+}
+
+impl<T> Reading<T> {
+    pub fn new(value: T, provenance: Provenance) -> Self {
+        Self { value, provenance }
+    }
+
+    /// Transform the value, keeping the provenance
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Reading<U> {
+        Reading::new(f(self.value), self.provenance)
+    }
+}
+
+/// Snapshot of dynamic GPU state
+///
+/// Every field is optional; sources that are not readable on this device
+/// (or for this user) are simply left empty.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Telemetry {
+    /// GPU busy percentage (0.0 - 100.0)
+    pub utilization: Option<Reading<f32>>,
+    /// Driver GPU memory accounting
+    pub memory: Option<Reading<GpuMemoryUsage>>,
+    /// GPU power draw in watts
+    pub power_watts: Option<Reading<f32>>,
+}
+
+impl Telemetry {
+    /// Fill empty fields from `other`, keeping values already present
+    pub fn merge(&mut self, other: Telemetry) {
+        self.utilization = self.utilization.or(other.utilization);
+        self.memory = self.memory.take().or(other.memory);
+        self.power_watts = self.power_watts.or(other.power_watts);
+    }
+
+    /// Read power from hwmon, if a GPU rail sensor exists
+    pub fn read_power(&mut self) {
+        if let Some(power) = hwmon::read_gpu_power() {
+            self.power_watts = Some(power);
+        }
+    }
+
+    /// Use a model-based power estimate unless a measured value is present
+    pub fn with_power_estimate(mut self, watts: f32) -> Self {
+        if self.power_watts.is_none() {
+            self.power_watts = Some(Reading::new(watts, Provenance::Estimated));
+        }
+        self
+    }
+
+    /// GPU memory in use by all processes, in bytes
+    pub fn memory_used_bytes(&self) -> Option<Reading<u64>> {
+        self.memory
+            .as_ref()
+            .map(|m| Reading::new(m.value.total_bytes, m.provenance))
+    }
+}
+
+/// GPU memory attributed to one process
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProcessGpuMemory {
+    /// Process (thread group) ID
+    pub pid: u32,
+    /// Bytes allocated by all GPU contexts of the process
+    pub bytes: u64,
+}
+
+/// Driver GPU memory accounting
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GpuMemoryUsage {
+    /// Bytes in use by all processes
+    pub total_bytes: u64,
+    /// Per-process breakdown sorted by PID; empty if the source only
+    /// reports a total
+    pub processes: Vec<ProcessGpuMemory>,
+}
+
+impl GpuMemoryUsage {
+    /// Bytes used by a given process, if it has any GPU allocations
+    pub fn for_pid(&self, pid: u32) -> Option<u64> {
+        self.processes.iter().find(|p| p.pid == pid).map(|p| p.bytes)
+    }
+}
+
+/// GPU busy percentage from KGSL or the devfreq `load` node
+pub fn read_utilization() -> Option<Reading<f32>> {
+    // Format: "42 %"
+    if let Some(busy) = read_node(Path::new(KGSL_BUSY_PATH)) {
+        if let Ok(percent) = busy.trim_end_matches('%').trim().parse::<f32>() {
+            return Some(Reading::new(percent, Provenance::Sysfs));
+        }
+    }
+
+    // devfreq load format: "<percent>@<freq>Hz"
+    let load = read_node(&devfreq::find_gpu_devfreq()?.join("load"))?;
+    let percent = load.split('@').next()?.trim().parse::<f32>().ok()?;
+    Some(Reading::new(percent, Provenance::Sysfs))
+}
+
+/// GPU temperature in degrees Celsius from the best-bound GPU thermal zone
+///
+/// SoC-wide proxy zones are skipped; see [`gpu_thermal_zones`] to use them.
+pub fn read_temperature() -> Option<Reading<f32>> {
+    gpu_thermal_zones()
+        .iter()
+        .filter(|zone| zone.confidence != thermal::ZoneConfidence::Proxy)
+        .find_map(|zone| zone.read_celsius())
+        .map(|celsius| Reading::new(celsius, Provenance::Sysfs))
+}
+
+/// Total system memory in bytes from /proc/meminfo
+///
+/// Mobile GPUs share system RAM, so this is also the upper bound on GPU
+/// memory.
+pub fn read_total_memory() -> Option<u64> {
+    let meminfo = fs::read_to_string(MEMINFO_PATH).ok()?;
+    meminfo
+        .lines()
+        .find(|line| line.starts_with("MemTotal:"))?
+        .split_whitespace()
+        .nth(1)?
+        .parse::<u64>()
+        .ok()
+        .map(|kb| kb * 1024)
+}
+
+/// Read a node and trim surrounding whitespace, `None` if unreadable or empty
+pub(crate) fn read_node(path: &Path) -> Option<String> {
+    fs::read_to_string(path)
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+/// System page size in bytes
+#[cfg_attr(not(feature = "mali-core"), allow(dead_code))]
+pub(crate) fn page_size() -> u64 {
+    // SAFETY: sysconf has no preconditions
+    let size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+    if size > 0 {
+        size as u64
+    } else {
+        4096
+    }
+}
@@ -0,0 +1,159 @@
+//! GPU power estimation
+//!
+//! [`estimate_gpu_power`] takes the best source available: a GPU rail
+//! sensor ([`hwmon`](super::hwmon)), else the Mali IPA simple power model
+//! from the device tree evaluated at the current clock, OPP voltage,
+//! temperature and utilization, else the battery's discharge power. The
+//! battery feeds the whole device, so that last figure only bounds the
+//! GPU's share from above.
+//!
+//! ```
+//! use armgpuinfo::telemetry::power::{GpuPowerEstimate, ModelInputs};
+//! use armgpuinfo::info::MaliPowerModel;
+//!
+//! let model = MaliPowerModel {
+//!     compatible: "arm,mali-simple-power-model".into(),
+//!     static_coefficient: 2_427_750,
+//!     dynamic_coefficient: 4_687,
+//!     temperature_scaling: [20_000, 2_000, -20, 2],
+//!     thermal_zone: None,
+//! };
+//! let inputs = ModelInputs { freq_hz: 800_000_000, voltage_mv: 800, temp_c: 40.0, utilization: 50.0 };
+//! let estimate = GpuPowerEstimate::from_model(&model, inputs);
+//! assert!((1400.0..1500.0).contains(&estimate.mw_current));
+//! ```
+
+use std::fs;
+use std::path::Path;
+
+use crate::info::MaliPowerModel;
+use crate::monitor::battery::POWER_SUPPLY_ROOT;
+
+use super::hwmon::read_gpu_power;
+use super::read_node;
+
+/// Where a [`GpuPowerEstimate`] came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PowerEstimateSource {
+    /// hwmon sensor on the GPU rail, measured
+    RailSensor,
+    /// IPA power model coefficients, modelled
+    PowerModel,
+    /// Battery discharge power of the whole device, an upper bound
+    Battery,
+}
+
+/// Current GPU power draw
+#[derive(Debug, Clone, PartialEq)]
+pub struct GpuPowerEstimate {
+    /// Power in milliwatts
+    pub mw_current: f32,
+    /// Model coefficients the estimate was computed with, `None` for
+    /// measured sources
+    pub coefficients: Option<MaliPowerModel>,
+    pub source: PowerEstimateSource,
+}
+
+/// Operating point a power model is evaluated at
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ModelInputs {
+    pub freq_hz: u64,
+    pub voltage_mv: u32,
+    pub temp_c: f32,
+    /// GPU busy percentage (0.0 - 100.0)
+    pub utilization: f32,
+}
+
+impl GpuPowerEstimate {
+    /// Evaluate `model` at `inputs`
+    pub fn from_model(model: &MaliPowerModel, inputs: ModelInputs) -> Self {
+        let watts = model.estimate_watts(inputs.freq_hz, inputs.voltage_mv, inputs.temp_c, inputs.utilization);
+        Self {
+            mw_current: watts * 1000.0,
+            coefficients: Some(model.clone()),
+            source: PowerEstimateSource::PowerModel,
+        }
+    }
+
+    /// Power in watts
+    pub fn watts(&self) -> f32 {
+        self.mw_current / 1000.0
+    }
+}
+
+/// Estimate the GPU's current power draw from the best available source
+///
+/// `None` if there is no rail sensor, no usable power model and no
+/// discharging battery.
+pub fn estimate_gpu_power() -> Option<GpuPowerEstimate> {
+    if let Some(reading) = read_gpu_power() {
+        return Some(GpuPowerEstimate {
+            mw_current: reading.value * 1000.0,
+            coefficients: None,
+            source: PowerEstimateSource::RailSensor,
+        });
+    }
+    #[cfg(feature = "mali-core")]
+    if let Some(estimate) = model_estimate() {
+        return Some(estimate);
+    }
+    read_battery_discharge_mw(Path::new(POWER_SUPPLY_ROOT)).map(|mw| GpuPowerEstimate {
+        mw_current: mw,
+        coefficients: None,
+        source: PowerEstimateSource::Battery,
+    })
+}
+
+/// IPA model of the first enabled Mali device-tree node at the current
+/// devfreq clock
+///
+/// Without a temperature reading the static part assumes 25 °C; without a
+/// utilization reading the GPU is taken to be fully busy.
+#[cfg(feature = "mali-core")]
+fn model_estimate() -> Option<GpuPowerEstimate> {
+    use crate::mali::devicetree::find_mali_nodes;
+    use crate::mali::read_power_model;
+
+    let node = find_mali_nodes().into_iter().find(|node| node.enabled)?;
+    let model = read_power_model(&node.path)?;
+    let freq_hz = super::devfreq::find_gpu_devfreq()
+        .and_then(|devfreq| super::devfreq::read_cur_freq_hz(&devfreq))
+        .or_else(|| node.max_freq_hz())?;
+    // Lowest OPP at or above the clock, as devfreq rounds up
+    let microvolt = node
+        .opps
+        .iter()
+        .find(|opp| opp.freq_hz >= freq_hz)
+        .or(node.opps.last())
+        .and_then(|opp| opp.microvolt)?;
+    let inputs = ModelInputs {
+        freq_hz,
+        voltage_mv: microvolt / 1000,
+        temp_c: super::read_temperature().map_or(25.0, |reading| reading.value),
+        utilization: super::read_utilization().map_or(100.0, |reading| reading.value),
+    };
+    Some(GpuPowerEstimate::from_model(&model, inputs))
+}
+
+/// Power drawn from the first discharging battery in the `power_supply`
+/// class directory `root`, in milliwatts
+///
+/// Uses `power_now` where the fuel gauge reports it, else
+/// `current_now`·`voltage_now`. The sign of the current differs between
+/// gauges and is ignored.
+pub fn read_battery_discharge_mw(root: &Path) -> Option<f32> {
+    let mut supplies: Vec<_> = fs::read_dir(root).ok()?.flatten().map(|entry| entry.path()).collect();
+    supplies.sort();
+    supplies.iter().find_map(|supply| {
+        let read = |node: &str| -> Option<f64> { read_node(&supply.join(node))?.parse().ok() };
+        if read_node(&supply.join("type")).as_deref() != Some("Battery")
+            || read_node(&supply.join("status")).as_deref() != Some("Discharging")
+        {
+            return None;
+        }
+        // Microwatts, or microamps times microvolts
+        let microwatts = read("power_now")
+            .or_else(|| Some(read("current_now")?.abs() * read("voltage_now")? / 1e6))?;
+        Some((microwatts.abs() / 1000.0) as f32)
+    })
+}
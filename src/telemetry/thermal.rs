@@ -0,0 +1,140 @@
+//! Thermal zone to GPU binding
+//!
+//! Thermal zone names are chosen by each SoC's device tree: Qualcomm uses
+//! `gpuss-N`, Rockchip and MediaTek `gpu-thermal`, Exynos `G3D`, and some
+//! boards only have SoC-wide zones. Known SoCs are resolved through a table;
+//! everything else falls back to name matching.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::read_node;
+
+/// Default thermal class directory
+pub const THERMAL_ROOT: &str = "/sys/class/thermal";
+
+const DT_COMPATIBLE_PATH: &str = "/proc/device-tree/compatible";
+const SOC_MACHINE_PATH: &str = "/sys/devices/soc0/machine";
+
+/// How a zone was bound to the GPU, best first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ZoneConfidence {
+    /// Listed for this SoC in the binding table
+    SocTable,
+    /// Zone name mentions the GPU
+    Heuristic,
+    /// SoC-wide zone that includes the GPU die area
+    Proxy,
+}
+
+/// A thermal zone resolved to the GPU
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GpuThermalZone {
+    /// Zone directory, e.g. `/sys/class/thermal/thermal_zone12`
+    pub path: PathBuf,
+    /// Contents of the zone's `type` node
+    pub zone_type: String,
+    pub confidence: ZoneConfidence,
+}
+
+impl GpuThermalZone {
+    /// Current temperature in degrees Celsius
+    pub fn read_celsius(&self) -> Option<f32> {
+        let millidegrees: i64 = read_node(&self.path.join("temp"))?.parse().ok()?;
+        Some(millidegrees as f32 / 1000.0)
+    }
+}
+
+/// Zone types per SoC; `soc` is matched as a prefix of the device tree
+/// compatible strings or the socinfo machine name (lowercase), zone names
+/// ending in `*` are prefixes
+struct ZoneBinding {
+    soc: &'static str,
+    zones: &'static [&'static str],
+}
+
+static ZONE_BINDINGS: &[ZoneBinding] = &[
+    ZoneBinding {
+        soc: "qcom,",
+        zones: &["gpuss-*", "gpu-top-thermal", "gpu-bottom-thermal", "gpu0-usr", "gpu1-usr", "gpu-usr"],
+    },
+    ZoneBinding { soc: "sm", zones: &["gpuss-*"] },
+    ZoneBinding { soc: "sdm", zones: &["gpu0-usr", "gpu1-usr", "gpu-usr"] },
+    ZoneBinding { soc: "rockchip,", zones: &["gpu-thermal", "gpu_thermal"] },
+    ZoneBinding { soc: "mediatek,", zones: &["gpu-thermal", "gpu1-thermal", "mtk-gpu"] },
+    ZoneBinding { soc: "samsung,exynos", zones: &["G3D", "gpu-thermal"] },
+    ZoneBinding { soc: "google,gs", zones: &["G3D", "gpu-thermal"] },
+    ZoneBinding { soc: "allwinner,", zones: &["gpu-thermal", "gpu_thermal"] },
+    ZoneBinding { soc: "amlogic,", zones: &["gpu-thermal"] },
+];
+
+/// SoC-wide zones used when nothing GPU-specific exists
+const PROXY_ZONES: &[&str] = &["soc-thermal", "soc_max", "center-thermal", "soc_thermal"];
+
+/// GPU thermal zones on this device, best binding first
+pub fn gpu_thermal_zones() -> Vec<GpuThermalZone> {
+    gpu_thermal_zones_in(Path::new(THERMAL_ROOT), &read_soc_identifiers())
+}
+
+/// [`gpu_thermal_zones`] under a custom class directory for the given SoC
+/// identifiers (device tree compatibles or machine names, lowercase)
+pub fn gpu_thermal_zones_in(root: &Path, soc: &[String]) -> Vec<GpuThermalZone> {
+    let Ok(entries) = fs::read_dir(root) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<PathBuf> = entries.flatten().map(|e| e.path()).collect();
+    paths.sort();
+
+    let table_zones: Vec<&str> = ZONE_BINDINGS
+        .iter()
+        .filter(|binding| soc.iter().any(|id| id.starts_with(binding.soc)))
+        .flat_map(|binding| binding.zones.iter().copied())
+        .collect();
+
+    let mut zones: Vec<GpuThermalZone> = paths
+        .into_iter()
+        .filter_map(|path| {
+            let zone_type = read_node(&path.join("type"))?;
+            let confidence = classify(&zone_type, &table_zones)?;
+            Some(GpuThermalZone { path, zone_type, confidence })
+        })
+        .collect();
+    // Stable sort keeps path order within a confidence level
+    zones.sort_by_key(|zone| zone.confidence);
+    zones
+}
+
+fn classify(zone_type: &str, table_zones: &[&str]) -> Option<ZoneConfidence> {
+    let in_table = table_zones.iter().any(|pattern| match pattern.strip_suffix('*') {
+        Some(prefix) => zone_type.starts_with(prefix),
+        None => zone_type == *pattern,
+    });
+    if in_table {
+        return Some(ZoneConfidence::SocTable);
+    }
+
+    let lower = zone_type.to_ascii_lowercase();
+    if ["gpu", "mali", "g3d", "kgsl"].iter().any(|marker| lower.contains(marker)) {
+        Some(ZoneConfidence::Heuristic)
+    } else if PROXY_ZONES.contains(&lower.as_str()) {
+        Some(ZoneConfidence::Proxy)
+    } else {
+        None
+    }
+}
+
+/// Device tree compatibles and the socinfo machine name, lowercase
+fn read_soc_identifiers() -> Vec<String> {
+    let mut ids: Vec<String> = fs::read(DT_COMPATIBLE_PATH)
+        .map(|raw| {
+            raw.split(|&b| b == 0)
+                .filter(|s| !s.is_empty())
+                .map(|s| String::from_utf8_lossy(s).to_ascii_lowercase())
+                .collect()
+        })
+        .unwrap_or_default();
+    if let Some(machine) = read_node(Path::new(SOC_MACHINE_PATH)) {
+        ids.push(machine.to_ascii_lowercase());
+    }
+    ids
+}
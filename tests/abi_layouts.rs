@@ -0,0 +1,17 @@
+//! UAPI layout checks
+
+#![cfg(feature = "adreno-kgsl")]
+
+use armgpuinfo::abi::{checks, LayoutCheck};
+
+fn expected(item: &str) -> Option<usize> {
+    checks().into_iter().find(|check| check.item == item).map(|check| check.expected)
+}
+
+#[test]
+fn kgsl_devinfo_is_the_kernel_struct() {
+    let (size, gmem_sizebytes) = if cfg!(target_pointer_width = "64") { (40, 32) } else { (24, 20) };
+    assert_eq!(expected("kgsl_devinfo"), Some(size));
+    assert_eq!(expected("kgsl_devinfo.gmem_sizebytes"), Some(gmem_sizebytes));
+    assert!(checks().iter().all(LayoutCheck::matches));
+}
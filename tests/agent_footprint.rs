@@ -0,0 +1,44 @@
+//! Binary-size budget of the `agent` profile
+//!
+//! Builds `agent_probe` and `agent_baseline` with the size-optimized
+//! `agent` profile in a separate target directory and checks that the
+//! library adds less than `FOOTPRINT_BUDGET_BYTES` to the binary.
+
+use std::path::Path;
+use std::process::Command;
+
+/// Mirrors `armgpuinfo::agent::FOOTPRINT_BUDGET_BYTES`, which is only
+/// compiled with the `agent` feature
+const FOOTPRINT_BUDGET_BYTES: u64 = 100 * 1024;
+
+#[test]
+fn agent_profile_fits_budget() {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let target_dir = manifest_dir.join("target").join("agent-footprint");
+
+    let status = Command::new(env!("CARGO"))
+        .current_dir(manifest_dir)
+        .args(["build", "--quiet", "--profile", "agent", "--no-default-features"])
+        .args(["--features", "agent,mali-kbase,adreno-kgsl"])
+        .args(["--example", "agent_probe", "--example", "agent_baseline"])
+        .arg("--target-dir")
+        .arg(&target_dir)
+        .status()
+        .expect("failed to run cargo");
+    assert!(status.success(), "building the agent examples failed");
+
+    let size = |name: &str| {
+        let path = target_dir.join("agent").join("examples").join(name);
+        std::fs::metadata(&path)
+            .unwrap_or_else(|e| panic!("{}: {e}", path.display()))
+            .len()
+    };
+    let probe = size("agent_probe");
+    let baseline = size("agent_baseline");
+    let footprint = probe.saturating_sub(baseline);
+
+    assert!(
+        footprint < FOOTPRINT_BUDGET_BYTES,
+        "agent profile adds {footprint} bytes ({probe} - {baseline}), budget is {FOOTPRINT_BUDGET_BYTES}"
+    );
+}
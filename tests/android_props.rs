@@ -0,0 +1,30 @@
+//! GPU identification from Android platform properties
+
+#![cfg(all(feature = "android-props", feature = "mali-core", feature = "adreno-core"))]
+
+use armgpuinfo::android_props::{gpu_info_for_platform, KNOWN_PLATFORMS};
+use armgpuinfo::{DataConfidence, GpuError, GpuVendor};
+
+#[test]
+fn every_known_platform_resolves() {
+    for known in KNOWN_PLATFORMS {
+        let info = gpu_info_for_platform(known.platform, None).unwrap();
+        assert_eq!(info.vendor, known.vendor(), "{}", known.platform);
+        assert_eq!(info.gpu_name, known.gpu_name);
+        assert_eq!(info.confidence, DataConfidence::Heuristic);
+    }
+}
+
+#[test]
+fn mali_platforms_use_the_stock_core_count() {
+    let info = gpu_info_for_platform("GS101", Some("mali")).unwrap();
+    assert_eq!(info.vendor, GpuVendor::Mali);
+    assert_eq!(info.gpu_name, "Mali-G78");
+    assert_eq!(info.num_shader_cores, 20);
+}
+
+#[test]
+fn conflicting_or_unknown_platforms_are_rejected() {
+    assert!(matches!(gpu_info_for_platform("kona", Some("mali")), Err(GpuError::InvalidData(_))));
+    assert!(matches!(gpu_info_for_platform("rk3588", None), Err(GpuError::InvalidData(_))));
+}
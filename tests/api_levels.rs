@@ -0,0 +1,64 @@
+//! Graphics API levels from the database entries
+
+use armgpuinfo::ApiVersion;
+#[cfg(any(feature = "mali-core", feature = "adreno-core"))]
+use armgpuinfo::GpuInfo;
+#[cfg(feature = "mali-core")]
+use armgpuinfo::MaliArch;
+
+#[cfg(feature = "mali-core")]
+fn mali(gpu_id: u32, cores: u32) -> GpuInfo {
+    GpuInfo::builder()
+        .gpu_id(gpu_id)
+        .raw_gpu_id(u64::from(gpu_id) << 16)
+        .gpu_name("Mali")
+        .architecture("Mali")
+        .architecture_major((gpu_id >> 12) as u8)
+        .architecture_minor(0)
+        .num_shader_cores(cores)
+        .num_l2_bytes(512 << 10)
+        .mali_arch(MaliArch::Valhall)
+        .build()
+        .unwrap()
+}
+
+#[cfg(feature = "mali-core")]
+#[test]
+fn mali_products() {
+    // T604: no Vulkan driver
+    let t604 = mali(0x6956, 4);
+    assert_eq!(t604.max_vulkan_version(), None);
+    assert_eq!(t604.max_gles_version(), Some(ApiVersion::new(3, 1)));
+
+    let g710 = mali(0xa002, 10);
+    assert_eq!(g710.max_vulkan_version(), Some(ApiVersion::new(1, 3)));
+    assert_eq!(g710.max_gles_version(), Some(ApiVersion::new(3, 2)));
+
+    // Newer than the database: the nearest family member's levels
+    assert_eq!(mali(0xa00f, 10).max_vulkan_version(), Some(ApiVersion::new(1, 3)));
+}
+
+#[cfg(feature = "adreno-core")]
+#[test]
+fn adreno_chips() {
+    let adreno = |chip_id: u32| {
+        GpuInfo::adreno_builder()
+            .chip_id(chip_id)
+            .gpu_name("Adreno")
+            .architecture("Adreno")
+            .num_shader_cores(2)
+            .gmem_bytes(1 << 20)
+            .build()
+            .unwrap()
+    };
+    assert_eq!(adreno(0x0401_0000).max_vulkan_version(), None);
+    assert_eq!(adreno(0x0601_0001).max_vulkan_version(), Some(ApiVersion::new(1, 1)));
+    assert_eq!(adreno(0x0706_0001).max_gles_version(), Some(ApiVersion::new(3, 2)));
+}
+
+#[test]
+fn versions_order_and_print() {
+    assert!(ApiVersion::new(1, 3) > ApiVersion::new(1, 1));
+    assert!(ApiVersion::new(3, 2) > ApiVersion::new(2, 9));
+    assert_eq!(ApiVersion::new(1, 3).to_string(), "1.3");
+}
@@ -0,0 +1,182 @@
+//! Arrow and Parquet export of monitor samples and summaries
+
+#![cfg(feature = "arrow")]
+
+use std::fs::File;
+use std::time::Duration;
+
+use arrow_array::cast::AsArray;
+use arrow_array::types::{Float32Type, Float64Type, UInt32Type, UInt64Type};
+use arrow_array::{Array, RecordBatch};
+use arrow_schema::DataType;
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+use armgpuinfo::monitor::arrow::{
+    sample_schema, samples_to_record_batch, summary_schema, summary_to_record_batch, write_ipc,
+    write_parquet,
+};
+use armgpuinfo::monitor::{MonitorSummary, PowerState, Sample};
+use armgpuinfo::telemetry::{Provenance, Reading};
+
+fn samples() -> Vec<Sample> {
+    vec![
+        Sample {
+            elapsed: Duration::from_millis(0),
+            freq_mhz: Some(585),
+            utilization: Some(Reading::new(40.0, Provenance::Sysfs)),
+            busy_time: None,
+            temperature_c: Some(Reading::new(45.5, Provenance::Hwmon)),
+            power_watts: None,
+            throttled: Some(false),
+            power_state: PowerState::External,
+        },
+        // Nothing readable but the timestamp and power state
+        Sample {
+            elapsed: Duration::from_millis(100),
+            freq_mhz: None,
+            utilization: None,
+            busy_time: None,
+            temperature_c: None,
+            power_watts: None,
+            throttled: None,
+            power_state: PowerState::Battery,
+        },
+        Sample {
+            elapsed: Duration::from_millis(200),
+            freq_mhz: Some(848),
+            utilization: Some(Reading::new(90.0, Provenance::Debugfs)),
+            busy_time: None,
+            temperature_c: Some(Reading::new(52.0, Provenance::Hwmon)),
+            power_watts: None,
+            throttled: Some(true),
+            power_state: PowerState::Battery,
+        },
+    ]
+}
+
+fn read_ipc(path: &std::path::Path) -> Vec<RecordBatch> {
+    let reader = arrow_ipc::reader::FileReader::try_new(File::open(path).unwrap(), None).unwrap();
+    reader.map(Result::unwrap).collect()
+}
+
+#[test]
+fn schemas() {
+    let samples = sample_schema();
+    let names: Vec<&str> = samples.fields().iter().map(|f| f.name().as_str()).collect();
+    assert_eq!(
+        names,
+        [
+            "elapsed_us",
+            "freq_mhz",
+            "utilization",
+            "utilization_source",
+            "temperature_c",
+            "temperature_source",
+            "power_watts",
+            "power_source",
+            "throttled",
+            "power_state",
+        ]
+    );
+    // Only the timestamp and power state are always known
+    for field in samples.fields() {
+        let required = matches!(field.name().as_str(), "elapsed_us" | "power_state");
+        assert_eq!(field.is_nullable(), !required, "{}", field.name());
+    }
+    assert_eq!(samples.field_with_name("freq_mhz").unwrap().data_type(), &DataType::UInt32);
+    assert_eq!(samples.field_with_name("utilization").unwrap().data_type(), &DataType::Float32);
+
+    let summary = summary_schema();
+    let names: Vec<&str> = summary.fields().iter().map(|f| f.name().as_str()).collect();
+    assert_eq!(names, ["metric", "count", "min", "mean", "p50", "p95", "max"]);
+    assert!(summary.fields().iter().all(|f| !f.is_nullable()));
+
+    let batch = samples_to_record_batch(&self::samples()).unwrap();
+    assert_eq!(batch.schema().as_ref(), &sample_schema());
+}
+
+#[test]
+fn missing_readings_are_null() {
+    let batch = samples_to_record_batch(&samples()).unwrap();
+    assert_eq!(batch.num_rows(), 3);
+
+    let elapsed = batch.column_by_name("elapsed_us").unwrap().as_primitive::<UInt64Type>();
+    assert_eq!(elapsed.values().as_ref(), [0, 100_000, 200_000]);
+
+    let freq = batch.column_by_name("freq_mhz").unwrap().as_primitive::<UInt32Type>();
+    assert_eq!(freq.iter().collect::<Vec<_>>(), [Some(585), None, Some(848)]);
+
+    let util = batch.column_by_name("utilization").unwrap().as_primitive::<Float32Type>();
+    assert_eq!(util.iter().collect::<Vec<_>>(), [Some(40.0), None, Some(90.0)]);
+    let util_src = batch.column_by_name("utilization_source").unwrap().as_string::<i32>();
+    assert_eq!(util_src.iter().collect::<Vec<_>>(), [Some("sysfs"), None, Some("debugfs")]);
+
+    // Never available: all null, not zero
+    let power = batch.column_by_name("power_watts").unwrap();
+    assert_eq!(power.null_count(), 3);
+    assert_eq!(batch.column_by_name("power_source").unwrap().null_count(), 3);
+
+    let throttled = batch.column_by_name("throttled").unwrap().as_boolean();
+    assert_eq!(throttled.iter().collect::<Vec<_>>(), [Some(false), None, Some(true)]);
+
+    let state = batch.column_by_name("power_state").unwrap().as_string::<i32>();
+    assert_eq!(state.null_count(), 0);
+    assert_eq!(state.value(1), "battery");
+}
+
+#[test]
+fn summary_rows_skip_missing_metrics() {
+    let samples = samples();
+    let summary = MonitorSummary::from_samples(&samples, Duration::from_secs(1));
+    let batch = summary_to_record_batch(&summary).unwrap();
+
+    let metrics = batch.column_by_name("metric").unwrap().as_string::<i32>();
+    assert_eq!(
+        metrics.iter().flatten().collect::<Vec<_>>(),
+        ["freq_mhz", "utilization", "temperature_c", "throttle_pct"]
+    );
+    let count = batch.column_by_name("count").unwrap().as_primitive::<UInt64Type>();
+    assert_eq!(count.values().as_ref(), [2, 2, 2, 3]);
+    let max = batch.column_by_name("max").unwrap().as_primitive::<Float64Type>();
+    assert_eq!(max.value(0), 848.0);
+    // One of two known throttle states was capped
+    assert_eq!(max.value(3), 50.0);
+}
+
+#[test]
+fn ipc_round_trip() {
+    let dir = tempfile::tempdir().unwrap();
+    let samples = samples();
+
+    let batch = samples_to_record_batch(&samples).unwrap();
+    let path = dir.path().join("samples.arrow");
+    write_ipc(&path, &batch).unwrap();
+    assert_eq!(read_ipc(&path), [batch]);
+
+    let summary = MonitorSummary::from_samples(&samples, Duration::from_secs(1));
+    let batch = summary_to_record_batch(&summary).unwrap();
+    let path = dir.path().join("summary.arrow");
+    write_ipc(&path, &batch).unwrap();
+    assert_eq!(read_ipc(&path), [batch]);
+}
+
+#[test]
+fn parquet_round_trip() {
+    let dir = tempfile::tempdir().unwrap();
+    let batch = samples_to_record_batch(&samples()).unwrap();
+    let path = dir.path().join("samples.parquet");
+    write_parquet(&path, &batch).unwrap();
+
+    let reader = ParquetRecordBatchReaderBuilder::try_new(File::open(&path).unwrap())
+        .unwrap()
+        .build()
+        .unwrap();
+    let read: Vec<RecordBatch> = reader.map(Result::unwrap).collect();
+    assert_eq!(read.len(), 1);
+    assert_eq!(read[0].num_rows(), 3);
+    assert_eq!(read[0].column_by_name("power_watts").unwrap().null_count(), 3);
+    assert_eq!(
+        read[0].column_by_name("freq_mhz").unwrap().as_primitive::<UInt32Type>().value(2),
+        848
+    );
+}
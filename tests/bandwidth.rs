@@ -0,0 +1,57 @@
+//! Memory bandwidth estimates
+
+#![cfg(feature = "adreno-core")]
+
+use armgpuinfo::adreno::{find_adreno_specs, MemoryType};
+use armgpuinfo::GpuInfo;
+
+fn adreno(chip_id: u32, bus_bits: u64) -> GpuInfo {
+    GpuInfo::adreno_builder()
+        .chip_id(chip_id)
+        .gpu_name("Adreno")
+        .architecture("Adreno")
+        .num_shader_cores(2)
+        .gmem_bytes(1 << 20)
+        .num_bus_bits(bus_bits)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn adreno_uses_database_memory() {
+    let a740 = find_adreno_specs(0x0706_0001).unwrap();
+    assert_eq!(a740.memory.memory_type, MemoryType::Lpddr5x);
+    assert_eq!(a740.memory.memory_type.to_string(), "LPDDR5X");
+
+    // 8 bytes per transfer at 8400 MT/s
+    let gbps = adreno(0x0706_0001, 64).estimated_bandwidth_gbps().unwrap();
+    assert!((gbps - 67.2).abs() < 1e-9);
+}
+
+#[test]
+fn unknown_bus_width_gives_nothing() {
+    assert_eq!(adreno(0x0706_0001, 0).estimated_bandwidth_gbps(), None);
+    assert_eq!(adreno(0x0706_0001, 0).estimated_bandwidth_gbps_with_clock(6_400_000_000), None);
+}
+
+#[test]
+fn budget_needs_a_memory_rate() {
+    use armgpuinfo::BudgetProfile;
+
+    let budget = adreno(0x0706_0001, 64).suggest_memory_budget_with_ram(BudgetProfile::Balanced, 8 << 30);
+    assert_eq!(budget.est_bandwidth_bytes_per_sec, Some(67_200_000_000));
+
+    // Bus width and GPU clock alone don't make a memory bandwidth
+    let a330 = GpuInfo::adreno_builder()
+        .chip_id(0x0303_0000)
+        .gpu_name("Adreno 330")
+        .architecture("Adreno 3xx")
+        .num_shader_cores(1)
+        .gmem_bytes(1 << 20)
+        .num_bus_bits(64)
+        .max_freq_mhz(578)
+        .build()
+        .unwrap();
+    let budget = a330.suggest_memory_budget_with_ram(BudgetProfile::Balanced, 8 << 30);
+    assert_eq!(budget.est_bandwidth_bytes_per_sec, None);
+}
@@ -0,0 +1,62 @@
+//! Measured max-frequency calibration against a fake devfreq node
+
+use std::time::Duration;
+
+use armgpuinfo::telemetry::calibration::{calibrate_max_freq, CalibrationConfig};
+use armgpuinfo::telemetry::Provenance;
+use armgpuinfo::GpuInfo;
+
+fn calibrate_at(hz: u64) -> armgpuinfo::telemetry::calibration::MaxFreqCalibration {
+    let devfreq = tempfile::tempdir().unwrap();
+    std::fs::write(devfreq.path().join("cur_freq"), format!("{hz}\n")).unwrap();
+    let config = CalibrationConfig {
+        window: Duration::from_millis(50),
+        interval: Duration::from_millis(5),
+        sustain: Duration::from_millis(10),
+        devfreq: Some(devfreq.path().to_path_buf()),
+    };
+    calibrate_max_freq(&config).unwrap()
+}
+
+#[test]
+fn calibration_changes_mali_output() {
+    let mut info = GpuInfo::builder()
+        .gpu_id(0x9093)
+        .raw_gpu_id(0x9093_0000)
+        .gpu_name("Mali-G57")
+        .architecture("Valhall")
+        .architecture_major(9)
+        .architecture_minor(0)
+        .num_shader_cores(3)
+        .num_l2_bytes(512 << 10)
+        .build()
+        .unwrap();
+    assert_eq!(info.mali_data.as_ref().unwrap().measured_max_freq_mhz, None);
+
+    let calibration = calibrate_at(848_000_000);
+    assert_eq!(calibration.sustained_max_mhz.value, 848);
+    assert_eq!(calibration.sustained_max_mhz.provenance, Provenance::Measured);
+    calibration.apply(&mut info);
+
+    assert_eq!(info.mali_data.as_ref().unwrap().measured_max_freq_mhz, Some(848));
+}
+
+#[test]
+fn calibration_changes_adreno_output() {
+    let mut info = GpuInfo::adreno_builder()
+        .chip_id(0x0704_0001)
+        .gpu_name("Adreno 740")
+        .architecture("Adreno")
+        .num_shader_cores(2)
+        .gmem_bytes(3 << 20)
+        .max_freq_mhz(719)
+        .build()
+        .unwrap();
+
+    calibrate_at(680_000_000).apply(&mut info);
+
+    let adreno = info.adreno_data.as_ref().unwrap();
+    assert_eq!(adreno.measured_max_freq_mhz, Some(680));
+    // The database boost clock is kept alongside
+    assert_eq!(adreno.max_freq_mhz, 719);
+}
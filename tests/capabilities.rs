@@ -0,0 +1,70 @@
+//! Capabilities derived from product and architecture
+
+use armgpuinfo::{GpuCapabilities, GpuInfo, MaliArch};
+
+fn mali(name: &str, gpu_id: u32, arch: MaliArch) -> GpuInfo {
+    GpuInfo::builder()
+        .gpu_id(gpu_id)
+        .raw_gpu_id(u64::from(gpu_id) << 16)
+        .gpu_name(name.to_string())
+        .architecture(arch.as_str())
+        .architecture_major((gpu_id >> 12) as u8)
+        .architecture_minor(0)
+        .num_shader_cores(10)
+        .num_l2_bytes(512 << 10)
+        .mali_arch(arch)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn mali_generations() {
+    let g71 = mali("Mali-G71", 0x6000, MaliArch::Bifrost).capabilities;
+    assert!(g71.fp16 && g71.afbc && g71.tile_based_rendering);
+    assert!(!g71.int8_dot_product && !g71.variable_rate_shading);
+
+    let g76 = mali("Mali-G76", 0x7001, MaliArch::Bifrost).capabilities;
+    assert!(g76.int8_dot_product);
+
+    let g710 = mali("Mali-G710", 0xa002, MaliArch::Valhall).capabilities;
+    assert!(g710.int8_dot_product);
+    assert!(!g710.variable_rate_shading && !g710.ray_tracing);
+
+    let g715 = mali("Immortalis-G715", 0xb002, MaliArch::Valhall).capabilities;
+    assert!(g715.variable_rate_shading && g715.ray_tracing);
+    assert!(!g715.ubwc);
+}
+
+#[test]
+fn adreno_series() {
+    let adreno = |chip_id: u32, name: &str| {
+        GpuInfo::adreno_builder()
+            .chip_id(chip_id)
+            .gpu_name(name.to_string())
+            .architecture(format!("Adreno {}xx", chip_id >> 24))
+            .num_shader_cores(2)
+            .gmem_bytes(1 << 20)
+            .build()
+            .unwrap()
+            .capabilities
+    };
+    let a530 = adreno(0x0503_0000, "Adreno 530");
+    assert!(a530.fp16 && a530.ubwc && a530.tile_based_rendering);
+    assert!(!a530.variable_rate_shading);
+
+    let a660 = adreno(0x0606_0000, "Adreno 660");
+    assert!(a660.variable_rate_shading && !a660.ray_tracing);
+    assert!(!a660.afbc);
+
+    // Unknown variants fall back to the series
+    let a8xx = adreno(0x0800_0000, "Adreno 8xx (unknown variant)");
+    assert!(a8xx.ray_tracing && a8xx.int8_dot_product);
+}
+
+#[test]
+fn supports_fp16_follows_capabilities() {
+    let mut info = mali("Mali-G52", 0x7002, MaliArch::Bifrost);
+    assert!(info.supports_fp16());
+    info.capabilities = GpuCapabilities::default();
+    assert!(!info.supports_fp16());
+}
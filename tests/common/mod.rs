@@ -0,0 +1,33 @@
+//! Fixtures shared by the integration tests
+
+#![allow(dead_code)]
+
+#[cfg(feature = "adreno-kgsl")]
+use armgpuinfo::adreno::KgslDeviceInfo;
+
+/// kbase record: key (property ID << 2 | size code) and little-endian value
+pub fn prop_u32(id: u32, value: u32) -> Vec<u8> {
+    [((id << 2) | 2).to_le_bytes(), value.to_le_bytes()].concat()
+}
+
+/// 64-bit kbase record
+pub fn prop_u64(id: u32, value: u64) -> Vec<u8> {
+    [((id << 2) | 3).to_le_bytes().as_slice(), &value.to_le_bytes()].concat()
+}
+
+/// Mali-G610 MC4
+pub fn g610() -> Vec<u8> {
+    g610_with_cores(4)
+}
+
+/// Mali-G610 with `cores` shader cores
+pub fn g610_with_cores(cores: u32) -> Vec<u8> {
+    let mask = (1u32 << cores) - 1;
+    [prop_u32(1, 0xa867), prop_u32(14, 18), prop_u32(15, 1), prop_u32(62, 1), prop_u32(64, mask)].concat()
+}
+
+/// Adreno 740 with 2 MiB of GMEM
+#[cfg(feature = "adreno-kgsl")]
+pub fn a740() -> KgslDeviceInfo {
+    KgslDeviceInfo { chip_id: 0x0706_0001, gmem_sizebytes: 2 << 20, ..Default::default() }
+}
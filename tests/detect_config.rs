@@ -0,0 +1,107 @@
+//! Detection order and node lists answered by the mock backend
+
+#![cfg(all(feature = "mock", feature = "mali-kbase", feature = "adreno-kgsl", feature = "auto-detect"))]
+
+use armgpuinfo::detect::{
+    enumerate_gpus, query_gpu_with_config, query_gpu_with_options, DetectConfig, DetectOptions,
+};
+use armgpuinfo::mock::MockGpuBackend;
+use armgpuinfo::{GpuError, GpuVendor};
+
+mod common;
+use common::{a740, g610};
+
+#[test]
+fn kgsl_subdirectory_is_a_default_path() {
+    let _mock = MockGpuBackend::new().adreno_device_info("/dev/kgsl/kgsl-3d0", a740()).install();
+
+    let info = query_gpu_with_config(&DetectConfig::new()).unwrap();
+    assert_eq!(info.gpu_name, "Adreno 740");
+}
+
+#[test]
+fn preferred_vendor_is_tried_first() {
+    let _mock = MockGpuBackend::new()
+        .mali("/dev/mali0", g610())
+        .adreno_device_info("/dev/kgsl-3d0", a740())
+        .install();
+
+    let mali = query_gpu_with_config(&DetectConfig::new()).unwrap();
+    assert_eq!(mali.vendor, GpuVendor::Mali);
+    let adreno = query_gpu_with_config(&DetectConfig::new().prefer([GpuVendor::Adreno])).unwrap();
+    assert_eq!(adreno.vendor, GpuVendor::Adreno);
+}
+
+#[test]
+fn other_preferred_vendors_keep_the_defaults() {
+    let _mock = MockGpuBackend::new().adreno_device_info("/dev/kgsl-3d0", a740()).install();
+
+    for prefer in [GpuVendor::Vivante, GpuVendor::Unknown] {
+        let info = query_gpu_with_config(&DetectConfig::new().prefer([prefer])).unwrap();
+        assert_eq!(info.gpu_name, "Adreno 740", "{prefer:?}");
+    }
+}
+
+#[test]
+fn device_list_replaces_the_defaults() {
+    let _mock = MockGpuBackend::new()
+        .mali("/dev/mali0", g610())
+        .mali("/dev/mali1", g610())
+        .install();
+
+    let info = query_gpu_with_config(&DetectConfig::new().devices("/dev/mali1")).unwrap();
+    assert_eq!(info.mali_data.unwrap().instance, Some(1));
+
+    let missing = query_gpu_with_config(&DetectConfig::new().devices("/dev/mali7"));
+    assert!(matches!(missing, Err(GpuError::DeviceNotFound)));
+}
+
+#[test]
+fn scanning_finds_other_instances() {
+    let _mock = MockGpuBackend::new().mali("/dev/mali2", g610()).install();
+
+    let config = DetectConfig { mali_paths: Vec::new(), ..DetectConfig::new() };
+    assert!(query_gpu_with_config(&config).is_ok());
+    assert!(query_gpu_with_config(&config.scan_dev(false)).is_err());
+}
+
+#[test]
+fn failures_of_every_node_are_reported() {
+    let _mock = MockGpuBackend::new().denied("/dev/mali0").denied("/dev/kgsl-3d0").install();
+
+    let error = armgpuinfo::query_gpu_auto(None::<&str>).unwrap_err();
+    let GpuError::DetectionFailed { attempts } = &error else {
+        panic!("{error:?}");
+    };
+    let tried: Vec<_> = attempts.iter().map(|(vendor, path, _)| (*vendor, path.to_str().unwrap())).collect();
+    assert_eq!(tried, [(GpuVendor::Mali, "/dev/mali0"), (GpuVendor::Adreno, "/dev/kgsl-3d0")]);
+    assert!(error.is_permission_error());
+    assert!(error.to_string().contains("/dev/kgsl-3d0 (adreno)"));
+}
+
+#[test]
+fn options_and_enumeration_report_every_node() {
+    let _mock = MockGpuBackend::new().denied("/dev/mali0").denied("/dev/kgsl-3d0").install();
+
+    for error in [
+        query_gpu_with_options(&DetectOptions::new()).unwrap_err(),
+        enumerate_gpus().unwrap_err(),
+    ] {
+        let GpuError::DetectionFailed { attempts } = &error else {
+            panic!("{error:?}");
+        };
+        let tried: Vec<_> = attempts.iter().map(|(vendor, path, _)| (*vendor, path.to_str().unwrap())).collect();
+        assert_eq!(tried, [(GpuVendor::Mali, "/dev/mali0"), (GpuVendor::Adreno, "/dev/kgsl-3d0")]);
+    }
+
+    // An explicit instance still reports its own error
+    let error = query_gpu_with_options(&DetectOptions::new().mali_instance(0)).unwrap_err();
+    assert!(matches!(error, GpuError::PermissionDenied), "{error:?}");
+}
+
+#[test]
+fn missing_nodes_are_not_found() {
+    let _mock = MockGpuBackend::new().install();
+    assert!(matches!(query_gpu_with_options(&DetectOptions::new()), Err(GpuError::DeviceNotFound)));
+    assert!(matches!(enumerate_gpus(), Err(GpuError::DeviceNotFound)));
+}
@@ -0,0 +1,40 @@
+//! inotify watch on GPU device nodes
+
+#![cfg(all(feature = "auto-detect", feature = "mali-kbase"))]
+
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::sync::mpsc;
+use std::time::Duration;
+
+use armgpuinfo::detect::{watch_in, DeviceEvent};
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+#[test]
+fn gpu_nodes_are_reported() {
+    let dev = tempfile::tempdir().unwrap();
+    let (tx, rx) = mpsc::channel();
+    let watch = watch_in(dev.path(), move |event| tx.send(event).unwrap()).unwrap();
+
+    // Not a GPU node
+    fs::write(dev.path().join("null"), "").unwrap();
+    let mali = dev.path().join("mali0");
+    fs::write(&mali, "").unwrap();
+    assert_eq!(rx.recv_timeout(TIMEOUT).unwrap(), DeviceEvent::Added(mali.clone()));
+
+    fs::set_permissions(&mali, fs::Permissions::from_mode(0o666)).unwrap();
+    assert_eq!(rx.recv_timeout(TIMEOUT).unwrap(), DeviceEvent::PermissionsChanged(mali.clone()));
+
+    fs::remove_file(&mali).unwrap();
+    assert_eq!(rx.recv_timeout(TIMEOUT).unwrap(), DeviceEvent::Removed(mali));
+
+    watch.stop();
+    assert!(rx.try_recv().is_err());
+}
+
+#[test]
+fn missing_root_fails() {
+    let dev = tempfile::tempdir().unwrap();
+    assert!(watch_in(dev.path().join("missing"), |_| {}).is_err());
+}
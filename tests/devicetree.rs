@@ -0,0 +1,76 @@
+//! Mali GPU nodes from a device tree
+
+#![cfg(feature = "mali-core")]
+
+use std::fs;
+use std::path::Path;
+
+use armgpuinfo::mali::devicetree::{find_mali_nodes_in, DtOpp};
+use armgpuinfo::MaliArch;
+
+fn property(node: &Path, name: &str, value: impl AsRef<[u8]>) {
+    fs::create_dir_all(node).unwrap();
+    fs::write(node.join(name), value).unwrap();
+}
+
+fn cells(values: &[u32]) -> Vec<u8> {
+    values.iter().flat_map(|v| v.to_be_bytes()).collect()
+}
+
+#[test]
+fn node_with_opp_table() {
+    let root = tempfile::tempdir().unwrap();
+    let soc = root.path().join("soc");
+    property(&soc, "#address-cells", cells(&[1]));
+    property(&soc, "#size-cells", cells(&[1]));
+
+    let gpu = soc.join("gpu@13000000");
+    property(&gpu, "compatible", "mediatek,mt8183-mali\0arm,mali-g72\0");
+    property(&gpu, "reg", cells(&[0x1304_0000, 0x4000]));
+    property(&gpu, "interrupts", cells(&[0, 280, 4, 0, 279, 4, 0, 278, 4]));
+    property(&gpu, "interrupt-names", "job\0mmu\0gpu\0");
+    property(&gpu, "operating-points-v2", cells(&[0x42]));
+
+    let table = root.path().join("opp-table-gpu");
+    property(&table, "phandle", cells(&[0x42]));
+    property(&table.join("opp-800000000"), "opp-hz", cells(&[0, 800_000_000]));
+    property(&table.join("opp-800000000"), "opp-microvolt", cells(&[825_000]));
+    property(&table.join("opp-300000000"), "opp-hz", cells(&[0, 300_000_000]));
+
+    let nodes = find_mali_nodes_in(root.path());
+    assert_eq!(nodes.len(), 1);
+    let node = &nodes[0];
+    assert!(node.enabled);
+    assert_eq!(node.reg, [(0x1304_0000, 0x4000)]);
+    assert_eq!(node.interrupts.len(), 9);
+    assert_eq!(node.interrupt_names, ["job", "mmu", "gpu"]);
+    assert_eq!(
+        node.opps,
+        [
+            DtOpp { freq_hz: 300_000_000, microvolt: None },
+            DtOpp { freq_hz: 800_000_000, microvolt: Some(825_000) },
+        ]
+    );
+    assert_eq!(node.max_freq_hz(), Some(800_000_000));
+    assert_eq!(node.product().map(|p| p.name), Some("Mali-G72"));
+    assert_eq!(node.architecture(), Some(MaliArch::Bifrost));
+}
+
+#[test]
+fn legacy_operating_points_and_disabled_nodes() {
+    let root = tempfile::tempdir().unwrap();
+    let gpu = root.path().join("gpu@ff9a0000");
+    property(&gpu, "compatible", "rockchip,rk3399-mali\0arm,mali-t860\0");
+    property(&gpu, "status", "disabled\0");
+    property(&gpu, "operating-points", cells(&[800_000, 1_100_000, 200_000, 825_000]));
+    // Not a GPU
+    property(&root.path().join("display@ff8f0000"), "compatible", "rockchip,rk3399-vop-lit\0");
+
+    let nodes = find_mali_nodes_in(root.path());
+    assert_eq!(nodes.len(), 1);
+    assert!(!nodes[0].enabled);
+    assert_eq!(nodes[0].opps[0], DtOpp { freq_hz: 200_000_000, microvolt: Some(825_000) });
+    assert_eq!(nodes[0].architecture(), Some(MaliArch::Midgard));
+
+    assert!(find_mali_nodes_in("/nonexistent").is_empty());
+}
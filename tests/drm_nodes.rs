@@ -0,0 +1,44 @@
+//! Render node enumeration with the driver taken from sysfs
+
+use std::fs;
+use std::os::unix::fs::symlink;
+
+use armgpuinfo::drm::{driver_vendor, enumerate_render_nodes_in};
+use armgpuinfo::GpuVendor;
+
+#[test]
+fn driver_comes_from_sysfs_when_the_ioctl_fails() {
+    let root = tempfile::tempdir().unwrap();
+    let dri = root.path().join("dev/dri");
+    fs::create_dir_all(&dri).unwrap();
+    // Plain files: the version ioctl fails on them
+    for node in ["card0", "renderD128", "renderD129"] {
+        fs::write(dri.join(node), "").unwrap();
+    }
+
+    let device = root.path().join("sys/devices/platform/fb000000.gpu");
+    fs::create_dir_all(&device).unwrap();
+    symlink(root.path().join("sys/bus/platform/drivers/panfrost"), device.join("driver")).unwrap();
+    symlink(root.path().join("sys/bus/platform"), device.join("subsystem")).unwrap();
+    let class = root.path().join("sys/class/drm");
+    fs::create_dir_all(class.join("renderD128")).unwrap();
+    symlink(&device, class.join("renderD128/device")).unwrap();
+
+    // renderD129 has no sysfs entry and is left out
+    let nodes = enumerate_render_nodes_in(&dri, &class);
+    assert_eq!(nodes.len(), 1);
+    let node = &nodes[0];
+    assert_eq!(node.path, dri.join("renderD128"));
+    assert_eq!(node.driver, "panfrost");
+    assert_eq!(node.vendor(), GpuVendor::Mali);
+    assert_eq!(node.bus_id.as_deref(), Some("platform:fb000000.gpu"));
+    assert_eq!(node.version, None);
+}
+
+#[test]
+fn drivers_map_to_vendors() {
+    assert_eq!(driver_vendor("msm"), GpuVendor::Adreno);
+    assert_eq!(driver_vendor("panthor"), GpuVendor::Mali);
+    assert_eq!(driver_vendor("etnaviv"), GpuVendor::Vivante);
+    assert_eq!(driver_vendor("amdgpu"), GpuVendor::Unknown);
+}
@@ -0,0 +1,25 @@
+//! GPU identification from renderer strings
+
+#![cfg(all(feature = "egl", feature = "mali-core"))]
+
+use armgpuinfo::egl::gpu_info_from_renderer;
+use armgpuinfo::{DataConfidence, GpuError, GpuVendor};
+
+#[test]
+fn mali_renderer_resolves_with_core_count() {
+    let info = gpu_info_from_renderer("ANGLE (ARM, Mali-G78 MP20, OpenGL ES 3.2)").unwrap();
+    assert_eq!(info.vendor, GpuVendor::Mali);
+    assert_eq!(info.gpu_name, "Mali-G78");
+    assert_eq!(info.num_shader_cores, 20);
+    assert_eq!(info.confidence, DataConfidence::ApiReported);
+    let mali = info.mali_data.unwrap();
+    assert_eq!(mali.shader_core_mask.count_ones(), 20);
+    assert_eq!(mali.core_groups.len(), 1);
+}
+
+#[test]
+fn unknown_renderers_are_rejected() {
+    for renderer in ["llvmpipe (LLVM 17.0.6, 256 bits)", "Mali-G9999"] {
+        assert!(matches!(gpu_info_from_renderer(renderer), Err(GpuError::InvalidData(_))), "{renderer}");
+    }
+}
@@ -0,0 +1,49 @@
+//! errno exposure and retry classification of GpuError
+
+use std::io;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use armgpuinfo::{GpuError, GpuVendor};
+
+fn os_error(errno: i32) -> GpuError {
+    GpuError::Io(io::Error::from_raw_os_error(errno))
+}
+
+#[test]
+fn errno_of_io_variants() {
+    assert_eq!(os_error(libc::ENOENT).errno(), Some(libc::ENOENT));
+    assert_eq!(
+        GpuError::OptionalIoctlFailed { request: "KGSL_PROP_GPU_MODEL", source: io::Error::from_raw_os_error(libc::EINVAL) }
+            .errno(),
+        Some(libc::EINVAL)
+    );
+    // Synthetic I/O errors have no errno
+    assert_eq!(GpuError::Io(io::Error::other("short read")).errno(), None);
+    assert_eq!(GpuError::DeviceNotFound.errno(), None);
+}
+
+#[test]
+fn transient_failures_are_retryable() {
+    assert!(os_error(libc::EAGAIN).is_retryable());
+    assert!(os_error(libc::EBUSY).is_retryable());
+    assert!(GpuError::Io(io::ErrorKind::Interrupted.into()).is_retryable());
+    assert!(GpuError::Timeout(Duration::from_secs(1)).is_retryable());
+
+    assert!(!os_error(libc::EACCES).is_retryable());
+    assert!(!GpuError::DriverNotSupported.is_retryable());
+    assert!(!GpuError::UnsupportedGpu { id: 0x1234, cores: 4 }.is_retryable());
+}
+
+#[test]
+fn detection_retryable_if_any_attempt_is() {
+    let attempt = |error| (GpuVendor::Mali, PathBuf::from("/dev/mali0"), error);
+    let permanent = GpuError::DetectionFailed { attempts: vec![attempt(GpuError::PermissionDenied)] };
+    assert!(!permanent.is_retryable());
+
+    let mixed = GpuError::DetectionFailed {
+        attempts: vec![attempt(GpuError::PermissionDenied), attempt(os_error(libc::EBUSY))],
+    };
+    assert!(mixed.is_retryable());
+    assert_eq!(mixed.errno(), None);
+}
@@ -0,0 +1,44 @@
+//! Stable numeric codes of GpuError
+
+use std::io;
+use std::time::Duration;
+
+use armgpuinfo::{GpuError, GpuErrorKind};
+
+#[test]
+fn codes_are_pinned() {
+    let pinned = [
+        (GpuError::Io(io::ErrorKind::NotFound.into()), GpuErrorKind::Io, 10),
+        (GpuError::DeviceNotFound, GpuErrorKind::DeviceNotFound, 14),
+        (GpuError::PermissionDenied, GpuErrorKind::PermissionDenied, 21),
+        (GpuError::DriverNotSupported, GpuErrorKind::DriverNotSupported, 22),
+        (GpuError::Timeout(Duration::from_secs(1)), GpuErrorKind::Timeout, 28),
+        (GpuError::DetectionFailed { attempts: Vec::new() }, GpuErrorKind::DetectionFailed, 30),
+    ];
+    for (error, kind, code) in pinned {
+        assert_eq!(error.kind(), kind);
+        assert_eq!(error.code(), code);
+    }
+}
+
+#[test]
+fn codes_round_trip() {
+    let kinds: Vec<GpuErrorKind> = (0..=u8::MAX as u32).filter_map(GpuErrorKind::from_code).collect();
+    assert_eq!(kinds.len(), 22);
+    assert_eq!(kinds.first().map(|kind| kind.code()), Some(10));
+    assert_eq!(kinds.last().map(|kind| kind.code()), Some(31));
+    for kind in kinds {
+        assert_eq!(GpuErrorKind::from_code(kind.code()), Some(kind));
+    }
+    assert_eq!(GpuErrorKind::from_code(1), None);
+    assert_eq!(GpuErrorKind::AdrenoPropertyError.to_string(), "adreno_property_error");
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn kind_serializes_as_name() {
+    let json = serde_json::to_string(&GpuErrorKind::DeviceNotFound).unwrap();
+    assert_eq!(json, "\"device_not_found\"");
+    let kind: GpuErrorKind = serde_json::from_str("\"permission_denied\"").unwrap();
+    assert_eq!(kind, GpuErrorKind::PermissionDenied);
+}
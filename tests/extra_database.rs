@@ -0,0 +1,80 @@
+//! Product entries loaded with `database::load_extra_entries`
+//!
+//! The databases are process-wide, so every test uses its own IDs.
+
+#![cfg(all(feature = "database-file", feature = "mali-core", feature = "adreno-core"))]
+
+use std::io::Write;
+
+use armgpuinfo::database::{load_extra_entries, LoadedEntries};
+use armgpuinfo::decode::batch;
+use armgpuinfo::{GpuError, GpuVendor, SpecConfidence};
+
+fn entries_file(json: &str) -> tempfile::NamedTempFile {
+    let mut file = tempfile::NamedTempFile::new().unwrap();
+    file.write_all(json.as_bytes()).unwrap();
+    file
+}
+
+#[test]
+fn loaded_entries_name_unknown_ids() {
+    let file = entries_file(
+        r#"{
+            "mali": [{
+                "id": "0xe00a", "name": "Mali-G9-Test", "architecture": "Gen5",
+                "fp32_fmas_per_engine": 64, "texels_per_core": 8,
+                "pixels_per_core": 4, "exec_engines": 1
+            }],
+            "adreno": [{
+                "chip_id": 118030336, "name": "Adreno 7T9", "architecture": "A7xx",
+                "shader_cores": 6, "stream_processors": 1536, "gmem_size_kb": 3072,
+                "bus_width_bits": 128, "max_freq_mhz": 1000, "process_nm": 3,
+                "year": 2026, "confidence": "Measured"
+            }]
+        }"#,
+    );
+    assert_eq!(load_extra_entries(file.path()).unwrap(), LoadedEntries { mali: 1, adreno: 1 });
+
+    // The revision nibbles are masked off like for built-in products
+    let decoded = batch([(GpuVendor::Mali, 0xe10a, 6), (GpuVendor::Adreno, 0x0709_0001, 0)]);
+    assert_eq!(decoded[0].name, Some("Mali-G9-Test"));
+    assert_eq!(decoded[0].confidence, Some(SpecConfidence::Measured));
+    assert_eq!(decoded[1].name, Some("Adreno 7T9"));
+    assert_eq!(decoded[1].confidence, Some(SpecConfidence::Measured));
+}
+
+#[test]
+fn loaded_entries_override_built_ins() {
+    let file = entries_file(
+        r#"{"adreno": [{
+            "chip_id": "0x06020000", "name": "Adreno 620 (patched)", "architecture": "A6xx",
+            "shader_cores": 2, "stream_processors": 512, "gmem_size_kb": 2048,
+            "bus_width_bits": 128, "max_freq_mhz": 600, "process_nm": 8, "year": 2020
+        }]}"#,
+    );
+    load_extra_entries(file.path()).unwrap();
+
+    let decoded = batch([(GpuVendor::Adreno, 0x0602_0000, 0)]);
+    assert_eq!(decoded[0].name, Some("Adreno 620 (patched)"));
+    assert_eq!(decoded[0].confidence, Some(SpecConfidence::Heuristic));
+}
+
+#[test]
+fn invalid_files_add_nothing() {
+    let file = entries_file(
+        r#"{"mali": [
+            {"id": "0xe00b", "name": "Mali-Valid", "architecture": "Gen5",
+             "fp32_fmas_per_engine": 64, "texels_per_core": 8, "pixels_per_core": 4, "exec_engines": 1},
+            {"id": "0xe00c", "name": "Mali-Odd", "architecture": "Gen5",
+             "fp32_fmas_per_engine": 48, "texels_per_core": 8, "pixels_per_core": 4, "exec_engines": 1}
+        ]}"#,
+    );
+    let error = load_extra_entries(file.path()).unwrap_err();
+    assert!(matches!(&error, GpuError::InvalidData(message) if message.contains("fp32_fmas_per_engine 48")));
+    assert_ne!(batch([(GpuVendor::Mali, 0xe00b, 6)])[0].name, Some("Mali-Valid"));
+
+    let file = entries_file(r#"{"adreno": [{"chip_id": "07090000"}]}"#);
+    assert!(matches!(load_extra_entries(file.path()), Err(GpuError::InvalidData(_))));
+
+    assert!(matches!(load_extra_entries("/nonexistent/gpus.json"), Err(GpuError::Io(_))));
+}
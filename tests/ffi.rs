@@ -0,0 +1,69 @@
+//! C interface over the mock backend
+
+#![cfg(all(feature = "ffi", feature = "mock", feature = "adreno-kgsl"))]
+
+use std::ffi::CStr;
+
+use armgpuinfo::adreno::KgslDeviceInfo;
+use armgpuinfo::ffi::{
+    error_code, gpuinfo_free, gpuinfo_last_error, gpuinfo_last_error_code, gpuinfo_query_auto,
+    GpuinfoGpu, GPUINFO_ABI_VERSION, GPUINFO_ERR_NOT_FOUND, GPUINFO_ERR_OTHER, GPUINFO_ERR_PANIC,
+    GPUINFO_ERR_PERMISSION, GPUINFO_OK, GPUINFO_VENDOR_ADRENO,
+};
+use armgpuinfo::mock::MockGpuBackend;
+use armgpuinfo::{GpuError, GpuErrorKind};
+
+#[test]
+fn query_fills_the_struct() {
+    let info = KgslDeviceInfo { chip_id: 0x0706_0001, gmem_sizebytes: 2 << 20, ..Default::default() };
+    let _mock = MockGpuBackend::new().adreno_device_info("/dev/kgsl-3d0", info).install();
+
+    let gpu = gpuinfo_query_auto();
+    assert!(!gpu.is_null());
+    assert_eq!(gpuinfo_last_error(), GPUINFO_OK);
+    assert_eq!(gpuinfo_last_error_code(), 0);
+
+    let fields = unsafe { *gpu };
+    assert_eq!(fields.struct_size as usize, std::mem::size_of::<GpuinfoGpu>());
+    assert_eq!(fields.abi_version, GPUINFO_ABI_VERSION);
+    assert_eq!(fields.vendor, GPUINFO_VENDOR_ADRENO);
+    assert_eq!(fields.adreno_chip_id, 0x0706_0001);
+    assert_eq!(fields.adreno_gmem_bytes, 2 << 20);
+    assert_eq!(fields.mali_gpu_id, 0);
+    let name = unsafe { CStr::from_ptr(fields.gpu_name.as_ptr()) };
+    assert_eq!(name.to_str(), Ok("Adreno 740"));
+
+    unsafe { gpuinfo_free(gpu) };
+    unsafe { gpuinfo_free(std::ptr::null_mut()) };
+}
+
+#[test]
+fn failures_set_the_error_code() {
+    let _mock = MockGpuBackend::new().install();
+    assert!(gpuinfo_query_auto().is_null());
+    assert_eq!(gpuinfo_last_error(), GPUINFO_ERR_NOT_FOUND);
+    assert_eq!(gpuinfo_last_error_code(), GpuErrorKind::DeviceNotFound.code());
+}
+
+#[test]
+fn panics_do_not_cross_the_boundary() {
+    let _mock = MockGpuBackend::new().panicking("/dev/kgsl-3d0").install();
+    assert!(gpuinfo_query_auto().is_null());
+    assert_eq!(gpuinfo_last_error(), GPUINFO_ERR_PANIC);
+    assert_eq!(gpuinfo_last_error_code(), 0);
+}
+
+#[test]
+fn io_errors_map_by_kind() {
+    let io = |errno| std::io::Error::from_raw_os_error(errno);
+    let cases = [
+        (GpuError::Io(io(libc::EACCES)), GPUINFO_ERR_PERMISSION),
+        (GpuError::Io(io(libc::EPERM)), GPUINFO_ERR_PERMISSION),
+        (GpuError::IoctlFailed { request: 0, source: io(libc::EACCES) }, GPUINFO_ERR_PERMISSION),
+        (GpuError::Io(io(libc::ENOENT)), GPUINFO_ERR_NOT_FOUND),
+        (GpuError::Io(io(libc::EIO)), GPUINFO_ERR_OTHER),
+    ];
+    for (error, code) in cases {
+        assert_eq!(error_code(&error), code, "{error:?}");
+    }
+}
@@ -0,0 +1,66 @@
+//! Adreno GMU discovery and firmware version nodes
+
+#![cfg(feature = "adreno-core")]
+
+use std::fs;
+
+use armgpuinfo::adreno::{find_gmu_device_in, parse_gmu_firmware_version, read_gmu_firmware_version_in};
+use armgpuinfo::GmuFirmwareVersion;
+
+const fn version(major: u32, minor: u32, step: u32) -> Option<GmuFirmwareVersion> {
+    Some(GmuFirmwareVersion { major, minor, step })
+}
+
+#[test]
+fn firmware_version_formats() {
+    let cases = [
+        ("0x20010004", version(2, 1, 4)),
+        ("0X1F020010\n", version(1, 0xF02, 0x10)),
+        ("4.3.7\n", version(4, 3, 7)),
+        ("  1.9 ", version(1, 9, 0)),
+        // Firmware not loaded yet
+        ("0x00000000", None),
+        ("0.0.0", None),
+        // Malformed
+        ("", None),
+        ("0x", None),
+        ("0x1234567890", None),
+        ("2", None),
+        ("2.1.4.0", None),
+        ("2..4", None),
+        ("v2.1", None),
+        ("-1.2.3", None),
+    ];
+    for (text, expected) in cases {
+        assert_eq!(parse_gmu_firmware_version(text), expected, "{text:?}");
+    }
+    assert_eq!(version(2, 1, 4).unwrap().to_string(), "2.1.4");
+}
+
+#[test]
+fn device_node_wins_over_debugfs() {
+    let sysfs = tempfile::tempdir().unwrap();
+    let debugfs = tempfile::tempdir().unwrap();
+    fs::create_dir(sysfs.path().join("3d6a000.qcom,kgsl-3d0")).unwrap();
+    let gmu = sysfs.path().join("3d6a000.qcom,gmu");
+    fs::create_dir(&gmu).unwrap();
+
+    assert_eq!(find_gmu_device_in(sysfs.path()).as_deref(), Some(gmu.as_path()));
+    assert_eq!(read_gmu_firmware_version_in(Some(&gmu), debugfs.path()), None);
+
+    fs::write(debugfs.path().join("gmu_fw_version"), "0x30020001\n").unwrap();
+    assert_eq!(read_gmu_firmware_version_in(None, debugfs.path()), version(3, 2, 1));
+    assert_eq!(read_gmu_firmware_version_in(Some(&gmu), debugfs.path()), version(3, 2, 1));
+
+    fs::write(gmu.join("fw_version"), "garbage").unwrap();
+    assert_eq!(read_gmu_firmware_version_in(Some(&gmu), debugfs.path()), version(3, 2, 1));
+    fs::write(gmu.join("fw_version"), "4.0.2").unwrap();
+    assert_eq!(read_gmu_firmware_version_in(Some(&gmu), debugfs.path()), version(4, 0, 2));
+}
+
+#[test]
+fn no_gmu_on_older_parts() {
+    let sysfs = tempfile::tempdir().unwrap();
+    fs::create_dir(sysfs.path().join("5000000.qcom,kgsl-3d0")).unwrap();
+    assert_eq!(find_gmu_device_in(sysfs.path()), None);
+}
@@ -0,0 +1,46 @@
+//! Processes holding GPU contexts
+
+#![cfg(feature = "mali-core")]
+
+use std::fs;
+use std::path::Path;
+
+use armgpuinfo::telemetry::clients::{list_gpu_clients_in, ClientPaths};
+use armgpuinfo::telemetry::Provenance;
+
+fn paths(root: &Path) -> ClientPaths {
+    ClientPaths {
+        kgsl_proc: root.join("kgsl/proc"),
+        mali_debugfs: root.join("mali0"),
+        procfs: root.join("proc"),
+    }
+}
+
+#[test]
+fn mali_contexts_are_grouped_by_process() {
+    let root = tempfile::tempdir().unwrap();
+    let debugfs = root.path().join("mali0");
+    for ctx in ["812_0", "812_3", "2001_1", "defaults"] {
+        fs::create_dir_all(debugfs.join("ctx").join(ctx)).unwrap();
+    }
+    fs::write(debugfs.join("gpu_memory"), "mali0 30\n  kctx-0x01 10 812\n  kctx-0x02 20 2001\n").unwrap();
+
+    let clients = list_gpu_clients_in(&paths(root.path()));
+    assert_eq!(clients.iter().map(|c| (c.pid, c.contexts)).collect::<Vec<_>>(), [(812, Some(2)), (2001, Some(1))]);
+    assert!(clients.iter().all(|c| c.provenance == Provenance::Debugfs && c.name.is_none()));
+    assert!(clients[1].gpu_memory_bytes.unwrap() > clients[0].gpu_memory_bytes.unwrap());
+}
+
+#[test]
+fn gpu_memory_alone_still_names_the_processes() {
+    let root = tempfile::tempdir().unwrap();
+    fs::create_dir_all(root.path().join("mali0")).unwrap();
+    fs::write(root.path().join("mali0/gpu_memory"), "mali0 5\n  kctx-0x01 5 77\n").unwrap();
+
+    let clients = list_gpu_clients_in(&paths(root.path()));
+    assert_eq!(clients.len(), 1);
+    assert_eq!(clients[0].pid, 77);
+    assert_eq!(clients[0].contexts, None);
+
+    assert!(list_gpu_clients_in(&paths(Path::new("/nonexistent"))).is_empty());
+}
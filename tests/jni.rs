@@ -0,0 +1,25 @@
+//! JSON behind the JNI binding, over the mock backend
+
+#![cfg(all(feature = "jni", feature = "mock", feature = "adreno-kgsl"))]
+
+use armgpuinfo::adreno::KgslDeviceInfo;
+use armgpuinfo::jni::query_json;
+use armgpuinfo::mock::MockGpuBackend;
+use armgpuinfo::{GpuError, GpuInfo};
+
+#[test]
+fn query_json_round_trips() {
+    let info = KgslDeviceInfo { chip_id: 0x0706_0001, gmem_sizebytes: 2 << 20, ..Default::default() };
+    let _mock = MockGpuBackend::new().adreno_device_info("/dev/kgsl-3d0", info).install();
+
+    let json = query_json().unwrap();
+    let info: GpuInfo = serde_json::from_str(&json).unwrap();
+    assert_eq!(info.gpu_name, "Adreno 740");
+    assert_eq!(info.adreno_data.unwrap().gmem_bytes, 2 << 20);
+}
+
+#[test]
+fn query_json_reports_missing_gpus() {
+    let _mock = MockGpuBackend::new().install();
+    assert!(matches!(query_json(), Err(GpuError::DeviceNotFound)));
+}
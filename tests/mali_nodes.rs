@@ -0,0 +1,195 @@
+//! Mali vendor procfs/sysfs/debugfs nodes from fixture files
+
+#![cfg(feature = "mali-core")]
+
+use std::fs;
+use std::path::Path;
+
+use armgpuinfo::telemetry::mali_nodes::{read_gpu_memory, MaliGpuinfo, MaliNodePaths, MaliNodes};
+use armgpuinfo::telemetry::{ProcessGpuMemory, Provenance};
+
+fn paths(root: &Path) -> MaliNodePaths {
+    MaliNodePaths {
+        sysfs: root.join("sysfs"),
+        debugfs: root.join("debugfs"),
+        procfs: root.join("procfs"),
+    }
+}
+
+fn node(dir: &Path, name: &str, contents: &str) {
+    fs::create_dir_all(dir).unwrap();
+    fs::write(dir.join(name), contents).unwrap();
+}
+
+#[test]
+fn gpuinfo_lines() {
+    let parsed = MaliGpuinfo::parse("Mali-G610 4 cores r0p0 0xA867").unwrap();
+    assert_eq!(
+        parsed,
+        MaliGpuinfo {
+            name: "Mali-G610".into(),
+            num_cores: 4,
+            revision: Some((0, 0)),
+            product_id: Some(0xa867),
+        }
+    );
+
+    let parsed = MaliGpuinfo::parse("Mali-T860 MP4 1 core r2p0 0X0880").unwrap();
+    assert_eq!(parsed.name, "Mali-T860 MP4");
+    assert_eq!(parsed.num_cores, 1);
+    assert_eq!(parsed.revision, Some((2, 0)));
+    assert_eq!(parsed.product_id, Some(0x0880));
+
+    // Revision and product ID are optional
+    let parsed = MaliGpuinfo::parse("  Mali-G78   20 cores \n").unwrap();
+    assert_eq!((parsed.name.as_str(), parsed.num_cores), ("Mali-G78", 20));
+    assert_eq!((parsed.revision, parsed.product_id), (None, None));
+}
+
+#[test]
+fn malformed_gpuinfo() {
+    for line in [
+        "",
+        "Mali-G610",
+        "4 cores",
+        "cores",
+        "Mali-G610 four cores r0p0",
+        "Mali-G610 -4 cores",
+        "Mali-G610 4cores r0p0 0xA867",
+    ] {
+        assert_eq!(MaliGpuinfo::parse(line), None, "{line:?}");
+    }
+
+    // Broken trailing tokens are dropped, not fatal
+    let parsed = MaliGpuinfo::parse("Mali-G610 4 cores rXp0 0xZZ").unwrap();
+    assert_eq!((parsed.revision, parsed.product_id), (None, None));
+}
+
+#[test]
+fn utilization_formats() {
+    for (contents, expected) in [
+        ("42\n", Some(42.0)),
+        ("42%", Some(42.0)),
+        ("gpu=42", Some(42.0)),
+        ("gpu/cljs0/cljs1=42/0/0", Some(42.0)),
+        ("12.5 %", Some(12.5)),
+        ("100", Some(100.0)),
+        // Out of range or not a number
+        ("142", None),
+        ("-1", None),
+        ("busy", None),
+        ("gpu=", None),
+        ("", None),
+    ] {
+        let root = tempfile::tempdir().unwrap();
+        let paths = paths(root.path());
+        node(&paths.procfs, "utilization", contents);
+
+        let nodes = MaliNodes::read_from(&paths);
+        let utilization = nodes.telemetry.utilization;
+        assert_eq!(utilization.map(|r| r.value), expected, "{contents:?}");
+        if expected.is_some() {
+            assert_eq!(utilization.unwrap().provenance, Provenance::Procfs);
+        }
+    }
+}
+
+#[test]
+fn sysfs_is_preferred() {
+    let root = tempfile::tempdir().unwrap();
+    let paths = paths(root.path());
+    node(&paths.sysfs, "gpuinfo", "Mali-G57 3 cores r0p1 0x9093");
+    node(&paths.procfs, "gpuinfo", "Mali-G57 2 cores r0p0 0x9091");
+    node(&paths.debugfs, "utilization", "7");
+
+    let nodes = MaliNodes::read_from(&paths);
+    let gpuinfo = nodes.gpuinfo.unwrap();
+    assert_eq!(gpuinfo.provenance, Provenance::Sysfs);
+    assert_eq!(gpuinfo.value.num_cores, 3);
+    let utilization = nodes.telemetry.utilization.unwrap();
+    assert_eq!((utilization.value, utilization.provenance), (7.0, Provenance::Debugfs));
+}
+
+#[test]
+fn missing_nodes() {
+    let root = tempfile::tempdir().unwrap();
+    let paths = paths(root.path());
+    node(&paths.sysfs, "gpuinfo", "not a gpuinfo line");
+
+    let nodes = MaliNodes::read_from(&paths);
+    assert!(nodes.gpuinfo.is_none());
+    assert!(nodes.telemetry.utilization.is_none());
+    assert!(nodes.telemetry.memory.is_none());
+}
+
+const GPU_MEMORY: &str = "\
+mali0                  1000
+  kctx-0x00000000deadbeef        100       1234
+  kctx-0x00000000cafef00d         50       4321
+  kctx-0x00000000feedface         20       1234
+";
+
+#[test]
+fn gpu_memory_per_process() {
+    let root = tempfile::tempdir().unwrap();
+    let paths = paths(root.path());
+    node(&paths.debugfs, "gpu_memory", GPU_MEMORY);
+
+    let usage = read_gpu_memory(&paths).unwrap();
+    assert_eq!(usage.provenance, Provenance::Debugfs);
+    let page = usage.value.total_bytes / 1000;
+    assert!(page.is_power_of_two());
+    // Contexts of the same process are summed, sorted by PID
+    assert_eq!(
+        usage.value.processes,
+        [
+            ProcessGpuMemory { pid: 1234, bytes: 120 * page },
+            ProcessGpuMemory { pid: 4321, bytes: 50 * page },
+        ]
+    );
+    assert_eq!(usage.value.for_pid(4321), Some(50 * page));
+    assert_eq!(usage.value.for_pid(1), None);
+
+    let telemetry = MaliNodes::read_from(&paths).telemetry;
+    assert_eq!(telemetry.memory_used_bytes().map(|r| r.value), Some(1000 * page));
+}
+
+#[test]
+fn gpu_memory_total_only() {
+    let root = tempfile::tempdir().unwrap();
+    let paths = paths(root.path());
+    node(&paths.procfs, "gpu_memory", "mali0 256\n");
+
+    let usage = read_gpu_memory(&paths).unwrap();
+    assert_eq!(usage.provenance, Provenance::Procfs);
+    assert!(usage.value.total_bytes > 0);
+    assert!(usage.value.processes.is_empty());
+}
+
+#[test]
+fn malformed_gpu_memory() {
+    for contents in ["", "mali0", "mali0 lots", "mali0 -1"] {
+        let root = tempfile::tempdir().unwrap();
+        let paths = paths(root.path());
+        node(&paths.debugfs, "gpu_memory", contents);
+        assert!(read_gpu_memory(&paths).is_none(), "{contents:?}");
+    }
+
+    // Unparseable context lines are skipped, the total is kept
+    let root = tempfile::tempdir().unwrap();
+    let paths = paths(root.path());
+    node(
+        &paths.debugfs,
+        "gpu_memory",
+        "mali0 64\n\
+         kctx-0x1 many 1234\n\
+         kctx-0x2 8\n\
+         kctx-0x3 8 pid\n\
+         total 8 1234\n\
+         kctx-0x4 8 1234 extra\n\
+         kctx-0x5 4 99\n",
+    );
+    let usage = read_gpu_memory(&paths).unwrap().value;
+    let page = usage.total_bytes / 64;
+    assert_eq!(usage.processes, [ProcessGpuMemory { pid: 99, bytes: 4 * page }]);
+}
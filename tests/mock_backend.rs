@@ -0,0 +1,132 @@
+//! Queries answered by the mock backend
+
+#![cfg(all(feature = "mock", feature = "mali-kbase", feature = "adreno-kgsl", feature = "auto-detect"))]
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use armgpuinfo::detect::{enumerate_gpus, wait_for_gpu, GpuReadiness};
+use armgpuinfo::mock::MockGpuBackend;
+use armgpuinfo::mali::UkVersion;
+use armgpuinfo::{Fallback, GpuError, GpuVendor, Instrumentation, MaliFlavor, Mode, QueryOptions, QueryTrace};
+
+mod common;
+use common::{a740, g610};
+
+#[test]
+fn queries_decode_the_fixture() {
+    let _mock = MockGpuBackend::new().mali("/dev/mali0", g610()).install();
+
+    let info = armgpuinfo::mali::query_mali_with_mode("/dev/mali0", Mode::Parity).unwrap();
+    assert_eq!(info.gpu_name, "Mali-G610");
+    assert_eq!(info.num_shader_cores, 4);
+    assert_eq!(info.device_path.as_deref(), Some("/dev/mali0".as_ref()));
+    assert_eq!(info.mali_data.unwrap().instance, Some(0));
+}
+
+#[test]
+fn job_manager_handshake_after_csf_refusal() {
+    // JM kernel answering the CSF number (0x34) with EPERM
+    let _mock = MockGpuBackend::new()
+        .mali("/dev/mali0", g610())
+        .mali_version_check("/dev/mali0", 0x34, Err(libc::EPERM))
+        .mali_version_check("/dev/mali0", 0x00, Ok(UkVersion::new(11, 34)))
+        .install();
+
+    let mali = armgpuinfo::mali::query_mali("/dev/mali0").unwrap().mali_data.unwrap();
+    assert_eq!(mali.flavor, Some(MaliFlavor::JobManager));
+    assert_eq!(mali.driver_version, Some((11, 34)));
+}
+
+#[test]
+fn refused_handshake_reports_no_version() {
+    let _mock = MockGpuBackend::new()
+        .mali("/dev/mali0", g610())
+        .mali_version_check("/dev/mali0", 0x34, Err(libc::EPERM))
+        .mali_version_check("/dev/mali0", 0x00, Err(libc::EPERM))
+        .mali("/dev/mali1", g610())
+        .mali_version_check("/dev/mali1", 0x34, Err(libc::EIO))
+        .install();
+
+    let mali = armgpuinfo::mali::query_mali("/dev/mali0").unwrap().mali_data.unwrap();
+    assert_eq!((mali.flavor, mali.driver_version), (None, None));
+    assert!(matches!(
+        armgpuinfo::mali::query_mali("/dev/mali1"),
+        Err(GpuError::IoctlFailed { request: 0x34, .. })
+    ));
+}
+
+#[test]
+fn missing_and_denied_nodes_fail_like_real_ones() {
+    let _mock = MockGpuBackend::new().denied("/dev/mali0").install();
+
+    assert!(matches!(armgpuinfo::mali::query_mali("/dev/mali0"), Err(GpuError::PermissionDenied)));
+    assert!(matches!(armgpuinfo::mali::query_mali("/dev/mali1"), Err(GpuError::DeviceNotFound)));
+    assert!(matches!(
+        armgpuinfo::adreno::query_adreno("/dev/kgsl-3d0"),
+        Err(GpuError::DeviceNotFound)
+    ));
+}
+
+#[test]
+fn enumeration_sees_only_mock_nodes() {
+    let _mock = MockGpuBackend::new()
+        .adreno_device_info("/dev/kgsl-3d0", a740())
+        .mali("/dev/mali1", g610())
+        .install();
+
+    let gpus = enumerate_gpus().unwrap();
+    let vendors: Vec<_> = gpus.iter().map(|gpu| gpu.vendor).collect();
+    assert_eq!(vendors, [GpuVendor::Mali, GpuVendor::Adreno]);
+    assert_eq!(gpus[1].gpu_name, "Adreno 740");
+}
+
+#[test]
+fn wait_for_gpu_reports_denied_nodes() {
+    let _mock = MockGpuBackend::new().denied("/dev/mali0").install();
+
+    match wait_for_gpu(Duration::ZERO) {
+        GpuReadiness::NodePresent { path, error } => {
+            assert_eq!(path, std::path::Path::new("/dev/mali0"));
+            assert!(matches!(error, GpuError::PermissionDenied));
+        }
+        other => panic!("unexpected readiness {other:?}"),
+    }
+}
+
+#[test]
+fn nested_backends_restore_the_outer_one() {
+    let _outer = MockGpuBackend::new().mali("/dev/mali0", g610()).install();
+    {
+        let _inner = MockGpuBackend::new().install();
+        assert!(enumerate_gpus().is_err());
+    }
+    assert_eq!(enumerate_gpus().unwrap().len(), 1);
+}
+
+#[test]
+fn instrumentation_reports_each_query() {
+    let _mock = MockGpuBackend::new().mali("/dev/mali0", g610()).install();
+    let traces = Arc::new(Mutex::new(Vec::<QueryTrace>::new()));
+    let options = QueryOptions::new(Mode::Extended)
+        .fallback(Fallback::Parity)
+        .instrument(Instrumentation::new({
+            let traces = Arc::clone(&traces);
+            move |trace| traces.lock().unwrap().push(trace.clone())
+        }));
+
+    armgpuinfo::mali::query_mali_with_opts("/dev/mali0", options.clone()).unwrap();
+    armgpuinfo::mali::query_mali_with_opts("/dev/mali1", options).unwrap_err();
+
+    let traces = traces.lock().unwrap();
+    assert_eq!(traces.len(), 2);
+    assert!(traces[0].succeeded);
+    assert_eq!(traces[0].mode, Some(Mode::Extended));
+    assert_eq!(traces[0].bytes, g610().len());
+    assert_eq!(traces[0].device_path.as_deref(), Some("/dev/mali0".as_ref()));
+    assert!(traces[0].total >= traces[0].parse + traces[0].lookup);
+
+    assert!(!traces[1].succeeded);
+    assert_eq!(traces[1].mode, None);
+    assert_eq!(traces[1].bytes, 0);
+}
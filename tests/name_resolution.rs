@@ -0,0 +1,120 @@
+//! Marketing and renderer names mapped onto database entries
+
+#![cfg(all(feature = "mali-core", feature = "adreno-core"))]
+
+use armgpuinfo::resolve::DatabaseEntry;
+use armgpuinfo::{parse_renderer_string, resolve_name, GpuVendor};
+
+#[test]
+fn marketing_names_resolve() {
+    let cases = [
+        ("Mali-G68 MC4", GpuVendor::Mali, "Mali-G68", Some(4)),
+        ("Mali-G78 MP20", GpuVendor::Mali, "Mali-G78", Some(20)),
+        ("Immortalis-G720 MC12", GpuVendor::Mali, "Immortalis-G720", Some(12)),
+        ("Mali-G57 3EE MC2", GpuVendor::Mali, "Mali-G57", Some(2)),
+        ("Mali G1-Ultra MC12", GpuVendor::Mali, "Mali G1-Ultra", Some(12)),
+        ("Mali-T880", GpuVendor::Mali, "Mali-T880", None),
+        ("Adreno (TM) 740", GpuVendor::Adreno, "Adreno 740", None),
+        ("Adreno (TM) 619L", GpuVendor::Adreno, "Adreno 619", None),
+        ("Adreno 615", GpuVendor::Adreno, "Adreno 612/615/616", None),
+    ];
+    for (name, vendor, canonical, cores) in cases {
+        let resolved = resolve_name(name).unwrap_or_else(|| panic!("{name:?} did not resolve"));
+        assert_eq!(resolved.vendor, vendor, "{name:?}");
+        assert_eq!(resolved.canonical_name, canonical, "{name:?}");
+        assert_eq!(resolved.core_count, cores, "{name:?}");
+    }
+}
+
+#[test]
+fn case_and_spacing_variants() {
+    for name in ["Mali-G68 MC4", "mali-g68 mc4", "MALI G68   MC4", "ARM Mali\u{2122}-G68 MC4", "Mali_G68 MP4", " Mali-G68 MC4 "] {
+        let resolved = resolve_name(name).unwrap_or_else(|| panic!("{name:?} did not resolve"));
+        assert_eq!((resolved.canonical_name, resolved.core_count), ("Mali-G68", Some(4)), "{name:?}");
+    }
+    for name in ["Adreno (TM) 740", "Qualcomm\u{ae} Adreno\u{2122} 740", "adreno 740", "ADRENO(TM)740"] {
+        let resolved = resolve_name(name).unwrap_or_else(|| panic!("{name:?} did not resolve"));
+        assert_eq!(resolved.canonical_name, "Adreno 740", "{name:?}");
+        assert!(matches!(resolved.entry, DatabaseEntry::Adreno { chip_id: 0x0706_0001, .. }), "{name:?}");
+    }
+}
+
+#[test]
+fn core_count_picks_the_matching_entry() {
+    // Mali-G715 and Mali-G615 share product ID 0xb002 and differ by core count
+    for (name, canonical, min_cores, cores) in [
+        ("Mali-G715 MC7", "Mali-G715", 7, 7),
+        ("Mali-G715 MC6", "Mali-G715", 7, 6),
+        ("Mali-G615 MC6", "Mali-G615", 1, 6),
+    ] {
+        let resolved = resolve_name(name).unwrap();
+        let DatabaseEntry::Mali(entry) = resolved.entry else {
+            panic!("{name} resolved to another vendor");
+        };
+        assert_eq!(resolved.canonical_name, canonical, "{name}");
+        assert_eq!((entry.id, entry.min_cores), (0xb002, min_cores), "{name}");
+        assert_eq!(resolved.core_count, Some(cores), "{name}");
+    }
+}
+
+#[test]
+fn unknown_names_do_not_resolve() {
+    for name in [
+        "",
+        "Mali",
+        "Adreno (TM)",
+        "Mali-G99 MC4",
+        "Adreno (TM) 642L",
+        "Adreno (TM) 799",
+        "Adreno 6xx",
+        "PowerVR Rogue GE8320",
+        "NVIDIA Tegra X1",
+        // No surrounding text; see parse_renderer_string for that
+        "ANGLE (ARM, Mali-G78 MP20, OpenGL ES 3.2)",
+        "Mali-G78 MP20 r1p0",
+    ] {
+        assert!(resolve_name(name).is_none(), "{name:?} resolved");
+    }
+}
+
+#[test]
+fn renderer_strings_parse() {
+    let cases = [
+        ("Mali-G78 MC24", GpuVendor::Mali, Some("Mali-G78"), Some(24)),
+        ("Immortalis-G720 MC12", GpuVendor::Mali, Some("Immortalis-G720"), Some(12)),
+        ("Adreno (TM) 740", GpuVendor::Adreno, Some("Adreno 740"), None),
+        // Newer than the database: vendor and core count only
+        ("Adreno (TM) 650", GpuVendor::Adreno, None, None),
+        ("Mali-G99 MC8", GpuVendor::Mali, None, Some(8)),
+        (
+            "ANGLE (ARM, Mali-G78 MP20, OpenGL ES 3.2)",
+            GpuVendor::Mali,
+            Some("Mali-G78"),
+            Some(20),
+        ),
+        (
+            "ANGLE (Qualcomm, Adreno (TM) 740, OpenGL ES 3.2)",
+            GpuVendor::Adreno,
+            Some("Adreno 740"),
+            None,
+        ),
+        (
+            "ANGLE (Qualcomm, Adreno (TM) 650, Vulkan 1.1.128 (0x80)",
+            GpuVendor::Adreno,
+            None,
+            None,
+        ),
+        ("Mali-G710 MC10 r0p0", GpuVendor::Mali, Some("Mali-G710"), Some(10)),
+    ];
+    for (renderer, vendor, product, cores) in cases {
+        let parsed = parse_renderer_string(renderer).unwrap_or_else(|| panic!("{renderer}"));
+        assert_eq!(parsed.vendor, vendor, "{renderer}");
+        assert_eq!(parsed.product, product, "{renderer}");
+        assert_eq!(parsed.core_count, cores, "{renderer}");
+        assert_eq!(parsed.entry.is_some(), product.is_some(), "{renderer}");
+    }
+
+    for renderer in ["", "llvmpipe (LLVM 15.0.7, 256 bits)", "ANGLE (NVIDIA, GeForce GTX 1080)"] {
+        assert!(parse_renderer_string(renderer).is_none(), "{renderer}");
+    }
+}
@@ -0,0 +1,56 @@
+//! Async queries and the monitor stream
+
+#![cfg(all(feature = "tokio", feature = "mock", feature = "mali-kbase", feature = "adreno-kgsl", feature = "auto-detect"))]
+
+use std::time::Duration;
+
+use armgpuinfo::mock::MockGpuBackend;
+use armgpuinfo::monitor::MonitorConfig;
+use armgpuinfo::nonblocking::{
+    enumerate_gpus_async, monitor_stream, query_adreno_async, query_gpu_auto_async, query_mali_async,
+};
+use armgpuinfo::GpuError;
+
+mod common;
+use common::{a740, g610};
+
+#[tokio::test(flavor = "multi_thread")]
+async fn queries_run_against_the_callers_mock() {
+    let (mali, adreno, auto, all) = {
+        let _mock = MockGpuBackend::new()
+            .mali("/dev/mali0", g610())
+            .adreno_device_info("/dev/kgsl-3d0", a740())
+            .install();
+        // Futures capture the mock when created, the guard may be gone
+        // by the time they run
+        (
+            query_mali_async("/dev/mali0"),
+            query_adreno_async("/dev/kgsl-3d0"),
+            query_gpu_auto_async(None::<&str>),
+            enumerate_gpus_async(),
+        )
+    };
+
+    assert_eq!(mali.await.unwrap().gpu_name, "Mali-G610");
+    assert_eq!(adreno.await.unwrap().gpu_name, "Adreno 740");
+    assert_eq!(auto.await.unwrap().gpu_name, "Mali-G610");
+    assert_eq!(all.await.unwrap().len(), 2);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn errors_are_returned_unchanged() {
+    let query = {
+        let _mock = MockGpuBackend::new().denied("/dev/mali0").install();
+        query_mali_async("/dev/mali0")
+    };
+    assert!(matches!(query.await, Err(GpuError::PermissionDenied)));
+}
+
+#[tokio::test]
+async fn monitor_stream_yields_samples() {
+    let config = MonitorConfig { interval: Duration::from_millis(1), ..MonitorConfig::default() };
+    let mut samples = monitor_stream(config);
+    let first = samples.next().await.unwrap();
+    let second = samples.next().await.unwrap();
+    assert!(second.elapsed > first.elapsed);
+}
@@ -0,0 +1,45 @@
+//! Battery discharge fallback of the power estimate
+
+use std::fs;
+use std::path::Path;
+
+use armgpuinfo::telemetry::power::read_battery_discharge_mw;
+
+fn supply(root: &Path, name: &str, nodes: &[(&str, &str)]) {
+    let dir = root.join(name);
+    fs::create_dir_all(&dir).unwrap();
+    for (node, value) in nodes {
+        fs::write(dir.join(node), format!("{value}\n")).unwrap();
+    }
+}
+
+#[test]
+fn power_now_is_preferred() {
+    let root = tempfile::tempdir().unwrap();
+    supply(
+        root.path(),
+        "battery",
+        &[("type", "Battery"), ("status", "Discharging"), ("power_now", "2500000"), ("current_now", "1")],
+    );
+    assert_eq!(read_battery_discharge_mw(root.path()), Some(2500.0));
+}
+
+#[test]
+fn current_times_voltage_ignores_sign() {
+    let root = tempfile::tempdir().unwrap();
+    supply(root.path(), "ac", &[("type", "Mains"), ("status", "Discharging"), ("power_now", "1")]);
+    supply(
+        root.path(),
+        "battery",
+        &[("type", "Battery"), ("status", "Discharging"), ("current_now", "-500000"), ("voltage_now", "4000000")],
+    );
+    assert_eq!(read_battery_discharge_mw(root.path()), Some(2000.0));
+}
+
+#[test]
+fn charging_battery_is_ignored() {
+    let root = tempfile::tempdir().unwrap();
+    supply(root.path(), "battery", &[("type", "Battery"), ("status", "Charging"), ("power_now", "2500000")]);
+    assert_eq!(read_battery_discharge_mw(root.path()), None);
+    assert_eq!(read_battery_discharge_mw(&root.path().join("missing")), None);
+}
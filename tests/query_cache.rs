@@ -0,0 +1,38 @@
+//! Cached queries answered by the mock backend
+
+#![cfg(all(feature = "mock", feature = "mali-kbase", feature = "auto-detect"))]
+
+use std::time::Duration;
+
+use armgpuinfo::cache::{invalidate, invalidate_device, set_ttl};
+use armgpuinfo::mock::MockGpuBackend;
+use armgpuinfo::query_gpu_cached;
+
+mod common;
+use common::g610_with_cores;
+
+// One test: the cache is shared by every thread of the process
+#[test]
+fn results_are_cached_until_invalidated() {
+    invalidate();
+    let path = Some("/dev/mali0");
+    {
+        let _mock = MockGpuBackend::new().mali("/dev/mali0", g610_with_cores(4)).install();
+        assert_eq!(query_gpu_cached(path).unwrap().num_shader_cores, 4);
+    }
+
+    // The device is gone, the cached result is not
+    let _empty = MockGpuBackend::new().install();
+    assert_eq!(query_gpu_cached(path).unwrap().num_shader_cores, 4);
+    invalidate_device(path);
+    assert!(query_gpu_cached(path).is_err());
+
+    {
+        let _mock = MockGpuBackend::new().mali("/dev/mali0", g610_with_cores(2)).install();
+        assert_eq!(query_gpu_cached(path).unwrap().num_shader_cores, 2);
+    }
+    set_ttl(Some(Duration::ZERO));
+    assert!(query_gpu_cached(path).is_err());
+    set_ttl(None);
+    invalidate();
+}
@@ -0,0 +1,201 @@
+//! Reports read back to the values they were rendered from
+//!
+//! Runs under a comma-decimal `LC_NUMERIC` to check that nothing in the
+//! formatting path picks up the process locale.
+
+use std::time::Duration;
+
+use armgpuinfo::monitor::{MonitorSummary, PowerState, Sample};
+use armgpuinfo::report::{parse_decimal, ReportFormat, ReportRenderer, Separators, REPORT_VERSION};
+use armgpuinfo::telemetry::{Provenance, Reading};
+use armgpuinfo::GpuInfo;
+
+const ALL_SEPARATORS: [Separators; 3] = [Separators::CSV, Separators::SEMICOLON, Separators::TSV];
+
+fn comma_locale() {
+    std::env::set_var("LC_ALL", "de_DE.UTF-8");
+    std::env::set_var("LC_NUMERIC", "de_DE.UTF-8");
+}
+
+fn sample(elapsed_ms: u64, utilization: f32, temperature: f32) -> Sample {
+    Sample {
+        elapsed: Duration::from_millis(elapsed_ms),
+        freq_mhz: Some(585),
+        utilization: Some(Reading::new(utilization, Provenance::Sysfs)),
+        busy_time: None,
+        temperature_c: Some(Reading::new(temperature, Provenance::Sysfs)),
+        power_watts: None,
+        throttled: Some(false),
+        power_state: PowerState::External,
+    }
+}
+
+/// Split a rendered table into fields, honouring quoted text
+fn fields(line: &str, separators: Separators) -> Vec<String> {
+    let mut fields = vec![String::new()];
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                fields.last_mut().unwrap().push('"');
+            }
+            '"' => quoted = !quoted,
+            c if c == separators.field() && !quoted => fields.push(String::new()),
+            c => fields.last_mut().unwrap().push(c),
+        }
+    }
+    fields
+}
+
+#[test]
+fn decimals_round_trip() {
+    comma_locale();
+    let values = [0.0, -0.5, 1.0 / 3.0, 42.125, 1e-7, 6.02e23, f64::MAX, f64::MIN_POSITIVE];
+    for separators in ALL_SEPARATORS {
+        let renderer = ReportRenderer::new(separators);
+        for value in values {
+            let text = renderer.decimal(value);
+            assert!(!text.contains(separators.field()), "{text:?}");
+            assert_eq!(parse_decimal(&text, separators), Some(value), "{text:?}");
+        }
+    }
+}
+
+#[test]
+fn samples_round_trip() {
+    comma_locale();
+    let samples = [sample(0, 12.5, 41.25), sample(1_250, 99.75, 43.0)];
+    for separators in ALL_SEPARATORS {
+        let report = ReportRenderer::new(separators).samples(&samples);
+        let mut lines = report.lines();
+        assert_eq!(fields(lines.next().unwrap(), separators)[0], "elapsed_s");
+
+        for (line, sample) in lines.zip(&samples) {
+            let fields = fields(line, separators);
+            assert_eq!(fields.len(), 6);
+            let number = |i: usize| parse_decimal(&fields[i], separators).unwrap();
+            assert_eq!(number(0), sample.elapsed.as_secs_f64());
+            assert_eq!(number(1), 585.0);
+            assert_eq!(number(2), f64::from(sample.utilization.unwrap().value));
+            assert_eq!(number(3), f64::from(sample.temperature_c.unwrap().value));
+            assert_eq!(fields[4], "");
+            assert_eq!(fields[5], "false");
+        }
+    }
+}
+
+#[test]
+fn summary_round_trip() {
+    comma_locale();
+    let samples = [sample(0, 10.0, 40.5), sample(100, 20.0, 41.5), sample(200, 35.5, 42.5)];
+    let summary = MonitorSummary::from_samples(&samples, Duration::from_secs(1));
+    let expected = summary.utilization.unwrap();
+
+    for separators in ALL_SEPARATORS {
+        let report = ReportRenderer::new(separators).header(false).summary(&summary);
+        let line = report.lines().find(|l| l.starts_with("utilization")).unwrap();
+        let fields = fields(line, separators);
+        let number = |i: usize| parse_decimal(&fields[i], separators).unwrap();
+        assert_eq!(fields[1], "3");
+        assert_eq!(number(2), expected.min);
+        assert_eq!(number(3), expected.mean);
+        assert_eq!(number(4), expected.p50);
+        assert_eq!(number(5), expected.p95);
+        assert_eq!(number(6), expected.max);
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn summary_serde_round_trip() {
+    let samples = [sample(0, 10.0, 40.5), sample(100, 20.0, 41.5), sample(200, 35.5, 42.5)];
+    let summary = MonitorSummary::from_samples(&samples, Duration::from_secs(1));
+
+    let json = serde_json::to_string(&summary).unwrap();
+    assert_eq!(serde_json::from_str::<MonitorSummary>(&json).unwrap(), summary);
+    let empty = MonitorSummary::from_samples(&[], Duration::from_secs(1));
+    assert_eq!(serde_json::from_str::<MonitorSummary>(&serde_json::to_string(&empty).unwrap()).unwrap(), empty);
+}
+
+#[test]
+fn text_fields_round_trip() {
+    comma_locale();
+    let info = GpuInfo::builder()
+        .gpu_name("Mali-G78; \"MP20\", rev 1")
+        .architecture("Valhall")
+        .architecture_major(9)
+        .architecture_minor(0)
+        .num_shader_cores(20)
+        .num_l2_bytes(2 << 20)
+        .num_bus_bits(128)
+        .gpu_id(0x9091)
+        .raw_gpu_id(0x9091_0010)
+        .build()
+        .unwrap();
+
+    for separators in ALL_SEPARATORS {
+        let report = ReportRenderer::new(separators).gpus(std::slice::from_ref(&info));
+        let fields = fields(report.lines().nth(1).unwrap(), separators);
+        assert_eq!(fields[1], info.gpu_name);
+        assert_eq!(fields[5], "20");
+        assert_eq!(fields[6], (2u64 << 20).to_string());
+    }
+}
+
+#[test]
+fn display_ignores_locale() {
+    comma_locale();
+    let info = GpuInfo::builder()
+        .gpu_name("Mali-G610")
+        .architecture("Valhall")
+        .architecture_major(10)
+        .architecture_minor(0)
+        .num_shader_cores(4)
+        .num_l2_bytes(1536 * 1024)
+        .gpu_id(0xa867)
+        .raw_gpu_id(0xa867_0010)
+        .build()
+        .unwrap();
+    let text = info.to_string();
+    assert!(text.contains("1536 KB"), "{text}");
+    assert!(!text.contains("1.536") && !text.contains("1,536"), "{text}");
+}
+
+#[test]
+fn json_report_parses_back() {
+    comma_locale();
+    let info = GpuInfo::builder()
+        .gpu_name("Mali-G78; \"MP20\"\n")
+        .architecture("Valhall")
+        .architecture_major(9)
+        .architecture_minor(0)
+        .num_shader_cores(20)
+        .num_l2_bytes(2 << 20)
+        .num_bus_bits(128)
+        .gpu_id(0x9091)
+        .raw_gpu_id(0x9091_0010)
+        .driver_version(11, 40)
+        .build()
+        .unwrap();
+
+    let report: serde_json::Value = serde_json::from_str(&info.to_json()).unwrap();
+    assert_eq!(report["report_version"], REPORT_VERSION);
+    assert_eq!(report["general"]["gpu_name"], *info.gpu_name);
+    assert_eq!(report["general"]["num_l2_bytes"], 2u64 << 20);
+    assert_eq!(report["mali"]["gpu_id"], "0x9091");
+    assert_eq!(report["mali"]["driver_version"], "11.40");
+    assert!(report.get("adreno").is_none());
+
+    let markdown = info.to_report(ReportFormat::Markdown);
+    assert!(markdown.contains("\n## Mali\n"), "{markdown}");
+}
+
+#[test]
+fn ambiguous_separators_rejected() {
+    assert!(Separators::new(',', ',').is_none());
+    assert!(Separators::new('-', '.').is_none());
+    let pipe = Separators::new('|', ',').unwrap();
+    assert_eq!((pipe.field(), pipe.decimal()), ('|', ','));
+}
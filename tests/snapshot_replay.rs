@@ -0,0 +1,147 @@
+//! Replay of dumped property buffers without hardware
+//!
+//! Buffers are built the way `dump_raw_properties` returns them, so a dump
+//! attached to a bug report can be dropped in here as a regression test.
+
+#![cfg(all(feature = "mali-core", feature = "adreno-core"))]
+
+use armgpuinfo::snapshot::Snapshot;
+use armgpuinfo::fields::FieldId;
+use armgpuinfo::{CoreGroup, DataConfidence, GpuError, GpuVendor, MaliArch, Mode};
+
+mod common;
+use common::{prop_u32, prop_u64};
+
+/// Mali-G610 MC4 as dumped from a CSF kbase driver
+fn g610_dump() -> Vec<u8> {
+    [
+        prop_u32(1, 0xa867),
+        prop_u32(14, 18),
+        prop_u32(15, 1),
+        prop_u32(29, 0x0713_0206),
+        prop_u32(30, 0x0000_0002),
+        prop_u64(55, 0xa867_0001),
+        prop_u32(59, 0x0400_0000),
+        prop_u32(62, 1),
+        prop_u64(64, 0b1111),
+    ]
+    .concat()
+}
+
+/// Adreno 740 KGSL device info structure
+fn a740_dump() -> Vec<u8> {
+    let words: [u32; 8] = [3, 0x0706_0001, 1, 0x0010_0000, 2 << 20, 0, 0, 0];
+    words.iter().flat_map(|word| word.to_le_bytes()).collect()
+}
+
+#[test]
+fn mali_replays_in_both_modes() {
+    let snapshot = Snapshot::from_raw(GpuVendor::Mali, g610_dump());
+
+    let parity = snapshot.parse(Mode::Parity).unwrap();
+    let extended = snapshot.parse(Mode::Extended).unwrap();
+    for info in [&parity, &extended] {
+        assert_eq!(info.gpu_name, "Mali-G610");
+        assert_eq!(info.num_shader_cores, 4);
+        assert_eq!(info.num_l2_bytes, 256 * 1024);
+        assert_eq!(info.device_path, None);
+        assert_eq!(info.confidence, DataConfidence::Measured);
+    }
+
+    assert!(parity.provenance.is_empty());
+    assert_eq!(extended.data_confidence(FieldId::NumL2Bytes), DataConfidence::Measured);
+    assert_eq!(extended.data_confidence(FieldId::GpuName), DataConfidence::DatabaseDerived);
+    assert_eq!(extended.data_confidence(FieldId::Flavor), DataConfidence::Unknown);
+
+    let mali = extended.mali_data.unwrap();
+    assert_eq!(mali.arch, Some(MaliArch::Valhall));
+    assert_eq!(mali.shader_core_mask, 0b1111);
+    assert!(mali.num_fp32_fmas_per_core > 0);
+    assert_eq!(mali.power_model, None);
+    assert_eq!(mali.instance, None);
+}
+
+#[test]
+fn mali_core_groups() {
+    let symmetric = Snapshot::from_raw(GpuVendor::Mali, g610_dump()).parse(Mode::Parity).unwrap();
+    let mali = symmetric.mali_data.unwrap();
+    assert_eq!(mali.core_groups, [CoreGroup::new(0b1111)]);
+    assert!(!mali.is_asymmetric());
+
+    // Mali-T760 with two core groups, one core fused off in the second
+    let dump = [
+        prop_u32(1, 0x0750),
+        prop_u32(14, 17),
+        prop_u32(15, 2),
+        prop_u32(62, 2),
+        prop_u64(64, 0x0f),
+        prop_u64(65, 0x70),
+    ]
+    .concat();
+    let info = Snapshot::from_raw(GpuVendor::Mali, dump).parse(Mode::Parity).unwrap();
+    let mali = info.mali_data.unwrap();
+    assert_eq!(mali.core_groups, [CoreGroup::new(0x0f), CoreGroup::new(0x70)]);
+    assert_eq!(mali.core_groups[1].core_count, 3);
+    assert!(mali.is_asymmetric());
+}
+
+#[test]
+fn mali_extended_limits() {
+    let dump = [
+        g610_dump(),
+        prop_u32(9, 0x00fe_001e),
+        prop_u32(18, 2048),
+        prop_u32(21, 0x10000),
+        prop_u32(33, 0xff),
+        prop_u32(51, 0x0809),
+        prop_u32(61, 1),
+        prop_u32(63, 31),
+    ]
+    .concat();
+    let snapshot = Snapshot::from_raw(GpuVendor::Mali, dump);
+
+    assert_eq!(snapshot.parse(Mode::Parity).unwrap().mali_data.unwrap().extended, None);
+    let limits = snapshot.parse(Mode::Extended).unwrap().mali_data.unwrap().extended.unwrap();
+    assert_eq!(limits.max_threads, 2048);
+    assert_eq!(limits.max_registers, 0x10000);
+    assert_eq!(limits.num_address_spaces, 8);
+    assert_eq!(limits.num_coherency_groups, 1);
+    assert_eq!(limits.coherency_mode, 31);
+    assert_eq!(limits.texture_features[0], 0x00fe_001e);
+    // Not decoded by this kernel, so taken from RAW_TILER_FEATURES
+    assert_eq!(limits.tiler_bin_size_bytes, 512);
+    assert_eq!(limits.tiler_max_active_levels, 8);
+}
+
+#[test]
+fn adreno_replays_in_both_modes() {
+    let snapshot = Snapshot::from_raw(GpuVendor::Adreno, a740_dump());
+
+    for mode in [Mode::Parity, Mode::Extended] {
+        let info = snapshot.parse(mode).unwrap();
+        assert_eq!(info.gpu_name, "Adreno 740");
+        let adreno = info.adreno_data.unwrap();
+        assert_eq!(adreno.chip_id, 0x0706_0001);
+        assert_eq!(adreno.gmem_bytes, 2 << 20);
+        assert!(adreno.gmu.is_none());
+        assert_ne!(info.confidence, DataConfidence::Measured);
+    }
+
+    let extended = snapshot.parse(Mode::Extended).unwrap();
+    assert_eq!(extended.data_confidence(FieldId::GmemBytes), DataConfidence::Measured);
+    assert_eq!(extended.data_confidence(FieldId::NumShaderCores), extended.confidence);
+}
+
+#[test]
+fn unknown_products_are_reported() {
+    let dump = [prop_u32(1, 0xfff0), prop_u32(62, 1), prop_u64(64, 1)].concat();
+    let snapshot = Snapshot::from_raw(GpuVendor::Mali, dump);
+    assert!(matches!(
+        snapshot.parse(Mode::Extended),
+        Err(GpuError::UnsupportedGpu { id: 0xfff0, .. })
+    ));
+
+    let words = [0u8; 32];
+    let snapshot = Snapshot::from_raw(GpuVendor::Adreno, words);
+    assert!(snapshot.parse(Mode::Parity).is_err());
+}
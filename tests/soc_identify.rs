@@ -0,0 +1,41 @@
+//! SoC identification from sysfs, the device tree and /proc/cpuinfo
+
+use std::fs;
+use std::path::Path;
+
+use armgpuinfo::soc::{identify_soc_in, SM8550};
+
+fn write(root: &Path, path: &str, contents: &str) {
+    let path = root.join(path);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, contents).unwrap();
+}
+
+#[test]
+fn qualcomm_soc0_and_cpuinfo() {
+    let root = tempfile::tempdir().unwrap();
+    write(root.path(), "sys/devices/soc0/family", "Snapdragon\n");
+    write(root.path(), "sys/devices/soc0/soc_id", "519\n");
+    write(root.path(), "proc/cpuinfo", "processor\t: 0\nHardware\t: Qualcomm Technologies, Inc SM8550\n");
+
+    let soc = identify_soc_in(root.path());
+    assert_eq!(soc.soc, Some(SM8550));
+    assert_eq!(soc.name(), Some("Snapdragon 8 Gen 2"));
+    assert_eq!(soc.family.as_deref(), Some("Snapdragon"));
+    assert_eq!(soc.soc_id.as_deref(), Some("519"));
+    assert_eq!(soc.mali_gpu(), None);
+}
+
+#[test]
+fn unknown_socs_keep_their_identifiers() {
+    let root = tempfile::tempdir().unwrap();
+    write(root.path(), "sys/devices/soc0/machine", "RK3588\n");
+    write(root.path(), "proc/device-tree/compatible", "radxa,rock-5b\0rockchip,rk3588\0");
+
+    let soc = identify_soc_in(root.path());
+    assert_eq!(soc.soc, None);
+    assert_eq!(soc.name(), Some("RK3588"));
+    assert_eq!(soc.compatible, ["radxa,rock-5b", "rockchip,rk3588"]);
+
+    assert_eq!(identify_soc_in(Path::new("/nonexistent")), Default::default());
+}
@@ -0,0 +1,53 @@
+//! Vivante results built from etnaviv parameters
+
+#![cfg(feature = "vivante")]
+
+use armgpuinfo::fields::fields_for;
+use armgpuinfo::info::VivanteData;
+use armgpuinfo::vivante::{gpu_info_from_core, CHIP_FEATURE_PIPE_3D};
+use armgpuinfo::{GpuVendor, ReportFormat};
+
+/// GC2000 of the i.MX6 Quad
+fn gc2000() -> VivanteData {
+    VivanteData {
+        model: 0x2000,
+        revision: 0x5108,
+        product_id: 0,
+        customer_id: 0,
+        eco_id: 0,
+        features: vec![0xe0287cad, 0xc1589eff, 0xfefbfad9, 0xeb9d4fbf, 0x000002c1],
+        stream_count: 4,
+        register_max: 64,
+        thread_count: 1024,
+        vertex_cache_size: 16,
+        pixel_pipes: 1,
+        instruction_count: 512,
+        num_constants: 168,
+        num_varyings: 8,
+    }
+}
+
+#[test]
+fn core_is_described() {
+    let core = gc2000();
+    assert!(core.has_feature(0, CHIP_FEATURE_PIPE_3D));
+    assert!(!core.has_feature(13, 0));
+
+    let info = gpu_info_from_core(core, 0);
+    assert_eq!(info.vendor, GpuVendor::Vivante);
+    assert_eq!(info.gpu_name, "Vivante GC2000");
+    assert_eq!((info.architecture_major, info.architecture_minor), (2, 0));
+    // Without a shader core count each pixel pipe counts as one
+    assert_eq!(info.num_shader_cores, 1);
+    assert!(info.revision().is_none());
+    assert!(info.display_pipeline_hints().unified_memory);
+}
+
+#[test]
+fn report_and_fields_cover_vivante() {
+    let info = gpu_info_from_core(gc2000(), 4);
+    let report = info.to_report(ReportFormat::Json);
+    assert!(report.contains(r#""vivante":{"model":"0x2000","revision":"0x5108""#), "{report}");
+    assert!(fields_for(GpuVendor::Vivante).any(|field| field.name == "vivante_data.thread_count"));
+    assert_eq!(GpuVendor::from_code("vivante"), Some(GpuVendor::Vivante));
+}